@@ -2,8 +2,12 @@ pub mod actor;
 pub mod clients;
 pub mod employee_offices;
 pub mod employees;
+pub mod office_calendars;
+pub mod office_links;
 pub mod offices;
+pub mod reports;
 pub mod roles;
 pub mod shipments;
+pub mod tariffs;
 pub mod users;
 mod validation;
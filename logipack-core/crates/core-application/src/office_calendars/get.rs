@@ -0,0 +1,32 @@
+use core_data::repository::office_calendars_repo::{
+    OfficeCalendarRepoError, OfficeCalendarsRepo, StoredCalendar,
+};
+use sea_orm::DatabaseConnection;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum GetOfficeCalendarError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("office calendar not found")]
+    NotFound,
+    #[error("{0}")]
+    Repo(#[from] OfficeCalendarRepoError),
+}
+
+pub async fn get_office_calendar(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    office_id: Uuid,
+) -> Result<StoredCalendar, GetOfficeCalendarError> {
+    if !actor.is_admin() {
+        return Err(GetOfficeCalendarError::Forbidden);
+    }
+
+    OfficeCalendarsRepo::get(db, office_id)
+        .await?
+        .ok_or(GetOfficeCalendarError::NotFound)
+}
@@ -0,0 +1,99 @@
+use chrono::NaiveDate;
+use core_data::repository::office_calendars_repo::{
+    OfficeCalendarRepoError, OfficeCalendarsRepo, StoredCalendar,
+};
+use core_domain::network::Holiday;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, DbErr, TransactionTrait};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum OfficeHolidayError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("office calendar not found")]
+    CalendarNotFound,
+    #[error("holiday not found")]
+    HolidayNotFound,
+    #[error("{0}")]
+    Repo(#[from] OfficeCalendarRepoError),
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+}
+
+/// Adds a holiday to the office's calendar, or renames it. Admin only.
+pub async fn set_office_holiday(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    office_id: Uuid,
+    holiday: Holiday,
+) -> Result<StoredCalendar, OfficeHolidayError> {
+    edit_holidays(db, actor, office_id, |holidays| {
+        holidays.retain(|h| h.date != holiday.date);
+        holidays.push(holiday);
+        holidays.sort_by_key(|h| h.date);
+        Ok(())
+    })
+    .await
+}
+
+/// Reopens the office on `date`. Admin only.
+pub async fn remove_office_holiday(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    office_id: Uuid,
+    date: NaiveDate,
+) -> Result<StoredCalendar, OfficeHolidayError> {
+    edit_holidays(db, actor, office_id, |holidays| {
+        let before = holidays.len();
+        holidays.retain(|h| h.date != date);
+
+        if holidays.len() == before {
+            return Err(OfficeHolidayError::HolidayNotFound);
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+async fn edit_holidays(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    office_id: Uuid,
+    edit: impl FnOnce(&mut Vec<Holiday>) -> Result<(), OfficeHolidayError>,
+) -> Result<StoredCalendar, OfficeHolidayError> {
+    if !actor.is_admin() {
+        return Err(OfficeHolidayError::Forbidden);
+    }
+
+    let txn = db.begin().await?;
+
+    match edit_holidays_txn(&txn, office_id, edit).await {
+        Ok(calendar) => {
+            txn.commit().await?;
+            Ok(calendar)
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+async fn edit_holidays_txn(
+    txn: &DatabaseTransaction,
+    office_id: Uuid,
+    edit: impl FnOnce(&mut Vec<Holiday>) -> Result<(), OfficeHolidayError>,
+) -> Result<StoredCalendar, OfficeHolidayError> {
+    let mut calendar = OfficeCalendarsRepo::get_for_update(txn, office_id)
+        .await?
+        .ok_or(OfficeHolidayError::CalendarNotFound)?
+        .calendar;
+
+    edit(&mut calendar.holidays)?;
+
+    Ok(OfficeCalendarsRepo::upsert(txn, office_id, &calendar).await?)
+}
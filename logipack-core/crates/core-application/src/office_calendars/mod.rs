@@ -0,0 +1,9 @@
+//! Office working calendars: weekly opening hours and holidays in the
+//! office's timezone. Delivery estimates wait for offices to open;
+//! offices without a calendar never close. Changing a calendar does not
+//! move promises already made.
+
+pub mod get;
+pub mod holidays;
+pub mod remove;
+pub mod set;
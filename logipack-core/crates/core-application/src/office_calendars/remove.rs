@@ -0,0 +1,33 @@
+use core_data::repository::office_calendars_repo::{OfficeCalendarRepoError, OfficeCalendarsRepo};
+use sea_orm::DatabaseConnection;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum RemoveOfficeCalendarError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("office calendar not found")]
+    NotFound,
+    #[error("{0}")]
+    Repo(#[from] OfficeCalendarRepoError),
+}
+
+/// Drops the calendar, so the office never closes. Admin only.
+pub async fn remove_office_calendar(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    office_id: Uuid,
+) -> Result<(), RemoveOfficeCalendarError> {
+    if !actor.is_admin() {
+        return Err(RemoveOfficeCalendarError::Forbidden);
+    }
+
+    if !OfficeCalendarsRepo::delete(db, office_id).await? {
+        return Err(RemoveOfficeCalendarError::NotFound);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use core_data::repository::office_calendars_repo::{
+    OfficeCalendarRepoError, OfficeCalendarsRepo, StoredCalendar,
+};
+use core_domain::errors::CalendarError;
+use core_domain::network::OfficeCalendar;
+use sea_orm::DatabaseConnection;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum SetOfficeCalendarError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid office calendar: {0}")]
+    Invalid(#[from] CalendarError),
+    #[error("office not found")]
+    OfficeNotFound,
+    #[error("{0}")]
+    Repo(OfficeCalendarRepoError),
+}
+
+/// Creates or replaces the calendar of an office. Admin only.
+pub async fn set_office_calendar(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    office_id: Uuid,
+    calendar: OfficeCalendar,
+) -> Result<StoredCalendar, SetOfficeCalendarError> {
+    if !actor.is_admin() {
+        return Err(SetOfficeCalendarError::Forbidden);
+    }
+
+    calendar.validate()?;
+
+    OfficeCalendarsRepo::upsert(db, office_id, &calendar)
+        .await
+        .map_err(|e| match e {
+            OfficeCalendarRepoError::OfficeNotFound => SetOfficeCalendarError::OfficeNotFound,
+            other => SetOfficeCalendarError::Repo(other),
+        })
+}
@@ -0,0 +1,25 @@
+use core_data::entity::office_links;
+use core_data::repository::office_links_repo::{OfficeLinkRepoError, OfficeLinksRepo};
+use sea_orm::DatabaseConnection;
+use thiserror::Error;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum ListOfficeLinksError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("{0}")]
+    Repo(#[from] OfficeLinkRepoError),
+}
+
+pub async fn list_office_links(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+) -> Result<Vec<office_links::Model>, ListOfficeLinksError> {
+    if !actor.is_admin() {
+        return Err(ListOfficeLinksError::Forbidden);
+    }
+
+    Ok(OfficeLinksRepo::list(db).await?)
+}
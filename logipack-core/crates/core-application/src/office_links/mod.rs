@@ -0,0 +1,8 @@
+//! Office network: directed links between offices with their transit time
+//! and cost. Shipments get a route planned over the network when they are
+//! created; changing a link does not re-plan routes already recorded.
+
+pub mod list;
+pub mod plan;
+pub mod remove;
+pub mod set;
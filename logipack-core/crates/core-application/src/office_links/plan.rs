@@ -0,0 +1,30 @@
+use core_data::repository::office_links_repo::{OfficeLinkRepoError, OfficeLinksRepo};
+use core_domain::network::Route;
+use sea_orm::DatabaseConnection;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum PlanRouteError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("{0}")]
+    Repo(#[from] OfficeLinkRepoError),
+}
+
+/// Route a shipment from `from` to `to` would be given now, `None` when
+/// `to` cannot be reached. Admin only.
+pub async fn plan_route(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    from: Uuid,
+    to: Uuid,
+) -> Result<Option<Route<Uuid>>, PlanRouteError> {
+    if !actor.is_admin() {
+        return Err(PlanRouteError::Forbidden);
+    }
+
+    Ok(OfficeLinksRepo::network(db).await?.plan(&from, &to))
+}
@@ -0,0 +1,33 @@
+use core_data::repository::office_links_repo::{OfficeLinkRepoError, OfficeLinksRepo};
+use sea_orm::DatabaseConnection;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum RemoveOfficeLinkError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("office link not found")]
+    NotFound,
+    #[error("{0}")]
+    Repo(#[from] OfficeLinkRepoError),
+}
+
+pub async fn remove_office_link(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    from_office_id: Uuid,
+    to_office_id: Uuid,
+) -> Result<(), RemoveOfficeLinkError> {
+    if !actor.is_admin() {
+        return Err(RemoveOfficeLinkError::Forbidden);
+    }
+
+    if !OfficeLinksRepo::delete(db, from_office_id, to_office_id).await? {
+        return Err(RemoveOfficeLinkError::NotFound);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use core_data::entity::office_links;
+use core_data::repository::office_links_repo::{OfficeLinkRepoError, OfficeLinksRepo};
+use core_domain::errors::OfficeLinkError;
+use core_domain::network::OfficeLink;
+use sea_orm::DatabaseConnection;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Clone)]
+pub struct SetOfficeLink {
+    pub from_office_id: Uuid,
+    pub to_office_id: Uuid,
+    pub transit_minutes: i32,
+    pub cost_cents: i64,
+}
+
+#[derive(Debug, Error)]
+pub enum SetOfficeLinkError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid office link: {0}")]
+    Invalid(#[from] OfficeLinkError),
+    #[error("office not found")]
+    OfficeNotFound,
+    #[error("{0}")]
+    Repo(OfficeLinkRepoError),
+}
+
+/// Creates the link from one office to another, or replaces its transit
+/// time and cost. Admin only.
+pub async fn set_office_link(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    input: SetOfficeLink,
+) -> Result<office_links::Model, SetOfficeLinkError> {
+    if !actor.is_admin() {
+        return Err(SetOfficeLinkError::Forbidden);
+    }
+
+    let link = OfficeLink {
+        from: input.from_office_id,
+        to: input.to_office_id,
+        transit_minutes: input.transit_minutes,
+        cost_cents: input.cost_cents,
+    };
+    link.validate()?;
+
+    OfficeLinksRepo::upsert(db, link)
+        .await
+        .map_err(|e| match e {
+            OfficeLinkRepoError::OfficeNotFound => SetOfficeLinkError::OfficeNotFound,
+            other => SetOfficeLinkError::Repo(other),
+        })
+}
@@ -0,0 +1,15 @@
+use core_data::repository::reports_repo::{OfficeCount, ReportsRepo};
+use sea_orm::DatabaseConnection;
+
+use crate::actor::ActorContext;
+use crate::reports::scope::{ReportQuery, ShipmentReportError, scoped_filter};
+
+pub async fn shipments_by_office(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    query: ReportQuery,
+) -> Result<Vec<OfficeCount>, ShipmentReportError> {
+    let filter = scoped_filter(actor, query)?;
+
+    Ok(ReportsRepo::shipments_by_office(db, &filter).await?)
+}
@@ -0,0 +1,17 @@
+use core_data::repository::reports_repo::{PeriodBucket, PeriodCount, ReportsRepo};
+use sea_orm::DatabaseConnection;
+
+use crate::actor::ActorContext;
+use crate::reports::scope::{ReportQuery, ShipmentReportError, scoped_filter};
+
+/// Shipment counts per `bucket`. Empty buckets are omitted.
+pub async fn shipments_by_period(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    query: ReportQuery,
+    bucket: PeriodBucket,
+) -> Result<Vec<PeriodCount>, ShipmentReportError> {
+    let filter = scoped_filter(actor, query)?;
+
+    Ok(ReportsRepo::shipments_by_period(db, &filter, bucket).await?)
+}
@@ -0,0 +1,15 @@
+use core_data::repository::reports_repo::{ReportsRepo, StatusCount};
+use sea_orm::DatabaseConnection;
+
+use crate::actor::ActorContext;
+use crate::reports::scope::{ReportQuery, ShipmentReportError, scoped_filter};
+
+pub async fn shipments_by_status(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    query: ReportQuery,
+) -> Result<Vec<StatusCount>, ShipmentReportError> {
+    let filter = scoped_filter(actor, query)?;
+
+    Ok(ReportsRepo::shipments_by_status(db, &filter).await?)
+}
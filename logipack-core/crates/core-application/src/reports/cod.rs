@@ -0,0 +1,29 @@
+use core_data::repository::reports_repo::{CodBalance, CodOutstanding, ReportsRepo};
+use sea_orm::DatabaseConnection;
+
+use crate::actor::ActorContext;
+use crate::reports::scope::{ReportQuery, ShipmentReportError, scoped_filter};
+
+/// Cash on delivery still owed to clients, uncollected and unremitted,
+/// per client, office and currency.
+pub async fn cod_outstanding(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    query: ReportQuery,
+) -> Result<Vec<CodOutstanding>, ShipmentReportError> {
+    let filter = scoped_filter(actor, query)?;
+
+    Ok(ReportsRepo::cod_outstanding(db, &filter).await?)
+}
+
+/// Cash collected, remitted and held per office and currency.
+/// Employees only see their own offices.
+pub async fn cod_balances(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    query: ReportQuery,
+) -> Result<Vec<CodBalance>, ShipmentReportError> {
+    let filter = scoped_filter(actor, query)?;
+
+    Ok(ReportsRepo::cod_balances(db, &filter).await?)
+}
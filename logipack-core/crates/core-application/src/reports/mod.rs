@@ -0,0 +1,8 @@
+pub mod by_client;
+pub mod by_office;
+pub mod by_period;
+pub mod by_status;
+pub mod cod;
+pub mod on_hold;
+pub mod scope;
+pub mod sla;
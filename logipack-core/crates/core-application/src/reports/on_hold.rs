@@ -0,0 +1,15 @@
+use core_data::repository::reports_repo::{HoldCount, ReportsRepo};
+use sea_orm::DatabaseConnection;
+
+use crate::actor::ActorContext;
+use crate::reports::scope::{ReportQuery, ShipmentReportError, scoped_filter};
+
+pub async fn shipments_on_hold(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    query: ReportQuery,
+) -> Result<Vec<HoldCount>, ShipmentReportError> {
+    let filter = scoped_filter(actor, query)?;
+
+    Ok(ReportsRepo::shipments_on_hold(db, &filter).await?)
+}
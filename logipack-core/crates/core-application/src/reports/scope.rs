@@ -0,0 +1,69 @@
+use core_data::repository::reports_repo::{ReportError, ReportFilter};
+use core_domain::shipment::ShipmentStatus;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum ShipmentReportError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("period start must be before its end")]
+    InvalidPeriod,
+    #[error("{0}")]
+    ReportError(#[from] ReportError),
+}
+
+/// Report filters as requested by the caller.
+///
+/// `from` is inclusive and `to` is exclusive, both on the shipment's
+/// creation time.
+#[derive(Debug, Clone, Default)]
+pub struct ReportQuery {
+    pub from: Option<DateTimeWithTimeZone>,
+    pub to: Option<DateTimeWithTimeZone>,
+    pub office_id: Option<Uuid>,
+    pub client_id: Option<Uuid>,
+    pub status: Option<ShipmentStatus>,
+    pub on_hold: Option<bool>,
+}
+
+/// Turns a caller's query into a repo filter.
+///
+/// Admins see every office. Employees only see shipments currently at one
+/// of their offices; asking for any other office is forbidden.
+pub(crate) fn scoped_filter(
+    actor: &ActorContext,
+    query: ReportQuery,
+) -> Result<ReportFilter, ShipmentReportError> {
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from >= to
+    {
+        return Err(ShipmentReportError::InvalidPeriod);
+    }
+
+    let office_ids = if actor.is_admin() {
+        query.office_id.map(|id| vec![id])
+    } else if actor.is_employee() {
+        match query.office_id {
+            Some(id) if !actor.allowed_office_ids.contains(&id) => {
+                return Err(ShipmentReportError::Forbidden);
+            }
+            Some(id) => Some(vec![id]),
+            None => Some(actor.allowed_office_ids.clone()),
+        }
+    } else {
+        return Err(ShipmentReportError::Forbidden);
+    };
+
+    Ok(ReportFilter {
+        from: query.from,
+        to: query.to,
+        office_ids,
+        client_id: query.client_id,
+        status: query.status,
+        on_hold: query.on_hold,
+    })
+}
@@ -0,0 +1,15 @@
+use core_data::repository::reports_repo::{ReportsRepo, SlaBreach};
+use sea_orm::DatabaseConnection;
+
+use crate::actor::ActorContext;
+use crate::reports::scope::{ReportQuery, ShipmentReportError, scoped_filter};
+
+pub async fn sla_breaches(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    query: ReportQuery,
+) -> Result<Vec<SlaBreach>, ShipmentReportError> {
+    let filter = scoped_filter(actor, query)?;
+
+    Ok(ReportsRepo::sla_breaches(db, &filter).await?)
+}
@@ -1,16 +1,25 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use core_data::repository::office_links_repo::OfficeLinkRepoError;
+use core_data::repository::shipment_pieces_repo::ShipmentPiecesRepo;
+use core_data::repository::shipment_routes_repo::ShipmentRoutesRepo;
 use core_data::repository::shipments_repo::ShipmentSnapshotError;
-use core_data::repository::shipments_repo::ShipmentsRepo;
-use core_domain::errors::TransitionError;
-use core_domain::shipment::{ShipmentStatus, validate_transition};
+use core_data::repository::shipments_repo::{HistoryRow, ShipmentsRepo};
+use core_domain::errors::{ProofOfDeliveryError, TransitionError};
+use core_domain::shipment::{
+    ActorRef, DeliveryFailureReason, EventCodec, OfficeContext, ProofOfDelivery, ShipmentStatus,
+    StatusChanged, TransitionContext, TransitionTable,
+};
+use core_eventstore::adapter::append::{AppendError, append_package_expecting};
 use core_eventstore::adapter::streams::EnsureStreamError;
-use sea_orm::DatabaseConnection;
-use strata::value::Value;
-use strata::{int, map, null, string};
+use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
 use thiserror::Error;
 use uuid::Uuid;
 
 use crate::actor::ActorContext;
+use crate::shipments::cod::collect_cod;
+use crate::shipments::pod::{ProofOfDeliveryInput, store_proof_of_delivery};
+use crate::shipments::route::{check_route_arrival, check_route_hop, plan_shipment_route};
+use crate::shipments::sla::record_sla_breach;
 
 #[derive(Debug, Clone)]
 pub struct ChangeStatus {
@@ -18,42 +27,106 @@ pub struct ChangeStatus {
     pub to_status: ShipmentStatus,
     pub to_office_id: Option<Uuid>,
     pub notes: Option<String>,
+    /// Required when moving to DELIVERY_FAILED, rejected otherwise.
+    pub failure_reason: Option<DeliveryFailureReason>,
+    /// Last stream seq the caller has seen. When set, the change is rejected
+    /// with `ChangeStatusError::Conflict` if anyone appended since.
+    pub expected_seq: Option<i64>,
+    /// Only accepted when moving to DELIVERED.
+    pub proof_of_delivery: Option<ProofOfDeliveryInput>,
+    /// Lets an admin hop the shipment off its planned route, which is then
+    /// planned again from the new office, or deliver it before the end.
+    pub override_route: bool,
 }
 
 #[derive(Debug, Error)]
 pub enum ChangeStatusError {
     #[error("forbidden")]
     Forbidden,
+    #[error(
+        "shipment changed concurrently: expected seq {expected_seq}, current seq {current_seq}"
+    )]
+    Conflict {
+        expected_seq: i64,
+        current_seq: i64,
+        current_status: String,
+        current_office_id: Option<Uuid>,
+    },
+    #[error("shipment is on hold ({reason})")]
+    OnHold { reason: String },
+    #[error("shipment has pieces, scan them instead")]
+    HasPieces,
+    /// IN_TRANSIT hops follow the planned route unless an admin overrides.
+    #[error("hop leaves the planned route")]
+    OffRoute { expected_office_id: Option<Uuid> },
+    /// Deliveries wait until the shipment reaches the end of its route.
+    #[error("shipment has not reached its destination")]
+    NotAtDestination { destination_office_id: Uuid },
     #[error("domain transition error: {0:?}")]
     Domain(#[from] TransitionError),
+    #[error("invalid proof of delivery: {0}")]
+    ProofOfDelivery(#[from] ProofOfDeliveryError),
     #[error("snapshot error: {0}")]
     SnapshotError(#[from] ShipmentSnapshotError),
     #[error("stream error: {0}")]
     StreamError(#[from] EnsureStreamError),
+    #[error("route planning error: {0}")]
+    Network(#[from] OfficeLinkRepoError),
     #[error("db error: {0}")]
     DbError(#[from] sea_orm::DbErr),
     #[error("eventstore error: {0}")]
-    EventstoreError(#[from] core_eventstore::adapter::append::AppendError),
+    EventstoreError(#[from] AppendError),
 }
 
 pub async fn change_status(
     db: &DatabaseConnection,
     actor: &ActorContext,
+    transitions: &TransitionTable,
     input: ChangeStatus,
 ) -> Result<(), ChangeStatusError> {
-    let snap = ShipmentsRepo::get_snapshot(db, input.shipment_id).await?;
+    // snapshot read, eventstore append, history and snapshot update
+    // commit together or not at all
+    let shipment_id = input.shipment_id;
+    let txn = db.begin().await?;
 
-    let from_status: ShipmentStatus = snap.current_status.parse().unwrap_or(ShipmentStatus::New);
-    let current_office = snap.current_office_id;
+    match change_status_txn(&txn, actor, transitions, input).await {
+        Ok(()) => {
+            txn.commit().await?;
+            Ok(())
+        }
+        Err(ChangeStatusError::EventstoreError(AppendError::Conflict { expected, actual })) => {
+            txn.rollback().await.ok();
 
-    // employees can only write within current shipment office
-    if !actor.is_admin() {
-        let shipment_office = current_office.ok_or(ChangeStatusError::Forbidden)?;
+            // report the state the caller lost against
+            let snap = ShipmentsRepo::get_snapshot(db, shipment_id).await?;
 
-        if !actor.allowed_office_ids.contains(&shipment_office) {
-            return Err(ChangeStatusError::Forbidden);
+            Err(ChangeStatusError::Conflict {
+                expected_seq: expected,
+                current_seq: actual,
+                current_status: snap.current_status,
+                current_office_id: snap.current_office_id,
+            })
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
         }
     }
+}
+
+async fn change_status_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    transitions: &TransitionTable,
+    input: ChangeStatus,
+) -> Result<(), ChangeStatusError> {
+    // locked, so a hold placed meanwhile is seen before the change is made
+    let snap = ShipmentsRepo::get_snapshot_for_update(txn, input.shipment_id).await?;
+
+    let from_status: ShipmentStatus = snap.current_status.parse().unwrap_or(ShipmentStatus::New);
+    let current_office = snap.current_office_id;
+
+    ensure_writable(actor, current_office, ChangeStatusError::Forbidden)?;
 
     // office hop policy for employees
     if !actor.is_admin()
@@ -63,68 +136,258 @@ pub async fn change_status(
         return Err(ChangeStatusError::Forbidden);
     }
 
+    // only admins leave the planned route
+    if input.override_route && !actor.is_admin() {
+        return Err(ChangeStatusError::Forbidden);
+    }
+
+    // a hold freezes the status until it is released
+    if let Some(reason) = snap.hold_reason {
+        return Err(ChangeStatusError::OnHold { reason });
+    }
+
+    // the status of a multi-piece shipment follows its piece scans
+    if !ShipmentPiecesRepo::list_by_shipment(txn, input.shipment_id)
+        .await?
+        .is_empty()
+    {
+        return Err(ChangeStatusError::HasPieces);
+    }
+
     let office_changed = input.to_office_id != current_office;
 
-    validate_transition(from_status, input.to_status, office_changed)
+    let ctx = TransitionContext {
+        office_changed,
+        has_note: input.notes.as_deref().is_some_and(|n| !n.trim().is_empty()),
+        is_admin: actor.is_admin(),
+        has_failure_reason: input.failure_reason.is_some(),
+        delivery_attempts: snap.delivery_attempts.try_into().unwrap_or(0),
+    };
+
+    let rule = transitions
+        .validate(from_status, input.to_status, ctx)
         .map_err(ChangeStatusError::Domain)?;
 
-    // projection trio
-    core_eventstore::adapter::streams::ensure_stream(db, input.shipment_id, "shipment").await?;
-
-    let occured_at = Utc::now().timestamp_millis();
-
-    let payload: Value = map! {
-        "event_type" => string!("StatusChanged"),
-        "shipment_id" => string!(input.shipment_id.to_string()),
-        "from_status" => string!(from_status.to_string()),
-        "to_status" => string!(input.to_status.to_string()),
-        "actor_user_id" => string!(actor.user_id.to_string()),
-        "from_office_id" => match snap.current_office_id {
-            Some(office_id) => string!(office_id.to_string()),
-            None => null!(),
+    // transit hops go to the next office on the planned route
+    let mut detour = None;
+    if input.to_status == ShipmentStatus::InTransit
+        && office_changed
+        && let Some(to_office) = input.to_office_id
+    {
+        let route = ShipmentRoutesRepo::get(txn, input.shipment_id).await?;
+
+        if let Err(expected_office_id) = check_route_hop(route.as_ref(), current_office, to_office)
+        {
+            if !input.override_route {
+                return Err(ChangeStatusError::OffRoute { expected_office_id });
+            }
+
+            detour = route
+                .as_ref()
+                .and_then(|route| route.destination().copied())
+                .map(|destination| (to_office, destination));
+        }
+    }
+
+    // deliveries start from the end of the route
+    if is_delivery(input.to_status) && !input.override_route {
+        let route = ShipmentRoutesRepo::get(txn, input.shipment_id).await?;
+
+        check_route_arrival(route.as_ref(), current_office).map_err(|destination_office_id| {
+            ChangeStatusError::NotAtDestination {
+                destination_office_id,
+            }
+        })?;
+    }
+
+    // a failed doorstep visit uses up one attempt
+    let attempt =
+        (input.to_status == ShipmentStatus::DeliveryFailed).then(|| snap.delivery_attempts + 1);
+
+    // snapshot update
+    // only hop office on transitions that allow it
+    let new_office = if rule.office_change {
+        input.to_office_id.or(current_office)
+    } else {
+        current_office
+    };
+
+    let proof_of_delivery = match input.proof_of_delivery {
+        Some(pod) => Some(store_proof_of_delivery(txn, input.to_status, pod).await?),
+        None => None,
+    };
+
+    write_status_change::<ChangeStatusError>(
+        txn,
+        actor,
+        StatusChangeWrite {
+            shipment_id: input.shipment_id,
+            expected_seq: input.expected_seq,
+            from_status,
+            to_status: input.to_status,
+            from_office: current_office,
+            to_office: input.to_office_id,
+            new_office,
+            notes: input.notes,
+            failure_reason: input.failure_reason,
+            attempt,
+            proof_of_delivery,
+            promised_by: snap.promised_by.map(|at| at.with_timezone(&Utc)),
         },
-        "to_office_id" => match input.to_office_id {
-            Some(office_id) => string!(office_id.to_string()),
-            None => null!(),
+    )
+    .await?;
+
+    if let Some((from, destination)) = detour {
+        plan_shipment_route::<ChangeStatusError>(txn, actor, input.shipment_id, from, destination)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// A validated status change of a whole shipment.
+pub(crate) struct StatusChangeWrite {
+    pub shipment_id: Uuid,
+    pub expected_seq: Option<i64>,
+    pub from_status: ShipmentStatus,
+    pub to_status: ShipmentStatus,
+    pub from_office: Option<Uuid>,
+    /// Office requested by the change, as recorded in the event.
+    pub to_office: Option<Uuid>,
+    /// Office the snapshot ends up at.
+    pub new_office: Option<Uuid>,
+    pub notes: Option<String>,
+    pub failure_reason: Option<DeliveryFailureReason>,
+    pub attempt: Option<i32>,
+    pub proof_of_delivery: Option<ProofOfDelivery>,
+    /// Promised delivery time, from the snapshot.
+    pub promised_by: Option<DateTime<Utc>>,
+}
+
+/// Writes the projection trio for a status change: event, history row and
+/// snapshot. Deliveries also collect the cash on delivery. Callers validate
+/// the change first.
+pub(crate) async fn write_status_change<E>(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    change: StatusChangeWrite,
+) -> Result<(), E>
+where
+    E: From<EnsureStreamError> + From<AppendError> + From<ShipmentSnapshotError>,
+{
+    // projection trio
+    core_eventstore::adapter::streams::ensure_stream(txn, change.shipment_id, "shipment").await?;
+
+    let event = StatusChanged {
+        shipment_id: change.shipment_id.to_string(),
+        from_status: change.from_status,
+        to_status: change.to_status,
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
         },
-        "occured_at" => int!(occured_at),
-        "notes" => match input.notes {
-            Some(ref notes) => string!(notes),
-            None => null!(),
-        }
+        from_office: change.from_office.map(office_context),
+        to_office: change.to_office.map(office_context),
+        occurred_at_ms: Utc::now().timestamp_millis(),
+        notes: change.notes.clone(),
+        failure_reason: change.failure_reason,
+        attempt: change.attempt.map(|n| n as u32),
+        proof_of_delivery: change.proof_of_delivery,
     };
 
     // immutable audit
-    core_eventstore::adapter::append::append_package(
-        db,
-        input.shipment_id,
-        "StatusChanged",
-        &payload,
+    append_package_expecting(
+        txn,
+        change.shipment_id,
+        change.expected_seq,
+        StatusChanged::EVENT_TYPE,
+        StatusChanged::SCHEMA_VERSION,
+        &event.encode(),
     )
     .await?;
 
+    // a change after the promise means it was broken while the shipment
+    // sat here; flag it now rather than leave it to the periodic check,
+    // which never sees the shipment once it is delivered
+    let now = Utc::now();
+    if let Some(promised_by) = change.promised_by
+        && promised_by < now
+    {
+        // whole millis, so the stamp matches the event exactly
+        let at = DateTime::from_timestamp_millis(now.timestamp_millis()).unwrap_or(now);
+
+        record_sla_breach::<E>(
+            txn,
+            change.shipment_id,
+            promised_by,
+            change.from_status,
+            change.from_office,
+            at,
+        )
+        .await?;
+    }
+
     // history row
-    ShipmentsRepo::insert_history(
-        db,
-        input.shipment_id,
-        Some(from_status),
-        input.to_status,
-        Some(actor.user_id),
-        current_office,
-        input.notes.clone(),
+    ShipmentsRepo::append_history(
+        txn,
+        change.shipment_id,
+        HistoryRow {
+            from_status: Some(change.from_status),
+            to_status: change.to_status,
+            actor_user_id: Some(actor.user_id),
+            office_id: change.from_office,
+            notes: change.notes,
+            failure_reason: change.failure_reason,
+            changed_at: Utc::now().into(),
+        },
     )
     .await?;
 
-    // snapshot update
-    // only hop office when going to IN_TRANSIT
-    let new_office = if input.to_status == ShipmentStatus::InTransit {
-        input.to_office_id.or(current_office)
-    } else {
-        None // Keep old
-    };
+    ShipmentsRepo::update_snapshot_status(
+        txn,
+        change.shipment_id,
+        change.to_status,
+        change.new_office,
+    )
+    .await?;
 
-    ShipmentsRepo::update_snapshot_status(db, input.shipment_id, input.to_status, new_office)
-        .await?;
+    if let Some(attempt) = change.attempt {
+        ShipmentsRepo::set_delivery_attempts(txn, change.shipment_id, attempt).await?;
+    }
+
+    if change.to_status == ShipmentStatus::Delivered {
+        collect_cod::<E>(txn, actor, change.shipment_id, change.from_office).await?;
+    }
 
     Ok(())
 }
+
+/// Statuses that hand the shipment to its recipient.
+pub(crate) fn is_delivery(status: ShipmentStatus) -> bool {
+    matches!(
+        status,
+        ShipmentStatus::OutForDelivery | ShipmentStatus::Delivered
+    )
+}
+
+/// Employees can only write to shipments currently at one of their
+/// offices; `forbidden` is returned otherwise.
+pub(crate) fn ensure_writable<E>(
+    actor: &ActorContext,
+    current_office: Option<Uuid>,
+    forbidden: E,
+) -> Result<(), E> {
+    if actor.is_admin() {
+        return Ok(());
+    }
+
+    match current_office {
+        Some(office) if actor.allowed_office_ids.contains(&office) => Ok(()),
+        _ => Err(forbidden),
+    }
+}
+
+pub(crate) fn office_context(office_id: Uuid) -> OfficeContext {
+    OfficeContext {
+        office_id: office_id.to_string(),
+    }
+}
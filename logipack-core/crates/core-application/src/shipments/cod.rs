@@ -0,0 +1,217 @@
+use chrono::Utc;
+use core_data::entity::cod_remittances;
+use core_data::repository::cod_repo::{CodCollectionRow, CodRepo, RemittanceRow};
+use core_data::repository::shipments_repo::{
+    ShipmentDetails, ShipmentSnapshotError, ShipmentsRepo,
+};
+use core_domain::shipment::{ActorRef, CodCollected, CodRemitted, Currency, EventCodec};
+use core_eventstore::adapter::append::{AppendError, append_package_expecting};
+use core_eventstore::adapter::streams::{EnsureStreamError, ensure_stream};
+use sea_orm::{DatabaseConnection, DatabaseTransaction, DbErr, TransactionTrait};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+use crate::shipments::change_status::office_context;
+
+#[derive(Debug, Clone)]
+pub struct RemitCod {
+    pub client_id: Uuid,
+    pub currency: Currency,
+    /// Only remit the cash held by this office.
+    pub office_id: Option<Uuid>,
+    pub reference: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum RemitCodError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("nothing to remit")]
+    NothingToRemit,
+    /// Some of the collections were remitted concurrently.
+    #[error("collections were remitted concurrently")]
+    Conflict,
+    #[error("snapshot error: {0}")]
+    SnapshotError(#[from] ShipmentSnapshotError),
+    #[error("stream error: {0}")]
+    StreamError(#[from] EnsureStreamError),
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+    #[error("eventstore error: {0}")]
+    EventstoreError(#[from] AppendError),
+}
+
+/// Records the cash on delivery of a shipment as collected by the office it
+/// was delivered from. No-op for shipments without cash on delivery.
+///
+/// Called by `write_status_change` on every change to DELIVERED, so whole
+/// shipments and piece-derived deliveries collect the same way.
+pub(crate) async fn collect_cod<E>(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    shipment_id: Uuid,
+    office_id: Option<Uuid>,
+) -> Result<(), E>
+where
+    E: From<EnsureStreamError> + From<AppendError> + From<ShipmentSnapshotError>,
+{
+    let snap = ShipmentsRepo::get_snapshot(txn, shipment_id).await?;
+
+    let Some(cod) = ShipmentDetails::from_model(&snap).cod else {
+        return Ok(());
+    };
+
+    // DELIVERED is terminal, but never collect twice
+    if CodRepo::find_by_shipment(txn, shipment_id).await?.is_some() {
+        return Ok(());
+    }
+
+    let collection_id = Uuid::new_v4();
+    let now = Utc::now();
+
+    ensure_stream(txn, shipment_id, "shipment").await?;
+
+    let event = CodCollected {
+        shipment_id: shipment_id.to_string(),
+        collection_id: collection_id.to_string(),
+        amount_cents: cod.amount_cents,
+        currency: cod.currency.clone(),
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
+        },
+        office: office_id.map(office_context),
+        occurred_at_ms: now.timestamp_millis(),
+    };
+
+    append_package_expecting(
+        txn,
+        shipment_id,
+        None,
+        CodCollected::EVENT_TYPE,
+        CodCollected::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await?;
+
+    CodRepo::insert_collection(
+        txn,
+        shipment_id,
+        CodCollectionRow {
+            id: collection_id,
+            client_id: snap.client_id,
+            office_id,
+            amount_cents: cod.amount_cents,
+            currency: cod.currency.to_string(),
+            collected_by: Some(actor.user_id),
+            collected_at: now.into(),
+            remittance_id: None,
+            remitted_at: None,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Pays out every collection of a client in one currency that is not
+/// remitted yet. Admin only.
+///
+/// Each remitted shipment gets a `CodRemitted` in its stream; the
+/// remittance row and the events commit together.
+pub async fn remit_cod(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    input: RemitCod,
+) -> Result<cod_remittances::Model, RemitCodError> {
+    if !actor.is_admin() {
+        return Err(RemitCodError::Forbidden);
+    }
+
+    let txn = db.begin().await?;
+
+    match remit_cod_txn(&txn, actor, input).await {
+        Ok(remittance) => {
+            txn.commit().await?;
+            Ok(remittance)
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+async fn remit_cod_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    input: RemitCod,
+) -> Result<cod_remittances::Model, RemitCodError> {
+    let collections = CodRepo::list_unremitted(
+        txn,
+        input.client_id,
+        input.currency.as_str(),
+        input.office_id,
+    )
+    .await?;
+
+    if collections.is_empty() {
+        return Err(RemitCodError::NothingToRemit);
+    }
+
+    let remittance_id = Uuid::new_v4();
+    let reference = input
+        .reference
+        .map(|r| r.trim().to_owned())
+        .filter(|r| !r.is_empty());
+    let now = Utc::now();
+
+    for collection in &collections {
+        ensure_stream(txn, collection.shipment_id, "shipment").await?;
+
+        let event = CodRemitted {
+            shipment_id: collection.shipment_id.to_string(),
+            collection_id: collection.id.to_string(),
+            remittance_id: remittance_id.to_string(),
+            amount_cents: collection.amount_cents,
+            currency: input.currency.clone(),
+            actor: ActorRef {
+                id: actor.user_id.to_string(),
+            },
+            occurred_at_ms: now.timestamp_millis(),
+            reference: reference.clone(),
+        };
+
+        append_package_expecting(
+            txn,
+            collection.shipment_id,
+            None,
+            CodRemitted::EVENT_TYPE,
+            CodRemitted::SCHEMA_VERSION,
+            &event.encode(),
+        )
+        .await?;
+    }
+
+    let remittance = CodRepo::insert_remittance(
+        txn,
+        RemittanceRow {
+            id: remittance_id,
+            client_id: input.client_id,
+            currency: input.currency.to_string(),
+            amount_cents: collections.iter().map(|c| c.amount_cents).sum(),
+            shipment_count: collections.len() as i32,
+            reference,
+            created_by: Some(actor.user_id),
+            created_at: now.into(),
+        },
+        collections.iter().map(|c| c.id).collect(),
+    )
+    .await
+    .map_err(|err| match err {
+        ShipmentSnapshotError::DbError(DbErr::RecordNotUpdated) => RemitCodError::Conflict,
+        err => err.into(),
+    })?;
+
+    Ok(remittance)
+}
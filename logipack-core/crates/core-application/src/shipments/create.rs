@@ -1,31 +1,74 @@
 use chrono::Utc;
-use core_data::repository::shipments_repo::ShipmentsRepo;
-use core_domain::shipment::ShipmentStatus;
+use core_data::repository::office_calendars_repo::{OfficeCalendarRepoError, OfficeCalendarsRepo};
+use core_data::repository::office_links_repo::{OfficeLinkRepoError, OfficeLinksRepo};
+use core_data::repository::offices_repo::{OfficeError, OfficesRepo};
+use core_data::repository::shipments_repo::{ShipmentDetails, ShipmentsRepo};
+use core_data::repository::tariffs_repo::TariffRepoError;
+use core_domain::errors::{CashOnDeliveryError, PricingError, TrackingNumberError};
+use core_domain::network::{Route, estimate_delivery};
+use core_domain::pricing::{Price, QuoteRequest, ServiceLevel, Tariff};
+use core_domain::shipment::tracking::{FALLBACK_PREFIX, office_prefix};
+use core_domain::shipment::{
+    ActorRef, CashOnDelivery, EventCodec, Parcel, Party, ShipmentCreated, ShipmentStatus,
+    TrackingNumber,
+};
 use core_eventstore::adapter::events::append_event;
 use core_eventstore::adapter::streams::ensure_stream;
-use sea_orm::DatabaseConnection;
+use core_eventstore::adapter::upcast::INITIAL_SCHEMA_VERSION;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
 use thiserror::Error;
 
 use uuid::Uuid;
 
 use crate::actor::ActorContext;
+use crate::shipments::change_status::office_context;
+use crate::shipments::route::record_shipment_route;
+use crate::tariffs::tariff_in_force;
+use crate::validation::shipment::{ShipmentValidationError, validate_shipment};
 
-use strata::value::Value;
-use strata::{int, map, null, string};
+use strata::map;
 
-#[derive(Debug, Clone)]
+/// `current_office_id` is the origin office. Sender, recipient and parcel
+/// details are optional so a shipment can be registered before they are known;
+/// it is priced only when origin, destination and parcel are all given.
+#[derive(Debug, Clone, Default)]
 pub struct CreateShipment {
     pub client_id: Uuid,
     pub current_office_id: Option<Uuid>,
     pub notes: Option<String>,
+    pub sender: Option<Party>,
+    pub recipient: Option<Party>,
+    pub destination_office_id: Option<Uuid>,
+    pub parcel: Option<Parcel>,
+    /// Collected from the recipient on delivery.
+    pub cod: Option<CashOnDelivery>,
+    pub service_level: ServiceLevel,
 }
 
 #[derive(Debug, Error)]
 pub enum CreateShipmentError {
     #[error("forbidden")]
     Forbidden,
+    #[error("validation error: {0}")]
+    Validation(#[from] ShipmentValidationError),
+    #[error("invalid cash on delivery: {0}")]
+    CashOnDelivery(#[from] CashOnDeliveryError),
+    #[error("destination office not found")]
+    DestinationNotFound,
+    #[error("cannot price shipment: {0}")]
+    Pricing(#[from] PricingError),
+    #[error("tariff lookup error: {0}")]
+    Tariff(#[from] TariffRepoError),
+    #[error("route planning error: {0}")]
+    Network(#[from] OfficeLinkRepoError),
+    #[error("office calendar error: {0}")]
+    Calendar(#[from] OfficeCalendarRepoError),
     #[error("db error: {0}")]
     DbError(#[from] sea_orm::DbErr),
+    #[error("office lookup error: {0}")]
+    OfficeError(#[from] OfficeError),
+    #[error("tracking number error: {0}")]
+    TrackingNumber(#[from] TrackingNumberError),
     #[error("eventstore error: {0}")]
     EventstoreError(#[from] core_eventstore::adapter::append::AppendError),
     #[error("stream error: {0}")]
@@ -34,9 +77,18 @@ pub enum CreateShipmentError {
     SnapshotError(#[from] core_data::repository::shipments_repo::ShipmentSnapshotError),
 }
 
+/// Creates a shipment in NEW.
+///
+/// Priced by the tariff version in force now, or by `tariff` when no
+/// version is; the price and the version are recorded with the shipment.
+/// Shipments with distinct origin and destination offices get a planned
+/// route; any shipment whose destination can be reached gets a promised
+/// delivery time that waits for the working hours of every office on the
+/// route.
 pub async fn create_shipment(
     db: &DatabaseConnection,
     actor: &ActorContext,
+    tariff: &Tariff,
     input: CreateShipment,
 ) -> Result<Uuid, CreateShipmentError> {
     // Office scope policy
@@ -51,22 +103,138 @@ pub async fn create_shipment(
         }
     }
 
+    validate_shipment(
+        input.sender.as_ref(),
+        input.recipient.as_ref(),
+        input.parcel.as_ref(),
+    )?;
+
+    if let Some(cod) = &input.cod {
+        cod.validate()?;
+    }
+
+    // tracking numbers are prefixed with the origin office city
+    let origin = match input.current_office_id {
+        Some(office_id) => OfficesRepo::get_office_by_id(db, office_id).await?,
+        None => None,
+    };
+    let prefix = origin
+        .as_ref()
+        .map(|office| office_prefix(&office.city))
+        .unwrap_or_else(|| FALLBACK_PREFIX.to_string());
+
+    let destination = match input.destination_office_id {
+        Some(office_id) => OfficesRepo::get_office_by_id(db, office_id)
+            .await
+            .map_err(|err| match err {
+                OfficeError::RecordNotFound => CreateShipmentError::DestinationNotFound,
+                err => err.into(),
+            })?,
+        None => None,
+    };
+
+    // price as charged, recorded with the shipment
+    let priced = match (&origin, &destination, &input.parcel) {
+        (Some(origin), Some(destination), Some(parcel)) => {
+            let (tariff_id, tariff) = tariff_in_force(db, Utc::now(), tariff).await?;
+            let price = tariff.quote(QuoteRequest {
+                origin_city: &origin.city,
+                destination_city: &destination.city,
+                parcel,
+                cod: input.cod.as_ref(),
+                service_level: input.service_level,
+            })?;
+
+            Some((tariff_id, price))
+        }
+        _ => None,
+    };
+
     let shipment_id = Uuid::new_v4();
+
+    // snapshot, history and eventstore commit together or not at all
+    let txn = db.begin().await?;
+
+    match create_shipment_txn(&txn, actor, shipment_id, &prefix, priced, input).await {
+        Ok(()) => {
+            txn.commit().await?;
+            Ok(shipment_id)
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+async fn create_shipment_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    shipment_id: Uuid,
+    tracking_prefix: &str,
+    priced: Option<(Option<Uuid>, Price)>,
+    input: CreateShipment,
+) -> Result<(), CreateShipmentError> {
     let status = ShipmentStatus::New;
+    let (origin, destination) = (input.current_office_id, input.destination_office_id);
+    let (tariff_id, price) = match priced {
+        Some((tariff_id, price)) => (tariff_id, Some(price)),
+        None => (None, None),
+    };
+
+    let now = Utc::now();
+
+    // the route sets the delivery promise, so it is planned up front
+    let route = match (origin, destination) {
+        (Some(origin), Some(destination)) => OfficeLinksRepo::network(txn)
+            .await?
+            .plan(&origin, &destination),
+        _ => None,
+    };
+    let promised_by_ms = match &route {
+        Some(route) => {
+            let calendars = OfficeCalendarsRepo::for_offices(txn, route.offices().copied()).await?;
+            estimate_delivery(
+                route,
+                now.timestamp_millis(),
+                input.service_level,
+                |office| calendars.get(office),
+            )
+        }
+        None => None,
+    };
+
+    let serial = ShipmentsRepo::next_tracking_serial(txn).await?;
+    let tracking_number = TrackingNumber::new(tracking_prefix, serial)?;
 
     // snapshot
     ShipmentsRepo::insert_snapshot(
-        db,
+        txn,
         shipment_id,
+        Some(tracking_number.to_string()),
         input.client_id,
         status,
         input.current_office_id,
+        ShipmentDetails {
+            sender: input.sender.clone(),
+            recipient: input.recipient.clone(),
+            destination_office_id: input.destination_office_id,
+            parcel: input.parcel.clone(),
+            cod: input.cod.clone(),
+            service_level: input.service_level,
+            price: price.clone(),
+            tariff_id,
+        },
     )
     .await?;
 
+    if let Some(promised_by) = promised_by_ms.and_then(chrono::DateTime::from_timestamp_millis) {
+        ShipmentsRepo::set_promised_by(txn, shipment_id, promised_by.into()).await?;
+    }
+
     // history
     ShipmentsRepo::insert_history(
-        db,
+        txn,
         shipment_id,
         None,
         status,
@@ -77,31 +245,56 @@ pub async fn create_shipment(
     .await?;
 
     // ensure stream
-    ensure_stream(db, shipment_id, "shipment").await?;
+    ensure_stream(txn, shipment_id, "shipment").await?;
 
     // eventstore
-    append_event(db, shipment_id, "shipment", &map! {}).await?;
-
-    let occured_at = Utc::now().timestamp_millis();
-
-    let payload: Value = map! {
-        "event_type" => string!("ShipmentCreated"),
-        "shipment_id" => string!(shipment_id.to_string()),
-        "status" => string!(status.to_string()),
-        "actor_user_id" => string!(actor.user_id.to_string()),
-        "office_id" => match input.current_office_id {
-            Some(office_id) => string!(office_id.to_string()),
-            None => null!(),
+    append_event(
+        txn,
+        shipment_id,
+        "shipment",
+        INITIAL_SCHEMA_VERSION,
+        &map! {},
+    )
+    .await?;
+
+    let event = ShipmentCreated {
+        shipment_id: shipment_id.to_string(),
+        tracking_number: Some(tracking_number.to_string()),
+        client_id: Some(input.client_id.to_string()),
+        status,
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
         },
-        "occured_at" => int!(occured_at),
-        "notes" => match input.notes {
-            Some(notes) => string!(notes),
-            None => null!(),
-        }
+        office: input.current_office_id.map(office_context),
+        occurred_at_ms: now.timestamp_millis(),
+        notes: input.notes,
+        sender: input.sender,
+        recipient: input.recipient,
+        destination_office: input.destination_office_id.map(office_context),
+        parcel: input.parcel,
+        cod: input.cod,
+        service_level: input.service_level,
+        price,
+        tariff_id: tariff_id.map(|id| id.to_string()),
+        promised_by_ms,
     };
 
-    core_eventstore::adapter::events::append_event(db, shipment_id, "ShipmentCreated", &payload)
-        .await?;
+    append_event(
+        txn,
+        shipment_id,
+        ShipmentCreated::EVENT_TYPE,
+        ShipmentCreated::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await?;
+
+    // a shipment handed in at its destination has no route to follow
+    if let (Some(origin), Some(destination)) = (origin, destination)
+        && origin != destination
+    {
+        let route = route.unwrap_or(Route { stops: Vec::new() });
+        record_shipment_route::<CreateShipmentError>(txn, actor, shipment_id, route).await?;
+    }
 
-    Ok(shipment_id)
+    Ok(())
 }
@@ -5,9 +5,18 @@ use core_data::{
 use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
+use crate::actor::ActorContext;
+use crate::shipments::visibility::{ReadPolicy, ensure_visible};
+
+/// Reads a shipment snapshot. Shipments hidden from `actor` by `policy`
+/// are reported as not found.
 pub async fn get_shipment(
     db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
     shipment_id: Uuid,
 ) -> Result<shipments::Model, ShipmentSnapshotError> {
+    ensure_visible(db, actor, policy, shipment_id).await?;
+
     ShipmentsRepo::get_snapshot(db, shipment_id).await
 }
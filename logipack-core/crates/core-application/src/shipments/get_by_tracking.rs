@@ -0,0 +1,39 @@
+use core_data::repository::shipments_repo::{ShipmentSnapshotError, ShipmentsRepo};
+use core_domain::errors::TrackingNumberError;
+use core_domain::shipment::TrackingNumber;
+use sea_orm::{DatabaseConnection, DbErr};
+use thiserror::Error;
+
+use crate::actor::ActorContext;
+use crate::shipments::get_detail::{ShipmentDetailView, get_shipment_detail};
+use crate::shipments::visibility::ReadPolicy;
+
+#[derive(Debug, Error)]
+pub enum TrackingLookupError {
+    #[error("invalid tracking number: {0}")]
+    InvalidTrackingNumber(#[from] TrackingNumberError),
+    #[error("{0}")]
+    Snapshot(#[from] ShipmentSnapshotError),
+}
+
+/// Resolves a tracking number typed by a person to the shipment detail.
+///
+/// The format and check digit are verified before any query, so typos are
+/// rejected without touching the database. Unknown numbers and shipments
+/// hidden from `actor` are both reported as not found.
+pub async fn get_shipment_by_tracking(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    code: &str,
+) -> Result<ShipmentDetailView, TrackingLookupError> {
+    let tracking_number = TrackingNumber::parse(code)?;
+
+    let shipment_id = ShipmentsRepo::find_id_by_tracking_number(db, tracking_number.as_str())
+        .await?
+        .ok_or_else(|| {
+            ShipmentSnapshotError::from(DbErr::RecordNotFound("shipment not found".into()))
+        })?;
+
+    Ok(get_shipment_detail(db, actor, policy, shipment_id).await?)
+}
@@ -0,0 +1,54 @@
+use core_data::{
+    entity::{clients, offices, shipment_status_history, shipments},
+    repository::shipment_routes_repo::ShipmentRoutesRepo,
+    repository::shipments_repo::{ShipmentSnapshotError, ShipmentsRepo},
+};
+use core_domain::network::Route;
+use core_eventstore::adapter::read::count_stream_packages;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+use crate::shipments::timeline::STREAM_METADATA_EVENT_TYPE;
+use crate::shipments::visibility::{ReadPolicy, ensure_visible};
+
+/// Shipment snapshot together with what a detail view needs around it.
+#[derive(Debug, Clone)]
+pub struct ShipmentDetailView {
+    pub shipment: shipments::Model,
+    /// `None` only if the client row is gone; the FK normally prevents that.
+    pub client: Option<clients::Model>,
+    pub current_office: Option<offices::Model>,
+    pub latest_change: Option<shipment_status_history::Model>,
+    /// Planned route, `None` when the shipment has none.
+    pub route: Option<Route<Uuid>>,
+    /// Domain events in the shipment stream, without the metadata package.
+    pub event_count: u64,
+}
+
+/// Reads a shipment with its client, current office, latest status change,
+/// planned route and event count. Shipments hidden from `actor` by `policy`
+/// are reported as not found.
+pub async fn get_shipment_detail(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    shipment_id: Uuid,
+) -> Result<ShipmentDetailView, ShipmentSnapshotError> {
+    ensure_visible(db, actor, policy, shipment_id).await?;
+
+    let (shipment, client, current_office) =
+        ShipmentsRepo::get_snapshot_with_relations(db, shipment_id).await?;
+    let latest_change = ShipmentsRepo::latest_history(db, shipment_id).await?;
+    let route = ShipmentRoutesRepo::get(db, shipment_id).await?;
+    let event_count = count_stream_packages(db, shipment_id, &[STREAM_METADATA_EVENT_TYPE]).await?;
+
+    Ok(ShipmentDetailView {
+        shipment,
+        client,
+        current_office,
+        latest_change,
+        route,
+        event_count,
+    })
+}
@@ -0,0 +1,185 @@
+use chrono::Utc;
+use core_data::repository::shipments_repo::{ShipmentSnapshotError, ShipmentsRepo};
+use core_domain::shipment::{
+    ActorRef, EventCodec, HoldPlaced, HoldReason, HoldReleased, ShipmentStatus, TransitionTable,
+};
+use core_eventstore::adapter::append::{AppendError, append_package_expecting};
+use core_eventstore::adapter::streams::EnsureStreamError;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+use crate::shipments::change_status::{ensure_writable, office_context};
+
+#[derive(Debug, Clone)]
+pub struct PlaceHold {
+    pub shipment_id: Uuid,
+    pub reason: HoldReason,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReleaseHold {
+    pub shipment_id: Uuid,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum HoldError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("shipment is already on hold ({reason})")]
+    AlreadyOnHold { reason: String },
+    #[error("shipment is not on hold")]
+    NotOnHold,
+    #[error("shipment is in terminal status {status}")]
+    TerminalState { status: ShipmentStatus },
+    #[error("snapshot error: {0}")]
+    SnapshotError(#[from] ShipmentSnapshotError),
+    #[error("stream error: {0}")]
+    StreamError(#[from] EnsureStreamError),
+    #[error("db error: {0}")]
+    DbError(#[from] sea_orm::DbErr),
+    #[error("eventstore error: {0}")]
+    EventstoreError(#[from] AppendError),
+}
+
+/// Puts a shipment on hold. Its status is kept, but `change_status`
+/// rejects every change until the hold is released.
+///
+/// Same write rules as `change_status`: employees may only hold shipments
+/// currently at one of their offices. Finished shipments cannot be held.
+pub async fn place_hold(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    transitions: &TransitionTable,
+    input: PlaceHold,
+) -> Result<(), HoldError> {
+    let txn = db.begin().await?;
+
+    match place_hold_txn(&txn, actor, transitions, input).await {
+        Ok(()) => {
+            txn.commit().await?;
+            Ok(())
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+/// Lifts the active hold of a shipment.
+pub async fn release_hold(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    input: ReleaseHold,
+) -> Result<(), HoldError> {
+    let txn = db.begin().await?;
+
+    match release_hold_txn(&txn, actor, input).await {
+        Ok(()) => {
+            txn.commit().await?;
+            Ok(())
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+async fn place_hold_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    transitions: &TransitionTable,
+    input: PlaceHold,
+) -> Result<(), HoldError> {
+    // locked, so concurrent holds see each other
+    let snap = ShipmentsRepo::get_snapshot_for_update(txn, input.shipment_id).await?;
+
+    ensure_writable(actor, snap.current_office_id, HoldError::Forbidden)?;
+
+    if let Some(reason) = snap.hold_reason {
+        return Err(HoldError::AlreadyOnHold { reason });
+    }
+
+    let status: ShipmentStatus = snap.current_status.parse().unwrap_or(ShipmentStatus::New);
+    if transitions.is_terminal(status) {
+        return Err(HoldError::TerminalState { status });
+    }
+
+    let now = Utc::now();
+
+    core_eventstore::adapter::streams::ensure_stream(txn, input.shipment_id, "shipment").await?;
+
+    let event = HoldPlaced {
+        shipment_id: input.shipment_id.to_string(),
+        reason: input.reason,
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
+        },
+        office: snap.current_office_id.map(office_context),
+        occurred_at_ms: now.timestamp_millis(),
+        notes: input.notes,
+    };
+
+    append_package_expecting(
+        txn,
+        input.shipment_id,
+        None,
+        HoldPlaced::EVENT_TYPE,
+        HoldPlaced::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await?;
+
+    ShipmentsRepo::set_hold(txn, input.shipment_id, Some((input.reason, now.into()))).await?;
+
+    Ok(())
+}
+
+async fn release_hold_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    input: ReleaseHold,
+) -> Result<(), HoldError> {
+    let snap = ShipmentsRepo::get_snapshot_for_update(txn, input.shipment_id).await?;
+
+    ensure_writable(actor, snap.current_office_id, HoldError::Forbidden)?;
+
+    // rows only ever hold reasons written by `place_hold`
+    let reason: HoldReason = snap
+        .hold_reason
+        .as_deref()
+        .and_then(|raw| raw.parse().ok())
+        .ok_or(HoldError::NotOnHold)?;
+
+    core_eventstore::adapter::streams::ensure_stream(txn, input.shipment_id, "shipment").await?;
+
+    let event = HoldReleased {
+        shipment_id: input.shipment_id.to_string(),
+        reason,
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
+        },
+        office: snap.current_office_id.map(office_context),
+        occurred_at_ms: Utc::now().timestamp_millis(),
+        notes: input.notes,
+    };
+
+    append_package_expecting(
+        txn,
+        input.shipment_id,
+        None,
+        HoldReleased::EVENT_TYPE,
+        HoldReleased::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await?;
+
+    ShipmentsRepo::set_hold(txn, input.shipment_id, None).await?;
+
+    Ok(())
+}
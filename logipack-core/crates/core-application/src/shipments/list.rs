@@ -1,11 +1,21 @@
-use core_data::{
-    entity::shipments,
-    repository::shipments_repo::{ShipmentSnapshotError, ShipmentsRepo},
+use core_data::repository::{
+    shipment_query::{ShipmentPage, ShipmentQuery, ShipmentQueryError},
+    shipments_repo::ShipmentsRepo,
 };
 use sea_orm::DatabaseConnection;
 
+use crate::actor::ActorContext;
+use crate::shipments::visibility::ReadPolicy;
+
+/// Lists shipments `actor` may read under `policy`. Any `visible_to` set by
+/// the caller is replaced.
 pub async fn list_shipments(
     db: &DatabaseConnection,
-) -> Result<Vec<shipments::Model>, ShipmentSnapshotError> {
-    ShipmentsRepo::list_snapshots(db).await
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    mut query: ShipmentQuery,
+) -> Result<ShipmentPage, ShipmentQueryError> {
+    query.visible_to = policy.visibility_for(actor);
+
+    ShipmentsRepo::query_snapshots(db, &query).await
 }
@@ -1,5 +1,19 @@
 pub mod change_status;
+pub mod cod;
 pub mod create;
 pub mod get;
+pub mod get_by_tracking;
+pub mod get_detail;
+pub mod hold;
 pub mod list;
+pub mod next_statuses;
+pub mod pieces;
+pub mod pod;
+pub mod quote;
+pub mod rebuild;
+pub mod route;
+pub mod sla;
 pub mod timeline;
+pub mod track;
+pub mod upcast;
+pub mod visibility;
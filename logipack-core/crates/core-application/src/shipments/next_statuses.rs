@@ -0,0 +1,55 @@
+use core_data::repository::shipment_pieces_repo::ShipmentPiecesRepo;
+use core_data::repository::shipments_repo::{ShipmentSnapshotError, ShipmentsRepo};
+use core_domain::shipment::{ShipmentStatus, TransitionRule, TransitionTable};
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+use crate::shipments::visibility::{ReadPolicy, ensure_visible};
+
+/// Status changes `actor` could make on a shipment right now.
+///
+/// Mirrors the checks of `change_status` that do not depend on the request:
+/// employees get nothing for shipments outside their offices, admin-only
+/// edges are left out for them, and so are edges whose delivery attempts
+/// are used up. Shipments on hold have no next status until released, and
+/// multi-piece shipments have none at all since their pieces are scanned
+/// instead. Guards needing request data (note, target office) are
+/// returned on the rules for the caller to satisfy.
+pub async fn allowed_next_statuses(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    transitions: &TransitionTable,
+    shipment_id: Uuid,
+) -> Result<Vec<TransitionRule>, ShipmentSnapshotError> {
+    ensure_visible(db, actor, policy, shipment_id).await?;
+
+    let snap = ShipmentsRepo::get_snapshot(db, shipment_id).await?;
+    let from_status: ShipmentStatus = snap.current_status.parse().unwrap_or(ShipmentStatus::New);
+
+    let can_write = actor.is_admin()
+        || snap
+            .current_office_id
+            .is_some_and(|office| actor.allowed_office_ids.contains(&office));
+
+    if !can_write || snap.hold_reason.is_some() || transitions.is_terminal(from_status) {
+        return Ok(Vec::new());
+    }
+
+    if !ShipmentPiecesRepo::list_by_shipment(db, shipment_id)
+        .await?
+        .is_empty()
+    {
+        return Ok(Vec::new());
+    }
+
+    let attempts = u32::try_from(snap.delivery_attempts).unwrap_or(0);
+
+    Ok(transitions
+        .next(from_status)
+        .filter(|rule| actor.is_admin() || !rule.admin_only)
+        .filter(|rule| rule.max_attempts.is_none_or(|max| attempts < max))
+        .copied()
+        .collect())
+}
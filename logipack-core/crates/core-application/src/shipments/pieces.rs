@@ -0,0 +1,395 @@
+use chrono::Utc;
+use core_data::entity::shipment_pieces;
+use core_data::repository::shipment_pieces_repo::{PieceRow, ShipmentPiecesRepo};
+use core_data::repository::shipment_routes_repo::ShipmentRoutesRepo;
+use core_data::repository::shipments_repo::{ShipmentSnapshotError, ShipmentsRepo};
+use core_domain::errors::{PieceBarcodeError, TrackingNumberError, TransitionError};
+use core_domain::shipment::{
+    ActorRef, DeliveryFailureReason, EventCodec, MAX_PIECES, PieceAdded, PieceBarcode,
+    PieceScanned, ShipmentStatus, TrackingNumber, TransitionContext, TransitionTable,
+    derive_parent_status,
+};
+use core_eventstore::adapter::append::{AppendError, append_package_expecting};
+use core_eventstore::adapter::streams::EnsureStreamError;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+use crate::shipments::change_status::{
+    StatusChangeWrite, ensure_writable, is_delivery, office_context, write_status_change,
+};
+use crate::shipments::route::{check_route_arrival, check_route_hop};
+use crate::shipments::visibility::{ReadPolicy, ensure_visible};
+
+#[derive(Debug, Clone, Default)]
+pub struct AddPiece {
+    pub shipment_id: Uuid,
+    pub weight_g: Option<i32>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScanPiece {
+    pub barcode: String,
+    pub to_status: ShipmentStatus,
+    pub to_office_id: Option<Uuid>,
+    pub notes: Option<String>,
+    /// Required when scanning to DELIVERY_FAILED, rejected otherwise.
+    pub failure_reason: Option<DeliveryFailureReason>,
+    /// Lets an admin hop the piece off the shipment's planned route, or
+    /// deliver it before the end. The route is kept for the other pieces.
+    pub override_route: bool,
+}
+
+/// Piece and shipment status after a scan.
+#[derive(Debug, Clone)]
+pub struct ScannedPiece {
+    pub piece: shipment_pieces::Model,
+    pub shipment_status: ShipmentStatus,
+}
+
+#[derive(Debug, Error)]
+pub enum PieceError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid piece barcode: {0}")]
+    InvalidBarcode(#[from] PieceBarcodeError),
+    #[error("piece not found")]
+    NotFound,
+    #[error("shipment has no tracking number")]
+    NoTrackingNumber,
+    #[error("pieces cannot be added once the shipment is {status}")]
+    PiecesClosed { status: ShipmentStatus },
+    #[error("a shipment has at most {MAX_PIECES} pieces")]
+    TooManyPieces,
+    #[error("weight must be positive")]
+    InvalidWeight,
+    #[error("shipment is on hold ({reason})")]
+    OnHold { reason: String },
+    /// IN_TRANSIT hops follow the planned route unless an admin overrides.
+    #[error("hop leaves the planned route")]
+    OffRoute { expected_office_id: Option<Uuid> },
+    /// Deliveries wait until the piece reaches the end of the route.
+    #[error("piece has not reached the shipment's destination")]
+    NotAtDestination { destination_office_id: Uuid },
+    #[error("domain transition error: {0:?}")]
+    Domain(#[from] TransitionError),
+    #[error("stored tracking number is invalid: {0}")]
+    TrackingNumber(#[from] TrackingNumberError),
+    #[error("snapshot error: {0}")]
+    SnapshotError(#[from] ShipmentSnapshotError),
+    #[error("stream error: {0}")]
+    StreamError(#[from] EnsureStreamError),
+    #[error("db error: {0}")]
+    DbError(#[from] sea_orm::DbErr),
+    #[error("eventstore error: {0}")]
+    EventstoreError(#[from] AppendError),
+}
+
+/// Adds a piece to a shipment.
+///
+/// Pieces can be added until the shipment is processed. Each one starts
+/// at the shipment's status and office and gets the next piece number.
+/// Once a shipment has pieces, its status only changes through
+/// `scan_piece`.
+pub async fn add_piece(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    input: AddPiece,
+) -> Result<shipment_pieces::Model, PieceError> {
+    let txn = db.begin().await?;
+
+    match add_piece_txn(&txn, actor, input).await {
+        Ok(piece) => {
+            txn.commit().await?;
+            Ok(piece)
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+/// Moves one piece to a new status.
+///
+/// The piece goes through the same transition table, permission and hold
+/// checks as `change_status` does for whole shipments. When the piece
+/// statuses then imply a new shipment status (see `derive_parent_status`),
+/// the shipment is moved too, with a regular `StatusChanged`.
+pub async fn scan_piece(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    transitions: &TransitionTable,
+    input: ScanPiece,
+) -> Result<ScannedPiece, PieceError> {
+    let txn = db.begin().await?;
+
+    match scan_piece_txn(&txn, actor, transitions, input).await {
+        Ok(scanned) => {
+            txn.commit().await?;
+            Ok(scanned)
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+/// Pieces of a shipment visible to `actor`, by piece number.
+pub async fn list_pieces(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    shipment_id: Uuid,
+) -> Result<Vec<shipment_pieces::Model>, ShipmentSnapshotError> {
+    ensure_visible(db, actor, policy, shipment_id).await?;
+
+    ShipmentPiecesRepo::list_by_shipment(db, shipment_id).await
+}
+
+async fn add_piece_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    input: AddPiece,
+) -> Result<shipment_pieces::Model, PieceError> {
+    // locked, so concurrent adds number their pieces one after another
+    let snap = ShipmentsRepo::get_snapshot_for_update(txn, input.shipment_id).await?;
+
+    ensure_writable(actor, snap.current_office_id, PieceError::Forbidden)?;
+
+    let status: ShipmentStatus = snap.current_status.parse().unwrap_or(ShipmentStatus::New);
+    if !matches!(status, ShipmentStatus::New | ShipmentStatus::Accepted) {
+        return Err(PieceError::PiecesClosed { status });
+    }
+
+    if input.weight_g.is_some_and(|w| w <= 0) {
+        return Err(PieceError::InvalidWeight);
+    }
+
+    let tracking_number = snap
+        .tracking_number
+        .as_deref()
+        .ok_or(PieceError::NoTrackingNumber)?;
+
+    let pieces = ShipmentPiecesRepo::list_by_shipment(txn, input.shipment_id).await?;
+    let piece_no = pieces.last().map_or(1, |piece| piece.piece_no + 1);
+    if piece_no as u32 > MAX_PIECES {
+        return Err(PieceError::TooManyPieces);
+    }
+
+    let barcode = PieceBarcode::new(TrackingNumber::parse(tracking_number)?, piece_no as u32)?;
+    let piece_id = Uuid::new_v4();
+    let now = Utc::now();
+
+    core_eventstore::adapter::streams::ensure_stream(txn, input.shipment_id, "shipment").await?;
+
+    let event = PieceAdded {
+        shipment_id: input.shipment_id.to_string(),
+        piece_id: piece_id.to_string(),
+        barcode: barcode.to_string(),
+        status,
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
+        },
+        office: snap.current_office_id.map(office_context),
+        occurred_at_ms: now.timestamp_millis(),
+        weight_g: input.weight_g,
+        description: input.description.clone(),
+    };
+
+    append_package_expecting(
+        txn,
+        input.shipment_id,
+        None,
+        PieceAdded::EVENT_TYPE,
+        PieceAdded::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await?;
+
+    let piece = ShipmentPiecesRepo::insert(
+        txn,
+        input.shipment_id,
+        PieceRow {
+            id: piece_id,
+            piece_no,
+            barcode: barcode.to_string(),
+            status,
+            current_office_id: snap.current_office_id,
+            weight_g: input.weight_g,
+            description: input.description,
+            created_at: now.into(),
+            updated_at: now.into(),
+        },
+    )
+    .await?;
+
+    Ok(piece)
+}
+
+async fn scan_piece_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    transitions: &TransitionTable,
+    input: ScanPiece,
+) -> Result<ScannedPiece, PieceError> {
+    let barcode = PieceBarcode::parse(&input.barcode)?;
+
+    let shipment_id = ShipmentPiecesRepo::find_by_barcode(txn, &barcode.to_string())
+        .await?
+        .ok_or(PieceError::NotFound)?
+        .shipment_id;
+
+    // locked before the piece is read, so scans of sibling pieces derive the
+    // parent status from each other's results rather than a stale snapshot
+    let snap = ShipmentsRepo::get_snapshot_for_update(txn, shipment_id).await?;
+    let piece = ShipmentPiecesRepo::find_by_barcode(txn, &barcode.to_string())
+        .await?
+        .ok_or(PieceError::NotFound)?;
+
+    // employees scan pieces at their offices, towards their offices
+    ensure_writable(actor, piece.current_office_id, PieceError::Forbidden)?;
+    if !actor.is_admin()
+        && let Some(to_office) = input.to_office_id
+        && !actor.allowed_office_ids.contains(&to_office)
+    {
+        return Err(PieceError::Forbidden);
+    }
+    if input.override_route && !actor.is_admin() {
+        return Err(PieceError::Forbidden);
+    }
+
+    if let Some(reason) = snap.hold_reason {
+        return Err(PieceError::OnHold { reason });
+    }
+
+    let from_status: ShipmentStatus = piece.status.parse().unwrap_or(ShipmentStatus::New);
+    let office_changed = input.to_office_id != piece.current_office_id;
+
+    let ctx = TransitionContext {
+        office_changed,
+        has_note: input.notes.as_deref().is_some_and(|n| !n.trim().is_empty()),
+        is_admin: actor.is_admin(),
+        has_failure_reason: input.failure_reason.is_some(),
+        delivery_attempts: snap.delivery_attempts.try_into().unwrap_or(0),
+    };
+
+    let rule = transitions.validate(from_status, input.to_status, ctx)?;
+
+    // pieces travel the shipment's route
+    if input.to_status == ShipmentStatus::InTransit
+        && office_changed
+        && !input.override_route
+        && let Some(to_office) = input.to_office_id
+    {
+        let route = ShipmentRoutesRepo::get(txn, piece.shipment_id).await?;
+
+        check_route_hop(route.as_ref(), piece.current_office_id, to_office)
+            .map_err(|expected_office_id| PieceError::OffRoute { expected_office_id })?;
+    }
+
+    if is_delivery(input.to_status) && !input.override_route {
+        let route = ShipmentRoutesRepo::get(txn, piece.shipment_id).await?;
+
+        check_route_arrival(route.as_ref(), piece.current_office_id).map_err(
+            |destination_office_id| PieceError::NotAtDestination {
+                destination_office_id,
+            },
+        )?;
+    }
+
+    let new_office = if rule.office_change {
+        input.to_office_id.or(piece.current_office_id)
+    } else {
+        piece.current_office_id
+    };
+
+    core_eventstore::adapter::streams::ensure_stream(txn, piece.shipment_id, "shipment").await?;
+
+    let event = PieceScanned {
+        shipment_id: piece.shipment_id.to_string(),
+        piece_id: piece.id.to_string(),
+        barcode: piece.barcode.clone(),
+        from_status,
+        to_status: input.to_status,
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
+        },
+        from_office: piece.current_office_id.map(office_context),
+        to_office: input.to_office_id.map(office_context),
+        occurred_at_ms: Utc::now().timestamp_millis(),
+        notes: input.notes.clone(),
+        failure_reason: input.failure_reason,
+    };
+
+    append_package_expecting(
+        txn,
+        piece.shipment_id,
+        None,
+        PieceScanned::EVENT_TYPE,
+        PieceScanned::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await?;
+
+    ShipmentPiecesRepo::update_status(txn, piece.id, input.to_status, new_office).await?;
+
+    let pieces = ShipmentPiecesRepo::list_by_shipment(txn, piece.shipment_id).await?;
+    let parent_status: ShipmentStatus = snap.current_status.parse().unwrap_or(ShipmentStatus::New);
+
+    let derived = derive_parent_status(
+        pieces
+            .iter()
+            .map(|p| p.status.parse().unwrap_or(ShipmentStatus::New)),
+    );
+
+    let shipment_status = match derived {
+        Some(to_status) if to_status != parent_status => {
+            // the shipment follows its pieces once they are all at one office
+            let to_office = pieces
+                .iter()
+                .all(|p| p.current_office_id == new_office)
+                .then_some(new_office)
+                .flatten()
+                .or(snap.current_office_id);
+
+            let failed = to_status == ShipmentStatus::DeliveryFailed;
+
+            write_status_change::<PieceError>(
+                txn,
+                actor,
+                StatusChangeWrite {
+                    shipment_id: piece.shipment_id,
+                    expected_seq: None,
+                    from_status: parent_status,
+                    to_status,
+                    from_office: snap.current_office_id,
+                    to_office,
+                    new_office: to_office,
+                    notes: None,
+                    failure_reason: input.failure_reason.filter(|_| failed),
+                    attempt: failed.then(|| snap.delivery_attempts + 1),
+                    proof_of_delivery: None,
+                    promised_by: snap.promised_by.map(|at| at.with_timezone(&Utc)),
+                },
+            )
+            .await?;
+
+            to_status
+        }
+        _ => parent_status,
+    };
+
+    let piece = pieces
+        .into_iter()
+        .find(|p| p.id == piece.id)
+        .ok_or(PieceError::NotFound)?;
+
+    Ok(ScannedPiece {
+        piece,
+        shipment_status,
+    })
+}
@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+use core_data::entity::blobs;
+use core_data::repository::blobs_repo::BlobsRepo;
+use core_domain::shipment::{
+    BlobHash, ProofOfDelivery, ReceiverRelationship, ShipmentEvent, ShipmentStatus,
+    image_content_type,
+};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+use crate::shipments::change_status::ChangeStatusError;
+use crate::shipments::timeline::{TimelineError, read_visible_timeline};
+use crate::shipments::visibility::ReadPolicy;
+
+/// Proof of delivery as captured by the courier, images still raw.
+#[derive(Debug, Clone)]
+pub struct ProofOfDeliveryInput {
+    pub receiver_name: String,
+    pub relationship: ReceiverRelationship,
+    /// Defaults to the time of the status change.
+    pub received_at: Option<DateTime<Utc>>,
+    pub signature: Option<Vec<u8>>,
+    pub photo: Option<Vec<u8>>,
+}
+
+/// Which image of a proof of delivery to download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PodImage {
+    Signature,
+    Photo,
+}
+
+/// Proof of delivery of a shipment with the change that recorded it.
+#[derive(Debug, Clone)]
+pub struct RecordedProofOfDelivery {
+    pub proof: ProofOfDelivery,
+    /// Seq of the `StatusChanged` package carrying the proof.
+    pub seq: i64,
+    pub actor_id: String,
+    pub delivered_at_ms: i64,
+}
+
+#[derive(Debug, Error)]
+pub enum PodError {
+    #[error("shipment has no proof of delivery")]
+    NotFound,
+    #[error("proof of delivery has no {0:?}")]
+    NoImage(PodImage),
+    /// Stored bytes no longer hash to their key.
+    #[error("blob {0} is missing or corrupted")]
+    Corrupted(BlobHash),
+    #[error("{0}")]
+    Timeline(#[from] TimelineError),
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+}
+
+/// Stores the images of `input` and returns the proof to put in the event.
+///
+/// Runs inside the status change transaction so blobs and the event
+/// referencing them commit together.
+pub(crate) async fn store_proof_of_delivery<C: ConnectionTrait>(
+    db: &C,
+    to_status: ShipmentStatus,
+    input: ProofOfDeliveryInput,
+) -> Result<ProofOfDelivery, ChangeStatusError> {
+    // check everything before writing any blob
+    let signature = input
+        .signature
+        .map(|bytes| image_content_type(&bytes).map(|ct| (ct, bytes)))
+        .transpose()?;
+    let photo = input
+        .photo
+        .map(|bytes| image_content_type(&bytes).map(|ct| (ct, bytes)))
+        .transpose()?;
+
+    let proof = ProofOfDelivery {
+        receiver_name: input.receiver_name.trim().to_owned(),
+        relationship: input.relationship,
+        received_at_ms: input
+            .received_at
+            .unwrap_or_else(Utc::now)
+            .timestamp_millis(),
+        signature: signature.as_ref().map(|(_, bytes)| BlobHash::of(bytes)),
+        photo: photo.as_ref().map(|(_, bytes)| BlobHash::of(bytes)),
+    };
+    proof.validate(to_status)?;
+
+    for (content_type, bytes) in signature.into_iter().chain(photo) {
+        BlobsRepo::put(db, content_type, bytes).await?;
+    }
+
+    Ok(proof)
+}
+
+/// Latest proof of delivery recorded in the shipment stream.
+///
+/// The stream is the source of truth: the proof is read from the
+/// `StatusChanged` package, which the hash chain covers.
+pub async fn get_proof_of_delivery(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    shipment_id: Uuid,
+) -> Result<RecordedProofOfDelivery, PodError> {
+    let entries = read_visible_timeline(db, actor, policy, shipment_id).await?;
+
+    entries
+        .into_iter()
+        .rev()
+        .find_map(|entry| match entry.event {
+            Some(ShipmentEvent::StatusChanged(changed)) => {
+                changed
+                    .proof_of_delivery
+                    .map(|proof| RecordedProofOfDelivery {
+                        proof,
+                        seq: entry.seq,
+                        actor_id: changed.actor.id,
+                        delivered_at_ms: changed.occurred_at_ms,
+                    })
+            }
+            _ => None,
+        })
+        .ok_or(PodError::NotFound)
+}
+
+/// Signature or photo of the latest proof of delivery.
+/// The bytes are checked against their hash before they are returned.
+pub async fn get_proof_of_delivery_image(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    shipment_id: Uuid,
+    image: PodImage,
+) -> Result<blobs::Model, PodError> {
+    let recorded = get_proof_of_delivery(db, actor, policy, shipment_id).await?;
+
+    let hash = match image {
+        PodImage::Signature => recorded.proof.signature,
+        PodImage::Photo => recorded.proof.photo,
+    }
+    .ok_or(PodError::NoImage(image))?;
+
+    let blob = BlobsRepo::get(db, &hash)
+        .await?
+        .ok_or(PodError::Corrupted(hash))?;
+
+    if BlobHash::of(&blob.bytes) != hash {
+        return Err(PodError::Corrupted(hash));
+    }
+
+    Ok(blob)
+}
@@ -0,0 +1,89 @@
+use chrono::Utc;
+use core_data::repository::offices_repo::{OfficeError, OfficesRepo};
+use core_data::repository::tariffs_repo::TariffRepoError;
+use core_domain::errors::{CashOnDeliveryError, PricingError};
+use core_domain::pricing::{Price, QuoteRequest, ServiceLevel, Tariff};
+use core_domain::shipment::{CashOnDelivery, Parcel};
+use sea_orm::{DatabaseConnection, DbErr};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::tariffs::tariff_in_force;
+use crate::validation::shipment::{ShipmentValidationError, validate_shipment};
+
+#[derive(Debug, Clone)]
+pub struct QuoteShipment {
+    pub origin_office_id: Uuid,
+    pub destination_office_id: Uuid,
+    pub parcel: Parcel,
+    pub cod: Option<CashOnDelivery>,
+    pub service_level: ServiceLevel,
+}
+
+/// Price and the tariff version that set it, `None` for the configured
+/// tariff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShipmentQuote {
+    pub tariff_id: Option<Uuid>,
+    pub price: Price,
+}
+
+#[derive(Debug, Error)]
+pub enum QuoteError {
+    #[error("office not found")]
+    OfficeNotFound,
+    #[error("validation error: {0}")]
+    Validation(#[from] ShipmentValidationError),
+    #[error("invalid cash on delivery: {0}")]
+    CashOnDelivery(#[from] CashOnDeliveryError),
+    #[error("cannot price shipment: {0}")]
+    Pricing(#[from] PricingError),
+    #[error("tariff lookup error: {0}")]
+    Tariff(#[from] TariffRepoError),
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+}
+
+impl From<OfficeError> for QuoteError {
+    fn from(err: OfficeError) -> Self {
+        match err {
+            OfficeError::RecordNotFound => QuoteError::OfficeNotFound,
+            OfficeError::OfficeDbError(db) => QuoteError::DbError(db),
+        }
+    }
+}
+
+/// Prices a shipment between two offices without creating it.
+///
+/// Uses the same tariff and checks as `create_shipment`: the version in
+/// force now, else `tariff`. The quote is what the shipment is charged
+/// when created right away.
+pub async fn quote_shipment(
+    db: &DatabaseConnection,
+    tariff: &Tariff,
+    input: QuoteShipment,
+) -> Result<ShipmentQuote, QuoteError> {
+    validate_shipment(None, None, Some(&input.parcel))?;
+
+    if let Some(cod) = &input.cod {
+        cod.validate()?;
+    }
+
+    let origin = OfficesRepo::get_office_by_id(db, input.origin_office_id)
+        .await?
+        .ok_or(QuoteError::OfficeNotFound)?;
+    let destination = OfficesRepo::get_office_by_id(db, input.destination_office_id)
+        .await?
+        .ok_or(QuoteError::OfficeNotFound)?;
+
+    let (tariff_id, tariff) = tariff_in_force(db, Utc::now(), tariff).await?;
+    let price = tariff.quote(QuoteRequest {
+        origin_city: &origin.city,
+        destination_city: &destination.city,
+        parcel: &input.parcel,
+        cod: input.cod.as_ref(),
+        service_level: input.service_level,
+    })?;
+
+    Ok(ShipmentQuote { tariff_id, price })
+}
@@ -0,0 +1,756 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use core_data::entity::{cod_collections, shipment_pieces, shipments};
+use core_data::repository::cod_repo::{CodCollectionRow, CodRepo};
+use core_data::repository::shipment_pieces_repo::{PieceRow, ShipmentPiecesRepo};
+use core_data::repository::shipment_routes_repo::ShipmentRoutesRepo;
+use core_data::repository::shipments_repo::{
+    HistoryRow, RestoredSnapshot, ShipmentDetails, ShipmentSnapshotError, ShipmentsRepo,
+};
+use core_domain::network::{Route, RouteStop};
+use core_domain::shipment::{
+    HoldReason, OfficeContext, PieceBarcode, ShipmentEvent, ShipmentStatus,
+};
+use core_eventstore::adapter::read::{ReadError, StreamPackage, read_stream_packages};
+use core_eventstore::adapter::streams::list_stream_ids;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, EntityTrait, TransactionTrait};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+use crate::shipments::timeline::decode_package;
+use crate::shipments::upcast::shipment_upcasters;
+
+/// Where rebuilt rows are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebuildTarget {
+    /// Rows are written inside a transaction that is always rolled back.
+    /// Constraints are still checked, nothing is changed.
+    Scratch,
+    /// Drifted rows are replaced in the live tables.
+    Live,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// Stream exists, projection row does not.
+    MissingSnapshot { shipment_id: Uuid },
+    /// Projection row exists without a shipment stream. Reported, never deleted.
+    OrphanSnapshot { shipment_id: Uuid },
+    /// Snapshot column differs from the replayed value.
+    Snapshot {
+        shipment_id: Uuid,
+        field: &'static str,
+        projected: String,
+        replayed: String,
+    },
+    /// History rows differ from the replayed ones.
+    /// `first_mismatch` is the index of the first differing row.
+    History {
+        shipment_id: Uuid,
+        projected_rows: usize,
+        replayed_rows: usize,
+        first_mismatch: usize,
+    },
+    /// Stream could not be replayed; its rows are left untouched.
+    Unreplayable {
+        shipment_id: Uuid,
+        seq: i64,
+        reason: String,
+    },
+}
+
+impl Drift {
+    pub fn shipment_id(&self) -> Uuid {
+        match self {
+            Drift::MissingSnapshot { shipment_id }
+            | Drift::OrphanSnapshot { shipment_id }
+            | Drift::Snapshot { shipment_id, .. }
+            | Drift::History { shipment_id, .. }
+            | Drift::Unreplayable { shipment_id, .. } => *shipment_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebuildReport {
+    pub target: RebuildTarget,
+    pub streams_replayed: usize,
+    /// Shipments whose rows were rewritten (rolled back for `Scratch`).
+    pub shipments_rewritten: usize,
+    pub drift: Vec<Drift>,
+}
+
+impl RebuildReport {
+    pub fn is_clean(&self) -> bool {
+        self.drift.is_empty()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RebuildError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("eventstore read error: {0}")]
+    Read(#[from] ReadError),
+    #[error("snapshot error: {0}")]
+    Snapshot(#[from] ShipmentSnapshotError),
+    #[error("db error: {0}")]
+    Db(#[from] sea_orm::DbErr),
+}
+
+/// Shipment state derived purely from its event stream.
+#[derive(Debug, Clone)]
+struct Replayed {
+    shipment_id: Uuid,
+    tracking_number: Option<String>,
+    client_id: Option<Uuid>,
+    status: ShipmentStatus,
+    office_id: Option<Uuid>,
+    origin_office_id: Option<Uuid>,
+    details: ShipmentDetails,
+    delivery_attempts: i32,
+    hold: Option<(HoldReason, DateTimeWithTimeZone)>,
+    promised_by: Option<DateTimeWithTimeZone>,
+    sla_breached_at: Option<DateTimeWithTimeZone>,
+    created_at: DateTimeWithTimeZone,
+    updated_at: DateTimeWithTimeZone,
+    history: Vec<HistoryRow>,
+    pieces: Vec<PieceRow>,
+    cod_collection: Option<CodCollectionRow>,
+    /// No stops when the shipment has no planned route.
+    route: Route<Uuid>,
+}
+
+/// Replays every shipment stream, diffs the result against
+/// `shipments` / `shipment_status_history` and rewrites drifted shipments
+/// into `target`. Admin only.
+///
+/// Timestamps are restored from `occurred_at_ms` but are not compared, since
+/// projection rows are stamped a few millis apart from their events.
+pub async fn rebuild_shipment_projections(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    target: RebuildTarget,
+) -> Result<RebuildReport, RebuildError> {
+    if !actor.is_admin() {
+        return Err(RebuildError::Forbidden);
+    }
+
+    let txn = db.begin().await?;
+
+    let result = rebuild_txn(&txn, target).await;
+
+    match (result, target) {
+        (Ok(report), RebuildTarget::Live) => {
+            txn.commit().await?;
+            Ok(report)
+        }
+        (Ok(report), RebuildTarget::Scratch) => {
+            txn.rollback().await?;
+            Ok(report)
+        }
+        (Err(err), _) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+async fn rebuild_txn(
+    txn: &DatabaseTransaction,
+    target: RebuildTarget,
+) -> Result<RebuildReport, RebuildError> {
+    let mut projected: HashMap<Uuid, shipments::Model> = shipments::Entity::find()
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|row| (row.id, row))
+        .collect();
+
+    let stream_ids = list_stream_ids(txn, "shipment").await?;
+
+    let mut report = RebuildReport {
+        target,
+        streams_replayed: 0,
+        shipments_rewritten: 0,
+        drift: Vec::new(),
+    };
+
+    for shipment_id in stream_ids {
+        let packages = read_stream_packages(txn, shipment_id, shipment_upcasters()).await?;
+        let snapshot = projected.remove(&shipment_id);
+
+        let replayed = match replay(shipment_id, &packages) {
+            Ok(Some(replayed)) => replayed,
+            // stream opened but no ShipmentCreated yet, nothing to project
+            Ok(None) => continue,
+            Err((seq, reason)) => {
+                report.drift.push(Drift::Unreplayable {
+                    shipment_id,
+                    seq,
+                    reason,
+                });
+                continue;
+            }
+        };
+
+        report.streams_replayed += 1;
+
+        // legacy ShipmentCreated packages carry no client_id
+        let Some(client_id) = replayed
+            .client_id
+            .or(snapshot.as_ref().map(|s| s.client_id))
+        else {
+            report.drift.push(Drift::Unreplayable {
+                shipment_id,
+                seq: 0,
+                reason: "client_id missing from events and projection".into(),
+            });
+            continue;
+        };
+
+        let history = ShipmentsRepo::list_history(txn, shipment_id).await?;
+        let pieces = ShipmentPiecesRepo::list_by_shipment(txn, shipment_id).await?;
+        let collection = CodRepo::find_by_shipment(txn, shipment_id).await?;
+        let route = ShipmentRoutesRepo::get(txn, shipment_id).await?;
+        let drift = diff(
+            &replayed,
+            client_id,
+            snapshot.as_ref(),
+            &history,
+            &pieces,
+            collection.as_ref(),
+            route.as_ref(),
+        );
+
+        if drift.is_empty() {
+            continue;
+        }
+
+        report.drift.extend(drift);
+
+        ShipmentsRepo::restore_snapshot(
+            txn,
+            RestoredSnapshot {
+                id: shipment_id,
+                tracking_number: replayed.tracking_number,
+                client_id,
+                status: replayed.status,
+                current_office_id: replayed.office_id,
+                origin_office_id: replayed.origin_office_id,
+                details: replayed.details,
+                delivery_attempts: replayed.delivery_attempts,
+                hold: replayed.hold,
+                promised_by: replayed.promised_by,
+                sla_breached_at: replayed.sla_breached_at,
+                created_at: replayed.created_at,
+                updated_at: replayed.updated_at,
+            },
+        )
+        .await?;
+
+        ShipmentsRepo::replace_history(txn, shipment_id, replayed.history).await?;
+        ShipmentPiecesRepo::replace_for_shipment(txn, shipment_id, replayed.pieces).await?;
+        CodRepo::replace_for_shipment(txn, shipment_id, replayed.cod_collection).await?;
+        ShipmentRoutesRepo::replace(txn, shipment_id, &replayed.route).await?;
+
+        report.shipments_rewritten += 1;
+    }
+
+    let mut orphans: Vec<Uuid> = projected.into_keys().collect();
+    orphans.sort();
+    report.drift.extend(
+        orphans
+            .into_iter()
+            .map(|shipment_id| Drift::OrphanSnapshot { shipment_id }),
+    );
+
+    Ok(report)
+}
+
+/// Folds a stream the same way `create_shipment`, `change_status`, the hold,
+/// piece and cash on delivery use cases and the SLA check write their
+/// projections. Errors carry the offending seq.
+fn replay(
+    shipment_id: Uuid,
+    packages: &[StreamPackage],
+) -> Result<Option<Replayed>, (i64, String)> {
+    let mut state: Option<Replayed> = None;
+
+    for pkg in packages {
+        let fail = |reason: String| (pkg.seq, reason);
+
+        let event = decode_package(pkg).map_err(|e| fail(e.to_string()))?;
+
+        match event {
+            // stream metadata
+            None => {}
+            Some(ShipmentEvent::Created(created)) => {
+                if state.is_some() {
+                    return Err(fail("duplicate ShipmentCreated".into()));
+                }
+
+                let office_id = parse_office(created.office.as_ref()).map_err(fail)?;
+                let destination_office_id =
+                    parse_office(created.destination_office.as_ref()).map_err(fail)?;
+                let at = timestamp(created.occurred_at_ms).map_err(fail)?;
+                let promised_by = created
+                    .promised_by_ms
+                    .map(timestamp)
+                    .transpose()
+                    .map_err(fail)?;
+
+                state = Some(Replayed {
+                    shipment_id,
+                    tracking_number: created.tracking_number,
+                    client_id: created
+                        .client_id
+                        .as_deref()
+                        .map(parse_id)
+                        .transpose()
+                        .map_err(fail)?,
+                    status: created.status,
+                    office_id,
+                    origin_office_id: office_id,
+                    details: ShipmentDetails {
+                        sender: created.sender,
+                        recipient: created.recipient,
+                        destination_office_id,
+                        parcel: created.parcel,
+                        cod: created.cod,
+                        service_level: created.service_level,
+                        price: created.price,
+                        tariff_id: created
+                            .tariff_id
+                            .as_deref()
+                            .map(parse_id)
+                            .transpose()
+                            .map_err(fail)?,
+                    },
+                    delivery_attempts: 0,
+                    hold: None,
+                    promised_by,
+                    sla_breached_at: None,
+                    created_at: at,
+                    updated_at: at,
+                    history: vec![HistoryRow {
+                        from_status: None,
+                        to_status: created.status,
+                        actor_user_id: Some(parse_id(&created.actor.id).map_err(fail)?),
+                        office_id,
+                        notes: created.notes,
+                        failure_reason: None,
+                        changed_at: at,
+                    }],
+                    pieces: Vec::new(),
+                    cod_collection: None,
+                    route: Route { stops: Vec::new() },
+                });
+            }
+            Some(ShipmentEvent::StatusChanged(changed)) => {
+                let Some(current) = state.as_mut() else {
+                    return Err(fail("StatusChanged before ShipmentCreated".into()));
+                };
+
+                let to_office_id = parse_office(changed.to_office.as_ref()).map_err(fail)?;
+                let at = timestamp(changed.occurred_at_ms).map_err(fail)?;
+
+                current.history.push(HistoryRow {
+                    from_status: Some(changed.from_status),
+                    to_status: changed.to_status,
+                    actor_user_id: Some(parse_id(&changed.actor.id).map_err(fail)?),
+                    office_id: current.office_id,
+                    notes: changed.notes,
+                    failure_reason: changed.failure_reason,
+                    changed_at: at,
+                });
+
+                // changes that may not hop offices are only accepted with
+                // the current office as target, so following it is safe
+                current.office_id = to_office_id.or(current.office_id);
+
+                if let Some(attempt) = changed.attempt {
+                    current.delivery_attempts = attempt as i32;
+                }
+
+                current.status = changed.to_status;
+                current.updated_at = at;
+            }
+            Some(ShipmentEvent::HoldPlaced(placed)) => {
+                let Some(current) = state.as_mut() else {
+                    return Err(fail("HoldPlaced before ShipmentCreated".into()));
+                };
+                if current.hold.is_some() {
+                    return Err(fail("HoldPlaced while already on hold".into()));
+                }
+
+                let at = timestamp(placed.occurred_at_ms).map_err(fail)?;
+
+                current.hold = Some((placed.reason, at));
+                current.updated_at = at;
+            }
+            Some(ShipmentEvent::HoldReleased(released)) => {
+                let Some(current) = state.as_mut() else {
+                    return Err(fail("HoldReleased before ShipmentCreated".into()));
+                };
+                if current.hold.is_none() {
+                    return Err(fail("HoldReleased while not on hold".into()));
+                }
+
+                current.hold = None;
+                current.updated_at = timestamp(released.occurred_at_ms).map_err(fail)?;
+            }
+            Some(ShipmentEvent::PieceAdded(added)) => {
+                let Some(current) = state.as_mut() else {
+                    return Err(fail("PieceAdded before ShipmentCreated".into()));
+                };
+
+                let barcode =
+                    PieceBarcode::parse(&added.barcode).map_err(|e| fail(e.to_string()))?;
+                let at = timestamp(added.occurred_at_ms).map_err(fail)?;
+
+                current.pieces.push(PieceRow {
+                    id: parse_id(&added.piece_id).map_err(fail)?,
+                    piece_no: barcode.piece_no() as i32,
+                    barcode: added.barcode,
+                    status: added.status,
+                    current_office_id: parse_office(added.office.as_ref()).map_err(fail)?,
+                    weight_g: added.weight_g,
+                    description: added.description,
+                    created_at: at,
+                    updated_at: at,
+                });
+                current.updated_at = at;
+            }
+            Some(ShipmentEvent::PieceScanned(scanned)) => {
+                let Some(current) = state.as_mut() else {
+                    return Err(fail("PieceScanned before ShipmentCreated".into()));
+                };
+
+                let piece_id = parse_id(&scanned.piece_id).map_err(fail)?;
+                let to_office_id = parse_office(scanned.to_office.as_ref()).map_err(fail)?;
+                let at = timestamp(scanned.occurred_at_ms).map_err(fail)?;
+
+                let Some(piece) = current.pieces.iter_mut().find(|p| p.id == piece_id) else {
+                    return Err(fail(format!("PieceScanned for unknown piece {piece_id}")));
+                };
+
+                // same office rule as StatusChanged
+                piece.current_office_id = to_office_id.or(piece.current_office_id);
+                piece.status = scanned.to_status;
+                piece.updated_at = at;
+                current.updated_at = at;
+            }
+            Some(ShipmentEvent::CodCollected(collected)) => {
+                let Some(current) = state.as_mut() else {
+                    return Err(fail("CodCollected before ShipmentCreated".into()));
+                };
+                if current.cod_collection.is_some() {
+                    return Err(fail("duplicate CodCollected".into()));
+                }
+                let Some(client_id) = current.client_id else {
+                    return Err(fail("CodCollected without client_id".into()));
+                };
+
+                let at = timestamp(collected.occurred_at_ms).map_err(fail)?;
+
+                current.cod_collection = Some(CodCollectionRow {
+                    id: parse_id(&collected.collection_id).map_err(fail)?,
+                    client_id,
+                    office_id: parse_office(collected.office.as_ref()).map_err(fail)?,
+                    amount_cents: collected.amount_cents,
+                    currency: collected.currency.to_string(),
+                    collected_by: Some(parse_id(&collected.actor.id).map_err(fail)?),
+                    collected_at: at,
+                    remittance_id: None,
+                    remitted_at: None,
+                });
+            }
+            Some(ShipmentEvent::CodRemitted(remitted)) => {
+                let Some(current) = state.as_mut() else {
+                    return Err(fail("CodRemitted before ShipmentCreated".into()));
+                };
+
+                let collection_id = parse_id(&remitted.collection_id).map_err(fail)?;
+                let Some(collection) = current
+                    .cod_collection
+                    .as_mut()
+                    .filter(|c| c.id == collection_id)
+                else {
+                    return Err(fail(format!(
+                        "CodRemitted for unknown collection {collection_id}"
+                    )));
+                };
+                if collection.remittance_id.is_some() {
+                    return Err(fail("CodRemitted for a remitted collection".into()));
+                }
+
+                collection.remittance_id = Some(parse_id(&remitted.remittance_id).map_err(fail)?);
+                collection.remitted_at = Some(timestamp(remitted.occurred_at_ms).map_err(fail)?);
+            }
+            Some(ShipmentEvent::RoutePlanned(planned)) => {
+                let Some(current) = state.as_mut() else {
+                    return Err(fail("RoutePlanned before ShipmentCreated".into()));
+                };
+
+                let stops = planned
+                    .route
+                    .stops
+                    .into_iter()
+                    .map(|stop| {
+                        Ok(RouteStop {
+                            office: parse_id(&stop.office)?,
+                            transit_minutes: stop.transit_minutes,
+                            cost_cents: stop.cost_cents,
+                        })
+                    })
+                    .collect::<Result<_, String>>()
+                    .map_err(fail)?;
+
+                // the route is not part of the snapshot, updated_at stays
+                current.route = Route { stops };
+            }
+            Some(ShipmentEvent::SlaBreached(breached)) => {
+                let Some(current) = state.as_mut() else {
+                    return Err(fail("SlaBreached before ShipmentCreated".into()));
+                };
+                if current.sla_breached_at.is_some() {
+                    return Err(fail("duplicate SlaBreached".into()));
+                }
+
+                let at = timestamp(breached.occurred_at_ms).map_err(fail)?;
+
+                current.sla_breached_at = Some(at);
+                current.updated_at = at;
+            }
+        }
+    }
+
+    Ok(state)
+}
+
+fn diff(
+    replayed: &Replayed,
+    client_id: Uuid,
+    snapshot: Option<&shipments::Model>,
+    history: &[core_data::entity::shipment_status_history::Model],
+    pieces: &[shipment_pieces::Model],
+    collection: Option<&cod_collections::Model>,
+    route: Option<&Route<Uuid>>,
+) -> Vec<Drift> {
+    let shipment_id = replayed.shipment_id;
+    let mut out = Vec::new();
+
+    match snapshot {
+        None => out.push(Drift::MissingSnapshot { shipment_id }),
+        Some(snap) => {
+            let projected_details = ShipmentDetails::from_model(snap);
+            let fields = [
+                (
+                    "tracking_number",
+                    format!("{:?}", snap.tracking_number),
+                    format!("{:?}", replayed.tracking_number),
+                ),
+                (
+                    "client_id",
+                    snap.client_id.to_string(),
+                    client_id.to_string(),
+                ),
+                (
+                    "current_status",
+                    snap.current_status.clone(),
+                    replayed.status.to_string(),
+                ),
+                (
+                    "current_office_id",
+                    fmt_opt(snap.current_office_id),
+                    fmt_opt(replayed.office_id),
+                ),
+                (
+                    "origin_office_id",
+                    fmt_opt(snap.origin_office_id),
+                    fmt_opt(replayed.origin_office_id),
+                ),
+                (
+                    "destination_office_id",
+                    fmt_opt(snap.destination_office_id),
+                    fmt_opt(replayed.details.destination_office_id),
+                ),
+                (
+                    "delivery_attempts",
+                    snap.delivery_attempts.to_string(),
+                    replayed.delivery_attempts.to_string(),
+                ),
+                (
+                    "hold_reason",
+                    format!("{:?}", snap.hold_reason),
+                    format!("{:?}", replayed.hold.map(|(reason, _)| reason.to_string())),
+                ),
+                (
+                    "pieces",
+                    format!("{:?}", pieces.iter().map(piece_key).collect::<Vec<_>>()),
+                    format!(
+                        "{:?}",
+                        replayed
+                            .pieces
+                            .iter()
+                            .map(|p| (
+                                p.piece_no,
+                                p.barcode.clone(),
+                                p.status.to_string(),
+                                p.current_office_id,
+                                p.weight_g,
+                            ))
+                            .collect::<Vec<_>>()
+                    ),
+                ),
+                (
+                    "cod",
+                    format!("{:?}", projected_details.cod),
+                    format!("{:?}", replayed.details.cod),
+                ),
+                (
+                    "cod_collection",
+                    format!("{:?}", collection.map(collection_key)),
+                    format!(
+                        "{:?}",
+                        replayed.cod_collection.as_ref().map(|c| (
+                            c.id,
+                            c.office_id,
+                            c.amount_cents,
+                            c.currency.clone(),
+                            c.remittance_id,
+                        ))
+                    ),
+                ),
+                (
+                    "sender",
+                    format!("{:?}", projected_details.sender),
+                    format!("{:?}", replayed.details.sender),
+                ),
+                (
+                    "recipient",
+                    format!("{:?}", projected_details.recipient),
+                    format!("{:?}", replayed.details.recipient),
+                ),
+                (
+                    "parcel",
+                    format!("{:?}", projected_details.parcel),
+                    format!("{:?}", replayed.details.parcel),
+                ),
+                (
+                    "service_level",
+                    projected_details.service_level.to_string(),
+                    replayed.details.service_level.to_string(),
+                ),
+                (
+                    "price",
+                    format!("{:?}", projected_details.price),
+                    format!("{:?}", replayed.details.price),
+                ),
+                (
+                    "tariff_id",
+                    format!("{:?}", projected_details.tariff_id),
+                    format!("{:?}", replayed.details.tariff_id),
+                ),
+                (
+                    "promised_by",
+                    format!("{:?}", snap.promised_by.map(|at| at.timestamp_millis())),
+                    format!("{:?}", replayed.promised_by.map(|at| at.timestamp_millis())),
+                ),
+                (
+                    "sla_breached",
+                    snap.sla_breached_at.is_some().to_string(),
+                    replayed.sla_breached_at.is_some().to_string(),
+                ),
+                (
+                    "route",
+                    format!("{:?}", route.map_or(&[][..], |r| &r.stops[..])),
+                    format!("{:?}", replayed.route.stops),
+                ),
+            ];
+
+            for (field, projected, replayed) in fields {
+                if projected != replayed {
+                    out.push(Drift::Snapshot {
+                        shipment_id,
+                        field,
+                        projected,
+                        replayed,
+                    });
+                }
+            }
+        }
+    }
+
+    let first_mismatch = replayed
+        .history
+        .iter()
+        .zip(history)
+        .position(|(want, have)| {
+            have.from_status != want.from_status.map(|s| s.to_string())
+                || have.to_status != want.to_status.to_string()
+                || have.actor_user_id != want.actor_user_id
+                || have.office_id != want.office_id
+                || have.notes != want.notes
+                || have.failure_reason != want.failure_reason.map(|r| r.to_string())
+        })
+        .or_else(|| {
+            (history.len() != replayed.history.len())
+                .then(|| history.len().min(replayed.history.len()))
+        });
+
+    if let Some(first_mismatch) = first_mismatch {
+        out.push(Drift::History {
+            shipment_id,
+            projected_rows: history.len(),
+            replayed_rows: replayed.history.len(),
+            first_mismatch,
+        });
+    }
+
+    out
+}
+
+fn piece_key(piece: &shipment_pieces::Model) -> (i32, String, String, Option<Uuid>, Option<i32>) {
+    (
+        piece.piece_no,
+        piece.barcode.clone(),
+        piece.status.clone(),
+        piece.current_office_id,
+        piece.weight_g,
+    )
+}
+
+fn collection_key(
+    collection: &cod_collections::Model,
+) -> (Uuid, Option<Uuid>, i64, String, Option<Uuid>) {
+    (
+        collection.id,
+        collection.office_id,
+        collection.amount_cents,
+        collection.currency.clone(),
+        collection.remittance_id,
+    )
+}
+
+fn fmt_opt(id: Option<Uuid>) -> String {
+    id.map(|id| id.to_string()).unwrap_or_else(|| "null".into())
+}
+
+fn parse_id(id: &str) -> Result<Uuid, String> {
+    id.parse().map_err(|_| format!("{id} is not a uuid"))
+}
+
+fn parse_office(office: Option<&OfficeContext>) -> Result<Option<Uuid>, String> {
+    office.map(|o| parse_id(&o.office_id)).transpose()
+}
+
+fn timestamp(millis: i64) -> Result<DateTimeWithTimeZone, String> {
+    DateTime::from_timestamp_millis(millis)
+        .map(|at| at.fixed_offset())
+        .ok_or_else(|| "occurred_at_ms out of range".into())
+}
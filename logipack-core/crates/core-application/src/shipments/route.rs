@@ -0,0 +1,112 @@
+use chrono::Utc;
+use core_data::repository::office_links_repo::{OfficeLinkRepoError, OfficeLinksRepo};
+use core_data::repository::shipment_routes_repo::ShipmentRoutesRepo;
+use core_data::repository::shipments_repo::ShipmentSnapshotError;
+use core_domain::network::{Route, RouteStop};
+use core_domain::shipment::{ActorRef, EventCodec, RoutePlanned};
+use core_eventstore::adapter::append::{AppendError, append_package_expecting};
+use sea_orm::DatabaseTransaction;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+/// Plans the fastest route from `from` to `destination` over the office
+/// network and records it on the shipment. Records an empty route when
+/// the destination cannot be reached, which clears the previous one.
+pub(crate) async fn plan_shipment_route<E>(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    shipment_id: Uuid,
+    from: Uuid,
+    destination: Uuid,
+) -> Result<(), E>
+where
+    E: From<OfficeLinkRepoError> + From<AppendError> + From<ShipmentSnapshotError>,
+{
+    let route = OfficeLinksRepo::network(txn)
+        .await?
+        .plan(&from, &destination)
+        .unwrap_or(Route { stops: Vec::new() });
+
+    record_shipment_route(txn, actor, shipment_id, route).await
+}
+
+/// Appends `RoutePlanned` and replaces the shipment's stored route.
+pub(crate) async fn record_shipment_route<E>(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    shipment_id: Uuid,
+    route: Route<Uuid>,
+) -> Result<(), E>
+where
+    E: From<AppendError> + From<ShipmentSnapshotError>,
+{
+    let event = RoutePlanned {
+        shipment_id: shipment_id.to_string(),
+        route: Route {
+            stops: route
+                .stops
+                .iter()
+                .map(|stop| RouteStop {
+                    office: stop.office.to_string(),
+                    transit_minutes: stop.transit_minutes,
+                    cost_cents: stop.cost_cents,
+                })
+                .collect(),
+        },
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
+        },
+        occurred_at_ms: Utc::now().timestamp_millis(),
+    };
+
+    append_package_expecting(
+        txn,
+        shipment_id,
+        None,
+        RoutePlanned::EVENT_TYPE,
+        RoutePlanned::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await?;
+
+    ShipmentRoutesRepo::replace(txn, shipment_id, &route).await?;
+
+    Ok(())
+}
+
+/// Checks an office hop against the planned route: it must go to the
+/// office after `from`. Shipments without a route hop freely.
+///
+/// On a detour the error carries the office the route expected, `None`
+/// when `from` is the destination or not on the route at all.
+pub(crate) fn check_route_hop(
+    route: Option<&Route<Uuid>>,
+    from: Option<Uuid>,
+    to: Uuid,
+) -> Result<(), Option<Uuid>> {
+    let Some(route) = route else {
+        return Ok(());
+    };
+
+    let expected = from.and_then(|from| route.next_after(&from)).copied();
+
+    match expected {
+        Some(next) if next == to => Ok(()),
+        _ => Err(expected),
+    }
+}
+
+/// Checks that a shipment at `at` has reached the end of its planned
+/// route, as it must before going out for delivery or being delivered.
+///
+/// The error carries the route's destination.
+pub(crate) fn check_route_arrival(
+    route: Option<&Route<Uuid>>,
+    at: Option<Uuid>,
+) -> Result<(), Uuid> {
+    match route.and_then(|route| route.destination().copied()) {
+        Some(destination) if at != Some(destination) => Err(destination),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use core_data::entity::shipments;
+use core_data::repository::shipments_repo::{ShipmentSnapshotError, ShipmentsRepo};
+use core_domain::shipment::{EventCodec, ShipmentStatus, SlaBreached, TransitionTable};
+use core_eventstore::adapter::append::{AppendError, append_package_expecting};
+use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::shipments::change_status::office_context;
+
+/// Overdue shipments flagged per check; the rest wait for the next one.
+pub const SLA_CHECK_BATCH: u64 = 500;
+
+#[derive(Debug, Error)]
+pub enum SlaCheckError {
+    #[error("snapshot error: {0}")]
+    SnapshotError(#[from] ShipmentSnapshotError),
+    #[error("db error: {0}")]
+    DbError(#[from] sea_orm::DbErr),
+    #[error("eventstore error: {0}")]
+    EventstoreError(#[from] AppendError),
+}
+
+/// Flags shipments still not delivered at their promised time: appends
+/// `SlaBreached` and stamps `sla_breached_at`, once per shipment. Statuses
+/// terminal in `transitions` are never flagged here; a shipment that
+/// settles late is flagged by the status change itself.
+///
+/// Runs without an actor, for the periodic check. Returns the shipments
+/// flagged by this call.
+pub async fn check_sla_breaches(
+    db: &DatabaseConnection,
+    transitions: &TransitionTable,
+    now: DateTime<Utc>,
+) -> Result<Vec<Uuid>, SlaCheckError> {
+    // whole millis, so the stamp matches the event exactly
+    let at = DateTime::from_timestamp_millis(now.timestamp_millis()).unwrap_or(now);
+
+    let overdue =
+        ShipmentsRepo::list_sla_overdue(db, at.into(), transitions.terminal(), SLA_CHECK_BATCH)
+            .await?;
+
+    let mut flagged = Vec::new();
+
+    for shipment in overdue {
+        let txn = db.begin().await?;
+
+        match flag_breach_txn(&txn, &shipment, at).await {
+            Ok(true) => {
+                txn.commit().await?;
+                flagged.push(shipment.id);
+            }
+            // flagged by a concurrent check
+            Ok(false) => {
+                txn.rollback().await.ok();
+            }
+            Err(err) => {
+                txn.rollback().await.ok();
+                return Err(err);
+            }
+        }
+    }
+
+    Ok(flagged)
+}
+
+async fn flag_breach_txn(
+    txn: &DatabaseTransaction,
+    shipment: &shipments::Model,
+    at: DateTime<Utc>,
+) -> Result<bool, SlaCheckError> {
+    let Some(promised_by) = shipment.promised_by else {
+        return Ok(false);
+    };
+
+    record_sla_breach(
+        txn,
+        shipment.id,
+        promised_by.with_timezone(&Utc),
+        shipment
+            .current_status
+            .parse()
+            .unwrap_or(ShipmentStatus::New),
+        shipment.current_office_id,
+        at,
+    )
+    .await
+}
+
+/// Stamps `sla_breached_at` and appends `SlaBreached` with the status and
+/// office the shipment had when its promise was broken. `false` when the
+/// shipment was already flagged.
+pub(crate) async fn record_sla_breach<E>(
+    txn: &DatabaseTransaction,
+    shipment_id: Uuid,
+    promised_by: DateTime<Utc>,
+    status: ShipmentStatus,
+    office: Option<Uuid>,
+    at: DateTime<Utc>,
+) -> Result<bool, E>
+where
+    E: From<AppendError> + From<ShipmentSnapshotError>,
+{
+    if !ShipmentsRepo::mark_sla_breached(txn, shipment_id, at.into()).await? {
+        return Ok(false);
+    }
+
+    let event = SlaBreached {
+        shipment_id: shipment_id.to_string(),
+        promised_by_ms: promised_by.timestamp_millis(),
+        status,
+        office: office.map(office_context),
+        occurred_at_ms: at.timestamp_millis(),
+    };
+
+    append_package_expecting(
+        txn,
+        shipment_id,
+        None,
+        SlaBreached::EVENT_TYPE,
+        SlaBreached::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await?;
+
+    Ok(true)
+}
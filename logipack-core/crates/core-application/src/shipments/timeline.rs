@@ -1,18 +1,181 @@
+use std::collections::{BTreeSet, HashMap};
+
+use core_data::repository::offices_repo::{OfficeError, OfficesRepo};
+use core_data::repository::shipments_repo::ShipmentSnapshotError;
+use core_data::repository::users_repo::{UserError, UserRepo};
+use core_domain::errors::EventDecodeError;
+use core_domain::shipment::{OfficeContext, ShipmentEvent};
 use core_eventstore::adapter::read::{ReadError, StreamPackage, read_stream_packages};
 use sea_orm::DatabaseConnection;
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::actor::ActorContext;
+use crate::shipments::upcast::shipment_upcasters;
+use crate::shipments::visibility::{ReadPolicy, ensure_visible};
+
+/// Event type of the metadata package that opens every shipment stream.
+pub const STREAM_METADATA_EVENT_TYPE: &str = "shipment";
+
 #[derive(Debug, Error)]
 pub enum TimelineError {
     #[error("eventstore read error: {0:?}")]
     Read(#[from] ReadError),
+    #[error("undecodable event at seq {seq}: {source}")]
+    Decode {
+        seq: i64,
+        #[source]
+        source: EventDecodeError,
+    },
+    #[error("office lookup error: {0}")]
+    Offices(#[from] OfficeError),
+    #[error("user lookup error: {0}")]
+    Users(#[from] UserError),
+    #[error("{0}")]
+    Snapshot(#[from] ShipmentSnapshotError),
+}
+
+/// A stored package together with its typed domain event.
+#[derive(Debug, Clone)]
+pub struct TimelineEntry {
+    pub seq: i64,
+    pub event_type: String,
+    pub hash: Vec<u8>,
+    pub prev_hash: Option<Vec<u8>>,
+    /// Strata Canonical Bytes as stored in the DB.
+    pub scb: Vec<u8>,
+    /// Schema version the package was stored with.
+    pub schema_version: i32,
+    /// Decoded Strata value, still scoped to the stream, payload upcast to
+    /// the current schema version.
+    pub value: strata::value::Value,
+    /// `None` for the stream metadata package.
+    pub event: Option<ShipmentEvent>,
 }
 
 pub async fn read_timeline(
     db: &DatabaseConnection,
     shipment_id: Uuid,
-) -> Result<Vec<StreamPackage>, TimelineError> {
-    let items = read_stream_packages(db, shipment_id).await?;
-    Ok(items)
+) -> Result<Vec<TimelineEntry>, TimelineError> {
+    let items = read_stream_packages(db, shipment_id, shipment_upcasters()).await?;
+
+    items.into_iter().map(decode_entry).collect()
+}
+
+/// Display names for the users and offices a timeline refers to.
+/// Ids that no longer resolve are simply absent.
+#[derive(Debug, Clone, Default)]
+pub struct TimelineNames {
+    pub users: HashMap<Uuid, String>,
+    pub offices: HashMap<Uuid, String>,
+}
+
+impl TimelineNames {
+    pub fn user(&self, id: &str) -> Option<&str> {
+        let id: Uuid = id.parse().ok()?;
+        self.users.get(&id).map(String::as_str)
+    }
+
+    pub fn office(&self, office: &OfficeContext) -> Option<&str> {
+        let id: Uuid = office.office_id.parse().ok()?;
+        self.offices.get(&id).map(String::as_str)
+    }
+}
+
+/// [`read_timeline`] for `actor`: shipments hidden by `policy` are
+/// reported as not found.
+pub async fn read_visible_timeline(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    shipment_id: Uuid,
+) -> Result<Vec<TimelineEntry>, TimelineError> {
+    ensure_visible(db, actor, policy, shipment_id).await?;
+
+    read_timeline(db, shipment_id).await
+}
+
+/// Same as [`read_visible_timeline`], plus the names needed to render it.
+pub async fn read_timeline_with_names(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    shipment_id: Uuid,
+) -> Result<(Vec<TimelineEntry>, TimelineNames), TimelineError> {
+    let entries = read_visible_timeline(db, actor, policy, shipment_id).await?;
+
+    let mut user_ids = BTreeSet::new();
+    let mut office_ids = BTreeSet::new();
+
+    for event in entries.iter().filter_map(|e| e.event.as_ref()) {
+        let (actor, offices) = match event {
+            ShipmentEvent::Created(e) => (Some(&e.actor), vec![&e.office, &e.destination_office]),
+            ShipmentEvent::StatusChanged(e) => (Some(&e.actor), vec![&e.from_office, &e.to_office]),
+            ShipmentEvent::HoldPlaced(e) => (Some(&e.actor), vec![&e.office]),
+            ShipmentEvent::HoldReleased(e) => (Some(&e.actor), vec![&e.office]),
+            ShipmentEvent::PieceAdded(e) => (Some(&e.actor), vec![&e.office]),
+            ShipmentEvent::PieceScanned(e) => (Some(&e.actor), vec![&e.from_office, &e.to_office]),
+            ShipmentEvent::CodCollected(e) => (Some(&e.actor), vec![&e.office]),
+            ShipmentEvent::CodRemitted(e) => (Some(&e.actor), vec![]),
+            ShipmentEvent::RoutePlanned(e) => {
+                office_ids.extend(e.route.offices().filter_map(|id| id.parse::<Uuid>().ok()));
+                (Some(&e.actor), vec![])
+            }
+            // recorded by the breach check, not by a user
+            ShipmentEvent::SlaBreached(e) => (None, vec![&e.office]),
+        };
+
+        user_ids.extend(actor.and_then(|actor| actor.id.parse::<Uuid>().ok()));
+        office_ids.extend(
+            offices
+                .into_iter()
+                .flatten()
+                .filter_map(|o| o.office_id.parse::<Uuid>().ok()),
+        );
+    }
+
+    let mut names = TimelineNames::default();
+
+    if !user_ids.is_empty() {
+        for user in UserRepo::get_by_ids(db, user_ids.into_iter().collect()).await? {
+            names.users.insert(user.id, user.name);
+        }
+    }
+
+    if !office_ids.is_empty() {
+        for office in OfficesRepo::get_offices_by_ids(db, office_ids.into_iter().collect()).await? {
+            names.offices.insert(office.id, office.name);
+        }
+    }
+
+    Ok((entries, names))
+}
+
+/// Decodes the domain event carried by a shipment stream package.
+pub fn decode_package(pkg: &StreamPackage) -> Result<Option<ShipmentEvent>, EventDecodeError> {
+    if pkg.event_type == STREAM_METADATA_EVENT_TYPE {
+        return Ok(None);
+    }
+
+    let payload = pkg.payload().ok_or(EventDecodeError::NotAMap)?;
+
+    ShipmentEvent::decode(&pkg.event_type, payload).map(Some)
+}
+
+fn decode_entry(pkg: StreamPackage) -> Result<TimelineEntry, TimelineError> {
+    let event = decode_package(&pkg).map_err(|source| TimelineError::Decode {
+        seq: pkg.seq,
+        source,
+    })?;
+
+    Ok(TimelineEntry {
+        seq: pkg.seq,
+        event_type: pkg.event_type,
+        hash: pkg.hash,
+        prev_hash: pkg.prev_hash,
+        scb: pkg.scb,
+        schema_version: pkg.schema_version,
+        value: pkg.value,
+        event,
+    })
 }
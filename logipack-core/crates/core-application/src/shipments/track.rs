@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use core_data::repository::{
+    offices_repo::{OfficeError, OfficesRepo},
+    shipments_repo::{ShipmentSnapshotError, ShipmentsRepo},
+};
+use core_domain::errors::TrackingNumberError;
+use core_domain::shipment::TrackingNumber;
+use sea_orm::{DatabaseConnection, prelude::DateTimeWithTimeZone};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PublicTrackingError {
+    #[error("invalid tracking number: {0}")]
+    InvalidTrackingNumber(#[from] TrackingNumberError),
+    #[error("shipment not found")]
+    NotFound,
+    #[error("{0}")]
+    Snapshot(#[from] ShipmentSnapshotError),
+    #[error("office lookup error: {0}")]
+    Offices(#[from] OfficeError),
+}
+
+/// One status change as shown to the public.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicTrackingStep {
+    pub status: String,
+    /// City of the office where the change happened, if any.
+    pub city: Option<String>,
+    pub at: DateTimeWithTimeZone,
+}
+
+/// What anyone holding a tracking number may see about a shipment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicTrackingView {
+    pub tracking_number: String,
+    pub status: String,
+    /// Oldest first.
+    pub history: Vec<PublicTrackingStep>,
+}
+
+/// Status history for a tracking number, without an actor.
+///
+/// Only statuses, office cities and timestamps leave this function: actors,
+/// notes, offices and client data stay out, so the result is safe to serve
+/// unauthenticated.
+pub async fn track_shipment(
+    db: &DatabaseConnection,
+    code: &str,
+) -> Result<PublicTrackingView, PublicTrackingError> {
+    let tracking_number = TrackingNumber::parse(code)?;
+
+    let shipment_id = ShipmentsRepo::find_id_by_tracking_number(db, tracking_number.as_str())
+        .await?
+        .ok_or(PublicTrackingError::NotFound)?;
+
+    let shipment = ShipmentsRepo::get_snapshot(db, shipment_id).await?;
+    let rows = ShipmentsRepo::list_history(db, shipment_id).await?;
+
+    let mut office_ids: Vec<_> = rows.iter().filter_map(|row| row.office_id).collect();
+    office_ids.sort_unstable();
+    office_ids.dedup();
+
+    let cities: HashMap<_, _> = OfficesRepo::get_offices_by_ids(db, office_ids)
+        .await?
+        .into_iter()
+        .map(|office| (office.id, office.city))
+        .collect();
+
+    let history = rows
+        .into_iter()
+        .map(|row| PublicTrackingStep {
+            status: row.to_status,
+            city: row.office_id.and_then(|id| cities.get(&id).cloned()),
+            at: row.changed_at,
+        })
+        .collect();
+
+    Ok(PublicTrackingView {
+        tracking_number: tracking_number.to_string(),
+        status: shipment.current_status,
+        history,
+    })
+}
@@ -0,0 +1,31 @@
+use std::sync::LazyLock;
+
+use core_domain::shipment::{EventCodec, ShipmentCreated, StatusChanged};
+use core_eventstore::adapter::upcast::{UpcastError, UpcasterRegistry};
+use strata::value::Value;
+
+static SHIPMENT_UPCASTERS: LazyLock<UpcasterRegistry> = LazyLock::new(|| {
+    UpcasterRegistry::new()
+        .register(ShipmentCreated::EVENT_TYPE, rename_occured_at)
+        .register(StatusChanged::EVENT_TYPE, rename_occured_at)
+});
+
+/// Upcasters for every event written to shipment streams.
+/// Register a step here whenever a shipment event's `SCHEMA_VERSION` is bumped.
+pub fn shipment_upcasters() -> &'static UpcasterRegistry {
+    &SHIPMENT_UPCASTERS
+}
+
+/// v1 -> v2: `occured_at` (always millis, despite older docs) becomes `occurred_at_ms`.
+fn rename_occured_at(value: Value) -> Result<Value, UpcastError> {
+    let Value::Map(mut fields) = value else {
+        return Err(UpcastError::invalid("payload is not a map"));
+    };
+
+    let at = fields
+        .remove("occured_at")
+        .ok_or_else(|| UpcastError::invalid("occured_at missing"))?;
+    fields.insert("occurred_at_ms".into(), at);
+
+    Ok(Value::Map(fields))
+}
@@ -0,0 +1,88 @@
+use core_data::repository::shipment_query::OfficeVisibility;
+use core_data::repository::shipments_repo::{ShipmentSnapshotError, ShipmentsRepo};
+use sea_orm::{DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+/// Which relations to an employee's offices make a shipment readable.
+/// Admins bypass the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadPolicy {
+    /// Shipment is currently at one of the employee's offices.
+    pub current_office: bool,
+    /// Shipment was created at one of the employee's offices.
+    pub origin_office: bool,
+    /// Shipment had a status change recorded at one of the employee's offices.
+    pub history_offices: bool,
+}
+
+impl Default for ReadPolicy {
+    fn default() -> Self {
+        Self {
+            current_office: true,
+            origin_office: true,
+            history_offices: true,
+        }
+    }
+}
+
+impl ReadPolicy {
+    /// Parses a comma-separated list of `current`, `origin` and `history`,
+    /// e.g. `current,origin`. `None` for unknown or no relations.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut policy = Self {
+            current_office: false,
+            origin_office: false,
+            history_offices: false,
+        };
+
+        let mut listed = false;
+
+        for part in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match part {
+                "current" => policy.current_office = true,
+                "origin" => policy.origin_office = true,
+                "history" => policy.history_offices = true,
+                _ => return None,
+            }
+            listed = true;
+        }
+
+        // an empty policy would hide every shipment from employees
+        listed.then_some(policy)
+    }
+
+    /// Office restriction for `actor`; `None` for admins.
+    pub fn visibility_for(&self, actor: &ActorContext) -> Option<OfficeVisibility> {
+        if actor.is_admin() {
+            return None;
+        }
+
+        Some(OfficeVisibility {
+            office_ids: actor.allowed_office_ids.clone(),
+            current: self.current_office,
+            origin: self.origin_office,
+            history: self.history_offices,
+        })
+    }
+}
+
+/// Fails with the same not-found error as a missing shipment when `actor`
+/// may not read it, so hidden shipments are indistinguishable from absent ones.
+pub async fn ensure_visible(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    policy: ReadPolicy,
+    shipment_id: Uuid,
+) -> Result<(), ShipmentSnapshotError> {
+    let Some(visibility) = policy.visibility_for(actor) else {
+        return Ok(());
+    };
+
+    if ShipmentsRepo::is_visible(db, shipment_id, &visibility).await? {
+        Ok(())
+    } else {
+        Err(DbErr::RecordNotFound("shipment not found".into()).into())
+    }
+}
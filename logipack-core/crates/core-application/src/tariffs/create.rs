@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use core_data::repository::tariffs_repo::{TariffRepoError, TariffVersion, TariffsRepo};
+use core_domain::errors::TariffError;
+use core_domain::pricing::Tariff;
+use core_eventstore::adapter::append::AppendError;
+use core_eventstore::adapter::events::append_event;
+use core_eventstore::adapter::streams::{EnsureStreamError, ensure_stream};
+use core_eventstore::adapter::upcast::INITIAL_SCHEMA_VERSION;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, DbErr, TransactionTrait};
+use strata::map;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+use crate::tariffs::{TARIFF_STREAM_KIND, TariffPeriodError, check_period, record_version};
+
+#[derive(Debug, Clone)]
+pub struct CreateTariff {
+    /// Defaults to now. Versions cannot start in the past, shipments
+    /// already priced keep their tariff.
+    pub effective_from: Option<DateTime<Utc>>,
+    pub effective_to: Option<DateTime<Utc>>,
+    pub tariff: Tariff,
+}
+
+#[derive(Debug, Error)]
+pub enum CreateTariffError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid tariff: {0}")]
+    Invalid(#[from] TariffError),
+    #[error("tariff versions cannot start in the past")]
+    Retroactive,
+    #[error("{0}")]
+    Period(#[from] TariffPeriodError),
+    #[error("{0}")]
+    Repo(#[from] TariffRepoError),
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+    #[error("stream error: {0}")]
+    StreamError(#[from] EnsureStreamError),
+    #[error("eventstore error: {0}")]
+    EventstoreError(#[from] AppendError),
+}
+
+/// Adds a tariff version. Admin only.
+pub async fn create_tariff(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    input: CreateTariff,
+) -> Result<TariffVersion, CreateTariffError> {
+    if !actor.is_admin() {
+        return Err(CreateTariffError::Forbidden);
+    }
+
+    input.tariff.validate()?;
+
+    let now = Utc::now();
+    let effective_from = match input.effective_from {
+        Some(from) if from < now => return Err(CreateTariffError::Retroactive),
+        Some(from) => from,
+        None => now,
+    };
+
+    let version = TariffVersion {
+        id: Uuid::new_v4(),
+        effective_from: effective_from.into(),
+        effective_to: input.effective_to.map(Into::into),
+        tariff: input.tariff,
+        created_by: Some(actor.user_id),
+        created_at: now.into(),
+        updated_at: now.into(),
+    };
+
+    let txn = db.begin().await?;
+
+    match create_tariff_txn(&txn, actor, version).await {
+        Ok(version) => {
+            txn.commit().await?;
+            Ok(version)
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+async fn create_tariff_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    version: TariffVersion,
+) -> Result<TariffVersion, CreateTariffError> {
+    check_period::<CreateTariffError>(
+        txn,
+        version.effective_from.into(),
+        version.effective_to.map(Into::into),
+        None,
+    )
+    .await?;
+
+    let version = TariffsRepo::insert(txn, version).await?;
+
+    ensure_stream(txn, version.id, TARIFF_STREAM_KIND).await?;
+    append_event(
+        txn,
+        version.id,
+        TARIFF_STREAM_KIND,
+        INITIAL_SCHEMA_VERSION,
+        &map! {},
+    )
+    .await?;
+    record_version(txn, actor, &version).await?;
+
+    Ok(version)
+}
@@ -0,0 +1,89 @@
+use chrono::Utc;
+use core_data::repository::tariffs_repo::{TariffRepoError, TariffsRepo};
+use core_domain::pricing::TariffVersionWithdrawn;
+use core_domain::shipment::{ActorRef, EventCodec};
+use core_eventstore::adapter::append::AppendError;
+use core_eventstore::adapter::events::append_event;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, DbErr, TransactionTrait};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum DeleteTariffError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("not found")]
+    NotFound,
+    #[error("tariff version is already in force")]
+    InForce,
+    #[error("{0}")]
+    Repo(#[from] TariffRepoError),
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+    #[error("eventstore error: {0}")]
+    EventstoreError(#[from] AppendError),
+}
+
+/// Deletes a tariff version that has not come into force yet. Admin only.
+///
+/// The version's stream is kept and closed with `TariffVersionWithdrawn`.
+pub async fn delete_tariff(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<(), DeleteTariffError> {
+    if !actor.is_admin() {
+        return Err(DeleteTariffError::Forbidden);
+    }
+
+    let txn = db.begin().await?;
+
+    match delete_tariff_txn(&txn, actor, id).await {
+        Ok(()) => {
+            txn.commit().await?;
+            Ok(())
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+async fn delete_tariff_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<(), DeleteTariffError> {
+    let existing = TariffsRepo::get(txn, id)
+        .await?
+        .ok_or(DeleteTariffError::NotFound)?;
+
+    let now = Utc::now();
+    if existing.effective_from <= now {
+        return Err(DeleteTariffError::InForce);
+    }
+
+    TariffsRepo::delete(txn, id).await?;
+
+    let event = TariffVersionWithdrawn {
+        tariff_id: id.to_string(),
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
+        },
+        occurred_at_ms: now.timestamp_millis(),
+    };
+
+    append_event(
+        txn,
+        id,
+        TariffVersionWithdrawn::EVENT_TYPE,
+        TariffVersionWithdrawn::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use core_data::repository::tariffs_repo::{TariffRepoError, TariffVersion, TariffsRepo};
+use sea_orm::DatabaseConnection;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum GetTariffError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("not found")]
+    NotFound,
+    #[error("{0}")]
+    Repo(#[from] TariffRepoError),
+}
+
+pub async fn get_tariff(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<TariffVersion, GetTariffError> {
+    // Only admin can read tariff versions
+    if !actor.is_admin() {
+        return Err(GetTariffError::Forbidden);
+    }
+
+    TariffsRepo::get(db, id)
+        .await?
+        .ok_or(GetTariffError::NotFound)
+}
@@ -0,0 +1,26 @@
+use core_data::repository::tariffs_repo::{TariffRepoError, TariffVersion, TariffsRepo};
+use sea_orm::DatabaseConnection;
+use thiserror::Error;
+
+use crate::actor::ActorContext;
+
+#[derive(Debug, Error)]
+pub enum ListTariffsError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("{0}")]
+    Repo(#[from] TariffRepoError),
+}
+
+/// Every tariff version, earliest first.
+pub async fn list_tariffs(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+) -> Result<Vec<TariffVersion>, ListTariffsError> {
+    // Only admin can list tariff versions
+    if !actor.is_admin() {
+        return Err(ListTariffsError::Forbidden);
+    }
+
+    Ok(TariffsRepo::list(db).await?)
+}
@@ -0,0 +1,101 @@
+//! Tariff versions. Each version prices the shipments created while it is
+//! in force, `[effective_from, effective_to)`; versions never overlap.
+//! Outside every version the configured tariff applies.
+//!
+//! Every change is appended to the version's own stream of kind `tariff`.
+
+pub mod create;
+pub mod delete;
+pub mod get;
+pub mod list;
+pub mod update;
+
+use chrono::{DateTime, Utc};
+use core_data::repository::tariffs_repo::{TariffRepoError, TariffVersion, TariffsRepo};
+use core_domain::pricing::{Tariff, TariffVersionRecorded};
+use core_domain::shipment::{ActorRef, EventCodec};
+use core_eventstore::adapter::append::AppendError;
+use core_eventstore::adapter::events::append_event;
+use sea_orm::{ConnectionTrait, DatabaseTransaction, DbErr};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+
+pub const TARIFF_STREAM_KIND: &str = "tariff";
+
+#[derive(Debug, Error)]
+pub enum TariffPeriodError {
+    #[error("effective_to must be after effective_from")]
+    Empty,
+    #[error("period overlaps tariff version {0}")]
+    Overlaps(Uuid),
+}
+
+/// Tariff in force at `at` and the id of its version; `fallback` with no
+/// id when no version covers `at`.
+pub async fn tariff_in_force<C: ConnectionTrait>(
+    db: &C,
+    at: DateTime<Utc>,
+    fallback: &Tariff,
+) -> Result<(Option<Uuid>, Tariff), TariffRepoError> {
+    Ok(match TariffsRepo::in_force(db, at.into()).await? {
+        Some(version) => (Some(version.id), version.tariff),
+        None => (None, fallback.clone()),
+    })
+}
+
+/// Rejects empty periods and periods overlapping any version but `except`.
+///
+/// Locks the table for the rest of the transaction, so two writers cannot
+/// both pass the check; the exclusion constraint backs it up.
+async fn check_period<E>(
+    txn: &DatabaseTransaction,
+    from: DateTime<Utc>,
+    to: Option<DateTime<Utc>>,
+    except: Option<Uuid>,
+) -> Result<(), E>
+where
+    E: From<TariffPeriodError> + From<DbErr>,
+{
+    if to.is_some_and(|to| to <= from) {
+        return Err(TariffPeriodError::Empty.into());
+    }
+
+    txn.execute_unprepared("LOCK TABLE tariffs IN SHARE ROW EXCLUSIVE MODE")
+        .await?;
+
+    if let Some(other) =
+        TariffsRepo::find_overlapping(txn, from.into(), to.map(Into::into), except).await?
+    {
+        return Err(TariffPeriodError::Overlaps(other.id).into());
+    }
+
+    Ok(())
+}
+
+async fn record_version(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    version: &TariffVersion,
+) -> Result<(), AppendError> {
+    let event = TariffVersionRecorded {
+        tariff_id: version.id.to_string(),
+        effective_from_ms: version.effective_from.timestamp_millis(),
+        effective_to_ms: version.effective_to.map(|to| to.timestamp_millis()),
+        tariff: version.tariff.clone(),
+        actor: ActorRef {
+            id: actor.user_id.to_string(),
+        },
+        occurred_at_ms: Utc::now().timestamp_millis(),
+    };
+
+    append_event(
+        txn,
+        version.id,
+        TariffVersionRecorded::EVENT_TYPE,
+        TariffVersionRecorded::SCHEMA_VERSION,
+        &event.encode(),
+    )
+    .await
+}
@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use core_data::repository::tariffs_repo::{TariffRepoError, TariffVersion, TariffsRepo};
+use core_domain::errors::TariffError;
+use core_domain::pricing::Tariff;
+use core_eventstore::adapter::append::AppendError;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, DbErr, TransactionTrait};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::actor::ActorContext;
+use crate::tariffs::{TariffPeriodError, check_period, record_version};
+
+/// Replaces a version's period and rules.
+#[derive(Debug, Clone)]
+pub struct UpdateTariff {
+    pub id: Uuid,
+    pub effective_from: DateTime<Utc>,
+    pub effective_to: Option<DateTime<Utc>>,
+    pub tariff: Tariff,
+}
+
+#[derive(Debug, Error)]
+pub enum UpdateTariffError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("not found")]
+    NotFound,
+    #[error("invalid tariff: {0}")]
+    Invalid(#[from] TariffError),
+    /// Versions that came into force only have their end moved, and not
+    /// into the past.
+    #[error("tariff version is already in force")]
+    InForce,
+    #[error("tariff versions cannot start in the past")]
+    Retroactive,
+    #[error("{0}")]
+    Period(#[from] TariffPeriodError),
+    #[error("{0}")]
+    Repo(#[from] TariffRepoError),
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+    #[error("eventstore error: {0}")]
+    EventstoreError(#[from] AppendError),
+}
+
+/// Edits a tariff version. Admin only.
+///
+/// Versions not yet in force can be changed freely. Once in force, the
+/// rules and start are fixed so every shipment keeps the tariff that
+/// priced it; the end can still be set or moved, as long as it is not in
+/// the past.
+pub async fn update_tariff(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    input: UpdateTariff,
+) -> Result<TariffVersion, UpdateTariffError> {
+    if !actor.is_admin() {
+        return Err(UpdateTariffError::Forbidden);
+    }
+
+    input.tariff.validate()?;
+
+    let txn = db.begin().await?;
+
+    match update_tariff_txn(&txn, actor, input).await {
+        Ok(version) => {
+            txn.commit().await?;
+            Ok(version)
+        }
+        Err(err) => {
+            txn.rollback().await.ok();
+            Err(err)
+        }
+    }
+}
+
+async fn update_tariff_txn(
+    txn: &DatabaseTransaction,
+    actor: &ActorContext,
+    input: UpdateTariff,
+) -> Result<TariffVersion, UpdateTariffError> {
+    let existing = TariffsRepo::get(txn, input.id)
+        .await?
+        .ok_or(UpdateTariffError::NotFound)?;
+
+    let now = Utc::now();
+
+    if existing.effective_from <= now {
+        let ended = existing.effective_to.is_some_and(|to| to <= now);
+        let moved_into_past = input.effective_to.is_some_and(|to| to < now);
+
+        if ended
+            || moved_into_past
+            || input.effective_from != existing.effective_from
+            || input.tariff != existing.tariff
+        {
+            return Err(UpdateTariffError::InForce);
+        }
+    } else if input.effective_from < now {
+        return Err(UpdateTariffError::Retroactive);
+    }
+
+    check_period::<UpdateTariffError>(
+        txn,
+        input.effective_from,
+        input.effective_to,
+        Some(input.id),
+    )
+    .await?;
+
+    let version = TariffVersion {
+        effective_from: input.effective_from.into(),
+        effective_to: input.effective_to.map(Into::into),
+        tariff: input.tariff,
+        updated_at: now.into(),
+        ..existing
+    };
+
+    let version = TariffsRepo::update(txn, version).await?;
+    record_version(txn, actor, &version).await?;
+
+    Ok(version)
+}
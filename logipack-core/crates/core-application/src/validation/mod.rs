@@ -1,3 +1,4 @@
 pub mod client;
 pub mod office;
+pub mod shipment;
 pub mod user;
@@ -0,0 +1,338 @@
+use core_domain::shipment::{Address, Parcel, Party};
+use thiserror::Error;
+
+use crate::validation::client;
+
+pub const MAX_WEIGHT_G: i32 = 70_000;
+pub const MAX_SIDE_MM: i32 = 3_000;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PartyValidationError {
+    #[error("name too short")]
+    NameTooShort,
+    #[error("name too long")]
+    NameTooLong,
+    #[error("invalid email")]
+    InvalidEmail,
+    #[error("invalid phone")]
+    InvalidPhone,
+    #[error("address too short")]
+    AddressTooShort,
+    #[error("address too long")]
+    AddressTooLong,
+    #[error("city too short")]
+    CityTooShort,
+    #[error("city too long")]
+    CityTooLong,
+    #[error("invalid postal code")]
+    InvalidPostalCode,
+    #[error("invalid country")]
+    InvalidCountry,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParcelValidationError {
+    #[error("invalid weight")]
+    InvalidWeight,
+    #[error("invalid dimensions")]
+    InvalidDimensions,
+    #[error("invalid declared value")]
+    InvalidDeclaredValue,
+    #[error("description too long")]
+    DescriptionTooLong,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ShipmentValidationError {
+    #[error("sender: {0}")]
+    Sender(PartyValidationError),
+    #[error("recipient: {0}")]
+    Recipient(PartyValidationError),
+    #[error("parcel: {0}")]
+    Parcel(#[from] ParcelValidationError),
+}
+
+pub fn validate_shipment(
+    sender: Option<&Party>,
+    recipient: Option<&Party>,
+    parcel: Option<&Parcel>,
+) -> Result<(), ShipmentValidationError> {
+    if let Some(sender) = sender {
+        validate_party(sender).map_err(ShipmentValidationError::Sender)?;
+    }
+
+    if let Some(recipient) = recipient {
+        validate_party(recipient).map_err(ShipmentValidationError::Recipient)?;
+    }
+
+    if let Some(parcel) = parcel {
+        validate_parcel(parcel)?;
+    }
+
+    Ok(())
+}
+
+pub fn validate_party(party: &Party) -> Result<(), PartyValidationError> {
+    validate_name(&party.name)?;
+
+    if let Some(email) = party.email.as_deref() {
+        client::validate_email(email).map_err(|_| PartyValidationError::InvalidEmail)?;
+    }
+
+    client::validate_phone(party.phone.as_deref())
+        .map_err(|_| PartyValidationError::InvalidPhone)?;
+
+    validate_address(&party.address)?;
+    Ok(())
+}
+
+pub fn validate_name(name: &str) -> Result<(), PartyValidationError> {
+    let trimmed = name.trim();
+    let len = trimmed.chars().count();
+
+    if len < 2 {
+        return Err(PartyValidationError::NameTooShort);
+    }
+
+    if len > 100 {
+        return Err(PartyValidationError::NameTooLong);
+    }
+
+    Ok(())
+}
+
+pub fn validate_address(address: &Address) -> Result<(), PartyValidationError> {
+    validate_address_line(&address.line1)?;
+
+    if let Some(line2) = address.line2.as_deref() {
+        validate_address_line(line2)?;
+    }
+
+    validate_city(&address.city)?;
+    validate_postal_code(&address.postal_code)?;
+    validate_country(&address.country)?;
+    Ok(())
+}
+
+pub fn validate_address_line(line: &str) -> Result<(), PartyValidationError> {
+    let trimmed = line.trim();
+    let len = trimmed.chars().count();
+
+    if len < 2 {
+        return Err(PartyValidationError::AddressTooShort);
+    }
+
+    if len > 200 {
+        return Err(PartyValidationError::AddressTooLong);
+    }
+
+    Ok(())
+}
+
+pub fn validate_city(city: &str) -> Result<(), PartyValidationError> {
+    let trimmed = city.trim();
+    let len = trimmed.chars().count();
+
+    if len < 2 {
+        return Err(PartyValidationError::CityTooShort);
+    }
+
+    if len > 100 {
+        return Err(PartyValidationError::CityTooLong);
+    }
+
+    Ok(())
+}
+
+pub fn validate_postal_code(code: &str) -> Result<(), PartyValidationError> {
+    let trimmed = code.trim();
+    let len = trimmed.chars().count();
+
+    if !(3..=10).contains(&len) {
+        return Err(PartyValidationError::InvalidPostalCode);
+    }
+
+    if !trimmed
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == ' ' || ch == '-')
+    {
+        return Err(PartyValidationError::InvalidPostalCode);
+    }
+
+    Ok(())
+}
+
+/// ISO 3166-1 alpha-2, upper case.
+pub fn validate_country(country: &str) -> Result<(), PartyValidationError> {
+    if country.len() != 2 || !country.chars().all(|ch| ch.is_ascii_uppercase()) {
+        return Err(PartyValidationError::InvalidCountry);
+    }
+
+    Ok(())
+}
+
+pub fn validate_parcel(parcel: &Parcel) -> Result<(), ParcelValidationError> {
+    if !(1..=MAX_WEIGHT_G).contains(&parcel.weight_g) {
+        return Err(ParcelValidationError::InvalidWeight);
+    }
+
+    if let Some(dims) = parcel.dimensions {
+        let sides = [dims.length_mm, dims.width_mm, dims.height_mm];
+
+        if !sides.iter().all(|side| (1..=MAX_SIDE_MM).contains(side)) {
+            return Err(ParcelValidationError::InvalidDimensions);
+        }
+    }
+
+    if parcel.declared_value_cents.is_some_and(|cents| cents < 0) {
+        return Err(ParcelValidationError::InvalidDeclaredValue);
+    }
+
+    if let Some(description) = parcel.description.as_deref()
+        && description.trim().chars().count() > 500
+    {
+        return Err(ParcelValidationError::DescriptionTooLong);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core_domain::shipment::Dimensions;
+
+    use super::*;
+
+    fn party() -> Party {
+        Party {
+            name: "Jane Doe".into(),
+            phone: Some("+359888123456".into()),
+            email: Some("jane@example.com".into()),
+            address: Address {
+                line1: "1 Vitosha Blvd".into(),
+                line2: None,
+                city: "Sofia".into(),
+                postal_code: "1000".into(),
+                country: "BG".into(),
+            },
+        }
+    }
+
+    fn parcel() -> Parcel {
+        Parcel {
+            weight_g: 1_200,
+            dimensions: Some(Dimensions {
+                length_mm: 300,
+                width_mm: 200,
+                height_mm: 100,
+            }),
+            declared_value_cents: Some(5_000),
+            description: Some("books".into()),
+        }
+    }
+
+    #[test]
+    fn valid_shipment() {
+        let result = validate_shipment(Some(&party()), Some(&party()), Some(&parcel()));
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn details_are_optional() {
+        assert_eq!(validate_shipment(None, None, None), Ok(()));
+    }
+
+    #[test]
+    fn errors_name_the_party() {
+        let mut recipient = party();
+        recipient.name = " ".into();
+
+        let result = validate_shipment(Some(&party()), Some(&recipient), None);
+        assert_eq!(
+            result,
+            Err(ShipmentValidationError::Recipient(
+                PartyValidationError::NameTooShort
+            ))
+        );
+    }
+
+    #[test]
+    fn invalid_contact_rejected() {
+        let mut p = party();
+        p.email = Some("jane@".into());
+        assert_eq!(validate_party(&p), Err(PartyValidationError::InvalidEmail));
+
+        let mut p = party();
+        p.phone = Some("0888123456".into());
+        assert_eq!(validate_party(&p), Err(PartyValidationError::InvalidPhone));
+    }
+
+    #[test]
+    fn invalid_address_cases() {
+        let mut address = party().address;
+        address.line2 = Some("A".into());
+        assert_eq!(
+            validate_address(&address),
+            Err(PartyValidationError::AddressTooShort)
+        );
+
+        for code in ["", "12", "12345678901", "10_00"] {
+            assert_eq!(
+                validate_postal_code(code),
+                Err(PartyValidationError::InvalidPostalCode)
+            );
+        }
+
+        for country in ["", "bg", "BGR", "B1"] {
+            assert_eq!(
+                validate_country(country),
+                Err(PartyValidationError::InvalidCountry)
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_parcel_cases() {
+        for weight_g in [0, -1, MAX_WEIGHT_G + 1] {
+            let p = Parcel {
+                weight_g,
+                ..parcel()
+            };
+            assert_eq!(
+                validate_parcel(&p),
+                Err(ParcelValidationError::InvalidWeight)
+            );
+        }
+
+        let p = Parcel {
+            dimensions: Some(Dimensions {
+                length_mm: 300,
+                width_mm: 0,
+                height_mm: 100,
+            }),
+            ..parcel()
+        };
+        assert_eq!(
+            validate_parcel(&p),
+            Err(ParcelValidationError::InvalidDimensions)
+        );
+
+        let p = Parcel {
+            declared_value_cents: Some(-1),
+            ..parcel()
+        };
+        assert_eq!(
+            validate_parcel(&p),
+            Err(ParcelValidationError::InvalidDeclaredValue)
+        );
+
+        let p = Parcel {
+            description: Some("a".repeat(501)),
+            ..parcel()
+        };
+        assert_eq!(
+            validate_parcel(&p),
+            Err(ParcelValidationError::DescriptionTooLong)
+        );
+    }
+}
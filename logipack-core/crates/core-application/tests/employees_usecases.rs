@@ -15,14 +15,20 @@ use uuid::Uuid;
 
 async fn cleanup(db: &DatabaseConnection) {
     let tables = [
+        "cod_collections",
+        "cod_remittances",
         "shipment_status_history",
+        "shipment_route_stops",
         "shipments",
+        "tariffs",
         "employee_offices",
         "employees",
         "user_roles",
         "users",
         "clients",
         "roles",
+        "office_calendars",
+        "office_links",
         "offices",
         "packages",
         "streams",
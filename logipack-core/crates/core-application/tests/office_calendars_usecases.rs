@@ -0,0 +1,540 @@
+use chrono::{Duration, NaiveDate, NaiveTime, Timelike, Utc, Weekday};
+use core_application::actor::ActorContext;
+use core_application::office_calendars::get::{GetOfficeCalendarError, get_office_calendar};
+use core_application::office_calendars::holidays::{
+    OfficeHolidayError, remove_office_holiday, set_office_holiday,
+};
+use core_application::office_calendars::remove::{
+    RemoveOfficeCalendarError, remove_office_calendar,
+};
+use core_application::office_calendars::set::{SetOfficeCalendarError, set_office_calendar};
+use core_application::office_links::set::{SetOfficeLink, set_office_link};
+use core_application::roles::Role;
+use core_application::shipments::create::{CreateShipment, create_shipment};
+use core_application::shipments::get_detail::get_shipment_detail;
+use core_application::shipments::visibility::ReadPolicy;
+use core_data::entity::{clients, employee_offices, employees, offices, users};
+use core_domain::errors::CalendarError;
+use core_domain::network::{Holiday, OfficeCalendar, OpeningHours, Tz};
+use core_domain::pricing::Tariff;
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DatabaseConnection, DbBackend, Set, Statement};
+use test_infra::test_db;
+use uuid::Uuid;
+
+async fn cleanup(db: &DatabaseConnection) {
+    let tables = [
+        "cod_collections",
+        "cod_remittances",
+        "shipment_status_history",
+        "shipment_route_stops",
+        "shipments",
+        "tariffs",
+        "employee_offices",
+        "employees",
+        "user_roles",
+        "users",
+        "clients",
+        "roles",
+        "office_calendars",
+        "office_links",
+        "offices",
+        "packages",
+        "streams",
+    ];
+
+    for t in tables {
+        db.execute(Statement::from_string(
+            DbBackend::Postgres,
+            format!("DELETE FROM {}", t),
+        ))
+        .await
+        .unwrap();
+    }
+}
+
+async fn seed_office(db: &DatabaseConnection) -> Uuid {
+    let id = Uuid::new_v4();
+
+    offices::ActiveModel {
+        id: Set(id),
+        name: Set("Office".into()),
+        city: Set("City".into()),
+        address: Set("Address".into()),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn seed_client(db: &DatabaseConnection) -> Uuid {
+    let id = Uuid::new_v4();
+
+    clients::ActiveModel {
+        id: Set(id),
+        name: Set("Test Client".into()),
+        phone: Set(None),
+        email: Set(None),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn seed_user(db: &DatabaseConnection, user_type: &str) -> Uuid {
+    let id = Uuid::new_v4();
+
+    users::ActiveModel {
+        id: Set(id),
+        name: Set("Test User".into()),
+        email: Set(Some(format!("{user_type}+{id}@test.com"))),
+        password_hash: Set(Some("x".into())),
+        auth0_sub: Set(None),
+        created_at: Set(chrono::Utc::now().into()),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn admin_actor(db: &DatabaseConnection) -> ActorContext {
+    let user_id = seed_user(db, "admin").await;
+
+    ActorContext {
+        user_id,
+        sub: "admin".into(),
+        roles: vec![Role::Admin],
+        employee_id: None,
+        allowed_office_ids: vec![],
+    }
+}
+
+async fn employee_actor(db: &DatabaseConnection, allowed_office_ids: Vec<Uuid>) -> ActorContext {
+    let user_id = seed_user(db, "employee").await;
+    let employee_id = Uuid::new_v4();
+
+    employees::ActiveModel {
+        id: Set(employee_id),
+        user_id: Set(user_id),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    for office_id in &allowed_office_ids {
+        employee_offices::ActiveModel {
+            employee_id: Set(employee_id),
+            office_id: Set(*office_id),
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    ActorContext {
+        user_id,
+        sub: "employee".into(),
+        roles: vec![Role::Employee],
+        employee_id: Some(employee_id),
+        allowed_office_ids,
+    }
+}
+
+async fn link(db: &DatabaseConnection, admin: &ActorContext, from: Uuid, to: Uuid, minutes: i32) {
+    set_office_link(
+        db,
+        admin,
+        SetOfficeLink {
+            from_office_id: from,
+            to_office_id: to,
+            transit_minutes: minutes,
+            cost_cents: 100,
+        },
+    )
+    .await
+    .unwrap();
+}
+
+fn time(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// Open 09:00-10:00 UTC every day of the week.
+fn one_hour_a_day() -> OfficeCalendar {
+    OfficeCalendar {
+        timezone: Tz::UTC,
+        hours: [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]
+        .into_iter()
+        .map(|weekday| OpeningHours {
+            weekday,
+            opens: time(9, 0),
+            closes: time(10, 0),
+        })
+        .collect(),
+        holidays: vec![],
+    }
+}
+
+fn holiday(day: u32, name: Option<&str>) -> Holiday {
+    Holiday {
+        date: date(2026, 12, day),
+        name: name.map(Into::into),
+    }
+}
+
+#[tokio::test]
+async fn admin_can_set_office_calendar() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+
+    let stored = set_office_calendar(&db, &admin, office, one_hour_a_day())
+        .await
+        .unwrap();
+    assert_eq!(stored.calendar, one_hour_a_day());
+
+    let fetched = get_office_calendar(&db, &admin, office).await.unwrap();
+    assert_eq!(fetched.calendar, one_hour_a_day());
+}
+
+#[tokio::test]
+async fn calendar_without_opening_hours_is_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+
+    let err = set_office_calendar(
+        &db,
+        &admin,
+        office,
+        OfficeCalendar {
+            hours: vec![],
+            ..one_hour_a_day()
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        SetOfficeCalendarError::Invalid(CalendarError::NoOpeningHours)
+    ));
+}
+
+#[tokio::test]
+async fn calendar_for_unknown_office_is_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let err = set_office_calendar(&db, &admin, Uuid::new_v4(), one_hour_a_day())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, SetOfficeCalendarError::OfficeNotFound));
+}
+
+#[tokio::test]
+async fn employee_cannot_set_office_calendar() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let employee = employee_actor(&db, vec![office]).await;
+
+    let err = set_office_calendar(&db, &employee, office, one_hour_a_day())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, SetOfficeCalendarError::Forbidden));
+}
+
+#[tokio::test]
+async fn getting_missing_office_calendar_returns_error() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+
+    let err = get_office_calendar(&db, &admin, office).await.unwrap_err();
+    assert!(matches!(err, GetOfficeCalendarError::NotFound));
+}
+
+#[tokio::test]
+async fn employee_cannot_get_office_calendar() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+    let employee = employee_actor(&db, vec![office]).await;
+
+    set_office_calendar(&db, &admin, office, one_hour_a_day())
+        .await
+        .unwrap();
+
+    let err = get_office_calendar(&db, &employee, office)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GetOfficeCalendarError::Forbidden));
+}
+
+#[tokio::test]
+async fn admin_can_remove_office_calendar() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+
+    set_office_calendar(&db, &admin, office, one_hour_a_day())
+        .await
+        .unwrap();
+
+    remove_office_calendar(&db, &admin, office).await.unwrap();
+
+    let err = get_office_calendar(&db, &admin, office).await.unwrap_err();
+    assert!(matches!(err, GetOfficeCalendarError::NotFound));
+}
+
+#[tokio::test]
+async fn removing_missing_office_calendar_returns_error() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+
+    let err = remove_office_calendar(&db, &admin, office)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RemoveOfficeCalendarError::NotFound));
+}
+
+#[tokio::test]
+async fn employee_cannot_remove_office_calendar() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+    let employee = employee_actor(&db, vec![office]).await;
+
+    set_office_calendar(&db, &admin, office, one_hour_a_day())
+        .await
+        .unwrap();
+
+    let err = remove_office_calendar(&db, &employee, office)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RemoveOfficeCalendarError::Forbidden));
+}
+
+#[tokio::test]
+async fn holidays_are_kept_in_date_order_and_renamed_in_place() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+
+    set_office_calendar(&db, &admin, office, one_hour_a_day())
+        .await
+        .unwrap();
+
+    for (day, name) in [
+        (25, "Christmas"),
+        (24, "Christmas Eve"),
+        (25, "Christmas Day"),
+    ] {
+        set_office_holiday(&db, &admin, office, holiday(day, Some(name)))
+            .await
+            .unwrap();
+    }
+
+    let calendar = get_office_calendar(&db, &admin, office)
+        .await
+        .unwrap()
+        .calendar;
+    let holidays: Vec<(NaiveDate, Option<String>)> = calendar
+        .holidays
+        .into_iter()
+        .map(|h| (h.date, h.name))
+        .collect();
+    assert_eq!(
+        holidays,
+        vec![
+            (date(2026, 12, 24), Some("Christmas Eve".into())),
+            (date(2026, 12, 25), Some("Christmas Day".into())),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn holiday_without_calendar_is_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+
+    let err = set_office_holiday(&db, &admin, office, holiday(31, None))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OfficeHolidayError::CalendarNotFound));
+}
+
+#[tokio::test]
+async fn employee_cannot_set_office_holiday() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+    let employee = employee_actor(&db, vec![office]).await;
+
+    set_office_calendar(&db, &admin, office, one_hour_a_day())
+        .await
+        .unwrap();
+
+    let err = set_office_holiday(&db, &employee, office, holiday(24, None))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OfficeHolidayError::Forbidden));
+}
+
+#[tokio::test]
+async fn admin_can_remove_office_holiday() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+
+    set_office_calendar(&db, &admin, office, one_hour_a_day())
+        .await
+        .unwrap();
+    for day in [24, 25] {
+        set_office_holiday(&db, &admin, office, holiday(day, None))
+            .await
+            .unwrap();
+    }
+
+    let calendar = remove_office_holiday(&db, &admin, office, date(2026, 12, 24))
+        .await
+        .unwrap()
+        .calendar;
+    assert_eq!(calendar.holidays.len(), 1);
+    assert_eq!(calendar.holidays[0].date, date(2026, 12, 25));
+}
+
+#[tokio::test]
+async fn removing_missing_holiday_returns_error() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+
+    set_office_calendar(&db, &admin, office, one_hour_a_day())
+        .await
+        .unwrap();
+
+    let err = remove_office_holiday(&db, &admin, office, date(2026, 12, 24))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OfficeHolidayError::HolidayNotFound));
+}
+
+#[tokio::test]
+async fn employee_cannot_remove_office_holiday() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let office = seed_office(&db).await;
+    let employee = employee_actor(&db, vec![office]).await;
+
+    set_office_calendar(&db, &admin, office, one_hour_a_day())
+        .await
+        .unwrap();
+    set_office_holiday(&db, &admin, office, holiday(24, None))
+        .await
+        .unwrap();
+
+    let err = remove_office_holiday(&db, &employee, office, date(2026, 12, 24))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OfficeHolidayError::Forbidden));
+}
+
+#[tokio::test]
+async fn delivery_promise_waits_for_the_destination_to_open() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let client = seed_client(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    link(&db, &admin, a, b, 60).await;
+
+    set_office_calendar(&db, &admin, b, one_hour_a_day())
+        .await
+        .unwrap();
+
+    let before = Utc::now();
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(a),
+            destination_office_id: Some(b),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let detail = get_shipment_detail(&db, &admin, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap();
+    let promised_by = detail.shipment.promised_by.unwrap();
+
+    // four hours at a, an hour on the road, then b starts within its hour
+    assert!(promised_by >= before + Duration::minutes(540) - Duration::milliseconds(1));
+    let started_at = (promised_by - Duration::minutes(240)).to_utc();
+    assert_eq!(started_at.hour(), 9, "started at {started_at}");
+}
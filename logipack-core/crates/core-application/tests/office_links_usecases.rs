@@ -0,0 +1,621 @@
+use core_application::actor::ActorContext;
+use core_application::office_links::list::{ListOfficeLinksError, list_office_links};
+use core_application::office_links::plan::plan_route;
+use core_application::office_links::remove::{RemoveOfficeLinkError, remove_office_link};
+use core_application::office_links::set::{SetOfficeLink, SetOfficeLinkError, set_office_link};
+use core_application::roles::Role;
+use core_application::shipments::change_status::{ChangeStatus, ChangeStatusError, change_status};
+use core_application::shipments::create::{CreateShipment, create_shipment};
+use core_application::shipments::get_detail::get_shipment_detail;
+use core_application::shipments::rebuild::{RebuildTarget, rebuild_shipment_projections};
+use core_application::shipments::timeline::read_timeline;
+use core_application::shipments::visibility::ReadPolicy;
+use core_data::entity::{clients, employee_offices, employees, offices, users};
+use core_domain::errors::OfficeLinkError;
+use core_domain::pricing::Tariff;
+use core_domain::shipment::{ShipmentEvent, ShipmentStatus, TransitionTable};
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DatabaseConnection, DbBackend, Set, Statement};
+use test_infra::test_db;
+use uuid::Uuid;
+
+async fn cleanup(db: &DatabaseConnection) {
+    let tables = [
+        "cod_collections",
+        "cod_remittances",
+        "shipment_status_history",
+        "shipment_route_stops",
+        "shipments",
+        "tariffs",
+        "employee_offices",
+        "employees",
+        "user_roles",
+        "users",
+        "clients",
+        "roles",
+        "office_calendars",
+        "office_links",
+        "offices",
+        "packages",
+        "streams",
+    ];
+
+    for t in tables {
+        db.execute(Statement::from_string(
+            DbBackend::Postgres,
+            format!("DELETE FROM {}", t),
+        ))
+        .await
+        .unwrap();
+    }
+}
+
+async fn seed_office(db: &DatabaseConnection) -> Uuid {
+    let id = Uuid::new_v4();
+
+    offices::ActiveModel {
+        id: Set(id),
+        name: Set("Office".into()),
+        city: Set("City".into()),
+        address: Set("Address".into()),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn seed_client(db: &DatabaseConnection) -> Uuid {
+    let id = Uuid::new_v4();
+
+    clients::ActiveModel {
+        id: Set(id),
+        name: Set("Test Client".into()),
+        phone: Set(None),
+        email: Set(None),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn seed_user(db: &DatabaseConnection, user_type: &str) -> Uuid {
+    let id = Uuid::new_v4();
+
+    users::ActiveModel {
+        id: Set(id),
+        name: Set("Test User".into()),
+        email: Set(Some(format!("{user_type}+{id}@test.com"))),
+        password_hash: Set(Some("x".into())),
+        auth0_sub: Set(None),
+        created_at: Set(chrono::Utc::now().into()),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn admin_actor(db: &DatabaseConnection) -> ActorContext {
+    let user_id = seed_user(db, "admin").await;
+
+    ActorContext {
+        user_id,
+        sub: "admin".into(),
+        roles: vec![Role::Admin],
+        employee_id: None,
+        allowed_office_ids: vec![],
+    }
+}
+
+async fn employee_actor(db: &DatabaseConnection, allowed_office_ids: Vec<Uuid>) -> ActorContext {
+    let user_id = seed_user(db, "employee").await;
+    let employee_id = Uuid::new_v4();
+
+    employees::ActiveModel {
+        id: Set(employee_id),
+        user_id: Set(user_id),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    for office_id in &allowed_office_ids {
+        employee_offices::ActiveModel {
+            employee_id: Set(employee_id),
+            office_id: Set(*office_id),
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    ActorContext {
+        user_id,
+        sub: "employee".into(),
+        roles: vec![Role::Employee],
+        employee_id: Some(employee_id),
+        allowed_office_ids,
+    }
+}
+
+async fn link(db: &DatabaseConnection, admin: &ActorContext, from: Uuid, to: Uuid, minutes: i32) {
+    set_office_link(
+        db,
+        admin,
+        SetOfficeLink {
+            from_office_id: from,
+            to_office_id: to,
+            transit_minutes: minutes,
+            cost_cents: 100,
+        },
+    )
+    .await
+    .unwrap();
+}
+
+fn hop(shipment_id: Uuid, to_status: ShipmentStatus, office: Uuid) -> ChangeStatus {
+    ChangeStatus {
+        shipment_id,
+        to_status,
+        to_office_id: Some(office),
+        notes: None,
+        failure_reason: None,
+        expected_seq: None,
+        proof_of_delivery: None,
+        override_route: false,
+    }
+}
+
+fn input(from: Uuid, to: Uuid, transit_minutes: i32) -> SetOfficeLink {
+    SetOfficeLink {
+        from_office_id: from,
+        to_office_id: to,
+        transit_minutes,
+        cost_cents: 0,
+    }
+}
+
+async fn route_offices(
+    db: &DatabaseConnection,
+    admin: &ActorContext,
+    from: Uuid,
+    to: Uuid,
+) -> Vec<Uuid> {
+    plan_route(db, admin, from, to)
+        .await
+        .unwrap()
+        .unwrap()
+        .offices()
+        .copied()
+        .collect()
+}
+
+/// Offices `a -> b -> c` plus a side link `d -> c`, with a shipment from `a`
+/// to `c` processed at `a` and ready to leave.
+struct Routed {
+    admin: ActorContext,
+    employee: ActorContext,
+    transitions: TransitionTable,
+    shipment_id: Uuid,
+    offices: [Uuid; 4],
+}
+
+async fn routed_shipment(db: &DatabaseConnection) -> Routed {
+    let admin = admin_actor(db).await;
+    let client = seed_client(db).await;
+    let a = seed_office(db).await;
+    let b = seed_office(db).await;
+    let c = seed_office(db).await;
+    let d = seed_office(db).await;
+    let employee = employee_actor(db, vec![a, b, c, d]).await;
+    let transitions = TransitionTable::default();
+
+    link(db, &admin, a, b, 60).await;
+    link(db, &admin, b, c, 60).await;
+    link(db, &admin, d, c, 60).await;
+
+    let shipment_id = create_shipment(
+        db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(a),
+            destination_office_id: Some(c),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    for to_status in [ShipmentStatus::Accepted, ShipmentStatus::Processed] {
+        change_status(db, &employee, &transitions, hop(shipment_id, to_status, a))
+            .await
+            .unwrap();
+    }
+
+    Routed {
+        admin,
+        employee,
+        transitions,
+        shipment_id,
+        offices: [a, b, c, d],
+    }
+}
+
+#[tokio::test]
+async fn admin_can_plan_route_across_links() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+
+    link(&db, &admin, a, b, 60).await;
+    link(&db, &admin, b, c, 60).await;
+    link(&db, &admin, a, c, 300).await;
+
+    let route = plan_route(&db, &admin, a, c).await.unwrap().unwrap();
+    assert_eq!(route.offices().copied().collect::<Vec<_>>(), vec![a, b, c]);
+    assert_eq!(route.transit_minutes(), 120);
+    assert_eq!(route.cost_cents(), 200);
+}
+
+#[tokio::test]
+async fn office_links_are_directed() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    link(&db, &admin, a, b, 60).await;
+
+    assert!(plan_route(&db, &admin, b, a).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn setting_existing_link_replaces_it() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+
+    link(&db, &admin, a, b, 60).await;
+    link(&db, &admin, b, c, 60).await;
+    link(&db, &admin, a, c, 300).await;
+
+    link(&db, &admin, a, c, 90).await;
+
+    let links = list_office_links(&db, &admin).await.unwrap();
+    assert_eq!(links.len(), 3);
+    assert_eq!(route_offices(&db, &admin, a, c).await, vec![a, c]);
+}
+
+#[tokio::test]
+async fn admin_can_remove_office_link() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+
+    link(&db, &admin, a, b, 60).await;
+    link(&db, &admin, b, c, 60).await;
+    link(&db, &admin, a, c, 90).await;
+
+    remove_office_link(&db, &admin, a, c).await.unwrap();
+
+    assert_eq!(route_offices(&db, &admin, a, c).await, vec![a, b, c]);
+}
+
+#[tokio::test]
+async fn removing_missing_link_returns_error() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    let err = remove_office_link(&db, &admin, a, b).await.unwrap_err();
+    assert!(matches!(err, RemoveOfficeLinkError::NotFound));
+}
+
+#[tokio::test]
+async fn self_loop_link_is_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let a = seed_office(&db).await;
+
+    let err = set_office_link(&db, &admin, input(a, a, 60))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        SetOfficeLinkError::Invalid(OfficeLinkError::SelfLoop)
+    ));
+}
+
+#[tokio::test]
+async fn non_positive_transit_is_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    let err = set_office_link(&db, &admin, input(a, b, 0))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        SetOfficeLinkError::Invalid(OfficeLinkError::NonPositiveTransit)
+    ));
+}
+
+#[tokio::test]
+async fn link_to_unknown_office_is_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let a = seed_office(&db).await;
+
+    let err = set_office_link(&db, &admin, input(a, Uuid::new_v4(), 60))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, SetOfficeLinkError::OfficeNotFound));
+}
+
+#[tokio::test]
+async fn employee_cannot_set_office_link() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let employee = employee_actor(&db, vec![a]).await;
+
+    let err = set_office_link(&db, &employee, input(a, b, 60))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, SetOfficeLinkError::Forbidden));
+}
+
+#[tokio::test]
+async fn employee_cannot_list_office_links() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let a = seed_office(&db).await;
+    let employee = employee_actor(&db, vec![a]).await;
+
+    let err = list_office_links(&db, &employee).await.unwrap_err();
+    assert!(matches!(err, ListOfficeLinksError::Forbidden));
+}
+
+#[tokio::test]
+async fn employee_cannot_remove_office_link() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let employee = employee_actor(&db, vec![a]).await;
+
+    link(&db, &admin, a, b, 60).await;
+
+    let err = remove_office_link(&db, &employee, a, b).await.unwrap_err();
+    assert!(matches!(err, RemoveOfficeLinkError::Forbidden));
+}
+
+#[tokio::test]
+async fn shipments_are_planned_along_office_links() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let Routed {
+        admin,
+        shipment_id,
+        offices: [a, b, c, _],
+        ..
+    } = routed_shipment(&db).await;
+
+    let detail = get_shipment_detail(&db, &admin, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap();
+    let route = detail.route.unwrap();
+    assert_eq!(route.offices().copied().collect::<Vec<_>>(), vec![a, b, c]);
+}
+
+#[tokio::test]
+async fn in_transit_hop_off_route_is_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let Routed {
+        employee,
+        transitions,
+        shipment_id,
+        offices: [_, b, c, _],
+        ..
+    } = routed_shipment(&db).await;
+
+    // skipping b leaves the route
+    let err = change_status(
+        &db,
+        &employee,
+        &transitions,
+        hop(shipment_id, ShipmentStatus::InTransit, c),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ChangeStatusError::OffRoute {
+            expected_office_id: Some(office)
+        } if office == b
+    ));
+}
+
+#[tokio::test]
+async fn employee_cannot_override_route() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let Routed {
+        employee,
+        transitions,
+        shipment_id,
+        offices: [_, _, c, _],
+        ..
+    } = routed_shipment(&db).await;
+
+    let err = change_status(
+        &db,
+        &employee,
+        &transitions,
+        ChangeStatus {
+            override_route: true,
+            ..hop(shipment_id, ShipmentStatus::InTransit, c)
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ChangeStatusError::Forbidden));
+}
+
+#[tokio::test]
+async fn delivery_before_destination_is_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let Routed {
+        employee,
+        transitions,
+        shipment_id,
+        offices: [_, b, c, _],
+        ..
+    } = routed_shipment(&db).await;
+
+    change_status(
+        &db,
+        &employee,
+        &transitions,
+        hop(shipment_id, ShipmentStatus::InTransit, b),
+    )
+    .await
+    .unwrap();
+
+    for to_status in [ShipmentStatus::OutForDelivery, ShipmentStatus::Delivered] {
+        let err = change_status(&db, &employee, &transitions, hop(shipment_id, to_status, b))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ChangeStatusError::NotAtDestination {
+                destination_office_id
+            } if destination_office_id == c
+        ));
+    }
+}
+
+#[tokio::test]
+async fn admin_override_replans_route() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let Routed {
+        admin,
+        employee,
+        transitions,
+        shipment_id,
+        offices: [_, b, c, d],
+    } = routed_shipment(&db).await;
+
+    for (to_status, office) in [
+        (ShipmentStatus::InTransit, b),
+        (ShipmentStatus::Processed, b),
+    ] {
+        change_status(
+            &db,
+            &employee,
+            &transitions,
+            hop(shipment_id, to_status, office),
+        )
+        .await
+        .unwrap();
+    }
+
+    // an admin detour re-plans from the new office
+    change_status(
+        &db,
+        &admin,
+        &transitions,
+        ChangeStatus {
+            override_route: true,
+            ..hop(shipment_id, ShipmentStatus::InTransit, d)
+        },
+    )
+    .await
+    .unwrap();
+
+    let detail = get_shipment_detail(&db, &admin, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap();
+    let route = detail.route.unwrap();
+    assert_eq!(route.offices().copied().collect::<Vec<_>>(), vec![d, c]);
+
+    for (to_status, office) in [
+        (ShipmentStatus::Processed, d),
+        (ShipmentStatus::InTransit, c),
+        (ShipmentStatus::OutForDelivery, c),
+    ] {
+        change_status(
+            &db,
+            &employee,
+            &transitions,
+            hop(shipment_id, to_status, office),
+        )
+        .await
+        .unwrap();
+    }
+
+    let timeline = read_timeline(&db, shipment_id).await.unwrap();
+    let planned = timeline
+        .iter()
+        .filter(|entry| matches!(entry.event, Some(ShipmentEvent::RoutePlanned(_))))
+        .count();
+    assert_eq!(planned, 2);
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
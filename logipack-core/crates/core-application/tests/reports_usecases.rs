@@ -0,0 +1,639 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use core_application::actor::ActorContext;
+use core_application::reports::by_client::shipments_by_client;
+use core_application::reports::by_office::shipments_by_office;
+use core_application::reports::by_period::shipments_by_period;
+use core_application::reports::by_status::shipments_by_status;
+use core_application::reports::cod::{cod_balances, cod_outstanding};
+use core_application::reports::on_hold::shipments_on_hold;
+use core_application::reports::scope::{ReportQuery, ShipmentReportError};
+use core_application::roles::Role;
+use core_data::entity::{clients, offices, shipments};
+use core_data::repository::cod_repo::{CodCollectionRow, CodRepo, RemittanceRow};
+use core_data::repository::reports_repo::PeriodBucket;
+use core_domain::shipment::ShipmentStatus;
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DatabaseConnection, DbBackend, Set, Statement};
+use test_infra::test_db;
+use uuid::Uuid;
+
+async fn cleanup(db: &DatabaseConnection) {
+    let tables = [
+        "cod_collections",
+        "cod_remittances",
+        "shipment_status_history",
+        "shipment_route_stops",
+        "shipments",
+        "tariffs",
+        "employee_offices",
+        "employees",
+        "user_roles",
+        "users",
+        "clients",
+        "roles",
+        "office_calendars",
+        "office_links",
+        "offices",
+        "packages",
+        "streams",
+    ];
+
+    for t in tables {
+        db.execute(Statement::from_string(
+            DbBackend::Postgres,
+            format!("DELETE FROM {}", t),
+        ))
+        .await
+        .unwrap();
+    }
+}
+
+async fn seed_client(db: &DatabaseConnection, name: &str) -> Uuid {
+    let id = Uuid::new_v4();
+
+    clients::ActiveModel {
+        id: Set(id),
+        name: Set(name.into()),
+        phone: Set(None),
+        email: Set(None),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn seed_office(db: &DatabaseConnection, name: &str) -> Uuid {
+    let id = Uuid::new_v4();
+
+    offices::ActiveModel {
+        id: Set(id),
+        name: Set(name.into()),
+        city: Set("City".into()),
+        address: Set("Address".into()),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+/// Reports read the projection only, so rows are written directly with a
+/// fixed `created_at`.
+async fn seed_shipment(
+    db: &DatabaseConnection,
+    client_id: Uuid,
+    office_id: Option<Uuid>,
+    status: ShipmentStatus,
+    created_at: DateTime<Utc>,
+) -> Uuid {
+    let id = Uuid::new_v4();
+
+    shipments::ActiveModel {
+        id: Set(id),
+        client_id: Set(client_id),
+        current_status: Set(status.to_string()),
+        current_office_id: Set(office_id),
+        created_at: Set(created_at.into()),
+        updated_at: Set(created_at.into()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+fn at(day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+}
+
+fn admin() -> ActorContext {
+    ActorContext {
+        user_id: Uuid::new_v4(),
+        sub: "admin".into(),
+        roles: vec![Role::Admin],
+        employee_id: None,
+        allowed_office_ids: vec![],
+    }
+}
+
+fn employee(offices: Vec<Uuid>) -> ActorContext {
+    ActorContext {
+        user_id: Uuid::new_v4(),
+        sub: "employee".into(),
+        roles: vec![Role::Employee],
+        employee_id: Some(Uuid::new_v4()),
+        allowed_office_ids: offices,
+    }
+}
+
+struct Dataset {
+    sofia: Uuid,
+    varna: Uuid,
+    acme: Uuid,
+    zeta: Uuid,
+}
+
+/// | client | office | status     | created_at  |
+/// |--------|--------|------------|-------------|
+/// | acme   | sofia  | NEW        | 03-01 00:00 |
+/// | acme   | sofia  | ACCEPTED   | 03-01 12:00 |
+/// | zeta   | varna  | NEW        | 03-02 00:00 |
+/// | zeta   | varna  | DELIVERED  | 03-09 08:00 |
+/// | acme   | -      | NEW        | 03-10 00:00 |
+async fn seed_dataset(db: &DatabaseConnection) -> Dataset {
+    cleanup(db).await;
+
+    let sofia = seed_office(db, "Sofia").await;
+    let varna = seed_office(db, "Varna").await;
+    let acme = seed_client(db, "Acme").await;
+    let zeta = seed_client(db, "Zeta").await;
+
+    seed_shipment(db, acme, Some(sofia), ShipmentStatus::New, at(1, 0)).await;
+    seed_shipment(db, acme, Some(sofia), ShipmentStatus::Accepted, at(1, 12)).await;
+    seed_shipment(db, zeta, Some(varna), ShipmentStatus::New, at(2, 0)).await;
+    seed_shipment(db, zeta, Some(varna), ShipmentStatus::Delivered, at(9, 8)).await;
+    seed_shipment(db, acme, None, ShipmentStatus::New, at(10, 0)).await;
+
+    Dataset {
+        sofia,
+        varna,
+        acme,
+        zeta,
+    }
+}
+
+#[tokio::test]
+async fn by_status_groups_and_orders_by_status() {
+    let db = test_db().await;
+    seed_dataset(&db).await;
+
+    let rows = shipments_by_status(&db, &admin(), ReportQuery::default())
+        .await
+        .unwrap();
+
+    let got: Vec<(&str, i64)> = rows.iter().map(|r| (r.status.as_str(), r.count)).collect();
+    assert_eq!(got, vec![("ACCEPTED", 1), ("DELIVERED", 1), ("NEW", 3)]);
+}
+
+#[tokio::test]
+async fn by_office_orders_by_name_with_unassigned_last() {
+    let db = test_db().await;
+    let data = seed_dataset(&db).await;
+
+    let rows = shipments_by_office(&db, &admin(), ReportQuery::default())
+        .await
+        .unwrap();
+
+    let got: Vec<(Option<Uuid>, Option<&str>, i64)> = rows
+        .iter()
+        .map(|r| (r.office_id, r.office_name.as_deref(), r.count))
+        .collect();
+
+    assert_eq!(
+        got,
+        vec![
+            (Some(data.sofia), Some("Sofia"), 2),
+            (Some(data.varna), Some("Varna"), 2),
+            (None, None, 1),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn by_client_applies_status_and_office_filters() {
+    let db = test_db().await;
+    let data = seed_dataset(&db).await;
+
+    let all = shipments_by_client(&db, &admin(), ReportQuery::default())
+        .await
+        .unwrap();
+    let got: Vec<(Uuid, &str, i64)> = all
+        .iter()
+        .map(|r| (r.client_id, r.client_name.as_str(), r.count))
+        .collect();
+    assert_eq!(got, vec![(data.acme, "Acme", 3), (data.zeta, "Zeta", 2)]);
+
+    let new_only = shipments_by_client(
+        &db,
+        &admin(),
+        ReportQuery {
+            status: Some(ShipmentStatus::New),
+            office_id: Some(data.varna),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let got: Vec<(Uuid, i64)> = new_only.iter().map(|r| (r.client_id, r.count)).collect();
+    assert_eq!(got, vec![(data.zeta, 1)]);
+
+    let by_client = shipments_by_status(
+        &db,
+        &admin(),
+        ReportQuery {
+            client_id: Some(data.acme),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let got: Vec<(&str, i64)> = by_client
+        .iter()
+        .map(|r| (r.status.as_str(), r.count))
+        .collect();
+    assert_eq!(got, vec![("ACCEPTED", 1), ("NEW", 2)]);
+}
+
+#[tokio::test]
+async fn period_bounds_are_inclusive_from_exclusive_to() {
+    let db = test_db().await;
+    seed_dataset(&db).await;
+
+    // [03-01 00:00, 03-02 00:00) holds the two shipments of 03-01 only
+    let first_day = shipments_by_status(
+        &db,
+        &admin(),
+        ReportQuery {
+            from: Some(at(1, 0).into()),
+            to: Some(at(2, 0).into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(first_day.iter().map(|r| r.count).sum::<i64>(), 2);
+
+    // the next period starts exactly where the previous one ended
+    let second_day = shipments_by_status(
+        &db,
+        &admin(),
+        ReportQuery {
+            from: Some(at(2, 0).into()),
+            to: Some(at(3, 0).into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(second_day.iter().map(|r| r.count).sum::<i64>(), 1);
+
+    let empty = shipments_by_status(
+        &db,
+        &admin(),
+        ReportQuery {
+            from: Some(at(2, 0).into()),
+            to: Some(at(2, 0).into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(empty, Err(ShipmentReportError::InvalidPeriod)));
+}
+
+#[tokio::test]
+async fn by_period_buckets_by_day_and_week() {
+    let db = test_db().await;
+    seed_dataset(&db).await;
+
+    let day = |d: u32| {
+        NaiveDate::from_ymd_opt(2026, 3, d)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    };
+
+    let daily = shipments_by_period(&db, &admin(), ReportQuery::default(), PeriodBucket::Day)
+        .await
+        .unwrap();
+    let got: Vec<_> = daily.iter().map(|r| (r.period_start, r.count)).collect();
+    assert_eq!(
+        got,
+        vec![(day(1), 2), (day(2), 1), (day(9), 1), (day(10), 1)]
+    );
+
+    // 2026-03-01 is a Sunday, so it belongs to the week of 02-23
+    let weekly = shipments_by_period(&db, &admin(), ReportQuery::default(), PeriodBucket::Week)
+        .await
+        .unwrap();
+    let got: Vec<_> = weekly.iter().map(|r| (r.period_start, r.count)).collect();
+    let feb_23 = NaiveDate::from_ymd_opt(2026, 2, 23)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    assert_eq!(got, vec![(feb_23, 2), (day(2), 1), (day(9), 2)]);
+}
+
+#[tokio::test]
+async fn employees_only_see_their_offices() {
+    let db = test_db().await;
+    let data = seed_dataset(&db).await;
+
+    let actor = employee(vec![data.varna]);
+
+    let rows = shipments_by_office(&db, &actor, ReportQuery::default())
+        .await
+        .unwrap();
+    let got: Vec<(Option<Uuid>, i64)> = rows.iter().map(|r| (r.office_id, r.count)).collect();
+    assert_eq!(got, vec![(Some(data.varna), 2)]);
+
+    let other = shipments_by_status(
+        &db,
+        &actor,
+        ReportQuery {
+            office_id: Some(data.sofia),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(other, Err(ShipmentReportError::Forbidden)));
+
+    let unassigned = shipments_by_status(&db, &employee(vec![]), ReportQuery::default())
+        .await
+        .unwrap();
+    assert!(unassigned.is_empty());
+}
+
+/// Holds the shipments matching `condition` for `reason`, two days after
+/// their creation.
+async fn hold_where(db: &DatabaseConnection, reason: &str, condition: String) {
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        format!(
+            "UPDATE shipments SET hold_reason = '{reason}', \
+             held_since = created_at + interval '2 days' WHERE {condition}"
+        ),
+    ))
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn on_hold_groups_by_reason_and_filters_other_reports() {
+    let db = test_db().await;
+    let data = seed_dataset(&db).await;
+
+    hold_where(
+        &db,
+        "CUSTOMS",
+        format!("current_office_id = '{}'", data.sofia),
+    )
+    .await;
+    hold_where(
+        &db,
+        "DAMAGED",
+        format!(
+            "current_office_id = '{}' AND current_status = 'NEW'",
+            data.varna
+        ),
+    )
+    .await;
+
+    let rows = shipments_on_hold(&db, &admin(), ReportQuery::default())
+        .await
+        .unwrap();
+    let got: Vec<(&str, i64, DateTime<Utc>)> = rows
+        .iter()
+        .map(|r| (r.reason.as_str(), r.count, r.oldest_since.to_utc()))
+        .collect();
+    assert_eq!(
+        got,
+        vec![("CUSTOMS", 2, at(3, 0)), ("DAMAGED", 1, at(4, 0))]
+    );
+
+    let held = shipments_by_status(
+        &db,
+        &admin(),
+        ReportQuery {
+            on_hold: Some(true),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let got: Vec<(&str, i64)> = held.iter().map(|r| (r.status.as_str(), r.count)).collect();
+    assert_eq!(got, vec![("ACCEPTED", 1), ("NEW", 2)]);
+
+    let moving = shipments_by_status(
+        &db,
+        &admin(),
+        ReportQuery {
+            on_hold: Some(false),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let got: Vec<(&str, i64)> = moving
+        .iter()
+        .map(|r| (r.status.as_str(), r.count))
+        .collect();
+    assert_eq!(got, vec![("DELIVERED", 1), ("NEW", 1)]);
+
+    // employees only see holds at their offices
+    let rows = shipments_on_hold(&db, &employee(vec![data.varna]), ReportQuery::default())
+        .await
+        .unwrap();
+    let got: Vec<(&str, i64)> = rows.iter().map(|r| (r.reason.as_str(), r.count)).collect();
+    assert_eq!(got, vec![("DAMAGED", 1)]);
+}
+
+/// Sets the cash on delivery of a seeded shipment.
+async fn set_cod(db: &DatabaseConnection, shipment_id: Uuid, amount_cents: i64, currency: &str) {
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        format!(
+            "UPDATE shipments SET cod_amount_cents = {amount_cents}, \
+             cod_currency = '{currency}' WHERE id = '{shipment_id}'"
+        ),
+    ))
+    .await
+    .unwrap();
+}
+
+async fn seed_collection(
+    db: &DatabaseConnection,
+    shipment_id: Uuid,
+    client_id: Uuid,
+    office_id: Uuid,
+    amount_cents: i64,
+    collected_at: DateTime<Utc>,
+) -> Uuid {
+    let id = Uuid::new_v4();
+
+    CodRepo::insert_collection(
+        db,
+        shipment_id,
+        CodCollectionRow {
+            id,
+            client_id,
+            office_id: Some(office_id),
+            amount_cents,
+            currency: "BGN".into(),
+            collected_by: None,
+            collected_at: collected_at.into(),
+            remittance_id: None,
+            remitted_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    id
+}
+
+#[tokio::test]
+async fn cod_reports_split_uncollected_unremitted_and_held_cash() {
+    let db = test_db().await;
+    let data = seed_dataset(&db).await;
+
+    let acme_new = seed_shipment(
+        &db,
+        data.acme,
+        Some(data.sofia),
+        ShipmentStatus::New,
+        at(4, 0),
+    )
+    .await;
+    set_cod(&db, acme_new, 1_000, "BGN").await;
+    let acme_eur = seed_shipment(
+        &db,
+        data.acme,
+        Some(data.sofia),
+        ShipmentStatus::Accepted,
+        at(4, 1),
+    )
+    .await;
+    set_cod(&db, acme_eur, 500, "EUR").await;
+    let cancelled = seed_shipment(
+        &db,
+        data.acme,
+        Some(data.sofia),
+        ShipmentStatus::Cancelled,
+        at(4, 2),
+    )
+    .await;
+    set_cod(&db, cancelled, 9_999, "BGN").await;
+
+    let held = seed_shipment(
+        &db,
+        data.zeta,
+        Some(data.varna),
+        ShipmentStatus::Delivered,
+        at(4, 3),
+    )
+    .await;
+    set_cod(&db, held, 2_000, "BGN").await;
+    seed_collection(&db, held, data.zeta, data.varna, 2_000, at(9, 0)).await;
+
+    let paid = seed_shipment(
+        &db,
+        data.zeta,
+        Some(data.varna),
+        ShipmentStatus::Delivered,
+        at(4, 4),
+    )
+    .await;
+    set_cod(&db, paid, 700, "BGN").await;
+    let collection = seed_collection(&db, paid, data.zeta, data.varna, 700, at(3, 0)).await;
+    CodRepo::insert_remittance(
+        &db,
+        RemittanceRow {
+            id: Uuid::new_v4(),
+            client_id: data.zeta,
+            currency: "BGN".into(),
+            amount_cents: 700,
+            shipment_count: 1,
+            reference: None,
+            created_by: None,
+            created_at: at(5, 0).into(),
+        },
+        vec![collection],
+    )
+    .await
+    .unwrap();
+
+    let rows = cod_outstanding(&db, &admin(), ReportQuery::default())
+        .await
+        .unwrap();
+    type Outstanding<'a> = (Option<&'a str>, &'a str, i64, i64, i64, i64);
+    let got: Vec<Outstanding> = rows
+        .iter()
+        .map(|r| {
+            (
+                r.office_name.as_deref(),
+                r.currency.as_str(),
+                r.uncollected_count,
+                r.uncollected_cents,
+                r.unremitted_count,
+                r.unremitted_cents,
+            )
+        })
+        .collect();
+    assert_eq!(
+        got,
+        vec![
+            (Some("Sofia"), "BGN", 1, 1_000, 0, 0),
+            (Some("Sofia"), "EUR", 1, 500, 0, 0),
+            (Some("Varna"), "BGN", 0, 0, 1, 2_000),
+        ]
+    );
+
+    let rows = cod_balances(&db, &admin(), ReportQuery::default())
+        .await
+        .unwrap();
+    type Balance<'a> = (Option<Uuid>, &'a str, i64, i64, i64, i64);
+    let got: Vec<Balance> = rows
+        .iter()
+        .map(|r| {
+            (
+                r.office_id,
+                r.currency.as_str(),
+                r.collected_cents,
+                r.remitted_cents,
+                r.balance_cents,
+                r.held_count,
+            )
+        })
+        .collect();
+    assert_eq!(got, vec![(Some(data.varna), "BGN", 2_700, 700, 2_000, 1)]);
+
+    // balances are bounded by the collection time
+    let rows = cod_balances(
+        &db,
+        &admin(),
+        ReportQuery {
+            from: Some(at(5, 0).into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(
+        (rows[0].collected_cents, rows[0].remitted_cents),
+        (2_000, 0)
+    );
+
+    // employees only see the cash of their offices
+    let sofia = employee(vec![data.sofia]);
+    let rows = cod_outstanding(&db, &sofia, ReportQuery::default())
+        .await
+        .unwrap();
+    assert!(rows.iter().all(|r| r.client_id == data.acme));
+    assert_eq!(rows.len(), 2);
+
+    let rows = cod_balances(&db, &sofia, ReportQuery::default())
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+}
@@ -1,30 +1,70 @@
 use core_application::roles::Role;
 use core_application::shipments::change_status::change_status;
-use core_application::shipments::create::{CreateShipment, create_shipment};
-use core_application::shipments::timeline::read_timeline;
-use core_application::{actor::ActorContext, shipments::change_status::ChangeStatus};
+use core_application::shipments::cod::{RemitCod, RemitCodError, remit_cod};
+use core_application::shipments::create::{CreateShipment, CreateShipmentError, create_shipment};
+use core_application::shipments::get::get_shipment;
+use core_application::shipments::get_by_tracking::{TrackingLookupError, get_shipment_by_tracking};
+use core_application::shipments::get_detail::get_shipment_detail;
+use core_application::shipments::hold::{
+    HoldError, PlaceHold, ReleaseHold, place_hold, release_hold,
+};
+use core_application::shipments::list::list_shipments;
+use core_application::shipments::next_statuses::allowed_next_statuses;
+use core_application::shipments::pieces::{
+    AddPiece, PieceError, ScanPiece, add_piece, list_pieces, scan_piece,
+};
+use core_application::shipments::pod::{
+    PodError, PodImage, ProofOfDeliveryInput, get_proof_of_delivery, get_proof_of_delivery_image,
+};
+use core_application::shipments::quote::{QuoteError, QuoteShipment, quote_shipment};
+use core_application::shipments::rebuild::{Drift, RebuildTarget, rebuild_shipment_projections};
+use core_application::shipments::timeline::{read_timeline, read_visible_timeline};
+use core_application::shipments::track::{PublicTrackingError, track_shipment};
+use core_application::shipments::visibility::ReadPolicy;
+use core_application::{
+    actor::ActorContext,
+    shipments::change_status::{ChangeStatus, ChangeStatusError},
+};
 use core_data::entity::{clients, employee_offices, employees, offices, users};
-use core_domain::shipment::ShipmentStatus;
+use core_data::repository::cod_repo::CodRepo;
+use core_data::repository::shipment_query::ShipmentQuery;
+use core_data::repository::shipments_repo::{ShipmentDetails, ShipmentSnapshotError};
+use core_domain::errors::{
+    CashOnDeliveryError, PricingError, ProofOfDeliveryError, TransitionError,
+};
+use core_domain::pricing::{Price, ServiceLevel, Tariff};
+use core_domain::shipment::{
+    Address, BlobHash, CashOnDelivery, Currency, DEFAULT_MAX_DELIVERY_ATTEMPTS,
+    DeliveryFailureReason, Dimensions, HoldReason, Parcel, Party, ReceiverRelationship,
+    ShipmentEvent, ShipmentStatus, TrackingNumber, TransitionRule, TransitionTable,
+};
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
-    PaginatorTrait, QueryFilter, Set, Statement,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, Statement,
 };
 use test_infra::test_db;
 use uuid::Uuid;
 
 async fn cleanup(db: &DatabaseConnection) {
     let tables = [
+        "cod_collections",
+        "cod_remittances",
         "shipment_status_history",
+        "shipment_route_stops",
         "shipments",
+        "tariffs",
         "employee_offices",
         "employees",
         "user_roles",
         "users",
         "clients",
         "roles",
+        "office_calendars",
+        "office_links",
         "offices",
         "packages",
         "streams",
+        "blobs",
     ];
 
     for t in tables {
@@ -166,10 +206,12 @@ async fn admin_can_change_status() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -178,11 +220,16 @@ async fn admin_can_change_status() {
     change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Accepted,
             to_office_id: Some(office),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -203,10 +250,12 @@ async fn employee_cannot_change_status_outside_office() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office1),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -217,11 +266,16 @@ async fn employee_cannot_change_status_outside_office() {
     let err = change_status(
         &db,
         &employee,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::InTransit,
             to_office_id: Some(office2),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -246,10 +300,12 @@ async fn employee_can_change_statuts_inside_office() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -260,11 +316,16 @@ async fn employee_can_change_statuts_inside_office() {
     change_status(
         &db,
         &employee,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Accepted,
             to_office_id: Some(office),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -285,10 +346,12 @@ async fn office_hop_only_allowed_when_in_transit() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office1),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -298,11 +361,16 @@ async fn office_hop_only_allowed_when_in_transit() {
     change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Accepted,
             to_office_id: Some(office1),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -311,11 +379,16 @@ async fn office_hop_only_allowed_when_in_transit() {
     change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Processed,
             to_office_id: Some(office1),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -325,11 +398,16 @@ async fn office_hop_only_allowed_when_in_transit() {
     let err = change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Delivered,
             to_office_id: Some(office1),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -344,11 +422,16 @@ async fn office_hop_only_allowed_when_in_transit() {
     change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::InTransit,
             to_office_id: Some(office2),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -368,10 +451,12 @@ async fn admin_can_create_shipment() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -401,10 +486,12 @@ async fn employee_can_create_shipment_in_allowed_office() {
     let shipment_id = create_shipment(
         &db,
         &employee,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -434,10 +521,12 @@ async fn employee_cannot_create_shipment_outside_allowed_office() {
     let err = create_shipment(
         &db,
         &employee,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(forbidden_office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -469,10 +558,12 @@ async fn create_shipment_creates_history_and_stream() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: Some("hello".into()),
+            ..Default::default()
         },
     )
     .await
@@ -498,6 +589,7 @@ async fn create_shipment_creates_history_and_stream() {
 
     let packages = core_eventstore::schema::packages::Entity::find()
         .filter(core_eventstore::schema::packages::Column::StreamId.eq(shipment_id))
+        .order_by_asc(core_eventstore::schema::packages::Column::Seq)
         .all(&db)
         .await
         .unwrap();
@@ -520,10 +612,12 @@ async fn timeline_contains_metadata_and_domain_events_in_order() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -532,11 +626,16 @@ async fn timeline_contains_metadata_and_domain_events_in_order() {
     change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Accepted,
             to_office_id: Some(office),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -545,11 +644,16 @@ async fn timeline_contains_metadata_and_domain_events_in_order() {
     change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Processed,
             to_office_id: Some(office),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -580,10 +684,12 @@ async fn timeline_is_strictly_ordered() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -592,11 +698,16 @@ async fn timeline_is_strictly_ordered() {
     change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Accepted,
             to_office_id: Some(office),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -622,10 +733,12 @@ async fn timeline_values_are_decodable() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -652,10 +765,12 @@ async fn forbidden_change_does_not_append_events() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office1),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -666,11 +781,16 @@ async fn forbidden_change_does_not_append_events() {
     let _ = change_status(
         &db,
         &employee,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::InTransit,
             to_office_id: Some(office2),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -699,10 +819,12 @@ async fn forbidden_change_does_not_mutate_snapshot() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office1),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -713,11 +835,16 @@ async fn forbidden_change_does_not_mutate_snapshot() {
     let _ = change_status(
         &db,
         &employee,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::InTransit,
             to_office_id: Some(office2),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -747,10 +874,12 @@ async fn forbidden_change_does_not_write_history() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office1),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -761,11 +890,16 @@ async fn forbidden_change_does_not_write_history() {
     let _ = change_status(
         &db,
         &employee,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::InTransit,
             to_office_id: Some(office2),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -779,3 +913,2462 @@ async fn forbidden_change_does_not_write_history() {
 
     assert_eq!(history.len(), 1);
 }
+
+#[tokio::test]
+async fn failed_create_leaves_no_partial_writes() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    // actor user row does not exist, so the history insert violates its FK
+    let ghost = ActorContext {
+        user_id: Uuid::new_v4(),
+        sub: "ghost".into(),
+        roles: vec![Role::Admin],
+        employee_id: None,
+        allowed_office_ids: vec![],
+    };
+
+    create_shipment(
+        &db,
+        &ghost,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    let shipments = core_data::entity::shipments::Entity::find()
+        .count(&db)
+        .await
+        .unwrap();
+    let streams = core_eventstore::schema::streams::Entity::find()
+        .count(&db)
+        .await
+        .unwrap();
+
+    assert_eq!(shipments, 0);
+    assert_eq!(streams, 0);
+}
+
+#[tokio::test]
+async fn failed_change_rolls_back_appended_event() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // event is appended before the history insert fails on the actor FK
+    let ghost = ActorContext {
+        user_id: Uuid::new_v4(),
+        ..admin.clone()
+    };
+
+    change_status(
+        &db,
+        &ghost,
+        &TransitionTable::default(),
+        ChangeStatus {
+            shipment_id,
+            to_status: ShipmentStatus::Accepted,
+            to_office_id: Some(office),
+            notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
+        },
+    )
+    .await
+    .unwrap_err();
+
+    let packages = core_eventstore::schema::packages::Entity::find()
+        .filter(core_eventstore::schema::packages::Column::StreamId.eq(shipment_id))
+        .count(&db)
+        .await
+        .unwrap();
+
+    let history = core_data::entity::shipment_status_history::Entity::find()
+        .filter(core_data::entity::shipment_status_history::Column::ShipmentId.eq(shipment_id))
+        .count(&db)
+        .await
+        .unwrap();
+
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(packages, 2);
+    assert_eq!(history, 1);
+    assert_eq!(snap.current_status, "NEW");
+}
+
+#[tokio::test]
+async fn stale_expected_seq_is_a_conflict() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // both writers saw seq 2; the first one wins
+    change_status(
+        &db,
+        &admin,
+        &TransitionTable::default(),
+        ChangeStatus {
+            shipment_id,
+            to_status: ShipmentStatus::Accepted,
+            to_office_id: Some(office),
+            notes: None,
+            failure_reason: None,
+            expected_seq: Some(2),
+            proof_of_delivery: None,
+            override_route: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    let err = change_status(
+        &db,
+        &admin,
+        &TransitionTable::default(),
+        ChangeStatus {
+            shipment_id,
+            to_status: ShipmentStatus::Cancelled,
+            to_office_id: Some(office),
+            notes: None,
+            failure_reason: None,
+            expected_seq: Some(2),
+            proof_of_delivery: None,
+            override_route: false,
+        },
+    )
+    .await
+    .unwrap_err();
+
+    match err {
+        ChangeStatusError::Conflict {
+            expected_seq,
+            current_seq,
+            current_status,
+            current_office_id,
+        } => {
+            assert_eq!(expected_seq, 2);
+            assert_eq!(current_seq, 3);
+            assert_eq!(current_status, "ACCEPTED");
+            assert_eq!(current_office_id, Some(office));
+        }
+        other => panic!("expected conflict, got {other:?}"),
+    }
+
+    let history = core_data::entity::shipment_status_history::Entity::find()
+        .filter(core_data::entity::shipment_status_history::Column::ShipmentId.eq(shipment_id))
+        .count(&db)
+        .await
+        .unwrap();
+
+    assert_eq!(history, 2);
+}
+
+async fn seed_shipment_with_change(db: &DatabaseConnection, admin: &ActorContext) -> Uuid {
+    let office = seed_office(db).await;
+    let client = seed_client(db).await;
+
+    let shipment_id = create_shipment(
+        db,
+        admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: Some("created".into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    change_status(
+        db,
+        admin,
+        &TransitionTable::default(),
+        ChangeStatus {
+            shipment_id,
+            to_status: ShipmentStatus::Accepted,
+            to_office_id: Some(office),
+            notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    shipment_id
+}
+
+#[tokio::test]
+async fn rebuild_of_consistent_projection_reports_no_drift() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    seed_shipment_with_change(&db, &admin).await;
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Live)
+        .await
+        .unwrap();
+
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+    assert_eq!(report.streams_replayed, 1);
+    assert_eq!(report.shipments_rewritten, 0);
+}
+
+#[tokio::test]
+async fn rebuild_reports_and_repairs_manual_edits() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        format!("UPDATE shipments SET current_status = 'DELIVERED' WHERE id = '{shipment_id}'"),
+    ))
+    .await
+    .unwrap();
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        format!(
+            "DELETE FROM shipment_status_history WHERE shipment_id = '{shipment_id}' AND to_status = 'ACCEPTED'"
+        ),
+    ))
+    .await
+    .unwrap();
+
+    // scratch reports but leaves the tables alone
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+
+    assert!(report.drift.contains(&Drift::Snapshot {
+        shipment_id,
+        field: "current_status",
+        projected: "DELIVERED".into(),
+        replayed: "ACCEPTED".into(),
+    }));
+    assert!(report.drift.contains(&Drift::History {
+        shipment_id,
+        projected_rows: 1,
+        replayed_rows: 2,
+        first_mismatch: 1,
+    }));
+
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(snap.current_status, "DELIVERED");
+
+    // live repairs
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Live)
+        .await
+        .unwrap();
+    assert_eq!(report.shipments_rewritten, 1);
+
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(snap.current_status, "ACCEPTED");
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
+
+#[tokio::test]
+async fn rebuild_restores_deleted_snapshot_and_history() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+
+    let before = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // cascades to history
+    core_data::entity::shipments::Entity::delete_by_id(shipment_id)
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Live)
+        .await
+        .unwrap();
+
+    assert!(
+        report
+            .drift
+            .contains(&Drift::MissingSnapshot { shipment_id })
+    );
+
+    let after = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(after.client_id, before.client_id);
+    assert_eq!(after.current_status, "ACCEPTED");
+    assert_eq!(after.current_office_id, before.current_office_id);
+
+    let history = core_data::entity::shipment_status_history::Entity::find()
+        .filter(core_data::entity::shipment_status_history::Column::ShipmentId.eq(shipment_id))
+        .count(&db)
+        .await
+        .unwrap();
+
+    assert_eq!(history, 2);
+}
+
+#[tokio::test]
+async fn rebuild_reports_snapshot_without_stream() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        format!("DELETE FROM packages WHERE stream_id = '{shipment_id}'"),
+    ))
+    .await
+    .unwrap();
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        format!("DELETE FROM streams WHERE id = '{shipment_id}'"),
+    ))
+    .await
+    .unwrap();
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Live)
+        .await
+        .unwrap();
+
+    assert_eq!(report.drift, vec![Drift::OrphanSnapshot { shipment_id }]);
+
+    // orphans are never deleted
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap();
+    assert!(snap.is_some());
+}
+
+#[tokio::test]
+async fn employee_cannot_rebuild_projections() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let employee = employee_actor(&db, vec![office]).await;
+
+    let err = rebuild_shipment_projections(&db, &employee, RebuildTarget::Scratch)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        core_application::shipments::rebuild::RebuildError::Forbidden
+    ));
+}
+
+#[tokio::test]
+async fn timeline_events_are_typed_and_match_stored_payloads() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+
+    let timeline = read_timeline(&db, shipment_id).await.unwrap();
+
+    assert!(timeline[0].event.is_none()); // metadata
+
+    match &timeline[1].event {
+        Some(ShipmentEvent::Created(created)) => {
+            assert_eq!(created.shipment_id, shipment_id.to_string());
+            assert_eq!(created.status, ShipmentStatus::New);
+            assert!(created.client_id.is_some());
+            assert_eq!(created.notes.as_deref(), Some("created"));
+        }
+        other => panic!("expected ShipmentCreated, got {other:?}"),
+    }
+
+    match &timeline[2].event {
+        Some(ShipmentEvent::StatusChanged(changed)) => {
+            assert_eq!(changed.from_status, ShipmentStatus::New);
+            assert_eq!(changed.to_status, ShipmentStatus::Accepted);
+            assert_eq!(changed.actor.id, admin.user_id.to_string());
+        }
+        other => panic!("expected StatusChanged, got {other:?}"),
+    }
+
+    // re-encoding the typed event reproduces the stored payload exactly
+    for entry in &timeline[1..] {
+        let event = entry.event.as_ref().unwrap();
+        let strata::value::Value::List(scoped) = &entry.value else {
+            panic!("package is not scoped");
+        };
+
+        assert_eq!(event.event_type(), entry.event_type);
+        assert_eq!(event.encode(), scoped[1]);
+    }
+}
+
+#[test]
+fn shipment_upcasters_reach_codec_schema_versions() {
+    use core_application::shipments::upcast::shipment_upcasters;
+    use core_domain::shipment::{EventCodec, ShipmentCreated, StatusChanged};
+
+    let registry = shipment_upcasters();
+
+    assert_eq!(
+        registry.current_version(ShipmentCreated::EVENT_TYPE),
+        ShipmentCreated::SCHEMA_VERSION
+    );
+    assert_eq!(
+        registry.current_version(StatusChanged::EVENT_TYPE),
+        StatusChanged::SCHEMA_VERSION
+    );
+}
+
+#[tokio::test]
+async fn v1_packages_are_read_in_current_shape() {
+    use core_data::repository::shipments_repo::ShipmentsRepo;
+    use strata::{int, map, null, string};
+
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let admin = admin_actor(&db).await;
+
+    // a shipment as written before schema versioning
+    let shipment_id = Uuid::new_v4();
+
+    ShipmentsRepo::insert_snapshot(
+        &db,
+        shipment_id,
+        None,
+        client,
+        ShipmentStatus::New,
+        Some(office),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    ShipmentsRepo::insert_history(
+        &db,
+        shipment_id,
+        None,
+        ShipmentStatus::New,
+        Some(admin.user_id),
+        Some(office),
+        None,
+    )
+    .await
+    .unwrap();
+
+    core_eventstore::adapter::streams::ensure_stream(&db, shipment_id, "shipment")
+        .await
+        .unwrap();
+    core_eventstore::adapter::append::append_package(&db, shipment_id, "shipment", &map! {})
+        .await
+        .unwrap();
+    core_eventstore::adapter::append::append_package(
+        &db,
+        shipment_id,
+        "ShipmentCreated",
+        &map! {
+            "event_type" => string!("ShipmentCreated"),
+            "shipment_id" => string!(shipment_id.to_string()),
+            "status" => string!("NEW"),
+            "actor_user_id" => string!(admin.user_id.to_string()),
+            "office_id" => string!(office.to_string()),
+            "occured_at" => int!(1_700_000_000_000),
+            "notes" => null!()
+        },
+    )
+    .await
+    .unwrap();
+
+    let timeline = read_timeline(&db, shipment_id).await.unwrap();
+
+    assert_eq!(timeline[1].schema_version, 1);
+    match &timeline[1].event {
+        Some(ShipmentEvent::Created(created)) => {
+            assert_eq!(created.occurred_at_ms, 1_700_000_000_000);
+            assert_eq!(created.client_id, None);
+        }
+        other => panic!("expected ShipmentCreated, got {other:?}"),
+    }
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
+
+#[tokio::test]
+async fn new_packages_are_stored_at_codec_schema_version() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+
+    let timeline = read_timeline(&db, shipment_id).await.unwrap();
+    let versions: Vec<i32> = timeline.iter().map(|e| e.schema_version).collect();
+
+    assert_eq!(versions, vec![1, 2, 2]);
+}
+
+/// Created at `origin`, handed over to `next` and delivered there.
+async fn seed_handed_over_shipment(
+    db: &DatabaseConnection,
+    admin: &ActorContext,
+    origin: Uuid,
+    next: Uuid,
+) -> Uuid {
+    let client = seed_client(db).await;
+
+    let shipment_id = create_shipment(
+        db,
+        admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(origin),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    for (to_status, office) in [
+        (ShipmentStatus::Accepted, origin),
+        (ShipmentStatus::Processed, origin),
+        (ShipmentStatus::InTransit, next),
+        (ShipmentStatus::Delivered, next),
+    ] {
+        change_status(
+            db,
+            admin,
+            &TransitionTable::default(),
+            ChangeStatus {
+                shipment_id,
+                to_status,
+                to_office_id: Some(office),
+                notes: None,
+                failure_reason: None,
+                expected_seq: None,
+                proof_of_delivery: None,
+                override_route: false,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    shipment_id
+}
+
+#[test]
+fn read_policy_parses_relation_lists() {
+    assert_eq!(
+        ReadPolicy::parse("current, history"),
+        Some(ReadPolicy {
+            current_office: true,
+            origin_office: false,
+            history_offices: true,
+        })
+    );
+    assert_eq!(ReadPolicy::parse("current,elsewhere"), None);
+    assert_eq!(ReadPolicy::parse(""), None);
+    assert_eq!(ReadPolicy::parse(" , "), None);
+}
+
+#[tokio::test]
+async fn read_policy_decides_which_offices_see_a_shipment() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let origin = seed_office(&db).await;
+    let next = seed_office(&db).await;
+    let unrelated = seed_office(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let shipment_id = seed_handed_over_shipment(&db, &admin, origin, next).await;
+
+    let at_origin = employee_actor(&db, vec![origin]).await;
+    let at_next = employee_actor(&db, vec![next]).await;
+    let at_unrelated = employee_actor(&db, vec![unrelated]).await;
+
+    let only = |current_office, origin_office, history_offices| ReadPolicy {
+        current_office,
+        origin_office,
+        history_offices,
+    };
+
+    let cases = [
+        (only(true, false, false), &at_origin, false),
+        (only(true, false, false), &at_next, true),
+        (only(false, true, false), &at_origin, true),
+        (only(false, true, false), &at_next, false),
+        (only(false, false, true), &at_origin, true),
+        (only(false, false, true), &at_next, true),
+        (ReadPolicy::default(), &at_unrelated, false),
+        (only(false, false, false), &at_next, false),
+        (only(false, false, false), &admin, true),
+    ];
+
+    for (i, (policy, actor, visible)) in cases.into_iter().enumerate() {
+        let page = list_shipments(&db, actor, policy, ShipmentQuery::default())
+            .await
+            .unwrap();
+        let listed = page.items.iter().any(|s| s.id == shipment_id);
+        assert_eq!(listed, visible, "list case {i}");
+
+        let fetched = get_shipment(&db, actor, policy, shipment_id).await;
+        assert_eq!(fetched.is_ok(), visible, "get case {i}");
+
+        let timeline = read_visible_timeline(&db, actor, policy, shipment_id).await;
+        assert_eq!(timeline.is_ok(), visible, "timeline case {i}");
+    }
+}
+
+#[tokio::test]
+async fn hidden_shipment_reads_as_not_found() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let origin = seed_office(&db).await;
+    let next = seed_office(&db).await;
+    let unrelated = seed_office(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let shipment_id = seed_handed_over_shipment(&db, &admin, origin, next).await;
+    let outsider = employee_actor(&db, vec![unrelated]).await;
+
+    let err = get_shipment(&db, &outsider, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        ShipmentSnapshotError::DbError(sea_orm::DbErr::RecordNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn shipment_detail_joins_client_office_latest_change_and_event_count() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let origin = seed_office(&db).await;
+    let next = seed_office(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let shipment_id = seed_handed_over_shipment(&db, &admin, origin, next).await;
+
+    let detail = get_shipment_detail(&db, &admin, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap();
+
+    assert_eq!(detail.shipment.id, shipment_id);
+    assert_eq!(detail.client.map(|c| c.id), Some(detail.shipment.client_id));
+    assert_eq!(detail.current_office.map(|o| o.id), Some(next));
+
+    let latest = detail.latest_change.unwrap();
+    assert_eq!(latest.from_status.as_deref(), Some("IN_TRANSIT"));
+    assert_eq!(latest.to_status, "DELIVERED");
+
+    // ShipmentCreated plus four StatusChanged
+    assert_eq!(detail.event_count, 5);
+
+    let outsider = employee_actor(&db, vec![seed_office(&db).await]).await;
+    let hidden = get_shipment_detail(&db, &outsider, ReadPolicy::default(), shipment_id).await;
+    assert!(matches!(
+        hidden,
+        Err(ShipmentSnapshotError::DbError(
+            sea_orm::DbErr::RecordNotFound(_)
+        ))
+    ));
+}
+
+fn party(name: &str, city: &str) -> Party {
+    Party {
+        name: name.into(),
+        phone: Some("+359888123456".into()),
+        email: None,
+        address: Address {
+            line1: "1 Vitosha Blvd".into(),
+            line2: None,
+            city: city.into(),
+            postal_code: "1000".into(),
+            country: "BG".into(),
+        },
+    }
+}
+
+#[tokio::test]
+async fn create_shipment_records_parties_parcel_and_destination() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let origin = seed_office(&db).await;
+    let destination = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let admin = admin_actor(&db).await;
+
+    let details = ShipmentDetails {
+        sender: Some(party("Jane Doe", "Sofia")),
+        recipient: Some(party("John Roe", "Plovdiv")),
+        destination_office_id: Some(destination),
+        parcel: Some(Parcel {
+            weight_g: 2_500,
+            dimensions: Some(Dimensions {
+                length_mm: 400,
+                width_mm: 300,
+                height_mm: 200,
+            }),
+            declared_value_cents: Some(12_000),
+            description: Some("ceramics".into()),
+        }),
+        cod: None,
+        service_level: ServiceLevel::Standard,
+        // both offices are in the same city; 4.8 kg by volume
+        price: Some(Price {
+            currency: "BGN".parse().unwrap(),
+            zone: "LOCAL".into(),
+            chargeable_weight_g: 4_800,
+            base_cents: 600,
+            express_fee_cents: 0,
+            cod_fee_cents: 0,
+            declared_value_fee_cents: 60,
+            tax_cents: 132,
+            total_cents: 792,
+        }),
+        tariff_id: None,
+    };
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(origin),
+            notes: None,
+            sender: details.sender.clone(),
+            recipient: details.recipient.clone(),
+            destination_office_id: details.destination_office_id,
+            parcel: details.parcel.clone(),
+            cod: None,
+            service_level: ServiceLevel::Standard,
+        },
+    )
+    .await
+    .unwrap();
+
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(snap.origin_office_id, Some(origin));
+    assert_eq!(snap.recipient_city.as_deref(), Some("Plovdiv"));
+    assert_eq!(ShipmentDetails::from_model(&snap), details);
+
+    let created = read_timeline(&db, shipment_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .find_map(|entry| match entry.event {
+            Some(ShipmentEvent::Created(created)) => Some(created),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(created.sender, details.sender);
+    assert_eq!(created.recipient, details.recipient);
+    assert_eq!(created.parcel, details.parcel);
+    assert_eq!(created.price, details.price);
+    assert_eq!(
+        created.destination_office.map(|o| o.office_id),
+        Some(destination.to_string())
+    );
+
+    // the events alone are enough to restore the columns
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        format!("UPDATE shipments SET recipient_city = 'Varna', weight_g = NULL WHERE id = '{shipment_id}'"),
+    ))
+    .await
+    .unwrap();
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Live)
+        .await
+        .unwrap();
+    assert_eq!(report.shipments_rewritten, 1);
+
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ShipmentDetails::from_model(&snap), details);
+}
+
+#[tokio::test]
+async fn invalid_shipment_details_are_rejected_before_writing() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let admin = admin_actor(&db).await;
+
+    let mut recipient = party("John Roe", "Plovdiv");
+    recipient.address.country = "Bulgaria".into();
+
+    let err = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            recipient: Some(recipient),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, CreateShipmentError::Validation(_)));
+    assert_eq!(
+        err.to_string(),
+        "validation error: recipient: invalid country"
+    );
+
+    let shipments = core_data::entity::shipments::Entity::find()
+        .count(&db)
+        .await
+        .unwrap();
+    assert_eq!(shipments, 0);
+}
+
+#[tokio::test]
+async fn unknown_destination_office_is_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let admin = admin_actor(&db).await;
+
+    let err = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            destination_office_id: Some(Uuid::new_v4()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, CreateShipmentError::DestinationNotFound));
+
+    let shipments = core_data::entity::shipments::Entity::find()
+        .count(&db)
+        .await
+        .unwrap();
+    assert_eq!(shipments, 0);
+}
+
+async fn tracking_number_of(db: &DatabaseConnection, shipment_id: Uuid) -> String {
+    core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .tracking_number
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_shipment_issues_office_prefixed_tracking_number() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let admin = admin_actor(&db).await;
+
+    let create = |office: Option<Uuid>| CreateShipment {
+        client_id: client,
+        current_office_id: office,
+        ..Default::default()
+    };
+
+    let first = create_shipment(&db, &admin, &Tariff::default(), create(Some(office)))
+        .await
+        .unwrap();
+    let second = create_shipment(&db, &admin, &Tariff::default(), create(None))
+        .await
+        .unwrap();
+
+    let first_number = tracking_number_of(&db, first).await;
+    let second_number = tracking_number_of(&db, second).await;
+
+    // seeded office is in "City"
+    assert!(first_number.starts_with("CIT"), "{first_number}");
+    assert!(second_number.starts_with("LP"), "{second_number}");
+    assert_ne!(first_number[3..], second_number[2..]);
+    TrackingNumber::parse(&first_number).unwrap();
+    TrackingNumber::parse(&second_number).unwrap();
+
+    let created = read_timeline(&db, first)
+        .await
+        .unwrap()
+        .into_iter()
+        .find_map(|entry| match entry.event {
+            Some(ShipmentEvent::Created(created)) => Some(created),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(
+        created.tracking_number.as_deref(),
+        Some(first_number.as_str())
+    );
+
+    // lower case and separators are accepted
+    let typed = format!("{} {}", &first_number[..3], &first_number[3..]).to_lowercase();
+    let detail = get_shipment_by_tracking(&db, &admin, ReadPolicy::default(), &typed)
+        .await
+        .unwrap();
+    assert_eq!(detail.shipment.id, first);
+}
+
+#[tokio::test]
+async fn tracking_lookup_checks_digit_before_querying() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let err = get_shipment_by_tracking(&db, &admin, ReadPolicy::default(), "SOF473124828")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, TrackingLookupError::InvalidTrackingNumber(_)));
+
+    let err = get_shipment_by_tracking(&db, &admin, ReadPolicy::default(), "SOF473124829")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        TrackingLookupError::Snapshot(ShipmentSnapshotError::DbError(
+            sea_orm::DbErr::RecordNotFound(_)
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn public_tracking_returns_statuses_cities_and_times_only() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let admin = admin_actor(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: Some("fragile, call first".into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    change_status(
+        &db,
+        &admin,
+        &TransitionTable::default(),
+        ChangeStatus {
+            shipment_id,
+            to_status: ShipmentStatus::Accepted,
+            to_office_id: Some(office),
+            notes: Some("internal note".into()),
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    let number = tracking_number_of(&db, shipment_id).await;
+    let view = track_shipment(&db, &number.to_lowercase()).await.unwrap();
+
+    assert_eq!(view.tracking_number, number);
+    assert_eq!(view.status, "ACCEPTED");
+
+    let steps: Vec<_> = view
+        .history
+        .iter()
+        .map(|step| (step.status.as_str(), step.city.as_deref()))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![("NEW", Some("City")), ("ACCEPTED", Some("City"))]
+    );
+
+    let err = track_shipment(&db, "SOF473124828").await.unwrap_err();
+    assert!(matches!(err, PublicTrackingError::InvalidTrackingNumber(_)));
+
+    let err = track_shipment(&db, "SOF473124829").await.unwrap_err();
+    assert!(matches!(err, PublicTrackingError::NotFound));
+}
+
+#[tokio::test]
+async fn transition_table_guards_apply_to_status_changes() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let admin = admin_actor(&db).await;
+    let employee = employee_actor(&db, vec![office]).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let cancel = TransitionRule {
+        from: ShipmentStatus::New,
+        to: ShipmentStatus::Cancelled,
+        office_change: false,
+        requires_note: true,
+        admin_only: true,
+        max_attempts: None,
+    };
+    let accept = TransitionRule {
+        from: ShipmentStatus::New,
+        to: ShipmentStatus::Accepted,
+        office_change: false,
+        requires_note: false,
+        admin_only: false,
+        max_attempts: None,
+    };
+    let table =
+        TransitionTable::new(vec![ShipmentStatus::Cancelled], vec![accept, cancel]).unwrap();
+
+    // admin-only edges are hidden from employees
+    let next = allowed_next_statuses(&db, &employee, ReadPolicy::default(), &table, shipment_id)
+        .await
+        .unwrap();
+    assert_eq!(next, vec![accept]);
+
+    let next = allowed_next_statuses(&db, &admin, ReadPolicy::default(), &table, shipment_id)
+        .await
+        .unwrap();
+    assert_eq!(next, vec![accept, cancel]);
+
+    let cancel_with = |notes: Option<&str>| ChangeStatus {
+        shipment_id,
+        to_status: ShipmentStatus::Cancelled,
+        to_office_id: Some(office),
+        notes: notes.map(Into::into),
+        failure_reason: None,
+        expected_seq: None,
+        proof_of_delivery: None,
+        override_route: false,
+    };
+
+    let err = change_status(&db, &employee, &table, cancel_with(Some("lost")))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ChangeStatusError::Domain(TransitionError::AdminOnly { .. })
+    ));
+
+    let err = change_status(&db, &admin, &table, cancel_with(Some("  ")))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ChangeStatusError::Domain(TransitionError::NoteRequired { .. })
+    ));
+
+    change_status(&db, &admin, &table, cancel_with(Some("lost")))
+        .await
+        .unwrap();
+
+    // nothing leaves a terminal state
+    let next = allowed_next_statuses(&db, &admin, ReadPolicy::default(), &table, shipment_id)
+        .await
+        .unwrap();
+    assert!(next.is_empty());
+}
+
+#[tokio::test]
+async fn next_statuses_are_empty_for_employees_outside_current_office() {
+    use core_data::repository::shipments_repo::ShipmentsRepo;
+
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let office = seed_office(&db).await;
+    let other_office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let admin = admin_actor(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // history policy lets the outsider read, but not write
+    let outsider = employee_actor(&db, vec![other_office]).await;
+    ShipmentsRepo::insert_history(
+        &db,
+        shipment_id,
+        None,
+        ShipmentStatus::New,
+        None,
+        Some(other_office),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let next = allowed_next_statuses(
+        &db,
+        &outsider,
+        ReadPolicy::default(),
+        &TransitionTable::default(),
+        shipment_id,
+    )
+    .await
+    .unwrap();
+    assert!(next.is_empty());
+}
+
+#[tokio::test]
+async fn failed_deliveries_are_counted_until_the_shipment_is_returned() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let origin = seed_office(&db).await;
+    let hub = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let admin = admin_actor(&db).await;
+    let table = TransitionTable::default();
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(origin),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let change = |to_status, office, failure_reason| ChangeStatus {
+        shipment_id,
+        to_status,
+        to_office_id: Some(office),
+        notes: None,
+        failure_reason,
+        expected_seq: None,
+        proof_of_delivery: None,
+        override_route: false,
+    };
+
+    for (to_status, office) in [
+        (ShipmentStatus::Accepted, origin),
+        (ShipmentStatus::Processed, origin),
+        (ShipmentStatus::InTransit, hub),
+    ] {
+        change_status(&db, &admin, &table, change(to_status, office, None))
+            .await
+            .unwrap();
+    }
+
+    // a failure without a reason is rejected
+    change_status(
+        &db,
+        &admin,
+        &table,
+        change(ShipmentStatus::OutForDelivery, hub, None),
+    )
+    .await
+    .unwrap();
+    let err = change_status(
+        &db,
+        &admin,
+        &table,
+        change(ShipmentStatus::DeliveryFailed, hub, None),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ChangeStatusError::Domain(TransitionError::FailureReasonRequired)
+    ));
+
+    for attempt in 1..=DEFAULT_MAX_DELIVERY_ATTEMPTS {
+        if attempt > 1 {
+            change_status(
+                &db,
+                &admin,
+                &table,
+                change(ShipmentStatus::OutForDelivery, hub, None),
+            )
+            .await
+            .unwrap();
+        }
+
+        change_status(
+            &db,
+            &admin,
+            &table,
+            change(
+                ShipmentStatus::DeliveryFailed,
+                hub,
+                Some(DeliveryFailureReason::RecipientAbsent),
+            ),
+        )
+        .await
+        .unwrap();
+    }
+
+    let err = change_status(
+        &db,
+        &admin,
+        &table,
+        change(ShipmentStatus::OutForDelivery, hub, None),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ChangeStatusError::Domain(TransitionError::AttemptsExhausted { attempts: 3, .. })
+    ));
+
+    let next = allowed_next_statuses(&db, &admin, ReadPolicy::default(), &table, shipment_id)
+        .await
+        .unwrap();
+    let next: Vec<_> = next.iter().map(|rule| rule.to).collect();
+    assert_eq!(next, vec![ShipmentStatus::Returning]);
+
+    // back to the origin office
+    change_status(
+        &db,
+        &admin,
+        &table,
+        change(ShipmentStatus::Returning, origin, None),
+    )
+    .await
+    .unwrap();
+    change_status(
+        &db,
+        &admin,
+        &table,
+        change(ShipmentStatus::Returned, origin, None),
+    )
+    .await
+    .unwrap();
+
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(snap.current_status, "RETURNED");
+    assert_eq!(snap.current_office_id, Some(origin));
+    assert_eq!(snap.delivery_attempts, 3);
+
+    let attempts: Vec<_> = read_timeline(&db, shipment_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|entry| match entry.event {
+            Some(ShipmentEvent::StatusChanged(changed)) => changed.attempt,
+            _ => None,
+        })
+        .collect();
+    assert_eq!(attempts, vec![1, 2, 3]);
+
+    let reasons = core_data::entity::shipment_status_history::Entity::find()
+        .filter(
+            core_data::entity::shipment_status_history::Column::FailureReason
+                .eq("RECIPIENT_ABSENT"),
+        )
+        .count(&db)
+        .await
+        .unwrap();
+    assert_eq!(reasons, 3);
+
+    // attempts and reasons survive a replay
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
+
+#[tokio::test]
+async fn concurrent_holds_and_status_changes_do_not_interleave() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let table = TransitionTable::default();
+
+    for _ in 0..8 {
+        let shipment_id = seed_shipment_with_change(&db, &admin).await;
+        let office = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .current_office_id;
+
+        let hold = |reason| PlaceHold {
+            shipment_id,
+            reason,
+            notes: None,
+        };
+
+        // only one of two racing holds is placed
+        let (damaged, customs) = tokio::join!(
+            place_hold(&db, &admin, &table, hold(HoldReason::Damaged)),
+            place_hold(&db, &admin, &table, hold(HoldReason::Customs)),
+        );
+        assert!(
+            damaged.is_ok() != customs.is_ok(),
+            "{damaged:?} / {customs:?}"
+        );
+        let err = damaged.err().or(customs.err()).unwrap();
+        assert!(matches!(err, HoldError::AlreadyOnHold { .. }));
+
+        release_hold(
+            &db,
+            &admin,
+            ReleaseHold {
+                shipment_id,
+                notes: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        // a status change racing a hold either lands before it or is refused
+        let (held, changed) = tokio::join!(
+            place_hold(&db, &admin, &table, hold(HoldReason::Damaged)),
+            change_status(
+                &db,
+                &admin,
+                &table,
+                ChangeStatus {
+                    shipment_id,
+                    to_status: ShipmentStatus::Processed,
+                    to_office_id: office,
+                    notes: None,
+                    failure_reason: None,
+                    expected_seq: None,
+                    proof_of_delivery: None,
+                    override_route: false,
+                },
+            ),
+        );
+        held.unwrap();
+        if let Err(err) = changed {
+            assert!(matches!(err, ChangeStatusError::OnHold { .. }), "{err:?}");
+        }
+
+        let events: Vec<&str> = read_timeline(&db, shipment_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|entry| match entry.event {
+                Some(ShipmentEvent::HoldPlaced(_)) => Some("placed"),
+                Some(ShipmentEvent::HoldReleased(_)) => Some("released"),
+                Some(ShipmentEvent::StatusChanged(_)) => Some("changed"),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(events.last(), Some(&"placed"), "{events:?}");
+        assert_eq!(events.iter().filter(|e| **e == "placed").count(), 2);
+    }
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
+
+#[tokio::test]
+async fn hold_blocks_status_changes_until_released() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let table = TransitionTable::default();
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+    let office = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .current_office_id
+        .unwrap();
+
+    let hold = |reason| PlaceHold {
+        shipment_id,
+        reason,
+        notes: Some("box torn open".into()),
+    };
+    let release = ReleaseHold {
+        shipment_id,
+        notes: None,
+    };
+    let process = ChangeStatus {
+        shipment_id,
+        to_status: ShipmentStatus::Processed,
+        to_office_id: Some(office),
+        notes: None,
+        failure_reason: None,
+        expected_seq: None,
+        proof_of_delivery: None,
+        override_route: false,
+    };
+
+    // employees of other offices cannot hold it
+    let outsider = employee_actor(&db, vec![seed_office(&db).await]).await;
+    let err = place_hold(&db, &outsider, &table, hold(HoldReason::Damaged))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, HoldError::Forbidden));
+
+    let err = release_hold(&db, &admin, release.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, HoldError::NotOnHold));
+
+    let employee = employee_actor(&db, vec![office]).await;
+    place_hold(&db, &employee, &table, hold(HoldReason::Damaged))
+        .await
+        .unwrap();
+
+    let err = place_hold(&db, &admin, &table, hold(HoldReason::Customs))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, HoldError::AlreadyOnHold { reason } if reason == "DAMAGED"));
+
+    let err = change_status(&db, &admin, &table, process.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ChangeStatusError::OnHold { reason } if reason == "DAMAGED"));
+
+    let next = allowed_next_statuses(&db, &admin, ReadPolicy::default(), &table, shipment_id)
+        .await
+        .unwrap();
+    assert!(next.is_empty());
+
+    let held = list_shipments(
+        &db,
+        &admin,
+        ReadPolicy::default(),
+        ShipmentQuery {
+            on_hold: Some(true),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(held.items.len(), 1);
+    assert_eq!(held.items[0].hold_reason.as_deref(), Some("DAMAGED"));
+    assert!(held.items[0].held_since.is_some());
+
+    let customs = list_shipments(
+        &db,
+        &admin,
+        ReadPolicy::default(),
+        ShipmentQuery {
+            hold_reason: Some(HoldReason::Customs),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert!(customs.items.is_empty());
+
+    // the hold is replayable while active
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+
+    release_hold(&db, &employee, release).await.unwrap();
+    change_status(&db, &admin, &table, process).await.unwrap();
+
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(snap.current_status, "PROCESSED");
+    assert_eq!(snap.hold_reason, None);
+    assert_eq!(snap.held_since, None);
+
+    let events: Vec<_> = read_timeline(&db, shipment_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|entry| match entry.event {
+            Some(ShipmentEvent::HoldPlaced(e)) => Some(format!("placed {}", e.reason)),
+            Some(ShipmentEvent::HoldReleased(e)) => Some(format!("released {}", e.reason)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(events, vec!["placed DAMAGED", "released DAMAGED"]);
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
+
+#[tokio::test]
+async fn terminal_shipments_cannot_be_held() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let table = TransitionTable::default();
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+    let office = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .current_office_id;
+
+    change_status(
+        &db,
+        &admin,
+        &table,
+        ChangeStatus {
+            shipment_id,
+            to_status: ShipmentStatus::Cancelled,
+            to_office_id: office,
+            notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    let err = place_hold(
+        &db,
+        &admin,
+        &table,
+        PlaceHold {
+            shipment_id,
+            reason: HoldReason::Other,
+            notes: None,
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        HoldError::TerminalState {
+            status: ShipmentStatus::Cancelled
+        }
+    ));
+}
+
+#[tokio::test]
+async fn piece_scans_derive_the_shipment_status() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let table = TransitionTable::default();
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+    let office = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .current_office_id
+        .unwrap();
+    let tracking_number = tracking_number_of(&db, shipment_id).await;
+    let employee = employee_actor(&db, vec![office]).await;
+
+    let add = |weight_g| AddPiece {
+        shipment_id,
+        weight_g: Some(weight_g),
+        description: None,
+    };
+
+    let err = add_piece(&db, &employee, add(0)).await.unwrap_err();
+    assert!(matches!(err, PieceError::InvalidWeight));
+
+    let first = add_piece(&db, &employee, add(1200)).await.unwrap();
+    let second = add_piece(&db, &employee, add(800)).await.unwrap();
+    assert_eq!(first.piece_no, 1);
+    assert_eq!(first.barcode, format!("{tracking_number}-01"));
+    assert_eq!(second.barcode, format!("{tracking_number}-02"));
+    assert_eq!(second.status, "ACCEPTED");
+    assert_eq!(second.current_office_id, Some(office));
+
+    // the shipment status now follows its pieces
+    let err = change_status(
+        &db,
+        &admin,
+        &table,
+        ChangeStatus {
+            shipment_id,
+            to_status: ShipmentStatus::Processed,
+            to_office_id: Some(office),
+            notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ChangeStatusError::HasPieces));
+
+    let next = allowed_next_statuses(&db, &admin, ReadPolicy::default(), &table, shipment_id)
+        .await
+        .unwrap();
+    assert!(next.is_empty());
+
+    let scan = |piece: &core_data::entity::shipment_pieces::Model, to_status| ScanPiece {
+        barcode: piece.barcode.clone(),
+        to_status,
+        to_office_id: Some(office),
+        notes: None,
+        failure_reason: None,
+        override_route: false,
+    };
+
+    let outsider = employee_actor(&db, vec![seed_office(&db).await]).await;
+    let err = scan_piece(
+        &db,
+        &outsider,
+        &table,
+        scan(&first, ShipmentStatus::Processed),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, PieceError::Forbidden));
+
+    let err = scan_piece(
+        &db,
+        &employee,
+        &table,
+        scan(&first, ShipmentStatus::Delivered),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, PieceError::Domain(_)));
+
+    let missing = ScanPiece {
+        barcode: format!("{tracking_number}-07"),
+        ..scan(&first, ShipmentStatus::Processed)
+    };
+    let err = scan_piece(&db, &employee, &table, missing)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PieceError::NotFound));
+
+    // mixed statuses without a delivery leave the shipment where it is
+    let scanned = scan_piece(
+        &db,
+        &employee,
+        &table,
+        scan(&first, ShipmentStatus::Processed),
+    )
+    .await
+    .unwrap();
+    assert_eq!(scanned.piece.status, "PROCESSED");
+    assert_eq!(scanned.shipment_status, ShipmentStatus::Accepted);
+
+    let scanned = scan_piece(
+        &db,
+        &employee,
+        &table,
+        scan(&second, ShipmentStatus::Processed),
+    )
+    .await
+    .unwrap();
+    assert_eq!(scanned.shipment_status, ShipmentStatus::Processed);
+
+    let err = add_piece(&db, &employee, add(500)).await.unwrap_err();
+    assert!(matches!(
+        err,
+        PieceError::PiecesClosed {
+            status: ShipmentStatus::Processed
+        }
+    ));
+
+    for to_status in [ShipmentStatus::InTransit, ShipmentStatus::Delivered] {
+        scan_piece(&db, &employee, &table, scan(&first, to_status))
+            .await
+            .unwrap();
+    }
+
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(snap.current_status, "PARTIALLY_DELIVERED");
+
+    // pieces are replayable mid-way
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+
+    for to_status in [ShipmentStatus::InTransit, ShipmentStatus::Delivered] {
+        scan_piece(&db, &employee, &table, scan(&second, to_status))
+            .await
+            .unwrap();
+    }
+
+    let statuses: Vec<_> = read_timeline(&db, shipment_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|entry| match entry.event {
+            Some(ShipmentEvent::StatusChanged(e)) => Some(e.to_status),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ShipmentStatus::Accepted,
+            ShipmentStatus::Processed,
+            ShipmentStatus::PartiallyDelivered,
+            ShipmentStatus::Delivered,
+        ]
+    );
+
+    let pieces = list_pieces(&db, &employee, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap();
+    assert!(pieces.iter().all(|p| p.status == "DELIVERED"));
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
+
+#[tokio::test]
+async fn concurrent_piece_scans_move_the_shipment_once() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let table = TransitionTable::default();
+
+    for _ in 0..8 {
+        let shipment_id = seed_shipment_with_change(&db, &admin).await;
+        let office = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .current_office_id;
+
+        let add = || AddPiece {
+            shipment_id,
+            weight_g: None,
+            description: None,
+        };
+        let (first, second) =
+            tokio::join!(add_piece(&db, &admin, add()), add_piece(&db, &admin, add()));
+        let (first, second) = (first.unwrap(), second.unwrap());
+        assert_ne!(first.piece_no, second.piece_no);
+
+        let scan = |piece: &core_data::entity::shipment_pieces::Model, to_status| ScanPiece {
+            barcode: piece.barcode.clone(),
+            to_status,
+            to_office_id: office,
+            notes: None,
+            failure_reason: (to_status == ShipmentStatus::DeliveryFailed)
+                .then_some(DeliveryFailureReason::RecipientAbsent),
+            override_route: false,
+        };
+
+        // each scan sees the other, so the last one moves the shipment
+        let race = |to_status| {
+            let a = scan_piece(&db, &admin, &table, scan(&first, to_status));
+            let b = scan_piece(&db, &admin, &table, scan(&second, to_status));
+            async move {
+                let (a, b) = tokio::join!(a, b);
+                let statuses = [a.unwrap().shipment_status, b.unwrap().shipment_status];
+                assert!(statuses.contains(&to_status), "{statuses:?}");
+            }
+        };
+
+        race(ShipmentStatus::Processed).await;
+        for to_status in [ShipmentStatus::InTransit, ShipmentStatus::OutForDelivery] {
+            for piece in [&first, &second] {
+                scan_piece(&db, &admin, &table, scan(piece, to_status))
+                    .await
+                    .unwrap();
+            }
+        }
+        race(ShipmentStatus::DeliveryFailed).await;
+        for piece in [&first, &second] {
+            scan_piece(
+                &db,
+                &admin,
+                &table,
+                scan(piece, ShipmentStatus::OutForDelivery),
+            )
+            .await
+            .unwrap();
+        }
+        race(ShipmentStatus::DeliveryFailed).await;
+        for piece in [&first, &second] {
+            scan_piece(
+                &db,
+                &admin,
+                &table,
+                scan(piece, ShipmentStatus::OutForDelivery),
+            )
+            .await
+            .unwrap();
+        }
+        race(ShipmentStatus::Delivered).await;
+
+        let changes: Vec<_> = read_timeline(&db, shipment_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|entry| match entry.event {
+                Some(ShipmentEvent::StatusChanged(e)) => {
+                    Some((e.from_status, e.to_status, e.attempt))
+                }
+                _ => None,
+            })
+            .skip_while(|(_, to, _)| *to != ShipmentStatus::Processed)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (ShipmentStatus::Accepted, ShipmentStatus::Processed, None),
+                (ShipmentStatus::Processed, ShipmentStatus::InTransit, None),
+                (
+                    ShipmentStatus::InTransit,
+                    ShipmentStatus::OutForDelivery,
+                    None
+                ),
+                (
+                    ShipmentStatus::OutForDelivery,
+                    ShipmentStatus::DeliveryFailed,
+                    Some(1)
+                ),
+                (
+                    ShipmentStatus::DeliveryFailed,
+                    ShipmentStatus::OutForDelivery,
+                    None
+                ),
+                (
+                    ShipmentStatus::OutForDelivery,
+                    ShipmentStatus::DeliveryFailed,
+                    Some(2)
+                ),
+                (
+                    ShipmentStatus::DeliveryFailed,
+                    ShipmentStatus::OutForDelivery,
+                    None
+                ),
+                (
+                    ShipmentStatus::OutForDelivery,
+                    ShipmentStatus::PartiallyDelivered,
+                    None
+                ),
+                (
+                    ShipmentStatus::PartiallyDelivered,
+                    ShipmentStatus::Delivered,
+                    None
+                ),
+            ]
+        );
+
+        let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(snap.delivery_attempts, 2);
+    }
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
+
+#[tokio::test]
+async fn rebuild_restores_drifted_pieces() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+
+    let piece = add_piece(
+        &db,
+        &admin,
+        AddPiece {
+            shipment_id,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    core_data::entity::shipment_pieces::Entity::delete_by_id(piece.id)
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Live)
+        .await
+        .unwrap();
+    assert!(report.drift.iter().any(|d| matches!(
+        d,
+        Drift::Snapshot {
+            field: "pieces",
+            ..
+        }
+    )));
+
+    let pieces = list_pieces(&db, &admin, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap();
+    assert_eq!(pieces.len(), 1);
+    assert_eq!(pieces[0].barcode, piece.barcode);
+}
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n-signature";
+const JPEG: &[u8] = &[0xff, 0xd8, 0xff, 0xe0, 0x00, 0x10];
+
+#[tokio::test]
+async fn proof_of_delivery_is_stored_by_hash_and_referenced_from_the_event() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let table = TransitionTable::default();
+    let shipment_id = seed_shipment_with_change(&db, &admin).await;
+    let office = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .current_office_id;
+
+    let change = |to_status, proof_of_delivery| ChangeStatus {
+        shipment_id,
+        to_status,
+        to_office_id: office,
+        notes: None,
+        failure_reason: None,
+        expected_seq: None,
+        proof_of_delivery,
+        override_route: false,
+    };
+    let pod = |signature: Option<&[u8]>| ProofOfDeliveryInput {
+        receiver_name: " Maria Ivanova ".into(),
+        relationship: ReceiverRelationship::FamilyMember,
+        received_at: None,
+        signature: signature.map(<[u8]>::to_vec),
+        photo: Some(JPEG.to_vec()),
+    };
+
+    // proof is only taken on delivery
+    let err = change_status(
+        &db,
+        &admin,
+        &table,
+        change(ShipmentStatus::Processed, Some(pod(Some(PNG)))),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ChangeStatusError::ProofOfDelivery(ProofOfDeliveryError::NotADelivery { .. })
+    ));
+
+    for to_status in [ShipmentStatus::Processed, ShipmentStatus::InTransit] {
+        change_status(&db, &admin, &table, change(to_status, None))
+            .await
+            .unwrap();
+    }
+
+    let err = change_status(
+        &db,
+        &admin,
+        &table,
+        change(ShipmentStatus::Delivered, Some(pod(Some(b"GIF89a")))),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ChangeStatusError::ProofOfDelivery(ProofOfDeliveryError::UnsupportedImage)
+    ));
+
+    let err = get_proof_of_delivery(&db, &admin, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PodError::NotFound));
+
+    change_status(
+        &db,
+        &admin,
+        &table,
+        change(ShipmentStatus::Delivered, Some(pod(Some(PNG)))),
+    )
+    .await
+    .unwrap();
+
+    let recorded = get_proof_of_delivery(&db, &admin, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap();
+    assert_eq!(recorded.proof.receiver_name, "Maria Ivanova");
+    assert_eq!(
+        recorded.proof.relationship,
+        ReceiverRelationship::FamilyMember
+    );
+    assert_eq!(recorded.proof.signature, Some(BlobHash::of(PNG)));
+    assert_eq!(recorded.proof.photo, Some(BlobHash::of(JPEG)));
+    assert_eq!(recorded.actor_id, admin.user_id.to_string());
+
+    let signature = get_proof_of_delivery_image(
+        &db,
+        &admin,
+        ReadPolicy::default(),
+        shipment_id,
+        PodImage::Signature,
+    )
+    .await
+    .unwrap();
+    assert_eq!(signature.content_type, "image/png");
+    assert_eq!(signature.bytes, PNG);
+
+    // tampering with the stored bytes is detected on download
+    core_data::entity::blobs::ActiveModel {
+        hash: Set(BlobHash::of(JPEG).as_bytes().to_vec()),
+        bytes: Set(b"\xff\xd8\xff-forged".to_vec()),
+        ..Default::default()
+    }
+    .update(&db)
+    .await
+    .unwrap();
+
+    let err = get_proof_of_delivery_image(
+        &db,
+        &admin,
+        ReadPolicy::default(),
+        shipment_id,
+        PodImage::Photo,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, PodError::Corrupted(hash) if hash == BlobHash::of(JPEG)));
+
+    let outsider = employee_actor(&db, vec![seed_office(&db).await]).await;
+    let err = get_proof_of_delivery(&db, &outsider, ReadPolicy::default(), shipment_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PodError::Timeline(_)));
+}
+
+#[tokio::test]
+async fn cash_on_delivery_is_collected_on_delivery_and_remitted_once() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let table = TransitionTable::default();
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+    let bgn: Currency = "BGN".parse().unwrap();
+
+    let create = |amount_cents| CreateShipment {
+        client_id: client,
+        current_office_id: Some(office),
+        cod: Some(CashOnDelivery {
+            amount_cents,
+            currency: bgn.clone(),
+        }),
+        ..Default::default()
+    };
+
+    let err = create_shipment(&db, &admin, &Tariff::default(), create(0))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        CreateShipmentError::CashOnDelivery(CashOnDeliveryError::InvalidAmount { .. })
+    ));
+
+    let shipment_id = create_shipment(&db, &admin, &Tariff::default(), create(2_550))
+        .await
+        .unwrap();
+    create_shipment(&db, &admin, &Tariff::default(), create(1_000))
+        .await
+        .unwrap();
+
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        ShipmentDetails::from_model(&snap).cod,
+        Some(CashOnDelivery {
+            amount_cents: 2_550,
+            currency: bgn.clone(),
+        })
+    );
+
+    for to_status in [
+        ShipmentStatus::Accepted,
+        ShipmentStatus::Processed,
+        ShipmentStatus::InTransit,
+        ShipmentStatus::Delivered,
+    ] {
+        assert!(
+            CodRepo::find_by_shipment(&db, shipment_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        change_status(
+            &db,
+            &admin,
+            &table,
+            ChangeStatus {
+                shipment_id,
+                to_status,
+                to_office_id: Some(office),
+                notes: None,
+                failure_reason: None,
+                expected_seq: None,
+                proof_of_delivery: None,
+                override_route: false,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let collection = CodRepo::find_by_shipment(&db, shipment_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(collection.client_id, client);
+    assert_eq!(collection.office_id, Some(office));
+    assert_eq!(collection.amount_cents, 2_550);
+    assert_eq!(collection.currency, "BGN");
+    assert_eq!(collection.remittance_id, None);
+
+    let timeline = read_timeline(&db, shipment_id).await.unwrap();
+    assert!(matches!(
+        timeline.last().unwrap().event,
+        Some(ShipmentEvent::CodCollected(ref e)) if e.collection_id == collection.id.to_string()
+    ));
+
+    let remit = |currency: &Currency| RemitCod {
+        client_id: client,
+        currency: currency.clone(),
+        office_id: None,
+        reference: Some(" TRF-1 ".into()),
+    };
+
+    let employee = employee_actor(&db, vec![office]).await;
+    let err = remit_cod(&db, &employee, remit(&bgn)).await.unwrap_err();
+    assert!(matches!(err, RemitCodError::Forbidden));
+
+    let eur: Currency = "EUR".parse().unwrap();
+    let err = remit_cod(&db, &admin, remit(&eur)).await.unwrap_err();
+    assert!(matches!(err, RemitCodError::NothingToRemit));
+
+    // only the delivered shipment is paid out
+    let remittance = remit_cod(&db, &admin, remit(&bgn)).await.unwrap();
+    assert_eq!(remittance.client_id, client);
+    assert_eq!(remittance.amount_cents, 2_550);
+    assert_eq!(remittance.shipment_count, 1);
+    assert_eq!(remittance.reference.as_deref(), Some("TRF-1"));
+
+    let collection = CodRepo::find_by_shipment(&db, shipment_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(collection.remittance_id, Some(remittance.id));
+    assert!(collection.remitted_at.is_some());
+
+    let timeline = read_timeline(&db, shipment_id).await.unwrap();
+    assert!(matches!(
+        timeline.last().unwrap().event,
+        Some(ShipmentEvent::CodRemitted(ref e))
+            if e.remittance_id == remittance.id.to_string() && e.amount_cents == 2_550
+    ));
+
+    let err = remit_cod(&db, &admin, remit(&bgn)).await.unwrap_err();
+    assert!(matches!(err, RemitCodError::NothingToRemit));
+
+    // the ledger is rebuilt from the stream
+    core_data::entity::cod_collections::Entity::delete_by_id(collection.id)
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Live)
+        .await
+        .unwrap();
+    assert!(report.drift.iter().any(|drift| matches!(
+        drift,
+        Drift::Snapshot { shipment_id: id, field: "cod_collection", .. } if *id == shipment_id
+    )));
+
+    let restored = CodRepo::find_by_shipment(&db, shipment_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored.id, collection.id);
+    assert_eq!(restored.remittance_id, Some(remittance.id));
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
+
+#[tokio::test]
+async fn quotes_price_like_creation_and_unpriceable_shipments_are_rejected() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let tariff = Tariff::default();
+    let client = seed_client(&db).await;
+    let origin = seed_office(&db).await;
+    let destination = seed_office(&db).await;
+
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        format!("UPDATE offices SET city = 'Varna' WHERE id = '{destination}'"),
+    ))
+    .await
+    .unwrap();
+
+    let parcel = |weight_g| Parcel {
+        weight_g,
+        dimensions: None,
+        declared_value_cents: None,
+        description: None,
+    };
+    let cod = CashOnDelivery {
+        amount_cents: 10_000,
+        currency: "BGN".parse().unwrap(),
+    };
+
+    let quote = quote_shipment(
+        &db,
+        &tariff,
+        QuoteShipment {
+            origin_office_id: origin,
+            destination_office_id: destination,
+            parcel: parcel(900),
+            cod: Some(cod.clone()),
+            service_level: ServiceLevel::Express,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(quote.price.zone, "NATIONAL");
+    assert_eq!(quote.price.base_cents, 600);
+    assert_eq!(quote.price.express_fee_cents, 300);
+    assert_eq!(quote.price.cod_fee_cents, 150);
+    assert_eq!(quote.price.total_cents, 1_260);
+
+    let create = |weight_g| CreateShipment {
+        client_id: client,
+        current_office_id: Some(origin),
+        destination_office_id: Some(destination),
+        parcel: Some(parcel(weight_g)),
+        cod: Some(cod.clone()),
+        service_level: ServiceLevel::Express,
+        ..Default::default()
+    };
+
+    let shipment_id = create_shipment(&db, &admin, &tariff, create(900))
+        .await
+        .unwrap();
+    let snap = core_data::entity::shipments::Entity::find_by_id(shipment_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    let details = ShipmentDetails::from_model(&snap);
+    assert_eq!(details.service_level, ServiceLevel::Express);
+    assert_eq!(details.price, Some(quote.price));
+    assert_eq!(details.tariff_id, None);
+
+    // the snapshot is restored from the event, not repriced
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+
+    let err = create_shipment(&db, &admin, &tariff, create(40_000))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        CreateShipmentError::Pricing(PricingError::TooHeavy { .. })
+    ));
+
+    let err = quote_shipment(
+        &db,
+        &tariff,
+        QuoteShipment {
+            origin_office_id: origin,
+            destination_office_id: Uuid::new_v4(),
+            parcel: parcel(900),
+            cod: None,
+            service_level: ServiceLevel::Standard,
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, QuoteError::OfficeNotFound));
+}
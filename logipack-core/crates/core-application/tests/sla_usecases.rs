@@ -0,0 +1,558 @@
+use chrono::{Duration, Utc};
+use core_application::actor::ActorContext;
+use core_application::office_links::set::{SetOfficeLink, set_office_link};
+use core_application::reports::scope::ReportQuery;
+use core_application::reports::sla::sla_breaches;
+use core_application::roles::Role;
+use core_application::shipments::change_status::{ChangeStatus, change_status};
+use core_application::shipments::create::{CreateShipment, create_shipment};
+use core_application::shipments::get_detail::get_shipment_detail;
+use core_application::shipments::pieces::{AddPiece, ScanPiece, add_piece, scan_piece};
+use core_application::shipments::rebuild::{RebuildTarget, rebuild_shipment_projections};
+use core_application::shipments::sla::check_sla_breaches;
+use core_application::shipments::timeline::read_timeline;
+use core_application::shipments::visibility::ReadPolicy;
+use core_data::entity::{clients, employee_offices, employees, offices, users};
+use core_data::repository::shipments_repo::ShipmentsRepo;
+use core_domain::pricing::{ServiceLevel, Tariff};
+use core_domain::shipment::{ShipmentEvent, ShipmentStatus, TransitionTable};
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DatabaseConnection, DbBackend, Set, Statement};
+use test_infra::test_db;
+use uuid::Uuid;
+
+async fn cleanup(db: &DatabaseConnection) {
+    let tables = [
+        "cod_collections",
+        "cod_remittances",
+        "shipment_status_history",
+        "shipment_route_stops",
+        "shipments",
+        "tariffs",
+        "employee_offices",
+        "employees",
+        "user_roles",
+        "users",
+        "clients",
+        "roles",
+        "office_calendars",
+        "office_links",
+        "offices",
+        "packages",
+        "streams",
+    ];
+
+    for t in tables {
+        db.execute(Statement::from_string(
+            DbBackend::Postgres,
+            format!("DELETE FROM {}", t),
+        ))
+        .await
+        .unwrap();
+    }
+}
+
+async fn seed_office(db: &DatabaseConnection) -> Uuid {
+    let id = Uuid::new_v4();
+
+    offices::ActiveModel {
+        id: Set(id),
+        name: Set("Office".into()),
+        city: Set("City".into()),
+        address: Set("Address".into()),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn seed_client(db: &DatabaseConnection) -> Uuid {
+    let id = Uuid::new_v4();
+
+    clients::ActiveModel {
+        id: Set(id),
+        name: Set("Test Client".into()),
+        phone: Set(None),
+        email: Set(None),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn seed_user(db: &DatabaseConnection, user_type: &str) -> Uuid {
+    let id = Uuid::new_v4();
+
+    users::ActiveModel {
+        id: Set(id),
+        name: Set("Test User".into()),
+        email: Set(Some(format!("{user_type}+{id}@test.com"))),
+        password_hash: Set(Some("x".into())),
+        auth0_sub: Set(None),
+        created_at: Set(chrono::Utc::now().into()),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn admin_actor(db: &DatabaseConnection) -> ActorContext {
+    let user_id = seed_user(db, "admin").await;
+
+    ActorContext {
+        user_id,
+        sub: "admin".into(),
+        roles: vec![Role::Admin],
+        employee_id: None,
+        allowed_office_ids: vec![],
+    }
+}
+
+async fn employee_actor(db: &DatabaseConnection, allowed_office_ids: Vec<Uuid>) -> ActorContext {
+    let user_id = seed_user(db, "employee").await;
+    let employee_id = Uuid::new_v4();
+
+    employees::ActiveModel {
+        id: Set(employee_id),
+        user_id: Set(user_id),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    for office_id in &allowed_office_ids {
+        employee_offices::ActiveModel {
+            employee_id: Set(employee_id),
+            office_id: Set(*office_id),
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    ActorContext {
+        user_id,
+        sub: "employee".into(),
+        roles: vec![Role::Employee],
+        employee_id: Some(employee_id),
+        allowed_office_ids,
+    }
+}
+
+async fn link(db: &DatabaseConnection, admin: &ActorContext, from: Uuid, to: Uuid, minutes: i32) {
+    set_office_link(
+        db,
+        admin,
+        SetOfficeLink {
+            from_office_id: from,
+            to_office_id: to,
+            transit_minutes: minutes,
+            cost_cents: 100,
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn shipments_past_their_promise_are_flagged_once() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let client = seed_client(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+    let transitions = TransitionTable::default();
+
+    link(&db, &admin, a, b, 60).await;
+    link(&db, &admin, b, c, 60).await;
+
+    let input = || CreateShipment {
+        client_id: client,
+        current_office_id: Some(a),
+        destination_office_id: Some(c),
+        service_level: ServiceLevel::Express,
+        ..Default::default()
+    };
+
+    let before = Utc::now();
+    let late = create_shipment(&db, &admin, &Tariff::default(), input())
+        .await
+        .unwrap();
+    let cancelled = create_shipment(&db, &admin, &Tariff::default(), input())
+        .await
+        .unwrap();
+    let unrouted = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(a),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let after = Utc::now();
+
+    // two 60 minute legs plus an hour of handling at each of three offices
+    let detail = get_shipment_detail(&db, &admin, ReadPolicy::default(), late)
+        .await
+        .unwrap();
+    let promised_by = detail.shipment.promised_by.unwrap();
+    assert!(promised_by >= before + Duration::minutes(300) - Duration::milliseconds(1));
+    assert!(promised_by <= after + Duration::minutes(300));
+
+    let detail = get_shipment_detail(&db, &admin, ReadPolicy::default(), unrouted)
+        .await
+        .unwrap();
+    assert!(detail.shipment.promised_by.is_none());
+
+    change_status(
+        &db,
+        &admin,
+        &transitions,
+        ChangeStatus {
+            shipment_id: cancelled,
+            to_status: ShipmentStatus::Cancelled,
+            to_office_id: Some(a),
+            notes: Some("client changed their mind".into()),
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    let flagged = check_sla_breaches(&db, &transitions, Utc::now())
+        .await
+        .unwrap();
+    assert!(flagged.is_empty());
+
+    let tomorrow = Utc::now() + Duration::days(1);
+    let flagged = check_sla_breaches(&db, &transitions, tomorrow)
+        .await
+        .unwrap();
+    assert_eq!(flagged, vec![late]);
+
+    // already flagged shipments are skipped
+    let flagged = check_sla_breaches(&db, &transitions, tomorrow)
+        .await
+        .unwrap();
+    assert!(flagged.is_empty());
+
+    let detail = get_shipment_detail(&db, &admin, ReadPolicy::default(), late)
+        .await
+        .unwrap();
+    assert!(detail.shipment.sla_breached_at.is_some());
+
+    let timeline = read_timeline(&db, late).await.unwrap();
+    let breach = timeline
+        .iter()
+        .find_map(|entry| match &entry.event {
+            Some(ShipmentEvent::SlaBreached(e)) => Some(e.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(breach.promised_by_ms, promised_by.timestamp_millis());
+    assert_eq!(breach.status, ShipmentStatus::New);
+    assert_eq!(breach.office.unwrap().office_id, a.to_string());
+
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
+
+#[tokio::test]
+async fn sla_breach_report_is_scoped_to_employee_offices() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let client = seed_client(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let local = employee_actor(&db, vec![a]).await;
+    let elsewhere = employee_actor(&db, vec![b]).await;
+
+    link(&db, &admin, a, b, 60).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(a),
+            destination_office_id: Some(b),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let rows = sla_breaches(&db, &admin, ReportQuery::default())
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+
+    check_sla_breaches(
+        &db,
+        &TransitionTable::default(),
+        Utc::now() + Duration::days(1),
+    )
+    .await
+    .unwrap();
+
+    let rows = sla_breaches(&db, &admin, ReportQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].shipment_id, shipment_id);
+    assert_eq!(rows[0].current_office_id, Some(a));
+    assert!(rows[0].breached_at > rows[0].promised_by);
+
+    let rows = sla_breaches(&db, &local, ReportQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+
+    let rows = sla_breaches(&db, &elsewhere, ReportQuery::default())
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+}
+
+fn change(shipment_id: Uuid, to_status: ShipmentStatus, office: Uuid) -> ChangeStatus {
+    ChangeStatus {
+        shipment_id,
+        to_status,
+        to_office_id: Some(office),
+        notes: None,
+        failure_reason: None,
+        expected_seq: None,
+        proof_of_delivery: None,
+        override_route: false,
+    }
+}
+
+async fn breaches(db: &DatabaseConnection, shipment_id: Uuid) -> Vec<(ShipmentStatus, String)> {
+    read_timeline(db, shipment_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|entry| match entry.event {
+            Some(ShipmentEvent::SlaBreached(e)) => Some((e.status, e.office?.office_id)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn late_deliveries_are_flagged_by_the_status_change() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let client = seed_client(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let transitions = TransitionTable::default();
+
+    link(&db, &admin, a, b, 60).await;
+
+    let tariff = Tariff::default();
+    let create = || {
+        create_shipment(
+            &db,
+            &admin,
+            &tariff,
+            CreateShipment {
+                client_id: client,
+                current_office_id: Some(a),
+                destination_office_id: Some(b),
+                ..Default::default()
+            },
+        )
+    };
+    let late = create().await.unwrap();
+    let on_time = create().await.unwrap();
+
+    for shipment_id in [late, on_time] {
+        for (to_status, office) in [
+            (ShipmentStatus::Accepted, a),
+            (ShipmentStatus::Processed, a),
+            (ShipmentStatus::InTransit, b),
+        ] {
+            change_status(
+                &db,
+                &admin,
+                &transitions,
+                change(shipment_id, to_status, office),
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    // the promise runs out between two periodic checks
+    let promised_by = Utc::now() - Duration::minutes(5);
+    ShipmentsRepo::set_promised_by(&db, late, promised_by.into())
+        .await
+        .unwrap();
+
+    for shipment_id in [late, on_time] {
+        change_status(
+            &db,
+            &admin,
+            &transitions,
+            change(shipment_id, ShipmentStatus::Delivered, b),
+        )
+        .await
+        .unwrap();
+    }
+
+    let detail = get_shipment_detail(&db, &admin, ReadPolicy::default(), late)
+        .await
+        .unwrap();
+    assert!(detail.shipment.sla_breached_at.unwrap() > promised_by);
+    assert_eq!(
+        breaches(&db, late).await,
+        vec![(ShipmentStatus::InTransit, b.to_string())]
+    );
+
+    let detail = get_shipment_detail(&db, &admin, ReadPolicy::default(), on_time)
+        .await
+        .unwrap();
+    assert!(detail.shipment.sla_breached_at.is_none());
+    assert!(breaches(&db, on_time).await.is_empty());
+
+    let rows = sla_breaches(&db, &admin, ReportQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].shipment_id, late);
+
+    // delivered shipments are not flagged again later
+    let flagged = check_sla_breaches(&db, &transitions, Utc::now() + Duration::days(1))
+        .await
+        .unwrap();
+    assert!(flagged.is_empty());
+    assert_eq!(breaches(&db, late).await.len(), 1);
+}
+
+#[tokio::test]
+async fn late_piece_scans_are_flagged_by_the_derived_status_change() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let client = seed_client(&db).await;
+    let a = seed_office(&db).await;
+    let transitions = TransitionTable::default();
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(a),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    change_status(
+        &db,
+        &admin,
+        &transitions,
+        change(shipment_id, ShipmentStatus::Accepted, a),
+    )
+    .await
+    .unwrap();
+
+    let add = || AddPiece {
+        shipment_id,
+        weight_g: None,
+        description: None,
+    };
+    let first = add_piece(&db, &admin, add()).await.unwrap();
+    let second = add_piece(&db, &admin, add()).await.unwrap();
+
+    let scan = |piece: &core_data::entity::shipment_pieces::Model, to_status| ScanPiece {
+        barcode: piece.barcode.clone(),
+        to_status,
+        to_office_id: Some(a),
+        notes: None,
+        failure_reason: None,
+        override_route: false,
+    };
+
+    for to_status in [ShipmentStatus::Processed, ShipmentStatus::InTransit] {
+        for piece in [&first, &second] {
+            scan_piece(&db, &admin, &transitions, scan(piece, to_status))
+                .await
+                .unwrap();
+        }
+    }
+
+    ShipmentsRepo::set_promised_by(&db, shipment_id, (Utc::now() - Duration::minutes(5)).into())
+        .await
+        .unwrap();
+
+    // a piece scan alone leaves the shipment to the periodic check
+    scan_piece(
+        &db,
+        &admin,
+        &transitions,
+        scan(&first, ShipmentStatus::Processed),
+    )
+    .await
+    .unwrap();
+    assert!(breaches(&db, shipment_id).await.is_empty());
+
+    // the scan that moves the shipment flags it
+    let scanned = scan_piece(
+        &db,
+        &admin,
+        &transitions,
+        scan(&second, ShipmentStatus::Processed),
+    )
+    .await
+    .unwrap();
+    assert_eq!(scanned.shipment_status, ShipmentStatus::Processed);
+    assert_eq!(
+        breaches(&db, shipment_id).await,
+        vec![(ShipmentStatus::InTransit, a.to_string())]
+    );
+
+    for to_status in [ShipmentStatus::InTransit, ShipmentStatus::Delivered] {
+        for piece in [&first, &second] {
+            scan_piece(&db, &admin, &transitions, scan(piece, to_status))
+                .await
+                .unwrap();
+        }
+    }
+    assert_eq!(breaches(&db, shipment_id).await.len(), 1);
+}
@@ -0,0 +1,705 @@
+use chrono::{Duration, Utc};
+use core_application::actor::ActorContext;
+use core_application::roles::Role;
+use core_application::shipments::create::{CreateShipment, create_shipment};
+use core_application::shipments::quote::{QuoteShipment, quote_shipment};
+use core_application::shipments::rebuild::{RebuildTarget, rebuild_shipment_projections};
+use core_application::tariffs::TariffPeriodError;
+use core_application::tariffs::create::{CreateTariff, CreateTariffError, create_tariff};
+use core_application::tariffs::delete::{DeleteTariffError, delete_tariff};
+use core_application::tariffs::get::{GetTariffError, get_tariff};
+use core_application::tariffs::list::{ListTariffsError, list_tariffs};
+use core_application::tariffs::update::{UpdateTariff, UpdateTariffError, update_tariff};
+use core_data::entity::{clients, offices, users};
+use core_data::repository::shipments_repo::ShipmentDetails;
+use core_data::repository::tariffs_repo::TariffVersion;
+use core_domain::errors::TariffError;
+use core_domain::pricing::{ServiceLevel, Tariff, TariffVersionRecorded};
+use core_domain::shipment::{EventCodec, Parcel};
+use core_eventstore::adapter::read::read_stream_packages;
+use core_eventstore::adapter::upcast::UpcasterRegistry;
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, Set, Statement,
+};
+use test_infra::test_db;
+use uuid::Uuid;
+
+async fn cleanup(db: &DatabaseConnection) {
+    let tables = [
+        "cod_collections",
+        "cod_remittances",
+        "shipment_status_history",
+        "shipment_route_stops",
+        "shipments",
+        "tariffs",
+        "employee_offices",
+        "employees",
+        "user_roles",
+        "users",
+        "clients",
+        "roles",
+        "office_calendars",
+        "office_links",
+        "offices",
+        "packages",
+        "streams",
+    ];
+
+    for t in tables {
+        db.execute(Statement::from_string(
+            DbBackend::Postgres,
+            format!("DELETE FROM {}", t),
+        ))
+        .await
+        .unwrap();
+    }
+}
+
+async fn seed_office(db: &DatabaseConnection, city: &str) -> Uuid {
+    let id = Uuid::new_v4();
+
+    offices::ActiveModel {
+        id: Set(id),
+        name: Set("Main Office".to_string()),
+        city: Set(city.to_string()),
+        address: Set("1 Test Street".to_string()),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn seed_client(db: &DatabaseConnection) -> Uuid {
+    let id = Uuid::new_v4();
+
+    clients::ActiveModel {
+        id: Set(id),
+        name: Set("Test Client".into()),
+        phone: Set(None),
+        email: Set(None),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+async fn admin_actor(db: &DatabaseConnection) -> ActorContext {
+    let user_id = Uuid::new_v4();
+
+    users::ActiveModel {
+        id: Set(user_id),
+        name: Set("Test Admin".into()),
+        email: Set(Some(format!("admin+{user_id}@test.com"))),
+        password_hash: Set(Some("x".into())),
+        auth0_sub: Set(None),
+        created_at: Set(Utc::now().into()),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    ActorContext {
+        user_id,
+        sub: "admin".into(),
+        roles: vec![Role::Admin],
+        employee_id: None,
+        allowed_office_ids: vec![],
+    }
+}
+
+fn employee(admin: &ActorContext) -> ActorContext {
+    ActorContext {
+        roles: vec![Role::Employee],
+        ..admin.clone()
+    }
+}
+
+/// Version starting `from` from now, open-ended unless `to` is given.
+fn create(from: Option<Duration>, to: Option<Duration>, tariff: Tariff) -> CreateTariff {
+    let now = Utc::now();
+
+    CreateTariff {
+        effective_from: from.map(|d| now + d),
+        effective_to: to.map(|d| now + d),
+        tariff,
+    }
+}
+
+/// Default tariff with every base price raised by `extra_cents`.
+fn tariff(extra_cents: i64) -> Tariff {
+    let mut tariff = Tariff::default();
+    for band in &mut tariff.bands {
+        for price in band.prices.values_mut() {
+            *price += extra_cents;
+        }
+    }
+    tariff
+}
+
+fn update(version: &TariffVersion) -> UpdateTariff {
+    UpdateTariff {
+        id: version.id,
+        effective_from: version.effective_from.into(),
+        effective_to: version.effective_to.map(Into::into),
+        tariff: version.tariff.clone(),
+    }
+}
+
+async fn recorded_versions(db: &DatabaseConnection, id: Uuid) -> Vec<String> {
+    read_stream_packages(db, id, &UpcasterRegistry::new())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|pkg| pkg.event_type)
+        .collect()
+}
+
+#[tokio::test]
+async fn admin_can_create_tariff() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let version = create_tariff(&db, &admin, create(None, None, tariff(0)))
+        .await
+        .unwrap();
+
+    assert_eq!(version.created_by, Some(admin.user_id));
+    assert_eq!(version.effective_to, None);
+    assert_eq!(version.tariff, tariff(0));
+    assert_eq!(
+        recorded_versions(&db, version.id).await,
+        vec!["tariff", TariffVersionRecorded::EVENT_TYPE]
+    );
+}
+
+#[tokio::test]
+async fn employee_cannot_create_tariff() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let err = create_tariff(&db, &employee(&admin), create(None, None, tariff(0)))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CreateTariffError::Forbidden));
+}
+
+#[tokio::test]
+async fn invalid_tariff_cannot_be_created() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let mut empty = tariff(0);
+    empty.bands.clear();
+
+    let err = create_tariff(&db, &admin, create(None, None, empty))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        CreateTariffError::Invalid(TariffError::NoWeightBands)
+    ));
+}
+
+#[tokio::test]
+async fn retroactive_tariff_cannot_be_created() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let err = create_tariff(
+        &db,
+        &admin,
+        create(Some(-Duration::days(1)), None, tariff(0)),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, CreateTariffError::Retroactive));
+}
+
+#[tokio::test]
+async fn empty_tariff_period_cannot_be_created() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let err = create_tariff(
+        &db,
+        &admin,
+        create(Some(Duration::days(2)), Some(Duration::days(1)), tariff(0)),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        CreateTariffError::Period(TariffPeriodError::Empty)
+    ));
+}
+
+#[tokio::test]
+async fn overlapping_tariff_cannot_be_created() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let current = create_tariff(&db, &admin, create(None, None, tariff(0)))
+        .await
+        .unwrap();
+
+    // open-ended, so every later start overlaps
+    let err = create_tariff(
+        &db,
+        &admin,
+        create(Some(Duration::days(90)), None, tariff(100)),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        CreateTariffError::Period(TariffPeriodError::Overlaps(id)) if id == current.id
+    ));
+}
+
+#[tokio::test]
+async fn closing_the_current_tariff_makes_room_for_the_next() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let current = create_tariff(&db, &admin, create(None, None, tariff(0)))
+        .await
+        .unwrap();
+
+    let closed = update_tariff(
+        &db,
+        &admin,
+        UpdateTariff {
+            effective_to: Some(Utc::now() + Duration::days(90)),
+            ..update(&current)
+        },
+    )
+    .await
+    .unwrap();
+    let next = create_tariff(
+        &db,
+        &admin,
+        create(Some(Duration::days(90)), None, tariff(100)),
+    )
+    .await
+    .unwrap();
+
+    let listed = list_tariffs(&db, &admin).await.unwrap();
+    assert_eq!(
+        listed.iter().map(|v| v.id).collect::<Vec<_>>(),
+        vec![current.id, next.id]
+    );
+    assert_eq!(listed[0].effective_to, closed.effective_to);
+
+    assert_eq!(
+        recorded_versions(&db, current.id).await,
+        vec![
+            "tariff",
+            TariffVersionRecorded::EVENT_TYPE,
+            TariffVersionRecorded::EVENT_TYPE
+        ]
+    );
+
+    let last = read_stream_packages(&db, current.id, &UpcasterRegistry::new())
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    let event = TariffVersionRecorded::decode(last.payload().unwrap()).unwrap();
+    assert_eq!(event.tariff_id, current.id.to_string());
+    assert_eq!(
+        event.effective_to_ms,
+        closed.effective_to.map(|to| to.timestamp_millis())
+    );
+    assert_eq!(event.tariff, tariff(0));
+}
+
+#[tokio::test]
+async fn rules_of_tariff_in_force_cannot_change() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let current = create_tariff(
+        &db,
+        &admin,
+        create(None, Some(Duration::days(30)), tariff(0)),
+    )
+    .await
+    .unwrap();
+
+    let err = update_tariff(
+        &db,
+        &admin,
+        UpdateTariff {
+            tariff: tariff(50),
+            ..update(&current)
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, UpdateTariffError::InForce));
+}
+
+#[tokio::test]
+async fn tariff_in_force_cannot_end_in_the_past() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let current = create_tariff(
+        &db,
+        &admin,
+        create(None, Some(Duration::days(30)), tariff(0)),
+    )
+    .await
+    .unwrap();
+
+    let err = update_tariff(
+        &db,
+        &admin,
+        UpdateTariff {
+            effective_to: Some(Utc::now() - Duration::days(1)),
+            ..update(&current)
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, UpdateTariffError::InForce));
+}
+
+#[tokio::test]
+async fn admin_can_reprice_future_tariff() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let future = create_tariff(
+        &db,
+        &admin,
+        create(Some(Duration::days(30)), None, tariff(100)),
+    )
+    .await
+    .unwrap();
+
+    let repriced = update_tariff(
+        &db,
+        &admin,
+        UpdateTariff {
+            tariff: tariff(200),
+            ..update(&future)
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(repriced.tariff, tariff(200));
+    assert_eq!(get_tariff(&db, &admin, future.id).await.unwrap(), repriced);
+}
+
+#[tokio::test]
+async fn employee_cannot_update_tariff() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let future = create_tariff(
+        &db,
+        &admin,
+        create(Some(Duration::days(30)), None, tariff(100)),
+    )
+    .await
+    .unwrap();
+
+    let err = update_tariff(&db, &employee(&admin), update(&future))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, UpdateTariffError::Forbidden));
+}
+
+#[tokio::test]
+async fn updating_nonexistent_tariff_returns_error() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let future = create_tariff(
+        &db,
+        &admin,
+        create(Some(Duration::days(30)), None, tariff(100)),
+    )
+    .await
+    .unwrap();
+
+    let err = update_tariff(
+        &db,
+        &admin,
+        UpdateTariff {
+            id: Uuid::new_v4(),
+            ..update(&future)
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, UpdateTariffError::NotFound));
+}
+
+#[tokio::test]
+async fn admin_can_delete_future_tariff() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let future = create_tariff(
+        &db,
+        &admin,
+        create(Some(Duration::days(30)), None, tariff(100)),
+    )
+    .await
+    .unwrap();
+
+    delete_tariff(&db, &admin, future.id).await.unwrap();
+
+    let err = get_tariff(&db, &admin, future.id).await.unwrap_err();
+    assert!(matches!(err, GetTariffError::NotFound));
+    assert_eq!(
+        recorded_versions(&db, future.id).await,
+        vec!["tariff", "TariffVersionRecorded", "TariffVersionWithdrawn"]
+    );
+}
+
+#[tokio::test]
+async fn tariff_in_force_cannot_be_deleted() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let current = create_tariff(&db, &admin, create(None, None, tariff(0)))
+        .await
+        .unwrap();
+
+    let err = delete_tariff(&db, &admin, current.id).await.unwrap_err();
+    assert!(matches!(err, DeleteTariffError::InForce));
+}
+
+#[tokio::test]
+async fn employee_cannot_delete_tariff() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let future = create_tariff(
+        &db,
+        &admin,
+        create(Some(Duration::days(30)), None, tariff(100)),
+    )
+    .await
+    .unwrap();
+
+    let err = delete_tariff(&db, &employee(&admin), future.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DeleteTariffError::Forbidden));
+}
+
+#[tokio::test]
+async fn deleting_nonexistent_tariff_returns_error() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let err = delete_tariff(&db, &admin, Uuid::new_v4())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DeleteTariffError::NotFound));
+}
+
+#[tokio::test]
+async fn employee_cannot_get_tariff() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let current = create_tariff(&db, &admin, create(None, None, tariff(0)))
+        .await
+        .unwrap();
+
+    let err = get_tariff(&db, &employee(&admin), current.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GetTariffError::Forbidden));
+}
+
+#[tokio::test]
+async fn getting_nonexistent_tariff_returns_error() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let err = get_tariff(&db, &admin, Uuid::new_v4()).await.unwrap_err();
+    assert!(matches!(err, GetTariffError::NotFound));
+}
+
+#[tokio::test]
+async fn employee_cannot_list_tariffs() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+
+    let err = list_tariffs(&db, &employee(&admin)).await.unwrap_err();
+    assert!(matches!(err, ListTariffsError::Forbidden));
+}
+
+fn shipment(client: Uuid, origin: Uuid, destination: Uuid) -> CreateShipment {
+    CreateShipment {
+        client_id: client,
+        current_office_id: Some(origin),
+        destination_office_id: Some(destination),
+        parcel: Some(Parcel {
+            weight_g: 900,
+            dimensions: None,
+            declared_value_cents: None,
+            description: None,
+        }),
+        ..Default::default()
+    }
+}
+
+async fn details(db: &DatabaseConnection, id: Uuid) -> ShipmentDetails {
+    let snap = core_data::entity::shipments::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+
+    ShipmentDetails::from_model(&snap)
+}
+
+#[tokio::test]
+async fn configured_tariff_applies_until_a_version_is_in_force() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let client = seed_client(&db).await;
+    let origin = seed_office(&db, "Sofia").await;
+    let destination = seed_office(&db, "Varna").await;
+
+    create_tariff(
+        &db,
+        &admin,
+        create(Some(Duration::days(30)), None, tariff(1_000)),
+    )
+    .await
+    .unwrap();
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        shipment(client, origin, destination),
+    )
+    .await
+    .unwrap();
+
+    let priced = details(&db, shipment_id).await;
+    assert_eq!(priced.tariff_id, None);
+    assert_eq!(priced.price.unwrap().base_cents, 600);
+}
+
+#[tokio::test]
+async fn quotes_use_the_tariff_in_force() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let origin = seed_office(&db, "Sofia").await;
+    let destination = seed_office(&db, "Varna").await;
+
+    let current = create_tariff(&db, &admin, create(None, None, tariff(100)))
+        .await
+        .unwrap();
+
+    let quote = quote_shipment(
+        &db,
+        &Tariff::default(),
+        QuoteShipment {
+            origin_office_id: origin,
+            destination_office_id: destination,
+            parcel: shipment(Uuid::nil(), origin, destination).parcel.unwrap(),
+            cod: None,
+            service_level: ServiceLevel::Standard,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(quote.tariff_id, Some(current.id));
+    assert_eq!(quote.price.base_cents, 700);
+}
+
+#[tokio::test]
+async fn shipments_are_priced_by_the_version_in_force() {
+    let db = test_db().await;
+    cleanup(&db).await;
+
+    let admin = admin_actor(&db).await;
+    let client = seed_client(&db).await;
+    let origin = seed_office(&db, "Sofia").await;
+    let destination = seed_office(&db, "Varna").await;
+
+    let current = create_tariff(&db, &admin, create(None, None, tariff(100)))
+        .await
+        .unwrap();
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        shipment(client, origin, destination),
+    )
+    .await
+    .unwrap();
+
+    let priced = details(&db, shipment_id).await;
+    assert_eq!(priced.tariff_id, Some(current.id));
+    assert_eq!(priced.price.unwrap().base_cents, 700);
+
+    // the version is replayed from ShipmentCreated
+    let report = rebuild_shipment_projections(&db, &admin, RebuildTarget::Scratch)
+        .await
+        .unwrap();
+    assert!(report.is_clean(), "unexpected drift: {:?}", report.drift);
+}
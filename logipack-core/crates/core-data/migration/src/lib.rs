@@ -6,6 +6,18 @@ mod m2026_01_27_email_nullable;
 mod m2026_01_27_password_hash_nullable;
 mod m2026_02_13_soft_delete;
 mod m2026_02_17_user_name;
+mod m2026_10_18_blobs;
+mod m2026_10_18_cash_on_delivery;
+mod m2026_10_18_delivery_attempts;
+mod m2026_10_18_office_calendars;
+mod m2026_10_18_office_links;
+mod m2026_10_18_shipment_details;
+mod m2026_10_18_shipment_holds;
+mod m2026_10_18_shipment_pieces;
+mod m2026_10_18_shipment_prices;
+mod m2026_10_18_shipment_sla;
+mod m2026_10_18_tariffs;
+mod m2026_10_18_tracking_numbers;
 
 pub struct Migrator;
 
@@ -19,6 +31,18 @@ impl MigratorTrait for Migrator {
             Box::new(m2026_01_27_email_nullable::Migration),
             Box::new(m2026_02_13_soft_delete::Migration),
             Box::new(m2026_02_17_user_name::Migration),
+            Box::new(m2026_10_18_shipment_details::Migration),
+            Box::new(m2026_10_18_tracking_numbers::Migration),
+            Box::new(m2026_10_18_delivery_attempts::Migration),
+            Box::new(m2026_10_18_shipment_holds::Migration),
+            Box::new(m2026_10_18_shipment_pieces::Migration),
+            Box::new(m2026_10_18_blobs::Migration),
+            Box::new(m2026_10_18_cash_on_delivery::Migration),
+            Box::new(m2026_10_18_shipment_prices::Migration),
+            Box::new(m2026_10_18_tariffs::Migration),
+            Box::new(m2026_10_18_office_links::Migration),
+            Box::new(m2026_10_18_shipment_sla::Migration),
+            Box::new(m2026_10_18_office_calendars::Migration),
         ]
     }
 
@@ -0,0 +1,110 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Amount to collect on delivery, both set or both empty
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                ADD COLUMN cod_amount_cents BIGINT CHECK (cod_amount_cents > 0),
+                ADD COLUMN cod_currency TEXT,
+                ADD CONSTRAINT shipments_cod_check
+                    CHECK ((cod_amount_cents IS NULL) = (cod_currency IS NULL));
+                "#,
+            )
+            .await?;
+
+        // Payouts of collected cash to a client
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE IF NOT EXISTS cod_remittances (
+                    id UUID PRIMARY KEY,
+                    client_id UUID NOT NULL REFERENCES clients(id),
+                    currency TEXT NOT NULL,
+                    amount_cents BIGINT NOT NULL CHECK (amount_cents > 0),
+                    shipment_count INTEGER NOT NULL CHECK (shipment_count > 0),
+                    reference TEXT,
+                    created_by UUID
+                        REFERENCES users(id) ON DELETE SET NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+                );
+                "#,
+            )
+            .await?;
+
+        // One row per collected shipment, the office holds the cash until
+        // the row is remitted
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE IF NOT EXISTS cod_collections (
+                    id UUID PRIMARY KEY,
+                    shipment_id UUID NOT NULL UNIQUE
+                        REFERENCES shipments(id) ON DELETE CASCADE,
+                    client_id UUID NOT NULL REFERENCES clients(id),
+                    office_id UUID
+                        REFERENCES offices(id) ON DELETE SET NULL,
+                    amount_cents BIGINT NOT NULL CHECK (amount_cents > 0),
+                    currency TEXT NOT NULL,
+                    collected_by UUID
+                        REFERENCES users(id) ON DELETE SET NULL,
+                    collected_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    remittance_id UUID REFERENCES cod_remittances(id),
+                    remitted_at TIMESTAMPTZ,
+                    CONSTRAINT cod_collections_remitted_check
+                        CHECK ((remittance_id IS NULL) = (remitted_at IS NULL))
+                );
+                "#,
+            )
+            .await?;
+
+        // Reports only look at cash not yet paid out
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX IF NOT EXISTS idx_cod_collections_unremitted
+                ON cod_collections(client_id, currency)
+                WHERE remittance_id IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(r#"DROP TABLE IF EXISTS cod_collections;"#)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(r#"DROP TABLE IF EXISTS cod_remittances;"#)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                DROP CONSTRAINT IF EXISTS shipments_cod_check,
+                DROP COLUMN IF EXISTS cod_currency,
+                DROP COLUMN IF EXISTS cod_amount_cents;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
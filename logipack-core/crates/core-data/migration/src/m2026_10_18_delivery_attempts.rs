@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Failed delivery attempts so far
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                ADD COLUMN delivery_attempts INTEGER NOT NULL DEFAULT 0
+                    CHECK (delivery_attempts >= 0);
+                "#,
+            )
+            .await?;
+
+        // Reason code of a change to DELIVERY_FAILED
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"ALTER TABLE shipment_status_history ADD COLUMN failure_reason TEXT;"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"ALTER TABLE shipment_status_history DROP COLUMN IF EXISTS failure_reason;"#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(r#"ALTER TABLE shipments DROP COLUMN IF EXISTS delivery_attempts;"#)
+            .await?;
+
+        Ok(())
+    }
+}
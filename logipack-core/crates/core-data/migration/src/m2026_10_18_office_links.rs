@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Directed links of the office network
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE IF NOT EXISTS office_links (
+                    from_office_id UUID NOT NULL
+                        REFERENCES offices(id) ON DELETE CASCADE,
+                    to_office_id UUID NOT NULL
+                        REFERENCES offices(id) ON DELETE CASCADE,
+                    transit_minutes INTEGER NOT NULL CHECK (transit_minutes > 0),
+                    cost_cents BIGINT NOT NULL CHECK (cost_cents >= 0),
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    PRIMARY KEY (from_office_id, to_office_id),
+                    CONSTRAINT office_links_no_self_loop
+                        CHECK (from_office_id <> to_office_id)
+                );
+                "#,
+            )
+            .await?;
+
+        // Planned route of a shipment, origin at seq 0; each stop carries
+        // the leg that reaches it as planned
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE IF NOT EXISTS shipment_route_stops (
+                    shipment_id UUID NOT NULL
+                        REFERENCES shipments(id) ON DELETE CASCADE,
+                    seq INTEGER NOT NULL CHECK (seq >= 0),
+                    office_id UUID NOT NULL
+                        REFERENCES offices(id),
+                    transit_minutes INTEGER NOT NULL CHECK (transit_minutes >= 0),
+                    cost_cents BIGINT NOT NULL CHECK (cost_cents >= 0),
+                    PRIMARY KEY (shipment_id, seq)
+                );
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TABLE IF EXISTS shipment_route_stops;
+                DROP TABLE IF EXISTS office_links;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
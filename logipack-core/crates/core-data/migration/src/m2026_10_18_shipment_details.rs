@@ -0,0 +1,132 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Origin and destination offices
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                ADD COLUMN origin_office_id UUID
+                    REFERENCES offices(id) ON DELETE SET NULL,
+                ADD COLUMN destination_office_id UUID
+                    REFERENCES offices(id) ON DELETE SET NULL;
+                "#,
+            )
+            .await?;
+
+        // Backfill the origin from the creation history row
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE shipments s
+                SET origin_office_id = h.office_id
+                FROM shipment_status_history h
+                WHERE h.shipment_id = s.id AND h.from_status IS NULL;
+                "#,
+            )
+            .await?;
+
+        // Sender and recipient contact/address blocks
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                ADD COLUMN sender_name TEXT,
+                ADD COLUMN sender_phone TEXT,
+                ADD COLUMN sender_email TEXT,
+                ADD COLUMN sender_address_line1 TEXT,
+                ADD COLUMN sender_address_line2 TEXT,
+                ADD COLUMN sender_city TEXT,
+                ADD COLUMN sender_postal_code TEXT,
+                ADD COLUMN sender_country TEXT,
+                ADD COLUMN recipient_name TEXT,
+                ADD COLUMN recipient_phone TEXT,
+                ADD COLUMN recipient_email TEXT,
+                ADD COLUMN recipient_address_line1 TEXT,
+                ADD COLUMN recipient_address_line2 TEXT,
+                ADD COLUMN recipient_city TEXT,
+                ADD COLUMN recipient_postal_code TEXT,
+                ADD COLUMN recipient_country TEXT;
+                "#,
+            )
+            .await?;
+
+        // Parcel attributes
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                ADD COLUMN weight_g INTEGER CHECK (weight_g > 0),
+                ADD COLUMN length_mm INTEGER CHECK (length_mm > 0),
+                ADD COLUMN width_mm INTEGER CHECK (width_mm > 0),
+                ADD COLUMN height_mm INTEGER CHECK (height_mm > 0),
+                ADD COLUMN declared_value_cents BIGINT CHECK (declared_value_cents >= 0),
+                ADD COLUMN description TEXT;
+                "#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX IF NOT EXISTS idx_shipments_destination_office_id
+                ON shipments (destination_office_id);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(r#"DROP INDEX IF EXISTS idx_shipments_destination_office_id;"#)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                DROP COLUMN IF EXISTS origin_office_id,
+                DROP COLUMN IF EXISTS destination_office_id,
+                DROP COLUMN IF EXISTS sender_name,
+                DROP COLUMN IF EXISTS sender_phone,
+                DROP COLUMN IF EXISTS sender_email,
+                DROP COLUMN IF EXISTS sender_address_line1,
+                DROP COLUMN IF EXISTS sender_address_line2,
+                DROP COLUMN IF EXISTS sender_city,
+                DROP COLUMN IF EXISTS sender_postal_code,
+                DROP COLUMN IF EXISTS sender_country,
+                DROP COLUMN IF EXISTS recipient_name,
+                DROP COLUMN IF EXISTS recipient_phone,
+                DROP COLUMN IF EXISTS recipient_email,
+                DROP COLUMN IF EXISTS recipient_address_line1,
+                DROP COLUMN IF EXISTS recipient_address_line2,
+                DROP COLUMN IF EXISTS recipient_city,
+                DROP COLUMN IF EXISTS recipient_postal_code,
+                DROP COLUMN IF EXISTS recipient_country,
+                DROP COLUMN IF EXISTS weight_g,
+                DROP COLUMN IF EXISTS length_mm,
+                DROP COLUMN IF EXISTS width_mm,
+                DROP COLUMN IF EXISTS height_mm,
+                DROP COLUMN IF EXISTS declared_value_cents,
+                DROP COLUMN IF EXISTS description;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Active hold, both set or both empty
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                ADD COLUMN hold_reason TEXT,
+                ADD COLUMN held_since TIMESTAMPTZ,
+                ADD CONSTRAINT shipments_hold_check
+                    CHECK ((hold_reason IS NULL) = (held_since IS NULL));
+                "#,
+            )
+            .await?;
+
+        // Few shipments are on hold at any time
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX IF NOT EXISTS idx_shipments_hold_reason
+                ON shipments(hold_reason)
+                WHERE hold_reason IS NOT NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(r#"DROP INDEX IF EXISTS idx_shipments_hold_reason;"#)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                DROP CONSTRAINT IF EXISTS shipments_hold_check,
+                DROP COLUMN IF EXISTS held_since,
+                DROP COLUMN IF EXISTS hold_reason;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
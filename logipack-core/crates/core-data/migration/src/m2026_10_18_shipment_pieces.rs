@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Boxes of a multi-piece shipment, each scanned on its own
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE IF NOT EXISTS shipment_pieces (
+                    id UUID PRIMARY KEY,
+                    shipment_id UUID NOT NULL
+                        REFERENCES shipments(id) ON DELETE CASCADE,
+                    piece_no INTEGER NOT NULL CHECK (piece_no BETWEEN 1 AND 99),
+                    barcode TEXT NOT NULL UNIQUE,
+                    status TEXT NOT NULL,
+                    current_office_id UUID
+                        REFERENCES offices(id) ON DELETE SET NULL,
+                    weight_g INTEGER CHECK (weight_g > 0),
+                    description TEXT,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    UNIQUE (shipment_id, piece_no)
+                );
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(r#"DROP TABLE IF EXISTS shipment_pieces;"#)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Service level and the price charged at creation, all price
+        // columns set or all empty
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                ADD COLUMN service_level TEXT NOT NULL DEFAULT 'STANDARD',
+                ADD COLUMN price_currency TEXT,
+                ADD COLUMN price_zone TEXT,
+                ADD COLUMN price_chargeable_weight_g INTEGER,
+                ADD COLUMN price_base_cents BIGINT,
+                ADD COLUMN price_express_fee_cents BIGINT,
+                ADD COLUMN price_cod_fee_cents BIGINT,
+                ADD COLUMN price_declared_value_fee_cents BIGINT,
+                ADD COLUMN price_tax_cents BIGINT,
+                ADD COLUMN price_total_cents BIGINT,
+                ADD CONSTRAINT shipments_price_check CHECK (
+                    num_nulls(
+                        price_currency,
+                        price_zone,
+                        price_chargeable_weight_g,
+                        price_base_cents,
+                        price_express_fee_cents,
+                        price_cod_fee_cents,
+                        price_declared_value_fee_cents,
+                        price_tax_cents,
+                        price_total_cents
+                    ) IN (0, 9)
+                );
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                DROP CONSTRAINT IF EXISTS shipments_price_check,
+                DROP COLUMN IF EXISTS price_total_cents,
+                DROP COLUMN IF EXISTS price_tax_cents,
+                DROP COLUMN IF EXISTS price_declared_value_fee_cents,
+                DROP COLUMN IF EXISTS price_cod_fee_cents,
+                DROP COLUMN IF EXISTS price_express_fee_cents,
+                DROP COLUMN IF EXISTS price_base_cents,
+                DROP COLUMN IF EXISTS price_chargeable_weight_g,
+                DROP COLUMN IF EXISTS price_zone,
+                DROP COLUMN IF EXISTS price_currency,
+                DROP COLUMN IF EXISTS service_level;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
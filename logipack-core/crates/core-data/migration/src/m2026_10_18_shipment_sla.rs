@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Delivery promised at creation and when the promise was found broken
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                ADD COLUMN promised_by TIMESTAMPTZ,
+                ADD COLUMN sla_breached_at TIMESTAMPTZ,
+                ADD CONSTRAINT shipments_sla_breach_check CHECK (
+                    sla_breached_at IS NULL OR promised_by IS NOT NULL
+                );
+
+                -- the breach check only scans promises still kept
+                CREATE INDEX shipments_promised_by_idx
+                ON shipments (promised_by)
+                WHERE sla_breached_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS shipments_promised_by_idx;
+
+                ALTER TABLE shipments
+                DROP CONSTRAINT IF EXISTS shipments_sla_breach_check,
+                DROP COLUMN IF EXISTS sla_breached_at,
+                DROP COLUMN IF EXISTS promised_by;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Tariff versions, in force from `effective_from` up to but
+        // excluding `effective_to`; no two versions overlap
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE IF NOT EXISTS tariffs (
+                    id UUID PRIMARY KEY,
+                    effective_from TIMESTAMPTZ NOT NULL,
+                    effective_to TIMESTAMPTZ,
+                    rules JSONB NOT NULL,
+                    created_by UUID
+                        REFERENCES users(id) ON DELETE SET NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    CONSTRAINT tariffs_period_check
+                        CHECK (effective_to IS NULL OR effective_to > effective_from),
+                    CONSTRAINT tariffs_no_overlap
+                        EXCLUDE USING gist (tstzrange(effective_from, effective_to) WITH &&)
+                );
+                "#,
+            )
+            .await?;
+
+        // Version that priced the shipment, empty when priced by the
+        // configured tariff
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE shipments
+                ADD COLUMN tariff_id UUID REFERENCES tariffs(id);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(r#"ALTER TABLE shipments DROP COLUMN IF EXISTS tariff_id;"#)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(r#"DROP TABLE IF EXISTS tariffs;"#)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serial part of tracking numbers, 8 digits
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE SEQUENCE IF NOT EXISTS shipment_tracking_seq
+                MINVALUE 1 MAXVALUE 99999999 NO CYCLE;
+                "#,
+            )
+            .await?;
+
+        // Existing shipments keep a NULL number: their ShipmentCreated
+        // events carry none, so a backfill would not survive a rebuild
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"ALTER TABLE shipments ADD COLUMN IF NOT EXISTS tracking_number TEXT;"#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_shipments_tracking_number_unique
+                ON shipments (tracking_number);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(r#"DROP INDEX IF EXISTS idx_shipments_tracking_number_unique;"#)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(r#"ALTER TABLE shipments DROP COLUMN IF EXISTS tracking_number;"#)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(r#"DROP SEQUENCE IF EXISTS shipment_tracking_seq;"#)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "cod_collections")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+
+    /// At most one collection per shipment.
+    pub shipment_id: Uuid,
+    pub client_id: Uuid,
+
+    /// Office holding the cash until it is remitted.
+    pub office_id: Option<Uuid>,
+
+    pub amount_cents: i64,
+    pub currency: String,
+
+    pub collected_by: Option<Uuid>,
+    pub collected_at: DateTimeWithTimeZone,
+
+    /// Both `None` until the cash is paid out to the client.
+    pub remittance_id: Option<Uuid>,
+    pub remitted_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Shipment,
+    Client,
+    Office,
+    Remittance,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Shipment => Entity::belongs_to(super::shipments::Entity)
+                .from(Column::ShipmentId)
+                .to(super::shipments::Column::Id)
+                .into(),
+            Self::Client => Entity::belongs_to(super::clients::Entity)
+                .from(Column::ClientId)
+                .to(super::clients::Column::Id)
+                .into(),
+            Self::Office => Entity::belongs_to(super::offices::Entity)
+                .from(Column::OfficeId)
+                .to(super::offices::Column::Id)
+                .into(),
+            Self::Remittance => Entity::belongs_to(super::cod_remittances::Entity)
+                .from(Column::RemittanceId)
+                .to(super::cod_remittances::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl Related<super::shipments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Shipment.def()
+    }
+}
+
+impl Related<super::cod_remittances::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Remittance.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,47 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "cod_remittances")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+
+    pub client_id: Uuid,
+    pub currency: String,
+
+    /// Sum of the remitted collections.
+    pub amount_cents: i64,
+    pub shipment_count: i32,
+
+    /// Bank transfer or cash slip reference.
+    pub reference: Option<String>,
+
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Client,
+    Collections,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Client => Entity::belongs_to(super::clients::Entity)
+                .from(Column::ClientId)
+                .to(super::clients::Column::Id)
+                .into(),
+            Self::Collections => Entity::has_many(super::cod_collections::Entity).into(),
+        }
+    }
+}
+
+impl Related<super::cod_collections::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Collections.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,9 +1,17 @@
+pub mod blobs;
 pub mod clients;
+pub mod cod_collections;
+pub mod cod_remittances;
 pub mod employee_offices;
 pub mod employees;
+pub mod office_calendars;
+pub mod office_links;
 pub mod offices;
 pub mod roles;
+pub mod shipment_pieces;
+pub mod shipment_route_stops;
 pub mod shipment_status_history;
 pub mod shipments;
+pub mod tariffs;
 pub mod user_roles;
 pub mod users;
@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "office_calendars")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub office_id: Uuid,
+
+    /// `core_domain::network::OfficeCalendar` as JSON.
+    pub calendar: Json,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Office,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Office => Entity::belongs_to(super::offices::Entity)
+                .from(Column::OfficeId)
+                .to(super::offices::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
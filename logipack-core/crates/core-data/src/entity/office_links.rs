@@ -0,0 +1,39 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "office_links")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub from_office_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub to_office_id: Uuid,
+
+    pub transit_minutes: i32,
+    pub cost_cents: i64,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    FromOffice,
+    ToOffice,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::FromOffice => Entity::belongs_to(super::offices::Entity)
+                .from(Column::FromOfficeId)
+                .to(super::offices::Column::Id)
+                .into(),
+            Self::ToOffice => Entity::belongs_to(super::offices::Entity)
+                .from(Column::ToOfficeId)
+                .to(super::offices::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
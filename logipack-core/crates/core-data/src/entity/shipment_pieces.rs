@@ -0,0 +1,52 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "shipment_pieces")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+
+    pub shipment_id: Uuid,
+
+    /// 1-based, unique within the shipment.
+    pub piece_no: i32,
+    pub barcode: String,
+
+    pub status: String,
+    pub current_office_id: Option<Uuid>,
+
+    pub weight_g: Option<i32>,
+    pub description: Option<String>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Shipment,
+    CurrentOffice,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Shipment => Entity::belongs_to(super::shipments::Entity)
+                .from(Column::ShipmentId)
+                .to(super::shipments::Column::Id)
+                .into(),
+            Self::CurrentOffice => Entity::belongs_to(super::offices::Entity)
+                .from(Column::CurrentOfficeId)
+                .to(super::offices::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl Related<super::shipments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Shipment.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
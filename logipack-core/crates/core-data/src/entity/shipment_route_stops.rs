@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "shipment_route_stops")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub shipment_id: Uuid,
+    /// 0 for the origin.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub seq: i32,
+
+    pub office_id: Uuid,
+
+    /// Leg that reaches this stop, zero for the origin.
+    pub transit_minutes: i32,
+    pub cost_cents: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Shipment,
+    Office,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Shipment => Entity::belongs_to(super::shipments::Entity)
+                .from(Column::ShipmentId)
+                .to(super::shipments::Column::Id)
+                .into(),
+            Self::Office => Entity::belongs_to(super::offices::Entity)
+                .from(Column::OfficeId)
+                .to(super::offices::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl Related<super::shipments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Shipment.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -17,6 +17,9 @@ pub struct Model {
     pub office_id: Option<Uuid>,
 
     pub notes: Option<String>,
+
+    /// Set on changes to DELIVERY_FAILED.
+    pub failure_reason: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
@@ -6,12 +6,76 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: Uuid,
 
+    /// `None` for shipments created before tracking numbers were issued.
+    pub tracking_number: Option<String>,
+
     pub client_id: Uuid,
 
     pub current_status: String,
 
     pub current_office_id: Option<Uuid>,
 
+    pub origin_office_id: Option<Uuid>,
+    pub destination_office_id: Option<Uuid>,
+
+    pub sender_name: Option<String>,
+    pub sender_phone: Option<String>,
+    pub sender_email: Option<String>,
+    pub sender_address_line1: Option<String>,
+    pub sender_address_line2: Option<String>,
+    pub sender_city: Option<String>,
+    pub sender_postal_code: Option<String>,
+    pub sender_country: Option<String>,
+
+    pub recipient_name: Option<String>,
+    pub recipient_phone: Option<String>,
+    pub recipient_email: Option<String>,
+    pub recipient_address_line1: Option<String>,
+    pub recipient_address_line2: Option<String>,
+    pub recipient_city: Option<String>,
+    pub recipient_postal_code: Option<String>,
+    pub recipient_country: Option<String>,
+
+    pub weight_g: Option<i32>,
+    pub length_mm: Option<i32>,
+    pub width_mm: Option<i32>,
+    pub height_mm: Option<i32>,
+    pub declared_value_cents: Option<i64>,
+    pub description: Option<String>,
+
+    /// Cash on delivery, both set or both `None`.
+    pub cod_amount_cents: Option<i64>,
+    pub cod_currency: Option<String>,
+
+    pub service_level: String,
+
+    /// Price charged at creation, all set or all `None`.
+    pub price_currency: Option<String>,
+    pub price_zone: Option<String>,
+    pub price_chargeable_weight_g: Option<i32>,
+    pub price_base_cents: Option<i64>,
+    pub price_express_fee_cents: Option<i64>,
+    pub price_cod_fee_cents: Option<i64>,
+    pub price_declared_value_fee_cents: Option<i64>,
+    pub price_tax_cents: Option<i64>,
+    pub price_total_cents: Option<i64>,
+    /// Tariff version that priced the shipment, `None` when it was priced
+    /// by the configured tariff.
+    pub tariff_id: Option<Uuid>,
+
+    /// Failed delivery attempts so far.
+    pub delivery_attempts: i32,
+
+    /// Reason of the active hold, `None` when not on hold.
+    pub hold_reason: Option<String>,
+    pub held_since: Option<DateTimeWithTimeZone>,
+
+    /// Delivery promised at creation, `None` when no route reached the
+    /// destination.
+    pub promised_by: Option<DateTimeWithTimeZone>,
+    /// When the shipment was found undelivered past `promised_by`.
+    pub sla_breached_at: Option<DateTimeWithTimeZone>,
+
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -20,7 +84,12 @@ pub struct Model {
 pub enum Relation {
     Client,
     CurrentOffice,
+    OriginOffice,
+    DestinationOffice,
     StatusHistory,
+    Pieces,
+    CodCollection,
+    Tariff,
 }
 
 impl RelationTrait for Relation {
@@ -34,7 +103,21 @@ impl RelationTrait for Relation {
                 .from(Column::CurrentOfficeId)
                 .to(super::offices::Column::Id)
                 .into(),
+            Self::OriginOffice => Entity::belongs_to(super::offices::Entity)
+                .from(Column::OriginOfficeId)
+                .to(super::offices::Column::Id)
+                .into(),
+            Self::DestinationOffice => Entity::belongs_to(super::offices::Entity)
+                .from(Column::DestinationOfficeId)
+                .to(super::offices::Column::Id)
+                .into(),
             Self::StatusHistory => Entity::has_many(super::shipment_status_history::Entity).into(),
+            Self::Pieces => Entity::has_many(super::shipment_pieces::Entity).into(),
+            Self::CodCollection => Entity::has_one(super::cod_collections::Entity).into(),
+            Self::Tariff => Entity::belongs_to(super::tariffs::Entity)
+                .from(Column::TariffId)
+                .to(super::tariffs::Column::Id)
+                .into(),
         }
     }
 }
@@ -51,4 +134,10 @@ impl Related<super::offices::Entity> for Entity {
     }
 }
 
+impl Related<super::tariffs::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tariff.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,41 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tariffs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+
+    /// In force from this instant up to but excluding `effective_to`.
+    pub effective_from: DateTimeWithTimeZone,
+    /// `None` while the version has no successor.
+    pub effective_to: Option<DateTimeWithTimeZone>,
+
+    /// `core_domain::pricing::Tariff` as JSON.
+    pub rules: Json,
+
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Shipments,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Shipments => Entity::has_many(super::shipments::Entity).into(),
+        }
+    }
+}
+
+impl Related<super::shipments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Shipments.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
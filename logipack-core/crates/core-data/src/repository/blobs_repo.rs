@@ -0,0 +1,55 @@
+use core_domain::shipment::BlobHash;
+use sea_orm::ActiveValue::Set;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ConnectionTrait, DbErr, EntityTrait};
+
+use crate::entity::blobs;
+
+/// Content-addressed blob storage.
+///
+/// Rows are keyed by the BLAKE3 hash of their bytes, so storing the same
+/// content twice is a no-op and a row can never change under its key.
+pub struct BlobsRepo;
+
+impl BlobsRepo {
+    /// Stores `bytes` and returns their hash. Existing content is kept.
+    pub async fn put<C: ConnectionTrait>(
+        db: &C,
+        content_type: &str,
+        bytes: Vec<u8>,
+    ) -> Result<BlobHash, DbErr> {
+        let hash = BlobHash::of(&bytes);
+
+        let model = blobs::ActiveModel {
+            hash: Set(hash.as_bytes().to_vec()),
+            content_type: Set(content_type.to_owned()),
+            size_bytes: Set(bytes
+                .len()
+                .try_into()
+                .map_err(|_| DbErr::Custom("blob too large".into()))?),
+            bytes: Set(bytes),
+            created_at: Set(chrono::Utc::now().into()),
+        };
+
+        blobs::Entity::insert(model)
+            .on_conflict(
+                OnConflict::column(blobs::Column::Hash)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec(db)
+            .await?;
+
+        Ok(hash)
+    }
+
+    pub async fn get<C: ConnectionTrait>(
+        db: &C,
+        hash: &BlobHash,
+    ) -> Result<Option<blobs::Model>, DbErr> {
+        blobs::Entity::find_by_id(hash.as_bytes().to_vec())
+            .one(db)
+            .await
+    }
+}
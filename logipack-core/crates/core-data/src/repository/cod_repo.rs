@@ -0,0 +1,173 @@
+use sea_orm::ActiveValue::Set;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
+use uuid::Uuid;
+
+use crate::entity::{cod_collections, cod_remittances};
+use crate::repository::shipments_repo::ShipmentSnapshotError;
+
+/// Collection row as written at delivery or rebuilt from the event store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodCollectionRow {
+    pub id: Uuid,
+    pub client_id: Uuid,
+    pub office_id: Option<Uuid>,
+    pub amount_cents: i64,
+    pub currency: String,
+    pub collected_by: Option<Uuid>,
+    pub collected_at: DateTimeWithTimeZone,
+    pub remittance_id: Option<Uuid>,
+    pub remitted_at: Option<DateTimeWithTimeZone>,
+}
+
+impl CodCollectionRow {
+    fn into_active_model(self, shipment_id: Uuid) -> cod_collections::ActiveModel {
+        cod_collections::ActiveModel {
+            id: Set(self.id),
+            shipment_id: Set(shipment_id),
+            client_id: Set(self.client_id),
+            office_id: Set(self.office_id),
+            amount_cents: Set(self.amount_cents),
+            currency: Set(self.currency),
+            collected_by: Set(self.collected_by),
+            collected_at: Set(self.collected_at),
+            remittance_id: Set(self.remittance_id),
+            remitted_at: Set(self.remitted_at),
+        }
+    }
+}
+
+/// Header of a payout to a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemittanceRow {
+    pub id: Uuid,
+    pub client_id: Uuid,
+    pub currency: String,
+    pub amount_cents: i64,
+    pub shipment_count: i32,
+    pub reference: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+/// Cash on delivery ledger: what was collected where, and what has been
+/// paid out to clients.
+///
+/// Collections are part of the shipment projection and share its error
+/// type. Like `ShipmentsRepo`, every method accepts any `ConnectionTrait`.
+pub struct CodRepo;
+
+impl CodRepo {
+    pub async fn insert_collection<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        row: CodCollectionRow,
+    ) -> Result<cod_collections::Model, ShipmentSnapshotError> {
+        Ok(row.into_active_model(shipment_id).insert(db).await?)
+    }
+
+    pub async fn find_by_shipment<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+    ) -> Result<Option<cod_collections::Model>, ShipmentSnapshotError> {
+        let row = cod_collections::Entity::find()
+            .filter(cod_collections::Column::ShipmentId.eq(shipment_id))
+            .one(db)
+            .await?;
+
+        Ok(row)
+    }
+
+    /// Collections of a client not paid out yet, oldest first.
+    /// `office_id` narrows them to the cash held by one office.
+    pub async fn list_unremitted<C: ConnectionTrait>(
+        db: &C,
+        client_id: Uuid,
+        currency: &str,
+        office_id: Option<Uuid>,
+    ) -> Result<Vec<cod_collections::Model>, ShipmentSnapshotError> {
+        let mut query = cod_collections::Entity::find()
+            .filter(cod_collections::Column::ClientId.eq(client_id))
+            .filter(cod_collections::Column::Currency.eq(currency))
+            .filter(cod_collections::Column::RemittanceId.is_null());
+
+        if let Some(office_id) = office_id {
+            query = query.filter(cod_collections::Column::OfficeId.eq(office_id));
+        }
+
+        let rows = query
+            .order_by_asc(cod_collections::Column::CollectedAt)
+            .order_by_asc(cod_collections::Column::Id)
+            .all(db)
+            .await?;
+
+        Ok(rows)
+    }
+
+    /// Records a remittance and marks `collection_ids` as paid out by it.
+    ///
+    /// Fails with `DbErr::RecordNotUpdated` if any of them was remitted in
+    /// the meantime, so a collection is never paid out twice.
+    pub async fn insert_remittance<C: ConnectionTrait>(
+        db: &C,
+        row: RemittanceRow,
+        collection_ids: Vec<Uuid>,
+    ) -> Result<cod_remittances::Model, ShipmentSnapshotError> {
+        let remittance = cod_remittances::ActiveModel {
+            id: Set(row.id),
+            client_id: Set(row.client_id),
+            currency: Set(row.currency),
+            amount_cents: Set(row.amount_cents),
+            shipment_count: Set(row.shipment_count),
+            reference: Set(row.reference),
+            created_by: Set(row.created_by),
+            created_at: Set(row.created_at),
+        }
+        .insert(db)
+        .await?;
+
+        let expected = collection_ids.len() as u64;
+
+        let updated = cod_collections::Entity::update_many()
+            .col_expr(
+                cod_collections::Column::RemittanceId,
+                Expr::value(remittance.id),
+            )
+            .col_expr(
+                cod_collections::Column::RemittedAt,
+                Expr::value(remittance.created_at),
+            )
+            .filter(cod_collections::Column::Id.is_in(collection_ids))
+            .filter(cod_collections::Column::RemittanceId.is_null())
+            .exec(db)
+            .await?;
+
+        if updated.rows_affected != expected {
+            return Err(DbErr::RecordNotUpdated.into());
+        }
+
+        Ok(remittance)
+    }
+
+    /// Replace the collection of a shipment. Used by the projection
+    /// rebuilder; the remittances it points to are kept as they are.
+    pub async fn replace_for_shipment<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        row: Option<CodCollectionRow>,
+    ) -> Result<(), ShipmentSnapshotError> {
+        cod_collections::Entity::delete_many()
+            .filter(cod_collections::Column::ShipmentId.eq(shipment_id))
+            .exec(db)
+            .await?;
+
+        if let Some(row) = row {
+            row.into_active_model(shipment_id).insert(db).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,15 @@
+pub mod blobs_repo;
 pub mod clients_repo;
+pub mod cod_repo;
 pub mod employee_offices_repo;
 pub mod employees_repo;
+pub mod office_calendars_repo;
+pub mod office_links_repo;
 pub mod offices_repo;
+pub mod reports_repo;
+pub mod shipment_pieces_repo;
+pub mod shipment_query;
+pub mod shipment_routes_repo;
 pub mod shipments_repo;
+pub mod tariffs_repo;
 pub mod users_repo;
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use sea_orm::ActiveValue::Set;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QuerySelect};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entity::{office_calendars, offices};
+use core_domain::network::OfficeCalendar;
+
+#[derive(Debug, Error)]
+pub enum OfficeCalendarRepoError {
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+    /// Stored calendar no longer deserializes as an `OfficeCalendar`.
+    #[error("invalid office calendar: {0}")]
+    InvalidCalendar(#[from] serde_json::Error),
+    #[error("office not found")]
+    OfficeNotFound,
+}
+
+/// Office calendar with its JSON deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredCalendar {
+    pub office_id: Uuid,
+    pub calendar: OfficeCalendar,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl StoredCalendar {
+    pub fn from_model(row: office_calendars::Model) -> Result<Self, OfficeCalendarRepoError> {
+        Ok(Self {
+            office_id: row.office_id,
+            calendar: serde_json::from_value(row.calendar)?,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+/// Working calendars by office. Every method accepts any `ConnectionTrait`,
+/// so estimates can read calendars inside the caller's transaction.
+pub struct OfficeCalendarsRepo;
+
+impl OfficeCalendarsRepo {
+    /// Creates or replaces the calendar of an office that exists and is
+    /// not soft-deleted.
+    pub async fn upsert<C: ConnectionTrait>(
+        db: &C,
+        office_id: Uuid,
+        calendar: &OfficeCalendar,
+    ) -> Result<StoredCalendar, OfficeCalendarRepoError> {
+        let live = offices::Entity::find_by_id(office_id)
+            .filter(offices::Column::DeletedAt.is_null())
+            .one(db)
+            .await?;
+
+        if live.is_none() {
+            return Err(OfficeCalendarRepoError::OfficeNotFound);
+        }
+
+        let now = chrono::Utc::now();
+        let model = office_calendars::ActiveModel {
+            office_id: Set(office_id),
+            calendar: Set(serde_json::to_value(calendar)?),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        };
+
+        let row = office_calendars::Entity::insert(model)
+            .on_conflict(
+                OnConflict::column(office_calendars::Column::OfficeId)
+                    .update_columns([
+                        office_calendars::Column::Calendar,
+                        office_calendars::Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_with_returning(db)
+            .await?;
+
+        StoredCalendar::from_model(row)
+    }
+
+    /// Returns false when the office had no calendar.
+    pub async fn delete<C: ConnectionTrait>(
+        db: &C,
+        office_id: Uuid,
+    ) -> Result<bool, OfficeCalendarRepoError> {
+        let result = office_calendars::Entity::delete_by_id(office_id)
+            .exec(db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    pub async fn get<C: ConnectionTrait>(
+        db: &C,
+        office_id: Uuid,
+    ) -> Result<Option<StoredCalendar>, OfficeCalendarRepoError> {
+        office_calendars::Entity::find_by_id(office_id)
+            .one(db)
+            .await?
+            .map(StoredCalendar::from_model)
+            .transpose()
+    }
+
+    /// Like `get`, locking the row until the caller's transaction ends so
+    /// read-modify-write edits do not overwrite each other.
+    pub async fn get_for_update<C: ConnectionTrait>(
+        db: &C,
+        office_id: Uuid,
+    ) -> Result<Option<StoredCalendar>, OfficeCalendarRepoError> {
+        office_calendars::Entity::find_by_id(office_id)
+            .lock_exclusive()
+            .one(db)
+            .await?
+            .map(StoredCalendar::from_model)
+            .transpose()
+    }
+
+    /// Calendars of the given offices; offices without one are left out.
+    pub async fn for_offices<C: ConnectionTrait>(
+        db: &C,
+        office_ids: impl IntoIterator<Item = Uuid>,
+    ) -> Result<HashMap<Uuid, OfficeCalendar>, OfficeCalendarRepoError> {
+        office_calendars::Entity::find()
+            .filter(office_calendars::Column::OfficeId.is_in(office_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|row| Ok((row.office_id, serde_json::from_value(row.calendar)?)))
+            .collect()
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+
+use sea_orm::ActiveValue::Set;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entity::{office_links, offices};
+use core_domain::network::{OfficeLink, OfficeNetwork};
+
+#[derive(Debug, Error)]
+pub enum OfficeLinkRepoError {
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+    #[error("office not found")]
+    OfficeNotFound,
+}
+
+/// Links of the office network. Every method accepts any `ConnectionTrait`,
+/// so route planning can read the network inside the caller's transaction.
+pub struct OfficeLinksRepo;
+
+impl OfficeLinksRepo {
+    /// Creates the link or replaces its transit time and cost. Both offices
+    /// must exist and not be soft-deleted.
+    pub async fn upsert<C: ConnectionTrait>(
+        db: &C,
+        link: OfficeLink<Uuid>,
+    ) -> Result<office_links::Model, OfficeLinkRepoError> {
+        let live = offices::Entity::find()
+            .filter(offices::Column::Id.is_in([link.from, link.to]))
+            .filter(offices::Column::DeletedAt.is_null())
+            .all(db)
+            .await?;
+
+        if live.len() != 2 {
+            return Err(OfficeLinkRepoError::OfficeNotFound);
+        }
+
+        let now = chrono::Utc::now();
+        let model = office_links::ActiveModel {
+            from_office_id: Set(link.from),
+            to_office_id: Set(link.to),
+            transit_minutes: Set(link.transit_minutes),
+            cost_cents: Set(link.cost_cents),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        };
+
+        let row = office_links::Entity::insert(model)
+            .on_conflict(
+                OnConflict::columns([
+                    office_links::Column::FromOfficeId,
+                    office_links::Column::ToOfficeId,
+                ])
+                .update_columns([
+                    office_links::Column::TransitMinutes,
+                    office_links::Column::CostCents,
+                    office_links::Column::UpdatedAt,
+                ])
+                .to_owned(),
+            )
+            .exec_with_returning(db)
+            .await?;
+
+        Ok(row)
+    }
+
+    /// Returns false when there was no such link.
+    pub async fn delete<C: ConnectionTrait>(
+        db: &C,
+        from_office_id: Uuid,
+        to_office_id: Uuid,
+    ) -> Result<bool, OfficeLinkRepoError> {
+        let result = office_links::Entity::delete_by_id((from_office_id, to_office_id))
+            .exec(db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Every link, by origin and then target office.
+    pub async fn list<C: ConnectionTrait>(
+        db: &C,
+    ) -> Result<Vec<office_links::Model>, OfficeLinkRepoError> {
+        let rows = office_links::Entity::find()
+            .order_by_asc(office_links::Column::FromOfficeId)
+            .order_by_asc(office_links::Column::ToOfficeId)
+            .all(db)
+            .await?;
+
+        Ok(rows)
+    }
+
+    /// Network of the links between offices that are not soft-deleted.
+    pub async fn network<C: ConnectionTrait>(
+        db: &C,
+    ) -> Result<OfficeNetwork<Uuid>, OfficeLinkRepoError> {
+        let live: HashSet<Uuid> = offices::Entity::find()
+            .filter(offices::Column::DeletedAt.is_null())
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|office| office.id)
+            .collect();
+
+        let links = Self::list(db)
+            .await?
+            .into_iter()
+            .filter(|row| live.contains(&row.from_office_id) && live.contains(&row.to_office_id))
+            .map(|row| OfficeLink {
+                from: row.from_office_id,
+                to: row.to_office_id,
+                transit_minutes: row.transit_minutes,
+                cost_cents: row.cost_cents,
+            });
+
+        Ok(OfficeNetwork::new(links))
+    }
+}
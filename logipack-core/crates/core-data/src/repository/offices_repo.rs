@@ -56,6 +56,19 @@ impl OfficesRepo {
         Ok(retrieved)
     }
 
+    /// Gets offices by ids, soft-deleted ones included since history
+    /// and events keep referring to them.
+    pub async fn get_offices_by_ids(
+        db: &DatabaseConnection,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<offices::Model>, OfficeError> {
+        let retrieved = offices::Entity::find()
+            .filter(offices::Column::Id.is_in(ids))
+            .all(db)
+            .await?;
+        Ok(retrieved)
+    }
+
     /// Lists all offices
     pub async fn list_offices(db: &DatabaseConnection) -> Result<Vec<offices::Model>, OfficeError> {
         // Only return offices that are not soft-deleted
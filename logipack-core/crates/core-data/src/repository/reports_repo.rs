@@ -0,0 +1,561 @@
+use sea_orm::prelude::{DateTime, DateTimeWithTimeZone, Expr};
+use sea_orm::sea_query::SimpleExpr;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, Select,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entity::{clients, cod_collections, offices, shipments};
+use core_domain::shipment::ShipmentStatus;
+
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+}
+
+/// Filters shared by every shipments report.
+///
+/// The period is half-open on `shipments.created_at`: `from` is inclusive,
+/// `to` is exclusive, so consecutive periods never count a shipment twice.
+#[derive(Debug, Clone, Default)]
+pub struct ReportFilter {
+    pub from: Option<DateTimeWithTimeZone>,
+    pub to: Option<DateTimeWithTimeZone>,
+    /// Only shipments currently at one of these offices. `None` means any
+    /// office, including shipments without one; an empty list matches nothing.
+    pub office_ids: Option<Vec<Uuid>>,
+    pub client_id: Option<Uuid>,
+    pub status: Option<ShipmentStatus>,
+    /// `Some(true)` counts only shipments on hold, `Some(false)` the others.
+    pub on_hold: Option<bool>,
+}
+
+/// Bucket size for the by-period report. Buckets start at UTC midnight;
+/// weeks start on Monday.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PeriodBucket {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl PeriodBucket {
+    fn as_sql(self) -> &'static str {
+        match self {
+            PeriodBucket::Day => "day",
+            PeriodBucket::Week => "week",
+            PeriodBucket::Month => "month",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusCount {
+    pub status: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfficeCount {
+    /// `None` groups shipments without a current office; sorted last.
+    pub office_id: Option<Uuid>,
+    pub office_name: Option<String>,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCount {
+    pub client_id: Uuid,
+    pub client_name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoldCount {
+    pub reason: String,
+    pub count: i64,
+    /// When the longest running hold with this reason was placed.
+    pub oldest_since: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodCount {
+    /// Start of the bucket, UTC.
+    pub period_start: DateTime,
+    pub count: i64,
+}
+
+/// Shipment found undelivered past its promised delivery.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlaBreach {
+    pub shipment_id: Uuid,
+    pub tracking_number: Option<String>,
+    pub client_id: Uuid,
+    pub current_status: String,
+    pub current_office_id: Option<Uuid>,
+    pub promised_by: DateTimeWithTimeZone,
+    pub breached_at: DateTimeWithTimeZone,
+}
+
+/// Cash on delivery a client is still owed, per office and currency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodOutstanding {
+    pub client_id: Uuid,
+    pub client_name: String,
+    /// `None` groups shipments without a current office; sorted last.
+    pub office_id: Option<Uuid>,
+    pub office_name: Option<String>,
+    pub currency: String,
+    /// Not delivered yet, cash still with the recipient.
+    pub uncollected_count: i64,
+    pub uncollected_cents: i64,
+    /// Collected, not paid out to the client yet.
+    pub unremitted_count: i64,
+    pub unremitted_cents: i64,
+}
+
+/// Cash collected by an office, per currency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodBalance {
+    /// `None` groups collections of deleted offices; sorted last.
+    pub office_id: Option<Uuid>,
+    pub office_name: Option<String>,
+    pub currency: String,
+    pub collected_cents: i64,
+    pub remitted_cents: i64,
+    /// Cash the office holds: collected and not remitted.
+    pub balance_cents: i64,
+    /// Collections making up the balance.
+    pub held_count: i64,
+}
+
+/// Aggregates over the `shipments` projection.
+///
+/// Rows are ordered by their grouping key (status, office name, client
+/// name, period start), with ids as tie-breakers, so repeated calls return
+/// the same order.
+pub struct ReportsRepo;
+
+impl ReportsRepo {
+    pub async fn shipments_by_status<C: ConnectionTrait>(
+        db: &C,
+        filter: &ReportFilter,
+    ) -> Result<Vec<StatusCount>, ReportError> {
+        let rows: Vec<(String, i64)> = filtered(filter)
+            .select_only()
+            .column(shipments::Column::CurrentStatus)
+            .column_as(shipments::Column::Id.count(), "count")
+            .group_by(shipments::Column::CurrentStatus)
+            .order_by_asc(shipments::Column::CurrentStatus)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(status, count)| StatusCount { status, count })
+            .collect())
+    }
+
+    pub async fn shipments_by_office<C: ConnectionTrait>(
+        db: &C,
+        filter: &ReportFilter,
+    ) -> Result<Vec<OfficeCount>, ReportError> {
+        let rows: Vec<(Option<Uuid>, Option<String>, i64)> = filtered(filter)
+            .select_only()
+            .column(shipments::Column::CurrentOfficeId)
+            .column_as(offices::Column::Name, "office_name")
+            .column_as(shipments::Column::Id.count(), "count")
+            .join(JoinType::LeftJoin, shipments::Relation::CurrentOffice.def())
+            .group_by(shipments::Column::CurrentOfficeId)
+            .group_by(offices::Column::Name)
+            .order_by_asc(offices::Column::Name)
+            .order_by_asc(shipments::Column::CurrentOfficeId)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(office_id, office_name, count)| OfficeCount {
+                office_id,
+                office_name,
+                count,
+            })
+            .collect())
+    }
+
+    pub async fn shipments_by_client<C: ConnectionTrait>(
+        db: &C,
+        filter: &ReportFilter,
+    ) -> Result<Vec<ClientCount>, ReportError> {
+        let rows: Vec<(Uuid, String, i64)> = filtered(filter)
+            .select_only()
+            .column(shipments::Column::ClientId)
+            .column_as(clients::Column::Name, "client_name")
+            .column_as(shipments::Column::Id.count(), "count")
+            .join(JoinType::InnerJoin, shipments::Relation::Client.def())
+            .group_by(shipments::Column::ClientId)
+            .group_by(clients::Column::Name)
+            .order_by_asc(clients::Column::Name)
+            .order_by_asc(shipments::Column::ClientId)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(client_id, client_name, count)| ClientCount {
+                client_id,
+                client_name,
+                count,
+            })
+            .collect())
+    }
+
+    /// Shipments currently on hold, per reason. Ignores `filter.on_hold`.
+    pub async fn shipments_on_hold<C: ConnectionTrait>(
+        db: &C,
+        filter: &ReportFilter,
+    ) -> Result<Vec<HoldCount>, ReportError> {
+        let rows: Vec<(String, i64, DateTimeWithTimeZone)> = filtered(filter)
+            .filter(shipments::Column::HoldReason.is_not_null())
+            .select_only()
+            .column(shipments::Column::HoldReason)
+            .column_as(shipments::Column::Id.count(), "count")
+            .column_as(shipments::Column::HeldSince.min(), "oldest_since")
+            .group_by(shipments::Column::HoldReason)
+            .order_by_asc(shipments::Column::HoldReason)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(reason, count, oldest_since)| HoldCount {
+                reason,
+                count,
+                oldest_since,
+            })
+            .collect())
+    }
+
+    /// Shipments that broke their delivery promise, longest overdue first.
+    /// Unlike the other reports this lists shipments instead of counting them.
+    pub async fn sla_breaches<C: ConnectionTrait>(
+        db: &C,
+        filter: &ReportFilter,
+    ) -> Result<Vec<SlaBreach>, ReportError> {
+        type Row = (
+            Uuid,
+            Option<String>,
+            Uuid,
+            String,
+            Option<Uuid>,
+            DateTimeWithTimeZone,
+            DateTimeWithTimeZone,
+        );
+
+        let rows: Vec<Row> = filtered(filter)
+            .filter(shipments::Column::SlaBreachedAt.is_not_null())
+            .select_only()
+            .column(shipments::Column::Id)
+            .column(shipments::Column::TrackingNumber)
+            .column(shipments::Column::ClientId)
+            .column(shipments::Column::CurrentStatus)
+            .column(shipments::Column::CurrentOfficeId)
+            .column(shipments::Column::PromisedBy)
+            .column(shipments::Column::SlaBreachedAt)
+            .order_by_asc(shipments::Column::PromisedBy)
+            .order_by_asc(shipments::Column::Id)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    shipment_id,
+                    tracking_number,
+                    client_id,
+                    current_status,
+                    current_office_id,
+                    promised_by,
+                    breached_at,
+                )| SlaBreach {
+                    shipment_id,
+                    tracking_number,
+                    client_id,
+                    current_status,
+                    current_office_id,
+                    promised_by,
+                    breached_at,
+                },
+            )
+            .collect())
+    }
+
+    /// Cash on delivery not yet paid out to clients: shipments still to be
+    /// delivered and collections still to be remitted. Cancelled and
+    /// returned shipments owe nothing. Offices are the shipments' current
+    /// offices.
+    pub async fn cod_outstanding<C: ConnectionTrait>(
+        db: &C,
+        filter: &ReportFilter,
+    ) -> Result<Vec<CodOutstanding>, ReportError> {
+        type Row = (
+            Uuid,
+            String,
+            Option<Uuid>,
+            Option<String>,
+            String,
+            i64,
+            i64,
+            i64,
+            i64,
+        );
+
+        let settled = [
+            ShipmentStatus::Cancelled.to_string(),
+            ShipmentStatus::Returned.to_string(),
+        ];
+
+        let rows: Vec<Row> = filtered(filter)
+            .filter(shipments::Column::CodAmountCents.is_not_null())
+            .join(JoinType::LeftJoin, shipments::Relation::CodCollection.def())
+            .filter(
+                Condition::any()
+                    .add(
+                        cod_collections::Column::Id
+                            .is_null()
+                            .and(shipments::Column::CurrentStatus.is_not_in(settled)),
+                    )
+                    .add(
+                        cod_collections::Column::Id
+                            .is_not_null()
+                            .and(cod_collections::Column::RemittanceId.is_null()),
+                    ),
+            )
+            .join(JoinType::InnerJoin, shipments::Relation::Client.def())
+            .join(JoinType::LeftJoin, shipments::Relation::CurrentOffice.def())
+            .select_only()
+            .column(shipments::Column::ClientId)
+            .column_as(clients::Column::Name, "client_name")
+            .column(shipments::Column::CurrentOfficeId)
+            .column_as(offices::Column::Name, "office_name")
+            .column(shipments::Column::CodCurrency)
+            .column_as(
+                Expr::cust(r#"COUNT(*) FILTER (WHERE "cod_collections"."id" IS NULL)"#),
+                "uncollected_count",
+            )
+            .column_as(
+                Expr::cust(
+                    r#"COALESCE(SUM("shipments"."cod_amount_cents") FILTER (WHERE "cod_collections"."id" IS NULL), 0)::BIGINT"#,
+                ),
+                "uncollected_cents",
+            )
+            .column_as(cod_collections::Column::Id.count(), "unremitted_count")
+            .column_as(
+                Expr::cust(r#"COALESCE(SUM("cod_collections"."amount_cents"), 0)::BIGINT"#),
+                "unremitted_cents",
+            )
+            .group_by(shipments::Column::ClientId)
+            .group_by(clients::Column::Name)
+            .group_by(shipments::Column::CurrentOfficeId)
+            .group_by(offices::Column::Name)
+            .group_by(shipments::Column::CodCurrency)
+            .order_by_asc(clients::Column::Name)
+            .order_by_asc(shipments::Column::ClientId)
+            .order_by_asc(offices::Column::Name)
+            .order_by_asc(shipments::Column::CurrentOfficeId)
+            .order_by_asc(shipments::Column::CodCurrency)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    client_id,
+                    client_name,
+                    office_id,
+                    office_name,
+                    currency,
+                    uncollected_count,
+                    uncollected_cents,
+                    unremitted_count,
+                    unremitted_cents,
+                )| CodOutstanding {
+                    client_id,
+                    client_name,
+                    office_id,
+                    office_name,
+                    currency,
+                    uncollected_count,
+                    uncollected_cents,
+                    unremitted_count,
+                    unremitted_cents,
+                },
+            )
+            .collect())
+    }
+
+    /// Cash collected per office and currency.
+    ///
+    /// Unlike the shipment reports, the period applies to `collected_at`
+    /// and offices to the collecting office. `status` and `on_hold` are
+    /// ignored.
+    pub async fn cod_balances<C: ConnectionTrait>(
+        db: &C,
+        filter: &ReportFilter,
+    ) -> Result<Vec<CodBalance>, ReportError> {
+        type Row = (Option<Uuid>, Option<String>, String, i64, i64, i64, i64);
+
+        let mut query = cod_collections::Entity::find();
+
+        if let Some(from) = filter.from {
+            query = query.filter(cod_collections::Column::CollectedAt.gte(from));
+        }
+
+        if let Some(to) = filter.to {
+            query = query.filter(cod_collections::Column::CollectedAt.lt(to));
+        }
+
+        if let Some(office_ids) = &filter.office_ids {
+            query = query.filter(cod_collections::Column::OfficeId.is_in(office_ids.clone()));
+        }
+
+        if let Some(client_id) = filter.client_id {
+            query = query.filter(cod_collections::Column::ClientId.eq(client_id));
+        }
+
+        let rows: Vec<Row> = query
+            .join(JoinType::LeftJoin, cod_collections::Relation::Office.def())
+            .select_only()
+            .column(cod_collections::Column::OfficeId)
+            .column_as(offices::Column::Name, "office_name")
+            .column(cod_collections::Column::Currency)
+            .column_as(
+                Expr::cust(r#"SUM("cod_collections"."amount_cents")::BIGINT"#),
+                "collected_cents",
+            )
+            .column_as(
+                Expr::cust(
+                    r#"COALESCE(SUM("cod_collections"."amount_cents") FILTER (WHERE "cod_collections"."remittance_id" IS NOT NULL), 0)::BIGINT"#,
+                ),
+                "remitted_cents",
+            )
+            .column_as(
+                Expr::cust(
+                    r#"COALESCE(SUM("cod_collections"."amount_cents") FILTER (WHERE "cod_collections"."remittance_id" IS NULL), 0)::BIGINT"#,
+                ),
+                "balance_cents",
+            )
+            .column_as(
+                Expr::cust(r#"COUNT(*) FILTER (WHERE "cod_collections"."remittance_id" IS NULL)"#),
+                "held_count",
+            )
+            .group_by(cod_collections::Column::OfficeId)
+            .group_by(offices::Column::Name)
+            .group_by(cod_collections::Column::Currency)
+            .order_by_asc(offices::Column::Name)
+            .order_by_asc(cod_collections::Column::OfficeId)
+            .order_by_asc(cod_collections::Column::Currency)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    office_id,
+                    office_name,
+                    currency,
+                    collected_cents,
+                    remitted_cents,
+                    balance_cents,
+                    held_count,
+                )| CodBalance {
+                    office_id,
+                    office_name,
+                    currency,
+                    collected_cents,
+                    remitted_cents,
+                    balance_cents,
+                    held_count,
+                },
+            )
+            .collect())
+    }
+
+    pub async fn shipments_by_period<C: ConnectionTrait>(
+        db: &C,
+        filter: &ReportFilter,
+        bucket: PeriodBucket,
+    ) -> Result<Vec<PeriodCount>, ReportError> {
+        let rows: Vec<(DateTime, i64)> = filtered(filter)
+            .select_only()
+            .column_as(period_start(bucket), "period_start")
+            .column_as(shipments::Column::Id.count(), "count")
+            .group_by(period_start(bucket))
+            .order_by_asc(period_start(bucket))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(period_start, count)| PeriodCount {
+                period_start,
+                count,
+            })
+            .collect())
+    }
+}
+
+fn filtered(filter: &ReportFilter) -> Select<shipments::Entity> {
+    let mut query = shipments::Entity::find();
+
+    if let Some(from) = filter.from {
+        query = query.filter(shipments::Column::CreatedAt.gte(from));
+    }
+
+    if let Some(to) = filter.to {
+        query = query.filter(shipments::Column::CreatedAt.lt(to));
+    }
+
+    if let Some(office_ids) = &filter.office_ids {
+        query = query.filter(shipments::Column::CurrentOfficeId.is_in(office_ids.clone()));
+    }
+
+    if let Some(client_id) = filter.client_id {
+        query = query.filter(shipments::Column::ClientId.eq(client_id));
+    }
+
+    if let Some(status) = filter.status {
+        query = query.filter(shipments::Column::CurrentStatus.eq(status.to_string()));
+    }
+
+    match filter.on_hold {
+        Some(true) => query = query.filter(shipments::Column::HoldReason.is_not_null()),
+        Some(false) => query = query.filter(shipments::Column::HoldReason.is_null()),
+        None => {}
+    }
+
+    query
+}
+
+fn period_start(bucket: PeriodBucket) -> SimpleExpr {
+    // bucket is a closed set, safe to inline
+    Expr::cust(format!(
+        "date_trunc('{}', \"shipments\".\"created_at\" AT TIME ZONE 'UTC')",
+        bucket.as_sql()
+    ))
+}
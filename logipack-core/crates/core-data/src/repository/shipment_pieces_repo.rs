@@ -0,0 +1,121 @@
+use sea_orm::ActiveValue::Set;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
+use uuid::Uuid;
+
+use crate::entity::shipment_pieces;
+use crate::repository::shipments_repo::ShipmentSnapshotError;
+use core_domain::shipment::ShipmentStatus;
+
+/// Piece row as written by `add_piece` or rebuilt from the event store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceRow {
+    pub id: Uuid,
+    pub piece_no: i32,
+    pub barcode: String,
+    pub status: ShipmentStatus,
+    pub current_office_id: Option<Uuid>,
+    pub weight_g: Option<i32>,
+    pub description: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl PieceRow {
+    fn into_active_model(self, shipment_id: Uuid) -> shipment_pieces::ActiveModel {
+        shipment_pieces::ActiveModel {
+            id: Set(self.id),
+            shipment_id: Set(shipment_id),
+            piece_no: Set(self.piece_no),
+            barcode: Set(self.barcode),
+            status: Set(self.status.to_string()),
+            current_office_id: Set(self.current_office_id),
+            weight_g: Set(self.weight_g),
+            description: Set(self.description),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+        }
+    }
+}
+
+/// Pieces of multi-piece shipments, part of the shipment projection.
+///
+/// Like `ShipmentsRepo`, every method accepts any `ConnectionTrait` so the
+/// writes can share the caller's transaction.
+pub struct ShipmentPiecesRepo;
+
+impl ShipmentPiecesRepo {
+    pub async fn insert<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        row: PieceRow,
+    ) -> Result<shipment_pieces::Model, ShipmentSnapshotError> {
+        Ok(row.into_active_model(shipment_id).insert(db).await?)
+    }
+
+    /// Pieces of a shipment by piece number.
+    pub async fn list_by_shipment<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+    ) -> Result<Vec<shipment_pieces::Model>, ShipmentSnapshotError> {
+        let rows = shipment_pieces::Entity::find()
+            .filter(shipment_pieces::Column::ShipmentId.eq(shipment_id))
+            .order_by_asc(shipment_pieces::Column::PieceNo)
+            .all(db)
+            .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_by_barcode<C: ConnectionTrait>(
+        db: &C,
+        barcode: &str,
+    ) -> Result<Option<shipment_pieces::Model>, ShipmentSnapshotError> {
+        let row = shipment_pieces::Entity::find()
+            .filter(shipment_pieces::Column::Barcode.eq(barcode))
+            .one(db)
+            .await?;
+
+        Ok(row)
+    }
+
+    pub async fn update_status<C: ConnectionTrait>(
+        db: &C,
+        piece_id: Uuid,
+        status: ShipmentStatus,
+        office_id: Option<Uuid>,
+    ) -> Result<(), ShipmentSnapshotError> {
+        let mut model: shipment_pieces::ActiveModel = shipment_pieces::Entity::find_by_id(piece_id)
+            .one(db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("piece not found".into()))?
+            .into();
+
+        model.status = Set(status.to_string());
+        model.current_office_id = Set(office_id);
+        model.updated_at = Set(chrono::Utc::now().into());
+
+        model.update(db).await?;
+        Ok(())
+    }
+
+    /// Replace every piece of a shipment. Used by the projection rebuilder.
+    pub async fn replace_for_shipment<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        rows: Vec<PieceRow>,
+    ) -> Result<(), ShipmentSnapshotError> {
+        shipment_pieces::Entity::delete_many()
+            .filter(shipment_pieces::Column::ShipmentId.eq(shipment_id))
+            .exec(db)
+            .await?;
+
+        for row in rows {
+            row.into_active_model(shipment_id).insert(db).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,351 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sea_orm::prelude::{DateTimeWithTimeZone, Expr};
+use sea_orm::sea_query::{IntoCondition, Query, SimpleExpr};
+use sea_orm::{ColumnTrait, Condition, DbErr, Order, Value};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entity::{shipment_status_history, shipments};
+use core_domain::shipment::{HoldReason, ShipmentStatus};
+
+pub const DEFAULT_PAGE_SIZE: u64 = 50;
+pub const MAX_PAGE_SIZE: u64 = 200;
+
+#[derive(Debug, Error)]
+pub enum ShipmentQueryError {
+    #[error("invalid cursor")]
+    InvalidCursor,
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShipmentSort {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Status,
+}
+
+impl ShipmentSort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ShipmentSort::CreatedAt => "created_at",
+            ShipmentSort::UpdatedAt => "updated_at",
+            ShipmentSort::Status => "status",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "created_at" => Some(ShipmentSort::CreatedAt),
+            "updated_at" => Some(ShipmentSort::UpdatedAt),
+            "status" => Some(ShipmentSort::Status),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn column(self) -> shipments::Column {
+        match self {
+            ShipmentSort::CreatedAt => shipments::Column::CreatedAt,
+            ShipmentSort::UpdatedAt => shipments::Column::UpdatedAt,
+            ShipmentSort::Status => shipments::Column::CurrentStatus,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "asc" => Some(SortDirection::Asc),
+            "desc" => Some(SortDirection::Desc),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn order(self) -> Order {
+        match self {
+            SortDirection::Asc => Order::Asc,
+            SortDirection::Desc => Order::Desc,
+        }
+    }
+}
+
+/// Offices through which a shipment becomes visible, and which relation to
+/// those offices counts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OfficeVisibility {
+    pub office_ids: Vec<Uuid>,
+    /// Shipment is currently at one of the offices.
+    pub current: bool,
+    /// Shipment was created at one of the offices.
+    pub origin: bool,
+    /// A status change was recorded at one of the offices.
+    pub history: bool,
+}
+
+impl OfficeVisibility {
+    /// Matches shipments satisfying any enabled relation. Nothing matches
+    /// when no relation is enabled or `office_ids` is empty.
+    pub fn condition(&self) -> Condition {
+        // an empty condition renders as TRUE, so close it explicitly
+        let nothing = || Condition::all().add(Expr::value(false));
+
+        if self.office_ids.is_empty() {
+            return nothing();
+        }
+
+        let mut cond = Condition::any();
+
+        if self.current {
+            cond = cond.add(shipments::Column::CurrentOfficeId.is_in(self.office_ids.clone()));
+        }
+
+        if self.origin || self.history {
+            let mut rows = Query::select()
+                .column(shipment_status_history::Column::ShipmentId)
+                .from(shipment_status_history::Entity)
+                .and_where(shipment_status_history::Column::OfficeId.is_in(self.office_ids.clone()))
+                .to_owned();
+
+            // the creation row is the only one without a from_status
+            if !self.history {
+                rows.and_where(shipment_status_history::Column::FromStatus.is_null());
+            }
+
+            cond = cond.add(shipments::Column::Id.in_subquery(rows));
+        }
+
+        if cond.is_empty() {
+            return nothing();
+        }
+
+        cond
+    }
+}
+
+/// Filters, ordering and page position for listing shipment snapshots.
+///
+/// Date ranges are half-open: `*_from` is inclusive, `*_to` exclusive.
+/// Rows are ordered by `sort` and then by id, so pages never overlap.
+#[derive(Debug, Clone, Default)]
+pub struct ShipmentQuery {
+    pub status: Option<ShipmentStatus>,
+    pub office_id: Option<Uuid>,
+    pub client_id: Option<Uuid>,
+    /// `Some(true)` keeps shipments on hold, `Some(false)` the others.
+    pub on_hold: Option<bool>,
+    /// Shipments on hold for this reason.
+    pub hold_reason: Option<HoldReason>,
+    pub created_from: Option<DateTimeWithTimeZone>,
+    pub created_to: Option<DateTimeWithTimeZone>,
+    pub updated_from: Option<DateTimeWithTimeZone>,
+    pub updated_to: Option<DateTimeWithTimeZone>,
+    pub sort: ShipmentSort,
+    pub direction: SortDirection,
+    /// Page size, clamped to `1..=MAX_PAGE_SIZE`. `None` means
+    /// `DEFAULT_PAGE_SIZE`.
+    pub limit: Option<u64>,
+    /// Continue after this position, as returned in `ShipmentPage::next_cursor`.
+    pub after: Option<ShipmentCursor>,
+    /// Restrict to shipments visible through these offices. `None` means
+    /// no restriction.
+    pub visible_to: Option<OfficeVisibility>,
+}
+
+impl ShipmentQuery {
+    pub fn page_size(&self) -> u64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+
+    pub(crate) fn filter_condition(&self) -> Condition {
+        let mut cond = Condition::all();
+
+        if let Some(status) = self.status {
+            cond = cond.add(shipments::Column::CurrentStatus.eq(status.to_string()));
+        }
+        if let Some(office_id) = self.office_id {
+            cond = cond.add(shipments::Column::CurrentOfficeId.eq(office_id));
+        }
+        if let Some(client_id) = self.client_id {
+            cond = cond.add(shipments::Column::ClientId.eq(client_id));
+        }
+        match self.on_hold {
+            Some(true) => cond = cond.add(shipments::Column::HoldReason.is_not_null()),
+            Some(false) => cond = cond.add(shipments::Column::HoldReason.is_null()),
+            None => {}
+        }
+        if let Some(reason) = self.hold_reason {
+            cond = cond.add(shipments::Column::HoldReason.eq(reason.to_string()));
+        }
+        if let Some(from) = self.created_from {
+            cond = cond.add(shipments::Column::CreatedAt.gte(from));
+        }
+        if let Some(to) = self.created_to {
+            cond = cond.add(shipments::Column::CreatedAt.lt(to));
+        }
+        if let Some(from) = self.updated_from {
+            cond = cond.add(shipments::Column::UpdatedAt.gte(from));
+        }
+        if let Some(to) = self.updated_to {
+            cond = cond.add(shipments::Column::UpdatedAt.lt(to));
+        }
+        if let Some(visibility) = &self.visible_to {
+            cond = cond.add(visibility.condition());
+        }
+
+        cond
+    }
+
+    /// Rows strictly after the cursor in the query's order.
+    pub(crate) fn after_condition(&self) -> Result<Option<Condition>, ShipmentQueryError> {
+        let Some(cursor) = &self.after else {
+            return Ok(None);
+        };
+
+        if cursor.sort != self.sort || cursor.direction != self.direction {
+            return Err(ShipmentQueryError::InvalidCursor);
+        }
+
+        let key: Value = match &cursor.key {
+            CursorKey::Timestamp(at) => (*at).into(),
+            CursorKey::Text(text) => text.clone().into(),
+        };
+
+        let column = self.sort.column();
+        let past = |col: shipments::Column, value: Value| -> SimpleExpr {
+            match self.direction {
+                SortDirection::Asc => col.gt(value),
+                SortDirection::Desc => col.lt(value),
+            }
+        };
+
+        Ok(Some(
+            Condition::any()
+                .add(past(column, key.clone()))
+                .add(
+                    Condition::all()
+                        .add(column.eq(key))
+                        .add(past(shipments::Column::Id, cursor.id.into())),
+                )
+                .into_condition(),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CursorKey {
+    Timestamp(DateTimeWithTimeZone),
+    Text(String),
+}
+
+/// Keyset position: the sort key and id of the last row of a page.
+///
+/// Encoded as an opaque URL-safe token. A cursor is only valid for the
+/// sort key and direction it was issued for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShipmentCursor {
+    sort: ShipmentSort,
+    direction: SortDirection,
+    id: Uuid,
+    key: CursorKey,
+}
+
+impl ShipmentCursor {
+    pub(crate) fn for_row(
+        sort: ShipmentSort,
+        direction: SortDirection,
+        row: &shipments::Model,
+    ) -> Self {
+        let key = match sort {
+            ShipmentSort::CreatedAt => CursorKey::Timestamp(row.created_at),
+            ShipmentSort::UpdatedAt => CursorKey::Timestamp(row.updated_at),
+            ShipmentSort::Status => CursorKey::Text(row.current_status.clone()),
+        };
+
+        Self {
+            sort,
+            direction,
+            id: row.id,
+            key,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let key = match &self.key {
+            // microseconds, the precision Postgres stores
+            CursorKey::Timestamp(at) => at.timestamp_micros().to_string(),
+            CursorKey::Text(text) => text.clone(),
+        };
+
+        let raw = format!(
+            "{}|{}|{}|{}",
+            self.sort.as_str(),
+            self.direction.as_str(),
+            self.id,
+            key
+        );
+
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub fn decode(token: &str) -> Result<Self, ShipmentQueryError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| ShipmentQueryError::InvalidCursor)?;
+        let raw = String::from_utf8(bytes).map_err(|_| ShipmentQueryError::InvalidCursor)?;
+
+        let mut parts = raw.splitn(4, '|');
+        let (Some(sort), Some(direction), Some(id), Some(key)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ShipmentQueryError::InvalidCursor);
+        };
+
+        let sort = ShipmentSort::parse(sort).ok_or(ShipmentQueryError::InvalidCursor)?;
+        let direction = SortDirection::parse(direction).ok_or(ShipmentQueryError::InvalidCursor)?;
+        let id = id.parse().map_err(|_| ShipmentQueryError::InvalidCursor)?;
+
+        let key = match sort {
+            ShipmentSort::CreatedAt | ShipmentSort::UpdatedAt => {
+                let micros: i64 = key.parse().map_err(|_| ShipmentQueryError::InvalidCursor)?;
+                let at = chrono::DateTime::from_timestamp_micros(micros)
+                    .ok_or(ShipmentQueryError::InvalidCursor)?;
+                CursorKey::Timestamp(at.fixed_offset())
+            }
+            ShipmentSort::Status => CursorKey::Text(key.to_owned()),
+        };
+
+        Ok(Self {
+            sort,
+            direction,
+            id,
+            key,
+        })
+    }
+}
+
+/// One page of shipment snapshots.
+#[derive(Debug, Clone)]
+pub struct ShipmentPage {
+    pub items: Vec<shipments::Model>,
+    /// `None` on the last page.
+    pub next_cursor: Option<ShipmentCursor>,
+}
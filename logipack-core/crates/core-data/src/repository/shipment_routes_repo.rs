@@ -0,0 +1,65 @@
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder,
+};
+use uuid::Uuid;
+
+use crate::entity::shipment_route_stops;
+use crate::repository::shipments_repo::ShipmentSnapshotError;
+use core_domain::network::{Route, RouteStop};
+
+/// Planned routes, part of the shipment projection.
+///
+/// Like `ShipmentsRepo`, every method accepts any `ConnectionTrait` so the
+/// writes can share the caller's transaction.
+pub struct ShipmentRoutesRepo;
+
+impl ShipmentRoutesRepo {
+    /// Planned route of a shipment, `None` when it has none.
+    pub async fn get<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+    ) -> Result<Option<Route<Uuid>>, ShipmentSnapshotError> {
+        let stops: Vec<RouteStop<Uuid>> = shipment_route_stops::Entity::find()
+            .filter(shipment_route_stops::Column::ShipmentId.eq(shipment_id))
+            .order_by_asc(shipment_route_stops::Column::Seq)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|row| RouteStop {
+                office: row.office_id,
+                transit_minutes: row.transit_minutes,
+                cost_cents: row.cost_cents,
+            })
+            .collect();
+
+        Ok((!stops.is_empty()).then_some(Route { stops }))
+    }
+
+    /// Replaces the planned route of a shipment; a route without stops
+    /// clears it. Used by route planning and the projection rebuilder.
+    pub async fn replace<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        route: &Route<Uuid>,
+    ) -> Result<(), ShipmentSnapshotError> {
+        shipment_route_stops::Entity::delete_many()
+            .filter(shipment_route_stops::Column::ShipmentId.eq(shipment_id))
+            .exec(db)
+            .await?;
+
+        for (seq, stop) in route.stops.iter().enumerate() {
+            shipment_route_stops::ActiveModel {
+                shipment_id: Set(shipment_id),
+                seq: Set(seq as i32),
+                office_id: Set(stop.office),
+                transit_minutes: Set(stop.transit_minutes),
+                cost_cents: Set(stop.cost_cents),
+            }
+            .insert(db)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,10 +1,21 @@
 use sea_orm::ActiveValue::{self, Set};
-use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, QueryOrder};
+use sea_orm::prelude::{DateTimeWithTimeZone, Expr};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, ModelTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Statement,
+};
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::entity::{shipment_status_history, shipments};
-use core_domain::shipment::ShipmentStatus;
+use crate::entity::{clients, offices, shipment_status_history, shipments};
+use crate::repository::shipment_query::{
+    OfficeVisibility, ShipmentCursor, ShipmentPage, ShipmentQuery, ShipmentQueryError,
+};
+use core_domain::pricing::{Price, ServiceLevel};
+use core_domain::shipment::{
+    Address, CashOnDelivery, DeliveryFailureReason, Dimensions, HoldReason, Parcel, Party,
+    ShipmentStatus,
+};
 
 #[derive(Debug, Error)]
 pub enum ShipmentSnapshotError {
@@ -12,33 +23,284 @@ pub enum ShipmentSnapshotError {
     DbError(#[from] DbErr),
 }
 
+/// History row as rebuilt from the event store, with its original timestamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryRow {
+    pub from_status: Option<ShipmentStatus>,
+    pub to_status: ShipmentStatus,
+    pub actor_user_id: Option<Uuid>,
+    pub office_id: Option<Uuid>,
+    pub notes: Option<String>,
+    pub failure_reason: Option<DeliveryFailureReason>,
+    pub changed_at: DateTimeWithTimeZone,
+}
+
+impl HistoryRow {
+    fn into_active_model(self, shipment_id: Uuid) -> shipment_status_history::ActiveModel {
+        shipment_status_history::ActiveModel {
+            id: ActiveValue::NotSet,
+            shipment_id: Set(shipment_id),
+            from_status: Set(self.from_status.map(|s| s.to_string())),
+            to_status: Set(self.to_status.to_string()),
+            actor_user_id: Set(self.actor_user_id),
+            office_id: Set(self.office_id),
+            notes: Set(self.notes),
+            failure_reason: Set(self.failure_reason.map(|r| r.to_string())),
+            changed_at: Set(self.changed_at),
+        }
+    }
+}
+
+/// Sender, recipient, parcel, cash on delivery and price columns of a
+/// snapshot, with the tariff version that set the price. All of them are
+/// empty on shipments created before they were recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShipmentDetails {
+    pub sender: Option<Party>,
+    pub recipient: Option<Party>,
+    pub destination_office_id: Option<Uuid>,
+    pub parcel: Option<Parcel>,
+    pub cod: Option<CashOnDelivery>,
+    pub service_level: ServiceLevel,
+    pub price: Option<Price>,
+    pub tariff_id: Option<Uuid>,
+}
+
+impl ShipmentDetails {
+    pub fn from_model(row: &shipments::Model) -> Self {
+        let sender = party_from_columns([
+            &row.sender_name,
+            &row.sender_phone,
+            &row.sender_email,
+            &row.sender_address_line1,
+            &row.sender_address_line2,
+            &row.sender_city,
+            &row.sender_postal_code,
+            &row.sender_country,
+        ]);
+        let recipient = party_from_columns([
+            &row.recipient_name,
+            &row.recipient_phone,
+            &row.recipient_email,
+            &row.recipient_address_line1,
+            &row.recipient_address_line2,
+            &row.recipient_city,
+            &row.recipient_postal_code,
+            &row.recipient_country,
+        ]);
+
+        let dimensions = match (row.length_mm, row.width_mm, row.height_mm) {
+            (Some(length_mm), Some(width_mm), Some(height_mm)) => Some(Dimensions {
+                length_mm,
+                width_mm,
+                height_mm,
+            }),
+            _ => None,
+        };
+
+        Self {
+            sender,
+            recipient,
+            destination_office_id: row.destination_office_id,
+            parcel: row.weight_g.map(|weight_g| Parcel {
+                weight_g,
+                dimensions,
+                declared_value_cents: row.declared_value_cents,
+                description: row.description.clone(),
+            }),
+            cod: match (row.cod_amount_cents, row.cod_currency.as_deref()) {
+                (Some(amount_cents), Some(currency)) => {
+                    currency.parse().ok().map(|currency| CashOnDelivery {
+                        amount_cents,
+                        currency,
+                    })
+                }
+                _ => None,
+            },
+            service_level: row.service_level.parse().unwrap_or_default(),
+            price: price_from_columns(row),
+            tariff_id: row.tariff_id,
+        }
+    }
+
+    fn apply(self, model: &mut shipments::ActiveModel) {
+        let [name, phone, email, line1, line2, city, postal_code, country] =
+            party_columns(self.sender);
+        model.sender_name = Set(name);
+        model.sender_phone = Set(phone);
+        model.sender_email = Set(email);
+        model.sender_address_line1 = Set(line1);
+        model.sender_address_line2 = Set(line2);
+        model.sender_city = Set(city);
+        model.sender_postal_code = Set(postal_code);
+        model.sender_country = Set(country);
+
+        let [name, phone, email, line1, line2, city, postal_code, country] =
+            party_columns(self.recipient);
+        model.recipient_name = Set(name);
+        model.recipient_phone = Set(phone);
+        model.recipient_email = Set(email);
+        model.recipient_address_line1 = Set(line1);
+        model.recipient_address_line2 = Set(line2);
+        model.recipient_city = Set(city);
+        model.recipient_postal_code = Set(postal_code);
+        model.recipient_country = Set(country);
+
+        model.destination_office_id = Set(self.destination_office_id);
+
+        let dimensions = self.parcel.as_ref().and_then(|p| p.dimensions);
+        model.weight_g = Set(self.parcel.as_ref().map(|p| p.weight_g));
+        model.length_mm = Set(dimensions.map(|d| d.length_mm));
+        model.width_mm = Set(dimensions.map(|d| d.width_mm));
+        model.height_mm = Set(dimensions.map(|d| d.height_mm));
+        model.declared_value_cents = Set(self.parcel.as_ref().and_then(|p| p.declared_value_cents));
+        model.description = Set(self.parcel.and_then(|p| p.description));
+
+        model.cod_amount_cents = Set(self.cod.as_ref().map(|c| c.amount_cents));
+        model.cod_currency = Set(self.cod.map(|c| c.currency.to_string()));
+
+        model.service_level = Set(self.service_level.to_string());
+
+        let price = self.price.as_ref();
+        model.price_currency = Set(price.map(|p| p.currency.to_string()));
+        model.price_zone = Set(price.map(|p| p.zone.clone()));
+        model.price_chargeable_weight_g = Set(price.map(|p| p.chargeable_weight_g));
+        model.price_base_cents = Set(price.map(|p| p.base_cents));
+        model.price_express_fee_cents = Set(price.map(|p| p.express_fee_cents));
+        model.price_cod_fee_cents = Set(price.map(|p| p.cod_fee_cents));
+        model.price_declared_value_fee_cents = Set(price.map(|p| p.declared_value_fee_cents));
+        model.price_tax_cents = Set(price.map(|p| p.tax_cents));
+        model.price_total_cents = Set(price.map(|p| p.total_cents));
+        model.tariff_id = Set(self.tariff_id);
+    }
+}
+
+fn price_from_columns(row: &shipments::Model) -> Option<Price> {
+    Some(Price {
+        currency: row.price_currency.as_deref()?.parse().ok()?,
+        zone: row.price_zone.clone()?,
+        chargeable_weight_g: row.price_chargeable_weight_g?,
+        base_cents: row.price_base_cents?,
+        express_fee_cents: row.price_express_fee_cents?,
+        cod_fee_cents: row.price_cod_fee_cents?,
+        declared_value_fee_cents: row.price_declared_value_fee_cents?,
+        tax_cents: row.price_tax_cents?,
+        total_cents: row.price_total_cents?,
+    })
+}
+
+/// name, phone, email, line1, line2, city, postal_code, country
+type PartyColumns = [Option<String>; 8];
+
+fn party_columns(party: Option<Party>) -> PartyColumns {
+    let Some(party) = party else {
+        return Default::default();
+    };
+
+    [
+        Some(party.name),
+        party.phone,
+        party.email,
+        Some(party.address.line1),
+        party.address.line2,
+        Some(party.address.city),
+        Some(party.address.postal_code),
+        Some(party.address.country),
+    ]
+}
+
+fn party_from_columns(columns: [&Option<String>; 8]) -> Option<Party> {
+    let [name, phone, email, line1, line2, city, postal_code, country] = columns;
+
+    Some(Party {
+        name: name.clone()?,
+        phone: phone.clone(),
+        email: email.clone(),
+        address: Address {
+            line1: line1.clone()?,
+            line2: line2.clone(),
+            city: city.clone()?,
+            postal_code: postal_code.clone()?,
+            country: country.clone()?,
+        },
+    })
+}
+
+/// Snapshot state replayed from a shipment stream, see `restore_snapshot`.
+#[derive(Debug, Clone)]
+pub struct RestoredSnapshot {
+    pub id: Uuid,
+    pub tracking_number: Option<String>,
+    pub client_id: Uuid,
+    pub status: ShipmentStatus,
+    pub current_office_id: Option<Uuid>,
+    pub origin_office_id: Option<Uuid>,
+    pub details: ShipmentDetails,
+    pub delivery_attempts: i32,
+    /// Active hold and when it was placed.
+    pub hold: Option<(HoldReason, DateTimeWithTimeZone)>,
+    pub promised_by: Option<DateTimeWithTimeZone>,
+    pub sla_breached_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// Shipment projection writes.
+///
+/// Every method accepts any `ConnectionTrait`, so callers can pass a
+/// `DatabaseTransaction` and commit the snapshot, history and eventstore
+/// writes as one unit.
 pub struct ShipmentsRepo;
 
 impl ShipmentsRepo {
-    /// Insert initial snapshot of shipment creation
-    pub async fn insert_snapshot(
-        db: &DatabaseConnection,
+    /// Next serial for `TrackingNumber::new`. Never reused, even when the
+    /// surrounding transaction rolls back.
+    pub async fn next_tracking_serial<C: ConnectionTrait>(
+        db: &C,
+    ) -> Result<u64, ShipmentSnapshotError> {
+        let row = db
+            .query_one(Statement::from_string(
+                db.get_database_backend(),
+                "SELECT nextval('shipment_tracking_seq') AS serial",
+            ))
+            .await?
+            .ok_or(DbErr::RecordNotFound("shipment_tracking_seq".into()))?;
+
+        let serial: i64 = row.try_get("", "serial")?;
+        Ok(serial as u64)
+    }
+
+    /// Insert initial snapshot of shipment creation.
+    /// `office_id` is recorded as both the current and the origin office.
+    pub async fn insert_snapshot<C: ConnectionTrait>(
+        db: &C,
         shipment_id: Uuid,
+        tracking_number: Option<String>,
         client_id: Uuid,
         status: ShipmentStatus,
         office_id: Option<Uuid>,
+        details: ShipmentDetails,
     ) -> Result<(), ShipmentSnapshotError> {
-        let model = shipments::ActiveModel {
+        let mut model = shipments::ActiveModel {
             id: Set(shipment_id),
+            tracking_number: Set(tracking_number),
             client_id: Set(client_id),
             current_status: Set(status.to_string()),
             current_office_id: Set(office_id),
+            origin_office_id: Set(office_id),
             created_at: Set(chrono::Utc::now().into()),
             updated_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
         };
+        details.apply(&mut model);
 
         model.insert(db).await?;
         Ok(())
     }
 
     /// Insert history row for any status change
-    pub async fn insert_history(
-        db: &DatabaseConnection,
+    pub async fn insert_history<C: ConnectionTrait>(
+        db: &C,
         shipment_id: Uuid,
         from_status: Option<ShipmentStatus>,
         to_status: ShipmentStatus,
@@ -46,24 +308,124 @@ impl ShipmentsRepo {
         office_id: Option<Uuid>,
         notes: Option<String>,
     ) -> Result<(), ShipmentSnapshotError> {
-        let model = shipment_status_history::ActiveModel {
-            id: ActiveValue::NotSet,
-            shipment_id: Set(shipment_id),
-            from_status: Set(from_status.map(|s| s.to_string())),
-            to_status: Set(to_status.to_string()),
-            actor_user_id: Set(actor_user_id),
-            office_id: Set(office_id),
-            notes: Set(notes),
-            changed_at: Set(chrono::Utc::now().into()),
+        let row = HistoryRow {
+            from_status,
+            to_status,
+            actor_user_id,
+            office_id,
+            notes,
+            failure_reason: None,
+            changed_at: chrono::Utc::now().into(),
         };
 
-        model.insert(db).await?;
+        Self::append_history(db, shipment_id, row).await
+    }
+
+    /// Insert a fully specified history row
+    pub async fn append_history<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        row: HistoryRow,
+    ) -> Result<(), ShipmentSnapshotError> {
+        row.into_active_model(shipment_id).insert(db).await?;
+        Ok(())
+    }
+
+    /// Store the failed delivery attempt count
+    pub async fn set_delivery_attempts<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        attempts: i32,
+    ) -> Result<(), ShipmentSnapshotError> {
+        let mut model: shipments::ActiveModel = shipments::Entity::find_by_id(shipment_id)
+            .one(db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("shipment not found".into()))?
+            .into();
+
+        model.delivery_attempts = Set(attempts);
+        model.update(db).await?;
+        Ok(())
+    }
+
+    /// Place (`Some`) or release (`None`) the hold on a snapshot
+    pub async fn set_hold<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        hold: Option<(HoldReason, DateTimeWithTimeZone)>,
+    ) -> Result<(), ShipmentSnapshotError> {
+        let mut model: shipments::ActiveModel = shipments::Entity::find_by_id(shipment_id)
+            .one(db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("shipment not found".into()))?
+            .into();
+
+        model.hold_reason = Set(hold.map(|(reason, _)| reason.to_string()));
+        model.held_since = Set(hold.map(|(_, since)| since));
+        model.updated_at = Set(chrono::Utc::now().into());
+        model.update(db).await?;
+        Ok(())
+    }
+
+    /// Store the delivery promised to the customer
+    pub async fn set_promised_by<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        promised_by: DateTimeWithTimeZone,
+    ) -> Result<(), ShipmentSnapshotError> {
+        let mut model: shipments::ActiveModel = shipments::Entity::find_by_id(shipment_id)
+            .one(db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("shipment not found".into()))?
+            .into();
+
+        model.promised_by = Set(Some(promised_by));
+        model.update(db).await?;
         Ok(())
     }
 
-    /// Update snapshot on transition
-    pub async fn update_snapshot_status(
-        db: &DatabaseConnection,
+    /// Flag a broken delivery promise. `false` when the shipment was
+    /// already flagged, so concurrent checks record a breach only once.
+    pub async fn mark_sla_breached<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        at: DateTimeWithTimeZone,
+    ) -> Result<bool, ShipmentSnapshotError> {
+        let result = shipments::Entity::update_many()
+            .col_expr(shipments::Column::SlaBreachedAt, Expr::value(at))
+            .col_expr(shipments::Column::UpdatedAt, Expr::value(at))
+            .filter(shipments::Column::Id.eq(shipment_id))
+            .filter(shipments::Column::SlaBreachedAt.is_null())
+            .exec(db)
+            .await?;
+
+        Ok(result.rows_affected == 1)
+    }
+
+    /// Shipments past their promised delivery that are not flagged yet and
+    /// not in one of the `settled` statuses, most overdue first.
+    pub async fn list_sla_overdue<C: ConnectionTrait>(
+        db: &C,
+        now: DateTimeWithTimeZone,
+        settled: &[ShipmentStatus],
+        limit: u64,
+    ) -> Result<Vec<shipments::Model>, ShipmentSnapshotError> {
+        Ok(shipments::Entity::find()
+            .filter(shipments::Column::PromisedBy.lt(now))
+            .filter(shipments::Column::SlaBreachedAt.is_null())
+            .filter(
+                shipments::Column::CurrentStatus
+                    .is_not_in(settled.iter().map(|status| status.to_string())),
+            )
+            .order_by_asc(shipments::Column::PromisedBy)
+            .order_by_asc(shipments::Column::Id)
+            .limit(limit)
+            .all(db)
+            .await?)
+    }
+
+    pub async fn update_snapshot_status<C: ConnectionTrait>(
+        db: &C,
         shipment_id: Uuid,
         new_status: ShipmentStatus,
         new_office_id: Option<Uuid>,
@@ -87,18 +449,69 @@ impl ShipmentsRepo {
     }
 
     /// Read snapshot
-    pub async fn get_snapshot(
-        db: &DatabaseConnection,
+    pub async fn get_snapshot<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+    ) -> Result<shipments::Model, ShipmentSnapshotError> {
+        Ok(shipments::Entity::find_by_id(shipment_id)
+            .one(db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("shipment not found".into()))?)
+    }
+
+    /// Like `get_snapshot`, locking the row until the caller's transaction
+    /// ends. Writers that check the snapshot before appending take it first,
+    /// so a concurrent hold or status change cannot slip in between.
+    pub async fn get_snapshot_for_update<C: ConnectionTrait>(
+        db: &C,
         shipment_id: Uuid,
     ) -> Result<shipments::Model, ShipmentSnapshotError> {
         Ok(shipments::Entity::find_by_id(shipment_id)
+            .lock_exclusive()
             .one(db)
             .await?
             .ok_or(DbErr::RecordNotFound("shipment not found".into()))?)
     }
 
-    pub async fn list_snapshots(
-        db: &DatabaseConnection,
+    /// Id of the shipment with this tracking number.
+    pub async fn find_id_by_tracking_number<C: ConnectionTrait>(
+        db: &C,
+        tracking_number: &str,
+    ) -> Result<Option<Uuid>, ShipmentSnapshotError> {
+        let row = shipments::Entity::find()
+            .filter(shipments::Column::TrackingNumber.eq(tracking_number))
+            .one(db)
+            .await?;
+
+        Ok(row.map(|row| row.id))
+    }
+
+    /// Snapshot with its client and current office, via the `Related` impls
+    /// of `shipments`. Soft-deleted clients and offices are still returned.
+    pub async fn get_snapshot_with_relations<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+    ) -> Result<
+        (
+            shipments::Model,
+            Option<clients::Model>,
+            Option<offices::Model>,
+        ),
+        ShipmentSnapshotError,
+    > {
+        let (shipment, client) = shipments::Entity::find_by_id(shipment_id)
+            .find_also_related(clients::Entity)
+            .one(db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("shipment not found".into()))?;
+
+        let office = shipment.find_related(offices::Entity).one(db).await?;
+
+        Ok((shipment, client, office))
+    }
+
+    pub async fn list_snapshots<C: ConnectionTrait>(
+        db: &C,
     ) -> Result<Vec<shipments::Model>, ShipmentSnapshotError> {
         let rows = shipments::Entity::find()
             .order_by_desc(shipments::Column::CreatedAt)
@@ -107,4 +520,139 @@ impl ShipmentsRepo {
 
         Ok(rows)
     }
+
+    /// One page of snapshots matching `query`, in the query's order.
+    pub async fn query_snapshots<C: ConnectionTrait>(
+        db: &C,
+        query: &ShipmentQuery,
+    ) -> Result<ShipmentPage, ShipmentQueryError> {
+        let mut select = shipments::Entity::find().filter(query.filter_condition());
+
+        if let Some(after) = query.after_condition()? {
+            select = select.filter(after);
+        }
+
+        let order = query.direction.order();
+        let limit = query.page_size();
+
+        // one extra row tells whether another page follows
+        let mut items = select
+            .order_by(query.sort.column(), order.clone())
+            .order_by(shipments::Column::Id, order)
+            .limit(limit + 1)
+            .all(db)
+            .await?;
+
+        let next_cursor = if items.len() as u64 > limit {
+            items.truncate(limit as usize);
+            items
+                .last()
+                .map(|row| ShipmentCursor::for_row(query.sort, query.direction, row))
+        } else {
+            None
+        };
+
+        Ok(ShipmentPage { items, next_cursor })
+    }
+
+    /// Whether the shipment exists and matches `visibility`.
+    pub async fn is_visible<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        visibility: &OfficeVisibility,
+    ) -> Result<bool, ShipmentSnapshotError> {
+        let count = shipments::Entity::find_by_id(shipment_id)
+            .filter(visibility.condition())
+            .count(db)
+            .await?;
+
+        Ok(count > 0)
+    }
+
+    /// Most recent history row, in the same order as `list_history`.
+    pub async fn latest_history<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+    ) -> Result<Option<shipment_status_history::Model>, ShipmentSnapshotError> {
+        let row = shipment_status_history::Entity::find()
+            .filter(shipment_status_history::Column::ShipmentId.eq(shipment_id))
+            .order_by_desc(shipment_status_history::Column::ChangedAt)
+            .order_by_desc(shipment_status_history::Column::Id)
+            .one(db)
+            .await?;
+
+        Ok(row)
+    }
+
+    /// History rows of a shipment in the order they were written.
+    pub async fn list_history<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+    ) -> Result<Vec<shipment_status_history::Model>, ShipmentSnapshotError> {
+        let rows = shipment_status_history::Entity::find()
+            .filter(shipment_status_history::Column::ShipmentId.eq(shipment_id))
+            .order_by_asc(shipment_status_history::Column::ChangedAt)
+            .order_by_asc(shipment_status_history::Column::Id)
+            .all(db)
+            .await?;
+
+        Ok(rows)
+    }
+
+    /// Overwrite a snapshot row as-is, inserting it when missing.
+    /// Used by the projection rebuilder; regular writes go through
+    /// `insert_snapshot` / `update_snapshot_status`.
+    pub async fn restore_snapshot<C: ConnectionTrait>(
+        db: &C,
+        row: RestoredSnapshot,
+    ) -> Result<(), ShipmentSnapshotError> {
+        let exists = shipments::Entity::find_by_id(row.id)
+            .one(db)
+            .await?
+            .is_some();
+
+        let mut model = shipments::ActiveModel {
+            id: Set(row.id),
+            tracking_number: Set(row.tracking_number),
+            client_id: Set(row.client_id),
+            current_status: Set(row.status.to_string()),
+            current_office_id: Set(row.current_office_id),
+            origin_office_id: Set(row.origin_office_id),
+            delivery_attempts: Set(row.delivery_attempts),
+            hold_reason: Set(row.hold.map(|(reason, _)| reason.to_string())),
+            held_since: Set(row.hold.map(|(_, since)| since)),
+            promised_by: Set(row.promised_by),
+            sla_breached_at: Set(row.sla_breached_at),
+            created_at: Set(row.created_at),
+            updated_at: Set(row.updated_at),
+            ..Default::default()
+        };
+        row.details.apply(&mut model);
+
+        if exists {
+            model.update(db).await?;
+        } else {
+            model.insert(db).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace every history row of a shipment.
+    pub async fn replace_history<C: ConnectionTrait>(
+        db: &C,
+        shipment_id: Uuid,
+        rows: Vec<HistoryRow>,
+    ) -> Result<(), ShipmentSnapshotError> {
+        shipment_status_history::Entity::delete_many()
+            .filter(shipment_status_history::Column::ShipmentId.eq(shipment_id))
+            .exec(db)
+            .await?;
+
+        for row in rows {
+            row.into_active_model(shipment_id).insert(db).await?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,157 @@
+use sea_orm::ActiveValue::Set;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entity::tariffs;
+use core_domain::pricing::Tariff;
+
+#[derive(Debug, Error)]
+pub enum TariffRepoError {
+    #[error("db error: {0}")]
+    DbError(#[from] DbErr),
+    /// Stored rules no longer deserialize as a `Tariff`.
+    #[error("invalid tariff rules: {0}")]
+    InvalidRules(#[from] serde_json::Error),
+}
+
+/// Tariff version with its rules deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TariffVersion {
+    pub id: Uuid,
+    pub effective_from: DateTimeWithTimeZone,
+    pub effective_to: Option<DateTimeWithTimeZone>,
+    pub tariff: Tariff,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl TariffVersion {
+    pub fn from_model(row: tariffs::Model) -> Result<Self, TariffRepoError> {
+        Ok(Self {
+            id: row.id,
+            effective_from: row.effective_from,
+            effective_to: row.effective_to,
+            tariff: serde_json::from_value(row.rules)?,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+
+    fn into_active_model(self) -> Result<tariffs::ActiveModel, TariffRepoError> {
+        Ok(tariffs::ActiveModel {
+            id: Set(self.id),
+            effective_from: Set(self.effective_from),
+            effective_to: Set(self.effective_to),
+            rules: Set(serde_json::to_value(&self.tariff)?),
+            created_by: Set(self.created_by),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+        })
+    }
+}
+
+/// Tariff versions by effective period. Every method accepts any
+/// `ConnectionTrait`, so writes can share the caller's transaction.
+pub struct TariffsRepo;
+
+impl TariffsRepo {
+    /// Returns the row as stored, timestamps at database precision.
+    pub async fn insert<C: ConnectionTrait>(
+        db: &C,
+        version: TariffVersion,
+    ) -> Result<TariffVersion, TariffRepoError> {
+        TariffVersion::from_model(version.into_active_model()?.insert(db).await?)
+    }
+
+    /// Overwrites every column but `created_by` and `created_at`.
+    pub async fn update<C: ConnectionTrait>(
+        db: &C,
+        version: TariffVersion,
+    ) -> Result<TariffVersion, TariffRepoError> {
+        let mut model = version.into_active_model()?;
+        model.created_by = sea_orm::ActiveValue::NotSet;
+        model.created_at = sea_orm::ActiveValue::NotSet;
+
+        TariffVersion::from_model(model.update(db).await?)
+    }
+
+    pub async fn delete<C: ConnectionTrait>(db: &C, id: Uuid) -> Result<(), TariffRepoError> {
+        tariffs::Entity::delete_by_id(id).exec(db).await?;
+        Ok(())
+    }
+
+    pub async fn get<C: ConnectionTrait>(
+        db: &C,
+        id: Uuid,
+    ) -> Result<Option<TariffVersion>, TariffRepoError> {
+        tariffs::Entity::find_by_id(id)
+            .one(db)
+            .await?
+            .map(TariffVersion::from_model)
+            .transpose()
+    }
+
+    /// Every version, earliest first.
+    pub async fn list<C: ConnectionTrait>(db: &C) -> Result<Vec<TariffVersion>, TariffRepoError> {
+        tariffs::Entity::find()
+            .order_by_asc(tariffs::Column::EffectiveFrom)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(TariffVersion::from_model)
+            .collect()
+    }
+
+    /// Version in force at `at`, if any.
+    pub async fn in_force<C: ConnectionTrait>(
+        db: &C,
+        at: DateTimeWithTimeZone,
+    ) -> Result<Option<TariffVersion>, TariffRepoError> {
+        tariffs::Entity::find()
+            .filter(tariffs::Column::EffectiveFrom.lte(at))
+            .filter(
+                Condition::any()
+                    .add(tariffs::Column::EffectiveTo.is_null())
+                    .add(tariffs::Column::EffectiveTo.gt(at)),
+            )
+            .one(db)
+            .await?
+            .map(TariffVersion::from_model)
+            .transpose()
+    }
+
+    /// Earliest version other than `except` whose period overlaps
+    /// `[from, to)`; `to` of `None` is open-ended.
+    pub async fn find_overlapping<C: ConnectionTrait>(
+        db: &C,
+        from: DateTimeWithTimeZone,
+        to: Option<DateTimeWithTimeZone>,
+        except: Option<Uuid>,
+    ) -> Result<Option<tariffs::Model>, DbErr> {
+        let mut query = tariffs::Entity::find().filter(
+            Condition::any()
+                .add(tariffs::Column::EffectiveTo.is_null())
+                .add(tariffs::Column::EffectiveTo.gt(from)),
+        );
+
+        if let Some(to) = to {
+            query = query.filter(tariffs::Column::EffectiveFrom.lt(to));
+        }
+
+        if let Some(except) = except {
+            query = query.filter(tariffs::Column::Id.ne(except));
+        }
+
+        query
+            .order_by_asc(tariffs::Column::EffectiveFrom)
+            .one(db)
+            .await
+    }
+}
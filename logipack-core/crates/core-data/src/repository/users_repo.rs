@@ -21,6 +21,18 @@ pub enum UserError {
 pub struct UserRepo;
 
 impl UserRepo {
+    /// Finds users by ids; unknown ids are skipped.
+    pub async fn get_by_ids(
+        db: &DatabaseConnection,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<users::Model>, UserError> {
+        let users = users::Entity::find()
+            .filter(users::Column::Id.is_in(ids))
+            .all(db)
+            .await?;
+        Ok(users)
+    }
+
     /// Finds a user by their Auth0 subject identifier.
     pub async fn get_by_auth0_sub(
         db: &DatabaseConnection,
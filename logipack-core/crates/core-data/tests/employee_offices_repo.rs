@@ -99,14 +99,20 @@ pub async fn seed_soft_deleted_office(db: &DatabaseConnection) -> Uuid {
 
 pub async fn cleanup_core_data(db: &DatabaseConnection) {
     let tables = [
+        "cod_collections",
+        "cod_remittances",
         "shipment_status_history",
+        "shipment_route_stops",
         "shipments",
+        "tariffs",
         "employee_offices",
         "employees",
         "user_roles",
         "users",
         "clients",
         "roles",
+        "office_calendars",
+        "office_links",
         "offices",
     ];
 
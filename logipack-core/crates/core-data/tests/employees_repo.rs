@@ -27,14 +27,20 @@ pub async fn seed_user(db: &DatabaseConnection) -> Uuid {
 
 pub async fn cleanup_core_data(db: &DatabaseConnection) {
     let tables = [
+        "cod_collections",
+        "cod_remittances",
         "shipment_status_history",
+        "shipment_route_stops",
         "shipments",
+        "tariffs",
         "employee_offices",
         "employees",
         "user_roles",
         "users",
         "clients",
         "roles",
+        "office_calendars",
+        "office_links",
         "offices",
     ];
 
@@ -1,10 +1,13 @@
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
-    QueryFilter, Set, Statement,
+    QueryFilter, Set, Statement, TransactionTrait,
 };
 use uuid::Uuid;
 
 use core_data::entity::{clients, shipment_status_history};
+use core_data::repository::shipment_query::{
+    ShipmentCursor, ShipmentQuery, ShipmentQueryError, ShipmentSort, SortDirection,
+};
 use core_data::repository::shipments_repo::ShipmentsRepo;
 use core_domain::shipment::ShipmentStatus;
 use test_infra::test_db;
@@ -48,14 +51,20 @@ pub async fn seed_user(db: &DatabaseConnection) -> Uuid {
 
 pub async fn cleanup_core_data(db: &DatabaseConnection) {
     let tables = [
+        "cod_collections",
+        "cod_remittances",
         "shipment_status_history",
+        "shipment_route_stops",
         "shipments",
+        "tariffs",
         "employee_offices",
         "employees",
         "user_roles",
         "users",
         "clients",
         "roles",
+        "office_calendars",
+        "office_links",
         "offices",
     ];
 
@@ -77,9 +86,17 @@ async fn insert_snapshot_creates_row() {
     let shipment_id = Uuid::new_v4();
     let client_id = seed_client(&db).await;
 
-    ShipmentsRepo::insert_snapshot(&db, shipment_id, client_id, ShipmentStatus::New, None)
-        .await
-        .unwrap();
+    ShipmentsRepo::insert_snapshot(
+        &db,
+        shipment_id,
+        None,
+        client_id,
+        ShipmentStatus::New,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
 
     let snap = ShipmentsRepo::get_snapshot(&db, shipment_id).await.unwrap();
 
@@ -94,9 +111,17 @@ async fn insert_history_creates_row() {
     let shipment_id = Uuid::new_v4();
     let client_id = seed_client(&db).await;
 
-    ShipmentsRepo::insert_snapshot(&db, shipment_id, client_id, ShipmentStatus::New, None)
-        .await
-        .unwrap();
+    ShipmentsRepo::insert_snapshot(
+        &db,
+        shipment_id,
+        None,
+        client_id,
+        ShipmentStatus::New,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
 
     ShipmentsRepo::insert_history(
         &db,
@@ -127,9 +152,17 @@ async fn update_snapshot_updates_status() {
     let shipment_id = Uuid::new_v4();
     let client_id = seed_client(&db).await;
 
-    ShipmentsRepo::insert_snapshot(&db, shipment_id, client_id, ShipmentStatus::New, None)
-        .await
-        .unwrap();
+    ShipmentsRepo::insert_snapshot(
+        &db,
+        shipment_id,
+        None,
+        client_id,
+        ShipmentStatus::New,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
 
     ShipmentsRepo::update_snapshot_status(&db, shipment_id, ShipmentStatus::Accepted, None)
         .await
@@ -139,3 +172,227 @@ async fn update_snapshot_updates_status() {
 
     assert_eq!(snap.current_status, "ACCEPTED");
 }
+
+#[tokio::test]
+async fn writes_in_rolled_back_transaction_are_discarded() {
+    let db = test_db().await;
+    cleanup_core_data(&db).await;
+
+    let shipment_id = Uuid::new_v4();
+    let client_id = seed_client(&db).await;
+
+    let txn = db.begin().await.unwrap();
+
+    ShipmentsRepo::insert_snapshot(
+        &txn,
+        shipment_id,
+        None,
+        client_id,
+        ShipmentStatus::New,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+
+    ShipmentsRepo::insert_history(
+        &txn,
+        shipment_id,
+        None,
+        ShipmentStatus::New,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // visible inside the transaction
+    ShipmentsRepo::get_snapshot(&txn, shipment_id)
+        .await
+        .unwrap();
+
+    txn.rollback().await.unwrap();
+
+    assert!(ShipmentsRepo::get_snapshot(&db, shipment_id).await.is_err());
+
+    let rows = shipment_status_history::Entity::find()
+        .filter(shipment_status_history::Column::ShipmentId.eq(shipment_id))
+        .all(&db)
+        .await
+        .unwrap();
+
+    assert!(rows.is_empty());
+}
+
+async fn seed_snapshot(
+    db: &DatabaseConnection,
+    client_id: Uuid,
+    status: ShipmentStatus,
+    created_at: chrono::DateTime<chrono::Utc>,
+) -> Uuid {
+    let id = Uuid::new_v4();
+
+    core_data::entity::shipments::ActiveModel {
+        id: Set(id),
+        client_id: Set(client_id),
+        current_status: Set(status.to_string()),
+        current_office_id: Set(None),
+        created_at: Set(created_at.into()),
+        updated_at: Set(created_at.into()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    id
+}
+
+/// Walks every page of `query`, checking each cursor survives a round trip.
+async fn collect_pages(db: &DatabaseConnection, mut query: ShipmentQuery) -> Vec<Vec<Uuid>> {
+    let mut pages = Vec::new();
+
+    loop {
+        let page = ShipmentsRepo::query_snapshots(db, &query).await.unwrap();
+        pages.push(page.items.iter().map(|row| row.id).collect());
+
+        let Some(cursor) = page.next_cursor else {
+            return pages;
+        };
+
+        query.after = Some(ShipmentCursor::decode(&cursor.encode()).unwrap());
+    }
+}
+
+#[tokio::test]
+async fn query_snapshots_pages_through_ties_without_overlap() {
+    let db = test_db().await;
+    cleanup_core_data(&db).await;
+
+    let client_id = seed_client(&db).await;
+    let base = chrono::Utc::now();
+
+    // two rows share a timestamp, so the id tie-breaker decides their order
+    let mut ids = Vec::new();
+    for offset in [0, 1, 1, 2, 3] {
+        let at = base + chrono::Duration::seconds(offset);
+        ids.push((
+            at,
+            seed_snapshot(&db, client_id, ShipmentStatus::New, at).await,
+        ));
+    }
+
+    let pages = collect_pages(
+        &db,
+        ShipmentQuery {
+            limit: Some(2),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert_eq!(
+        pages.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![2, 2, 1]
+    );
+
+    // default order is newest first
+    ids.sort_by(|a, b| b.cmp(a));
+    let expected: Vec<Uuid> = ids.into_iter().map(|(_, id)| id).collect();
+    assert_eq!(pages.concat(), expected);
+}
+
+#[tokio::test]
+async fn query_snapshots_filters_and_sorts_by_status() {
+    let db = test_db().await;
+    cleanup_core_data(&db).await;
+
+    let client_id = seed_client(&db).await;
+    let other_client = seed_client(&db).await;
+    let now = chrono::Utc::now();
+
+    let new = seed_snapshot(&db, client_id, ShipmentStatus::New, now).await;
+    let accepted = seed_snapshot(&db, client_id, ShipmentStatus::Accepted, now).await;
+    let delivered = seed_snapshot(&db, client_id, ShipmentStatus::Delivered, now).await;
+    seed_snapshot(&db, other_client, ShipmentStatus::New, now).await;
+
+    let pages = collect_pages(
+        &db,
+        ShipmentQuery {
+            client_id: Some(client_id),
+            sort: ShipmentSort::Status,
+            direction: SortDirection::Asc,
+            limit: Some(1),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert_eq!(pages.concat(), vec![accepted, delivered, new]);
+
+    let only_new = ShipmentsRepo::query_snapshots(
+        &db,
+        &ShipmentQuery {
+            status: Some(ShipmentStatus::New),
+            created_from: Some(now.into()),
+            created_to: Some((now + chrono::Duration::seconds(1)).into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(only_new.items.len(), 2);
+    assert!(only_new.next_cursor.is_none());
+
+    let none_before = ShipmentsRepo::query_snapshots(
+        &db,
+        &ShipmentQuery {
+            created_to: Some(now.into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(none_before.items.is_empty());
+}
+
+#[tokio::test]
+async fn query_snapshots_rejects_foreign_cursors() {
+    let db = test_db().await;
+    cleanup_core_data(&db).await;
+
+    let client_id = seed_client(&db).await;
+    for _ in 0..2 {
+        seed_snapshot(&db, client_id, ShipmentStatus::New, chrono::Utc::now()).await;
+    }
+
+    let page = ShipmentsRepo::query_snapshots(
+        &db,
+        &ShipmentQuery {
+            limit: Some(1),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let err = ShipmentsRepo::query_snapshots(
+        &db,
+        &ShipmentQuery {
+            sort: ShipmentSort::UpdatedAt,
+            after: page.next_cursor,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, ShipmentQueryError::InvalidCursor));
+    assert!(matches!(
+        ShipmentCursor::decode("not a cursor"),
+        Err(ShipmentQueryError::InvalidCursor)
+    ));
+}
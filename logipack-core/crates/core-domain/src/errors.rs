@@ -1,4 +1,5 @@
 use crate::shipment::ShipmentStatus;
+use chrono::{NaiveDate, Weekday};
 use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
@@ -14,12 +15,211 @@ pub enum TransitionError {
         to: ShipmentStatus,
     },
 
-    /// Office change is not allowed when transitioning to IN_TRANSIT.
+    /// Office change is not allowed by the transition's rule.
     #[error("office hop not allowed from {from} to {to}")]
     OfficeHopNotAllowed {
         from: ShipmentStatus,
         to: ShipmentStatus,
     },
+
+    /// Transition is reserved for admins.
+    #[error("transition from {from} to {to} is admin only")]
+    AdminOnly {
+        from: ShipmentStatus,
+        to: ShipmentStatus,
+    },
+
+    /// Transition must carry a note.
+    #[error("transition from {from} to {to} requires a note")]
+    NoteRequired {
+        from: ShipmentStatus,
+        to: ShipmentStatus,
+    },
+
+    /// A failed delivery must say why.
+    #[error("failed delivery requires a reason")]
+    FailureReasonRequired,
+
+    /// Failure reasons only go with DELIVERY_FAILED.
+    #[error("failure reason not allowed when transitioning to {to}")]
+    UnexpectedFailureReason { to: ShipmentStatus },
+
+    /// No delivery attempts left; the shipment has to be returned.
+    #[error("{attempts} of {max} delivery attempts used")]
+    AttemptsExhausted { attempts: u32, max: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TransitionTableError {
+    /// Terminal states cannot have outgoing edges.
+    #[error("edge {from} -> {to} leaves a terminal state")]
+    EdgeFromTerminal {
+        from: ShipmentStatus,
+        to: ShipmentStatus,
+    },
+
+    /// A status cannot transition to itself.
+    #[error("self transition on {status}")]
+    SelfLoop { status: ShipmentStatus },
+
+    /// The same edge is listed twice.
+    #[error("duplicate edge {from} -> {to}")]
+    DuplicateEdge {
+        from: ShipmentStatus,
+        to: ShipmentStatus,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EventDecodeError {
+    /// Payload is not a Strata map.
+    #[error("event payload is not a map")]
+    NotAMap,
+
+    /// Payload `event_type` does not match the event being decoded.
+    #[error("expected event type {expected}, found {found}")]
+    EventType {
+        expected: &'static str,
+        found: String,
+    },
+
+    /// No codec is registered for this event type.
+    #[error("unknown event type {0}")]
+    UnknownEventType(String),
+
+    /// Required field is absent.
+    #[error("missing field {0}")]
+    MissingField(&'static str),
+
+    /// Field is present but has the wrong type or an unparseable value.
+    #[error("invalid field {0}")]
+    InvalidField(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TrackingNumberError {
+    /// Not a prefix of 2-3 letters followed by serial and check digit.
+    #[error("invalid tracking number format")]
+    InvalidFormat,
+
+    /// Check digit does not match the serial.
+    #[error("invalid tracking number check digit")]
+    InvalidCheckDigit,
+
+    /// Serial does not fit the fixed number of digits.
+    #[error("tracking serial out of range")]
+    SerialOutOfRange,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PieceBarcodeError {
+    /// Not a tracking number, a dash and a two digit piece number.
+    #[error("invalid piece barcode format")]
+    InvalidFormat,
+
+    /// The tracking number part does not validate.
+    #[error("invalid piece barcode: {0}")]
+    TrackingNumber(#[from] TrackingNumberError),
+
+    /// Piece numbers run from 1 to `MAX_PIECES`.
+    #[error("piece number out of range")]
+    PieceOutOfRange,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ProofOfDeliveryError {
+    /// Proof of delivery is only taken on changes to DELIVERED.
+    #[error("proof of delivery not allowed when transitioning to {to}")]
+    NotADelivery { to: ShipmentStatus },
+
+    #[error("receiver name is required")]
+    ReceiverNameRequired,
+
+    /// Neither a signature nor a photo was given.
+    #[error("proof of delivery needs a signature or a photo")]
+    EvidenceRequired,
+
+    /// Images must be PNG or JPEG.
+    #[error("unsupported image format")]
+    UnsupportedImage,
+
+    #[error("image exceeds {max} bytes")]
+    ImageTooLarge { max: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CashOnDeliveryError {
+    /// Not an ISO 4217 code: three uppercase letters.
+    #[error("invalid currency")]
+    InvalidCurrency,
+
+    #[error("cash on delivery amount must be between 1 and {max} cents")]
+    InvalidAmount { max: i64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TariffError {
+    #[error("tariff has no weight bands")]
+    NoWeightBands,
+
+    /// Bands must be listed by strictly increasing weight.
+    #[error("weight band up to {max_weight_g} g is out of order")]
+    UnsortedWeightBands { max_weight_g: i32 },
+
+    /// Every band prices every zone the tariff can select.
+    #[error("weight band up to {max_weight_g} g has no price for zone {zone}")]
+    MissingZonePrice { zone: String, max_weight_g: i32 },
+
+    #[error("{field} must not be negative")]
+    NegativeAmount { field: &'static str },
+
+    #[error("volumetric divisor must be positive")]
+    InvalidVolumetricDivisor,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PricingError {
+    /// Heavier than the last weight band.
+    #[error("chargeable weight {chargeable_weight_g} g exceeds the {max_weight_g} g limit")]
+    TooHeavy {
+        chargeable_weight_g: i64,
+        max_weight_g: i32,
+    },
+
+    /// Cash on delivery is collected in the tariff currency only.
+    #[error("cash on delivery in {found}, tariff is in {expected}")]
+    CurrencyMismatch { expected: String, found: String },
+
+    /// Tariff does not price the zone; only for unvalidated tariffs.
+    #[error("no price for zone {zone}")]
+    UnpricedZone { zone: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum OfficeLinkError {
+    #[error("office cannot link to itself")]
+    SelfLoop,
+
+    #[error("transit time must be positive")]
+    NonPositiveTransit,
+
+    #[error("cost must not be negative")]
+    NegativeCost,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CalendarError {
+    #[error("calendar has no opening hours")]
+    NoOpeningHours,
+
+    #[error("opening hours on {weekday} close before they open")]
+    ClosesBeforeOpens { weekday: Weekday },
+
+    #[error("opening hours on {weekday} overlap")]
+    OverlappingHours { weekday: Weekday },
+
+    #[error("holiday {date} is listed twice")]
+    DuplicateHoliday { date: NaiveDate },
 }
 
 #[cfg(test)]
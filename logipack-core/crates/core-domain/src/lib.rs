@@ -1,2 +1,4 @@
 pub mod errors;
+pub mod network;
+pub mod pricing;
 pub mod shipment;
@@ -0,0 +1,336 @@
+use chrono::{
+    DateTime, Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CalendarError;
+
+/// How far ahead to look for an opening before giving up; covers a year
+/// of holidays.
+const HORIZON_DAYS: u64 = 400;
+
+/// When an office works, as far as delivery estimates are concerned.
+pub trait WorkingCalendar {
+    /// Earliest moment at or after `at_ms` (Unix millis) the office works,
+    /// `None` when it does not open again.
+    fn next_working_moment(&self, at_ms: i64) -> Option<i64>;
+}
+
+impl<T: WorkingCalendar + ?Sized> WorkingCalendar for &T {
+    fn next_working_moment(&self, at_ms: i64) -> Option<i64> {
+        (**self).next_working_moment(at_ms)
+    }
+}
+
+/// Offices without a calendar never close.
+impl<T: WorkingCalendar> WorkingCalendar for Option<T> {
+    fn next_working_moment(&self, at_ms: i64) -> Option<i64> {
+        match self {
+            Some(calendar) => calendar.next_working_moment(at_ms),
+            None => Some(at_ms),
+        }
+    }
+}
+
+/// Office that never closes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AlwaysOpen;
+
+impl WorkingCalendar for AlwaysOpen {
+    fn next_working_moment(&self, at_ms: i64) -> Option<i64> {
+        Some(at_ms)
+    }
+}
+
+/// Opening hours on a weekday, local time, `closes` exclusive. A day may
+/// have several, e.g. around a lunch break; none may cross midnight.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpeningHours {
+    pub weekday: Weekday,
+    pub opens: NaiveTime,
+    pub closes: NaiveTime,
+}
+
+/// Local date the office stays closed all day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: Option<String>,
+}
+
+/// Weekly opening hours of an office and the holidays it closes on, in
+/// the office's timezone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfficeCalendar {
+    pub timezone: Tz,
+    pub hours: Vec<OpeningHours>,
+    #[serde(default)]
+    pub holidays: Vec<Holiday>,
+}
+
+impl OfficeCalendar {
+    pub fn validate(&self) -> Result<(), CalendarError> {
+        if self.hours.is_empty() {
+            return Err(CalendarError::NoOpeningHours);
+        }
+
+        let mut hours: Vec<&OpeningHours> = self.hours.iter().collect();
+        hours.sort_by_key(|h| (h.weekday.num_days_from_monday(), h.opens));
+
+        for (i, h) in hours.iter().enumerate() {
+            if h.closes <= h.opens {
+                return Err(CalendarError::ClosesBeforeOpens { weekday: h.weekday });
+            }
+
+            if let Some(next) = hours.get(i + 1)
+                && next.weekday == h.weekday
+                && next.opens < h.closes
+            {
+                return Err(CalendarError::OverlappingHours { weekday: h.weekday });
+            }
+        }
+
+        let mut dates: Vec<NaiveDate> = self.holidays.iter().map(|h| h.date).collect();
+        dates.sort();
+        if let Some(pair) = dates.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(CalendarError::DuplicateHoliday { date: pair[0] });
+        }
+
+        Ok(())
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.iter().any(|h| h.date == date)
+    }
+
+    /// Earliest moment at or after `at` the office is open, `None` when it
+    /// does not open within the next year or so.
+    pub fn next_working_moment(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let local = at.with_timezone(&self.timezone).naive_local();
+
+        for offset in 0..=HORIZON_DAYS {
+            let date = local.date().checked_add_days(Days::new(offset))?;
+            if self.is_holiday(date) {
+                continue;
+            }
+
+            let from = if offset == 0 {
+                local.time()
+            } else {
+                NaiveTime::MIN
+            };
+
+            let start = self
+                .hours
+                .iter()
+                .filter(|h| h.weekday == date.weekday() && h.closes > from)
+                .map(|h| h.opens.max(from))
+                .min();
+
+            match start {
+                // already open
+                Some(start) if offset == 0 && start == from => return Some(at),
+                Some(start) => return self.to_utc(date.and_time(start)),
+                None => {}
+            }
+        }
+
+        None
+    }
+
+    /// Local time to an instant. Repeated times take the earlier instant;
+    /// times skipped by a DST change move an hour later.
+    fn to_utc(&self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        self.timezone
+            .from_local_datetime(&local)
+            .earliest()
+            .or_else(|| {
+                self.timezone
+                    .from_local_datetime(&(local + TimeDelta::hours(1)))
+                    .earliest()
+            })
+            .map(|at| at.with_timezone(&Utc))
+    }
+}
+
+impl WorkingCalendar for OfficeCalendar {
+    fn next_working_moment(&self, at_ms: i64) -> Option<i64> {
+        let at = DateTime::from_timestamp_millis(at_ms)?;
+        self.next_working_moment(at).map(|at| at.timestamp_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        date(y, m, d).and_time(time(h, min)).and_utc()
+    }
+
+    fn hours(weekday: Weekday, opens: (u32, u32), closes: (u32, u32)) -> OpeningHours {
+        OpeningHours {
+            weekday,
+            opens: time(opens.0, opens.1),
+            closes: time(closes.0, closes.1),
+        }
+    }
+
+    /// Sofia office open 9-13 and 14-18 on weekdays, 10-14 on Saturdays.
+    fn sofia() -> OfficeCalendar {
+        let mut week: Vec<OpeningHours> = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]
+        .into_iter()
+        .flat_map(|day| [hours(day, (9, 0), (13, 0)), hours(day, (14, 0), (18, 0))])
+        .collect();
+        week.push(hours(Weekday::Sat, (10, 0), (14, 0)));
+
+        OfficeCalendar {
+            timezone: chrono_tz::Europe::Sofia,
+            hours: week,
+            holidays: vec![Holiday {
+                date: date(2026, 3, 3),
+                name: Some("Liberation Day".into()),
+            }],
+        }
+    }
+
+    #[test]
+    fn open_moments_are_returned_as_is() {
+        // Mon 2026-03-02 10:30 in Sofia (UTC+2)
+        let at = utc(2026, 3, 2, 8, 30);
+
+        assert_eq!(sofia().next_working_moment(at), Some(at));
+    }
+
+    #[test]
+    fn waits_for_the_next_opening() {
+        let calendar = sofia();
+
+        // lunch break, Mon 13:30 local
+        assert_eq!(
+            calendar.next_working_moment(utc(2026, 3, 2, 11, 30)),
+            Some(utc(2026, 3, 2, 12, 0))
+        );
+
+        // Saturday evening to Monday morning
+        assert_eq!(
+            calendar.next_working_moment(utc(2026, 3, 7, 16, 0)),
+            Some(utc(2026, 3, 9, 7, 0))
+        );
+    }
+
+    #[test]
+    fn skips_holidays() {
+        // Mon evening, Tue 2026-03-03 is a holiday
+        assert_eq!(
+            sofia().next_working_moment(utc(2026, 3, 2, 17, 0)),
+            Some(utc(2026, 3, 4, 7, 0))
+        );
+    }
+
+    #[test]
+    fn follows_daylight_saving_time() {
+        // Sofia moves to UTC+3 on 2026-03-29: Fri evening to Sat 10:00 at
+        // UTC+2, Sat afternoon to Mon 09:00 at UTC+3
+        assert_eq!(
+            sofia().next_working_moment(utc(2026, 3, 27, 17, 0)),
+            Some(utc(2026, 3, 28, 8, 0))
+        );
+        assert_eq!(
+            sofia().next_working_moment(utc(2026, 3, 28, 13, 0)),
+            Some(utc(2026, 3, 30, 6, 0))
+        );
+    }
+
+    #[test]
+    fn office_that_never_opens_has_no_working_moment() {
+        let calendar = OfficeCalendar {
+            timezone: chrono_tz::UTC,
+            hours: vec![hours(Weekday::Mon, (9, 0), (17, 0))],
+            holidays: (0..HORIZON_DAYS + 7)
+                .map(|day| Holiday {
+                    date: date(2026, 1, 1) + Days::new(day),
+                    name: None,
+                })
+                .collect(),
+        };
+
+        assert_eq!(calendar.next_working_moment(utc(2026, 1, 1, 0, 0)), None);
+    }
+
+    #[test]
+    fn millis_follow_the_calendar_and_missing_calendars_never_close() {
+        let at = utc(2026, 3, 7, 16, 0).timestamp_millis();
+
+        assert_eq!(
+            WorkingCalendar::next_working_moment(&sofia(), at),
+            Some(utc(2026, 3, 9, 7, 0).timestamp_millis())
+        );
+        assert_eq!(None::<OfficeCalendar>.next_working_moment(at), Some(at));
+    }
+
+    #[test]
+    fn invalid_calendars_are_rejected() {
+        assert_eq!(
+            OfficeCalendar {
+                hours: vec![],
+                ..sofia()
+            }
+            .validate(),
+            Err(CalendarError::NoOpeningHours)
+        );
+
+        assert_eq!(
+            OfficeCalendar {
+                hours: vec![hours(Weekday::Tue, (17, 0), (9, 0))],
+                ..sofia()
+            }
+            .validate(),
+            Err(CalendarError::ClosesBeforeOpens {
+                weekday: Weekday::Tue
+            })
+        );
+
+        assert_eq!(
+            OfficeCalendar {
+                hours: vec![
+                    hours(Weekday::Wed, (12, 0), (18, 0)),
+                    hours(Weekday::Wed, (9, 0), (13, 0)),
+                    hours(Weekday::Thu, (9, 0), (13, 0)),
+                ],
+                ..sofia()
+            }
+            .validate(),
+            Err(CalendarError::OverlappingHours {
+                weekday: Weekday::Wed
+            })
+        );
+
+        let mut calendar = sofia();
+        calendar.holidays.push(calendar.holidays[0].clone());
+        assert_eq!(
+            calendar.validate(),
+            Err(CalendarError::DuplicateHoliday {
+                date: date(2026, 3, 3)
+            })
+        );
+
+        assert_eq!(sofia().validate(), Ok(()));
+    }
+}
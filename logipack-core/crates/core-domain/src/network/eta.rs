@@ -0,0 +1,116 @@
+use crate::network::{Route, WorkingCalendar};
+use crate::pricing::ServiceLevel;
+
+const MINUTE_MS: i64 = 60_000;
+
+impl ServiceLevel {
+    /// Time a shipment spends at every office on its route: sorting at the
+    /// origin and the hubs, preparing the delivery at the destination.
+    pub fn handling_minutes(self) -> i64 {
+        match self {
+            ServiceLevel::Standard => 240,
+            ServiceLevel::Express => 60,
+        }
+    }
+}
+
+/// Moment a shipment handed in at `handed_in_at_ms` is ready at its
+/// destination, in Unix millis. `None` for an empty route or when an
+/// office on it does not open again.
+///
+/// Each office starts handling it at its next working moment after the
+/// shipment arrives and takes the service level's handling time; legs
+/// take their transit time.
+pub fn estimate_delivery<N, C>(
+    route: &Route<N>,
+    handed_in_at_ms: i64,
+    service_level: ServiceLevel,
+    calendar_of: impl Fn(&N) -> C,
+) -> Option<i64>
+where
+    C: WorkingCalendar,
+{
+    if route.stops.is_empty() {
+        return None;
+    }
+
+    let handling_ms = service_level.handling_minutes() * MINUTE_MS;
+
+    let mut at = handed_in_at_ms;
+    for stop in &route.stops {
+        at += i64::from(stop.transit_minutes) * MINUTE_MS;
+        at = calendar_of(&stop.office).next_working_moment(at)? + handling_ms;
+    }
+
+    Some(at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{AlwaysOpen, RouteStop};
+
+    const HOUR_MS: i64 = 60 * MINUTE_MS;
+
+    /// Closed until `opens_at_ms`, open for good afterwards.
+    struct OpensAt(i64);
+
+    impl WorkingCalendar for OpensAt {
+        fn next_working_moment(&self, at_ms: i64) -> Option<i64> {
+            Some(at_ms.max(self.0))
+        }
+    }
+
+    fn route(legs: &[(&'static str, i32)]) -> Route<&'static str> {
+        Route {
+            stops: legs
+                .iter()
+                .map(|&(office, transit_minutes)| RouteStop {
+                    office,
+                    transit_minutes,
+                    cost_cents: 0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn adds_transit_and_handling_at_every_office() {
+        let route = route(&[("SOF", 0), ("PLO", 120), ("VAR", 300)]);
+
+        let standard = estimate_delivery(&route, 0, ServiceLevel::Standard, |_| AlwaysOpen);
+        assert_eq!(standard, Some(7 * HOUR_MS + 3 * 4 * HOUR_MS));
+
+        let express = estimate_delivery(&route, 0, ServiceLevel::Express, |_| AlwaysOpen);
+        assert_eq!(express, Some(7 * HOUR_MS + 3 * HOUR_MS));
+    }
+
+    #[test]
+    fn waits_for_closed_offices() {
+        let route = route(&[("SOF", 0), ("VAR", 60)]);
+
+        // VAR opens ten hours in; the shipment arrives after two
+        let eta = estimate_delivery(&route, 0, ServiceLevel::Express, |office| {
+            OpensAt(if *office == "VAR" { 10 * HOUR_MS } else { 0 })
+        });
+        assert_eq!(eta, Some(10 * HOUR_MS + HOUR_MS));
+    }
+
+    #[test]
+    fn single_office_route_is_handled_once() {
+        let route = route(&[("SOF", 0)]);
+
+        let eta = estimate_delivery(&route, 5, ServiceLevel::Standard, |_| AlwaysOpen);
+        assert_eq!(eta, Some(5 + 4 * HOUR_MS));
+    }
+
+    #[test]
+    fn empty_route_has_no_estimate() {
+        let route = route(&[]);
+
+        assert_eq!(
+            estimate_delivery(&route, 0, ServiceLevel::Standard, |_| AlwaysOpen),
+            None
+        );
+    }
+}
@@ -0,0 +1,8 @@
+pub mod calendar;
+pub mod eta;
+pub mod route;
+
+pub use calendar::{AlwaysOpen, Holiday, OfficeCalendar, OpeningHours, WorkingCalendar};
+pub use chrono_tz::Tz;
+pub use eta::estimate_delivery;
+pub use route::{OfficeLink, OfficeNetwork, Route, RouteStop};
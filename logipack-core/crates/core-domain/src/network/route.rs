@@ -0,0 +1,273 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::OfficeLinkError;
+
+/// Directed link shipments travel from one office to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfficeLink<N> {
+    pub from: N,
+    pub to: N,
+    pub transit_minutes: i32,
+    /// Minor currency units.
+    pub cost_cents: i64,
+}
+
+impl<N: PartialEq> OfficeLink<N> {
+    pub fn validate(&self) -> Result<(), OfficeLinkError> {
+        if self.from == self.to {
+            return Err(OfficeLinkError::SelfLoop);
+        }
+
+        if self.transit_minutes <= 0 {
+            return Err(OfficeLinkError::NonPositiveTransit);
+        }
+
+        if self.cost_cents < 0 {
+            return Err(OfficeLinkError::NegativeCost);
+        }
+
+        Ok(())
+    }
+}
+
+/// Office on a route with the leg that reaches it. The origin's leg is
+/// zero.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteStop<N> {
+    pub office: N,
+    pub transit_minutes: i32,
+    pub cost_cents: i64,
+}
+
+/// Offices a shipment passes through, origin first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Route<N> {
+    pub stops: Vec<RouteStop<N>>,
+}
+
+impl<N: PartialEq> Route<N> {
+    pub fn origin(&self) -> Option<&N> {
+        self.stops.first().map(|stop| &stop.office)
+    }
+
+    pub fn destination(&self) -> Option<&N> {
+        self.stops.last().map(|stop| &stop.office)
+    }
+
+    pub fn offices(&self) -> impl Iterator<Item = &N> {
+        self.stops.iter().map(|stop| &stop.office)
+    }
+
+    /// Office after `office`, `None` at the destination or off the route.
+    pub fn next_after(&self, office: &N) -> Option<&N> {
+        let at = self.stops.iter().position(|stop| stop.office == *office)?;
+        self.stops.get(at + 1).map(|stop| &stop.office)
+    }
+
+    pub fn transit_minutes(&self) -> i64 {
+        self.stops
+            .iter()
+            .map(|s| i64::from(s.transit_minutes))
+            .sum()
+    }
+
+    pub fn cost_cents(&self) -> i64 {
+        self.stops.iter().map(|s| s.cost_cents).sum()
+    }
+}
+
+/// Directed graph of office links.
+#[derive(Debug, Clone, Default)]
+pub struct OfficeNetwork<N> {
+    outgoing: BTreeMap<N, Vec<OfficeLink<N>>>,
+}
+
+impl<N: Clone + Ord> OfficeNetwork<N> {
+    /// Builds the graph from validated links.
+    pub fn new(links: impl IntoIterator<Item = OfficeLink<N>>) -> Self {
+        let mut outgoing: BTreeMap<N, Vec<OfficeLink<N>>> = BTreeMap::new();
+        for link in links {
+            outgoing.entry(link.from.clone()).or_default().push(link);
+        }
+
+        Self { outgoing }
+    }
+
+    /// Fastest route from `from` to `to`, `None` when `to` is unreachable.
+    ///
+    /// Dijkstra over transit time; equally fast routes are compared by
+    /// cost, then by number of hops, then by office order, so the same
+    /// network always yields the same route.
+    pub fn plan(&self, from: &N, to: &N) -> Option<Route<N>> {
+        // (minutes, cost, hops) from `from`
+        let mut best: BTreeMap<N, (i64, i64, usize)> = BTreeMap::new();
+        let mut via: BTreeMap<N, &OfficeLink<N>> = BTreeMap::new();
+        let mut queue = BinaryHeap::new();
+
+        best.insert(from.clone(), (0, 0, 0));
+        queue.push(Reverse((0i64, 0i64, 0usize, from.clone())));
+
+        while let Some(Reverse((minutes, cost, hops, office))) = queue.pop() {
+            if best.get(&office) != Some(&(minutes, cost, hops)) {
+                // stale entry, a better one was already settled
+                continue;
+            }
+
+            if office == *to {
+                break;
+            }
+
+            for link in self.outgoing.get(&office).into_iter().flatten() {
+                let reached = (
+                    minutes + i64::from(link.transit_minutes),
+                    cost + link.cost_cents,
+                    hops + 1,
+                );
+
+                if best.get(&link.to).is_none_or(|known| reached < *known) {
+                    best.insert(link.to.clone(), reached);
+                    via.insert(link.to.clone(), link);
+                    queue.push(Reverse((reached.0, reached.1, reached.2, link.to.clone())));
+                }
+            }
+        }
+
+        best.get(to)?;
+
+        let mut stops = Vec::new();
+        let mut office = to;
+        while let Some(link) = via.get(office) {
+            stops.push(RouteStop {
+                office: link.to.clone(),
+                transit_minutes: link.transit_minutes,
+                cost_cents: link.cost_cents,
+            });
+            office = &link.from;
+        }
+        stops.push(RouteStop {
+            office: from.clone(),
+            transit_minutes: 0,
+            cost_cents: 0,
+        });
+        stops.reverse();
+
+        Some(Route { stops })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(
+        from: &'static str,
+        to: &'static str,
+        minutes: i32,
+        cost: i64,
+    ) -> OfficeLink<&'static str> {
+        OfficeLink {
+            from,
+            to,
+            transit_minutes: minutes,
+            cost_cents: cost,
+        }
+    }
+
+    fn offices(route: &Route<&'static str>) -> Vec<&'static str> {
+        route.offices().copied().collect()
+    }
+
+    #[test]
+    fn plans_the_fastest_route() {
+        let network = OfficeNetwork::new([
+            link("SOF", "VAR", 600, 1_000),
+            link("SOF", "PLO", 120, 400),
+            link("PLO", "BUR", 180, 500),
+            link("BUR", "VAR", 120, 300),
+        ]);
+
+        let route = network.plan(&"SOF", &"VAR").unwrap();
+
+        assert_eq!(offices(&route), vec!["SOF", "PLO", "BUR", "VAR"]);
+        assert_eq!(route.transit_minutes(), 420);
+        assert_eq!(route.cost_cents(), 1_200);
+        assert_eq!(route.stops[0].transit_minutes, 0);
+        assert_eq!(route.stops[2].cost_cents, 500);
+    }
+
+    #[test]
+    fn equally_fast_routes_are_decided_by_cost_then_hops() {
+        let network = OfficeNetwork::new([
+            link("A", "B", 60, 500),
+            link("B", "D", 60, 500),
+            link("A", "C", 60, 200),
+            link("C", "D", 60, 200),
+            link("A", "D", 120, 450),
+        ]);
+
+        let route = network.plan(&"A", &"D").unwrap();
+        assert_eq!(offices(&route), vec!["A", "C", "D"]);
+
+        let network = OfficeNetwork::new([
+            link("A", "C", 60, 200),
+            link("C", "D", 60, 200),
+            link("A", "D", 120, 400),
+        ]);
+
+        let route = network.plan(&"A", &"D").unwrap();
+        assert_eq!(offices(&route), vec!["A", "D"]);
+    }
+
+    #[test]
+    fn links_are_directed() {
+        let network = OfficeNetwork::new([link("A", "B", 60, 100)]);
+
+        assert!(network.plan(&"A", &"B").is_some());
+        assert_eq!(network.plan(&"B", &"A"), None);
+        assert_eq!(network.plan(&"A", &"Z"), None);
+    }
+
+    #[test]
+    fn route_to_the_same_office_has_a_single_stop() {
+        let route = OfficeNetwork::new([link("A", "B", 60, 100)])
+            .plan(&"A", &"A")
+            .unwrap();
+
+        assert_eq!(offices(&route), vec!["A"]);
+        assert_eq!(route.transit_minutes(), 0);
+    }
+
+    #[test]
+    fn next_office_follows_the_route() {
+        let route = OfficeNetwork::new([link("A", "B", 60, 100), link("B", "C", 60, 100)])
+            .plan(&"A", &"C")
+            .unwrap();
+
+        assert_eq!(route.next_after(&"A"), Some(&"B"));
+        assert_eq!(route.next_after(&"B"), Some(&"C"));
+        assert_eq!(route.next_after(&"C"), None);
+        assert_eq!(route.next_after(&"Z"), None);
+        assert_eq!(route.origin(), Some(&"A"));
+        assert_eq!(route.destination(), Some(&"C"));
+    }
+
+    #[test]
+    fn invalid_links_are_rejected() {
+        assert_eq!(
+            link("A", "A", 60, 100).validate(),
+            Err(OfficeLinkError::SelfLoop)
+        );
+        assert_eq!(
+            link("A", "B", 0, 100).validate(),
+            Err(OfficeLinkError::NonPositiveTransit)
+        );
+        assert_eq!(
+            link("A", "B", 60, -1).validate(),
+            Err(OfficeLinkError::NegativeCost)
+        );
+        assert_eq!(link("A", "B", 60, 0).validate(), Ok(()));
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+
+use strata::value::Value;
+
+use crate::errors::EventDecodeError;
+use crate::pricing::{
+    Fee, Tariff, TariffVersionRecorded, TariffVersionWithdrawn, WeightBand, ZoneRoute,
+};
+use crate::shipment::ActorRef;
+use crate::shipment::EventCodec;
+use crate::shipment::codec::{
+    currency_field, event_fields, i32_field, int_field, list_field, map_item, opt_int_field,
+    opt_map_field, string_field,
+};
+
+impl EventCodec for TariffVersionRecorded {
+    const EVENT_TYPE: &'static str = "TariffVersionRecorded";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert("tariff_id".into(), Value::String(self.tariff_id.clone()));
+        fields.insert(
+            "effective_from_ms".into(),
+            Value::Int(self.effective_from_ms),
+        );
+        fields.insert(
+            "effective_to_ms".into(),
+            self.effective_to_ms.map_or(Value::Null, Value::Int),
+        );
+        fields.insert("tariff".into(), tariff_value(&self.tariff));
+        fields.insert("actor_user_id".into(), Value::String(self.actor.id.clone()));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            tariff_id: string_field(fields, "tariff_id")?,
+            effective_from_ms: int_field(fields, "effective_from_ms")?,
+            effective_to_ms: opt_int_field(fields, "effective_to_ms")?,
+            tariff: tariff_field(fields, "tariff")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+        })
+    }
+}
+
+impl EventCodec for TariffVersionWithdrawn {
+    const EVENT_TYPE: &'static str = "TariffVersionWithdrawn";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert("tariff_id".into(), Value::String(self.tariff_id.clone()));
+        fields.insert("actor_user_id".into(), Value::String(self.actor.id.clone()));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            tariff_id: string_field(fields, "tariff_id")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+        })
+    }
+}
+
+fn tariff_value(tariff: &Tariff) -> Value {
+    let routes = tariff
+        .routes
+        .iter()
+        .map(|route| {
+            let mut fields = BTreeMap::new();
+            fields.insert("from_city".into(), Value::String(route.from_city.clone()));
+            fields.insert("to_city".into(), Value::String(route.to_city.clone()));
+            fields.insert("zone".into(), Value::String(route.zone.clone()));
+            Value::Map(fields)
+        })
+        .collect();
+
+    let bands = tariff
+        .bands
+        .iter()
+        .map(|band| {
+            let prices = band
+                .prices
+                .iter()
+                .map(|(zone, cents)| (zone.clone(), Value::Int(*cents)))
+                .collect();
+
+            let mut fields = BTreeMap::new();
+            fields.insert("max_weight_g".into(), Value::Int(band.max_weight_g.into()));
+            fields.insert("prices".into(), Value::Map(prices));
+            Value::Map(fields)
+        })
+        .collect();
+
+    let mut fields = BTreeMap::new();
+    fields.insert(
+        "currency".into(),
+        Value::String(tariff.currency.to_string()),
+    );
+    fields.insert(
+        "local_zone".into(),
+        Value::String(tariff.local_zone.clone()),
+    );
+    fields.insert(
+        "default_zone".into(),
+        Value::String(tariff.default_zone.clone()),
+    );
+    fields.insert("routes".into(), Value::List(routes));
+    fields.insert(
+        "volumetric_divisor".into(),
+        Value::Int(tariff.volumetric_divisor),
+    );
+    fields.insert("bands".into(), Value::List(bands));
+    fields.insert("express".into(), fee_value(&tariff.express));
+    fields.insert("cod".into(), fee_value(&tariff.cod));
+    fields.insert("declared_value".into(), fee_value(&tariff.declared_value));
+    fields.insert("tax_bps".into(), Value::Int(tariff.tax_bps));
+
+    Value::Map(fields)
+}
+
+fn fee_value(fee: &Fee) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert("rate_bps".into(), Value::Int(fee.rate_bps));
+    fields.insert("min_cents".into(), Value::Int(fee.min_cents));
+
+    Value::Map(fields)
+}
+
+fn tariff_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Tariff, EventDecodeError> {
+    let tariff = opt_map_field(fields, key)?.ok_or(EventDecodeError::MissingField(key))?;
+
+    let routes = list_field(tariff, "routes")?
+        .iter()
+        .map(|route| {
+            let route = map_item(route, "routes")?;
+            Ok(ZoneRoute {
+                from_city: string_field(route, "from_city")?,
+                to_city: string_field(route, "to_city")?,
+                zone: string_field(route, "zone")?,
+            })
+        })
+        .collect::<Result<_, EventDecodeError>>()?;
+
+    let bands = list_field(tariff, "bands")?
+        .iter()
+        .map(|band| {
+            let band = map_item(band, "bands")?;
+            let prices = opt_map_field(band, "prices")?
+                .ok_or(EventDecodeError::MissingField("prices"))?
+                .iter()
+                .map(|(zone, cents)| match cents {
+                    Value::Int(cents) => Ok((zone.clone(), *cents)),
+                    _ => Err(EventDecodeError::InvalidField("prices")),
+                })
+                .collect::<Result<_, EventDecodeError>>()?;
+
+            Ok(WeightBand {
+                max_weight_g: i32_field(band, "max_weight_g")?,
+                prices,
+            })
+        })
+        .collect::<Result<_, EventDecodeError>>()?;
+
+    Ok(Tariff {
+        currency: currency_field(tariff, "currency")?,
+        local_zone: string_field(tariff, "local_zone")?,
+        default_zone: string_field(tariff, "default_zone")?,
+        routes,
+        volumetric_divisor: int_field(tariff, "volumetric_divisor")?,
+        bands,
+        express: fee_field(tariff, "express")?,
+        cod: fee_field(tariff, "cod")?,
+        declared_value: fee_field(tariff, "declared_value")?,
+        tax_bps: int_field(tariff, "tax_bps")?,
+    })
+}
+
+fn fee_field(fields: &BTreeMap<String, Value>, key: &'static str) -> Result<Fee, EventDecodeError> {
+    let fee = opt_map_field(fields, key)?.ok_or(EventDecodeError::MissingField(key))?;
+
+    Ok(Fee {
+        rate_bps: int_field(fee, "rate_bps")?,
+        min_cents: int_field(fee, "min_cents")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor() -> ActorRef {
+        ActorRef {
+            id: "user-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn tariff_version_recorded_round_trips() {
+        let mut tariff = Tariff::default();
+        tariff.routes.push(ZoneRoute {
+            from_city: "Sofia".to_string(),
+            to_city: "Plovdiv".to_string(),
+            zone: "NATIONAL".to_string(),
+        });
+
+        let event = TariffVersionRecorded {
+            tariff_id: "tariff-1".to_string(),
+            effective_from_ms: 1_700_000_000_000,
+            effective_to_ms: Some(1_710_000_000_000),
+            tariff,
+            actor: actor(),
+            occurred_at_ms: 1_699_000_000_000,
+        };
+        assert_eq!(
+            TariffVersionRecorded::decode(&event.encode()).unwrap(),
+            event
+        );
+
+        let open_ended = TariffVersionRecorded {
+            effective_to_ms: None,
+            ..event
+        };
+        assert_eq!(
+            TariffVersionRecorded::decode(&open_ended.encode()).unwrap(),
+            open_ended
+        );
+    }
+
+    #[test]
+    fn tariff_version_withdrawn_round_trips() {
+        let event = TariffVersionWithdrawn {
+            tariff_id: "tariff-1".to_string(),
+            actor: actor(),
+            occurred_at_ms: 1_700_000_000_000,
+        };
+        assert_eq!(
+            TariffVersionWithdrawn::decode(&event.encode()).unwrap(),
+            event
+        );
+
+        assert!(matches!(
+            TariffVersionRecorded::decode(&event.encode()),
+            Err(EventDecodeError::EventType { .. })
+        ));
+    }
+}
@@ -0,0 +1,29 @@
+use crate::pricing::Tariff;
+use crate::shipment::ActorRef;
+
+/// Domain event emitted when a tariff version is created or edited.
+///
+/// Carries the whole version, so the last one in the stream is the
+/// version as it stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TariffVersionRecorded {
+    pub tariff_id: String,
+    /// Unix timestamp in millis, inclusive.
+    pub effective_from_ms: i64,
+    /// Unix timestamp in millis, exclusive. `None` while open-ended.
+    pub effective_to_ms: Option<i64>,
+    pub tariff: Tariff,
+    pub actor: ActorRef,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
+}
+
+/// Domain event emitted when a tariff version is deleted before it came
+/// into force.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TariffVersionWithdrawn {
+    pub tariff_id: String,
+    pub actor: ActorRef,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
+}
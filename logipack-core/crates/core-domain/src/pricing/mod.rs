@@ -0,0 +1,8 @@
+pub mod codec;
+pub mod events;
+pub mod quote;
+pub mod tariff;
+
+pub use events::{TariffVersionRecorded, TariffVersionWithdrawn};
+pub use quote::{Price, QuoteRequest, ServiceLevel};
+pub use tariff::{Fee, Tariff, WeightBand, ZoneRoute};
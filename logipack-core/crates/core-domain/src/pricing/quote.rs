@@ -0,0 +1,265 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::PricingError;
+use crate::pricing::tariff::{Tariff, bps};
+use crate::shipment::{CashOnDelivery, Currency, Parcel};
+
+/// How fast a shipment is carried.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ServiceLevel {
+    #[default]
+    Standard,
+    Express,
+}
+
+impl std::str::FromStr for ServiceLevel {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "STANDARD" => Ok(ServiceLevel::Standard),
+            "EXPRESS" => Ok(ServiceLevel::Express),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for ServiceLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level_str = match self {
+            ServiceLevel::Standard => "STANDARD",
+            ServiceLevel::Express => "EXPRESS",
+        };
+        write!(f, "{}", level_str)
+    }
+}
+
+/// What a shipment is priced on.
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteRequest<'a> {
+    pub origin_city: &'a str,
+    pub destination_city: &'a str,
+    pub parcel: &'a Parcel,
+    pub cod: Option<&'a CashOnDelivery>,
+    pub service_level: ServiceLevel,
+}
+
+/// Price of a shipment with its breakdown, in minor currency units.
+///
+/// Recorded on the shipment at creation so the price stays as charged
+/// when the tariff changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Price {
+    pub currency: Currency,
+    pub zone: String,
+    /// Greater of the actual and the volumetric weight.
+    pub chargeable_weight_g: i32,
+    pub base_cents: i64,
+    pub express_fee_cents: i64,
+    pub cod_fee_cents: i64,
+    pub declared_value_fee_cents: i64,
+    pub tax_cents: i64,
+    pub total_cents: i64,
+}
+
+impl Price {
+    pub fn subtotal_cents(&self) -> i64 {
+        self.total_cents - self.tax_cents
+    }
+}
+
+impl Tariff {
+    pub fn quote(&self, request: QuoteRequest<'_>) -> Result<Price, PricingError> {
+        let chargeable_weight_g = self.chargeable_weight_g(request.parcel);
+
+        let band = self
+            .band(chargeable_weight_g)
+            .ok_or_else(|| PricingError::TooHeavy {
+                chargeable_weight_g,
+                max_weight_g: self.bands.last().map_or(0, |band| band.max_weight_g),
+            })?;
+
+        let zone = self.zone(request.origin_city, request.destination_city);
+        let base_cents = *band
+            .prices
+            .get(zone)
+            .ok_or_else(|| PricingError::UnpricedZone {
+                zone: zone.to_string(),
+            })?;
+
+        let express_fee_cents = match request.service_level {
+            ServiceLevel::Standard => 0,
+            ServiceLevel::Express => self.express.of(base_cents),
+        };
+
+        let cod_fee_cents = match request.cod {
+            Some(cod) if cod.currency != self.currency => {
+                return Err(PricingError::CurrencyMismatch {
+                    expected: self.currency.to_string(),
+                    found: cod.currency.to_string(),
+                });
+            }
+            Some(cod) => self.cod.of(cod.amount_cents),
+            None => 0,
+        };
+
+        let declared_value_fee_cents = match request.parcel.declared_value_cents {
+            Some(value) if value > 0 => self.declared_value.of(value),
+            _ => 0,
+        };
+
+        let subtotal = base_cents + express_fee_cents + cod_fee_cents + declared_value_fee_cents;
+        let tax_cents = bps(subtotal, self.tax_bps);
+
+        Ok(Price {
+            currency: self.currency.clone(),
+            zone: zone.to_string(),
+            chargeable_weight_g: chargeable_weight_g as i32,
+            base_cents,
+            express_fee_cents,
+            cod_fee_cents,
+            declared_value_fee_cents,
+            tax_cents,
+            total_cents: subtotal + tax_cents,
+        })
+    }
+
+    /// Greater of the actual weight and the volume over the divisor,
+    /// rounded up to the gram.
+    fn chargeable_weight_g(&self, parcel: &Parcel) -> i64 {
+        let volumetric_g = parcel.dimensions.map_or(0, |d| {
+            let volume_mm3 =
+                i128::from(d.length_mm) * i128::from(d.width_mm) * i128::from(d.height_mm);
+            let divisor = i128::from(self.volumetric_divisor.max(1));
+
+            ((volume_mm3 * 1_000 + divisor - 1) / divisor).min(i128::from(i64::MAX)) as i64
+        });
+
+        i64::from(parcel.weight_g).max(volumetric_g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shipment::Dimensions;
+
+    fn parcel(weight_g: i32) -> Parcel {
+        Parcel {
+            weight_g,
+            dimensions: None,
+            declared_value_cents: None,
+            description: None,
+        }
+    }
+
+    fn quote(parcel: &Parcel, cod: Option<&CashOnDelivery>, level: ServiceLevel) -> Price {
+        Tariff::default()
+            .quote(QuoteRequest {
+                origin_city: "Sofia",
+                destination_city: "Varna",
+                parcel,
+                cod,
+                service_level: level,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn service_level_display_and_parse_round_trip() {
+        for level in [ServiceLevel::Standard, ServiceLevel::Express] {
+            assert_eq!(level.to_string().parse(), Ok(level));
+        }
+
+        assert_eq!("express".parse::<ServiceLevel>(), Err(()));
+    }
+
+    #[test]
+    fn base_price_comes_from_the_weight_band_and_zone() {
+        let price = quote(&parcel(1_000), None, ServiceLevel::Standard);
+
+        assert_eq!(price.zone, "NATIONAL");
+        assert_eq!(price.chargeable_weight_g, 1_000);
+        assert_eq!(price.base_cents, 600);
+        assert_eq!(price.tax_cents, 120);
+        assert_eq!(price.total_cents, 720);
+        assert_eq!(price.subtotal_cents(), 600);
+
+        let price = quote(&parcel(1_001), None, ServiceLevel::Standard);
+        assert_eq!(price.base_cents, 850);
+    }
+
+    #[test]
+    fn bulky_parcels_are_charged_by_volume() {
+        // 40 x 30 x 20 cm is 4.8 kg at 5000 cm³/kg
+        let bulky = Parcel {
+            dimensions: Some(Dimensions {
+                length_mm: 400,
+                width_mm: 300,
+                height_mm: 200,
+            }),
+            ..parcel(500)
+        };
+
+        let price = quote(&bulky, None, ServiceLevel::Standard);
+        assert_eq!(price.chargeable_weight_g, 4_800);
+        assert_eq!(price.base_cents, 850);
+    }
+
+    #[test]
+    fn surcharges_are_added_before_tax() {
+        let insured = Parcel {
+            declared_value_cents: Some(50_000),
+            ..parcel(800)
+        };
+        let cod = CashOnDelivery {
+            amount_cents: 20_000,
+            currency: "BGN".parse().unwrap(),
+        };
+
+        let price = quote(&insured, Some(&cod), ServiceLevel::Express);
+        assert_eq!(price.base_cents, 600);
+        assert_eq!(price.express_fee_cents, 300);
+        assert_eq!(price.cod_fee_cents, 300);
+        assert_eq!(price.declared_value_fee_cents, 250);
+        assert_eq!(price.subtotal_cents(), 1_450);
+        assert_eq!(price.tax_cents, 290);
+        assert_eq!(price.total_cents, 1_740);
+    }
+
+    #[test]
+    fn unpriceable_shipments_are_rejected() {
+        let tariff = Tariff::default();
+        let (heavy, light) = (parcel(32_001), parcel(100));
+        let request = |parcel, cod| QuoteRequest {
+            origin_city: "Sofia",
+            destination_city: "Sofia",
+            parcel,
+            cod,
+            service_level: ServiceLevel::Standard,
+        };
+
+        assert_eq!(
+            tariff.quote(request(&heavy, None)),
+            Err(PricingError::TooHeavy {
+                chargeable_weight_g: 32_001,
+                max_weight_g: 32_000
+            })
+        );
+
+        let euros = CashOnDelivery {
+            amount_cents: 1_000,
+            currency: "EUR".parse().unwrap(),
+        };
+        assert_eq!(
+            tariff.quote(request(&light, Some(&euros))),
+            Err(PricingError::CurrencyMismatch {
+                expected: "BGN".into(),
+                found: "EUR".into()
+            })
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::TariffError;
+use crate::shipment::Currency;
+
+/// Zone of shipments between two cities, in either direction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZoneRoute {
+    pub from_city: String,
+    pub to_city: String,
+    pub zone: String,
+}
+
+/// Base prices of shipments up to `max_weight_g`, per zone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeightBand {
+    pub max_weight_g: i32,
+    /// Minor currency units by zone.
+    pub prices: BTreeMap<String, i64>,
+}
+
+/// Percentage of an amount with a floor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fee {
+    /// Basis points: 100 is 1%.
+    #[serde(default)]
+    pub rate_bps: i64,
+    /// Charged when the rate comes out lower.
+    #[serde(default)]
+    pub min_cents: i64,
+}
+
+impl Fee {
+    /// Fee on `amount_cents`, rounded half up.
+    pub fn of(&self, amount_cents: i64) -> i64 {
+        bps(amount_cents, self.rate_bps).max(self.min_cents)
+    }
+}
+
+/// `amount_cents * rate_bps / 10_000`, rounded half up.
+pub(crate) fn bps(amount_cents: i64, rate_bps: i64) -> i64 {
+    let scaled = i128::from(amount_cents) * i128::from(rate_bps);
+    ((scaled + 5_000) / 10_000) as i64
+}
+
+/// What shipments cost: zones between office cities, base prices by
+/// weight band and zone, surcharges and tax.
+///
+/// The default is the built-in LogiPack price list. Other tariffs are
+/// deserialized from the same shape and checked with `validate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tariff {
+    /// Currency of every amount in the tariff.
+    pub currency: Currency,
+    /// Zone of shipments within one city.
+    pub local_zone: String,
+    /// Zone of city pairs not listed in `routes`.
+    pub default_zone: String,
+    #[serde(default)]
+    pub routes: Vec<ZoneRoute>,
+    /// Cubic millimetres per kilogram of volumetric weight.
+    pub volumetric_divisor: i64,
+    /// By increasing `max_weight_g`.
+    pub bands: Vec<WeightBand>,
+    /// On the base price of express shipments.
+    #[serde(default)]
+    pub express: Fee,
+    /// On the cash on delivery amount.
+    #[serde(default)]
+    pub cod: Fee,
+    /// On the declared value.
+    #[serde(default)]
+    pub declared_value: Fee,
+    /// On the subtotal, basis points.
+    #[serde(default)]
+    pub tax_bps: i64,
+}
+
+impl Default for Tariff {
+    fn default() -> Self {
+        let band = |max_weight_g, local, national| WeightBand {
+            max_weight_g,
+            prices: BTreeMap::from([
+                ("LOCAL".to_string(), local),
+                ("NATIONAL".to_string(), national),
+            ]),
+        };
+
+        Self {
+            currency: "BGN".parse().expect("valid currency"),
+            local_zone: "LOCAL".to_string(),
+            default_zone: "NATIONAL".to_string(),
+            routes: vec![],
+            // 5000 cm³ per kg
+            volumetric_divisor: 5_000_000,
+            bands: vec![
+                band(1_000, 450, 600),
+                band(5_000, 600, 850),
+                band(10_000, 800, 1_200),
+                band(20_000, 1_200, 1_800),
+                band(32_000, 1_800, 2_600),
+            ],
+            express: Fee {
+                rate_bps: 5_000,
+                min_cents: 300,
+            },
+            cod: Fee {
+                rate_bps: 150,
+                min_cents: 100,
+            },
+            declared_value: Fee {
+                rate_bps: 50,
+                min_cents: 50,
+            },
+            tax_bps: 2_000,
+        }
+    }
+}
+
+impl Tariff {
+    pub fn validate(&self) -> Result<(), TariffError> {
+        if self.bands.is_empty() {
+            return Err(TariffError::NoWeightBands);
+        }
+
+        if self.volumetric_divisor <= 0 {
+            return Err(TariffError::InvalidVolumetricDivisor);
+        }
+
+        let fees = [
+            ("express.rate_bps", self.express.rate_bps),
+            ("express.min_cents", self.express.min_cents),
+            ("cod.rate_bps", self.cod.rate_bps),
+            ("cod.min_cents", self.cod.min_cents),
+            ("declared_value.rate_bps", self.declared_value.rate_bps),
+            ("declared_value.min_cents", self.declared_value.min_cents),
+            ("tax_bps", self.tax_bps),
+        ];
+        if let Some((field, _)) = fees.iter().find(|(_, amount)| *amount < 0) {
+            return Err(TariffError::NegativeAmount { field });
+        }
+
+        let zones: Vec<&str> = [self.local_zone.as_str(), self.default_zone.as_str()]
+            .into_iter()
+            .chain(self.routes.iter().map(|route| route.zone.as_str()))
+            .collect();
+
+        for (i, band) in self.bands.iter().enumerate() {
+            if band.max_weight_g <= 0
+                || i > 0 && band.max_weight_g <= self.bands[i - 1].max_weight_g
+            {
+                return Err(TariffError::UnsortedWeightBands {
+                    max_weight_g: band.max_weight_g,
+                });
+            }
+
+            for zone in &zones {
+                match band.prices.get(*zone) {
+                    None => {
+                        return Err(TariffError::MissingZonePrice {
+                            zone: zone.to_string(),
+                            max_weight_g: band.max_weight_g,
+                        });
+                    }
+                    Some(price) if *price < 0 => {
+                        return Err(TariffError::NegativeAmount {
+                            field: "bands.prices",
+                        });
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Zone between two office cities. Cities compare case-insensitively.
+    pub fn zone(&self, from_city: &str, to_city: &str) -> &str {
+        let same = |a: &str, b: &str| a.trim().to_lowercase() == b.trim().to_lowercase();
+
+        if same(from_city, to_city) {
+            return &self.local_zone;
+        }
+
+        self.routes
+            .iter()
+            .find(|route| {
+                same(&route.from_city, from_city) && same(&route.to_city, to_city)
+                    || same(&route.from_city, to_city) && same(&route.to_city, from_city)
+            })
+            .map_or(&self.default_zone, |route| &route.zone)
+    }
+
+    /// Lightest band the weight fits in.
+    pub fn band(&self, weight_g: i64) -> Option<&WeightBand> {
+        self.bands
+            .iter()
+            .find(|band| i64::from(band.max_weight_g) >= weight_g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_tariff_is_valid() {
+        assert_eq!(Tariff::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn zones_are_local_routed_or_default() {
+        let tariff = Tariff {
+            routes: vec![ZoneRoute {
+                from_city: "Sofia".into(),
+                to_city: "Plovdiv".into(),
+                zone: "REGIONAL".into(),
+            }],
+            ..Tariff::default()
+        };
+
+        assert_eq!(tariff.zone("Sofia", " sofia "), "LOCAL");
+        assert_eq!(tariff.zone("Sofia", "Plovdiv"), "REGIONAL");
+        assert_eq!(tariff.zone("PLOVDIV", "Sofia"), "REGIONAL");
+        assert_eq!(tariff.zone("Sofia", "Varna"), "NATIONAL");
+    }
+
+    #[test]
+    fn routed_zones_must_be_priced_in_every_band() {
+        let tariff = Tariff {
+            routes: vec![ZoneRoute {
+                from_city: "Sofia".into(),
+                to_city: "Plovdiv".into(),
+                zone: "REGIONAL".into(),
+            }],
+            ..Tariff::default()
+        };
+
+        assert_eq!(
+            tariff.validate(),
+            Err(TariffError::MissingZonePrice {
+                zone: "REGIONAL".into(),
+                max_weight_g: 1_000
+            })
+        );
+    }
+
+    #[test]
+    fn bands_must_increase() {
+        let mut tariff = Tariff::default();
+        tariff.bands.swap(1, 2);
+
+        assert_eq!(
+            tariff.validate(),
+            Err(TariffError::UnsortedWeightBands {
+                max_weight_g: 5_000
+            })
+        );
+
+        tariff.bands.clear();
+        assert_eq!(tariff.validate(), Err(TariffError::NoWeightBands));
+    }
+
+    #[test]
+    fn negative_amounts_are_rejected() {
+        let tariff = Tariff {
+            cod: Fee {
+                rate_bps: -1,
+                min_cents: 0,
+            },
+            ..Tariff::default()
+        };
+
+        assert_eq!(
+            tariff.validate(),
+            Err(TariffError::NegativeAmount {
+                field: "cod.rate_bps"
+            })
+        );
+    }
+
+    #[test]
+    fn fees_round_half_up_and_respect_the_minimum() {
+        let fee = Fee {
+            rate_bps: 150,
+            min_cents: 100,
+        };
+
+        assert_eq!(fee.of(10_000), 150);
+        assert_eq!(fee.of(1_000), 100);
+        assert_eq!(bps(1_001, 5_000), 501);
+        assert_eq!(bps(1_000, 2_000), 200);
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CashOnDeliveryError;
+
+/// Largest amount collected on one shipment, in minor units.
+pub const MAX_COD_CENTS: i64 = 100_000_000;
+
+/// ISO 4217 currency code, e.g. `BGN`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Currency(String);
+
+impl Currency {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::str::FromStr for Currency {
+    type Err = CashOnDeliveryError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.len() != 3 || !value.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(CashOnDeliveryError::InvalidCurrency);
+        }
+
+        Ok(Self(value.to_owned()))
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = CashOnDeliveryError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        currency.0
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Amount the courier collects from the recipient on delivery and owes
+/// to the client afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CashOnDelivery {
+    /// In minor currency units.
+    pub amount_cents: i64,
+    pub currency: Currency,
+}
+
+impl CashOnDelivery {
+    pub fn validate(&self) -> Result<(), CashOnDeliveryError> {
+        if self.amount_cents <= 0 || self.amount_cents > MAX_COD_CENTS {
+            return Err(CashOnDeliveryError::InvalidAmount { max: MAX_COD_CENTS });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bgn() -> Currency {
+        "BGN".parse().unwrap()
+    }
+
+    #[test]
+    fn currency_is_three_uppercase_letters() {
+        assert_eq!(bgn().to_string(), "BGN");
+        assert_eq!(bgn().as_str(), "BGN");
+
+        for bad in ["bgn", "BG", "BGNN", "B1N", "ЛВ."] {
+            assert_eq!(
+                bad.parse::<Currency>(),
+                Err(CashOnDeliveryError::InvalidCurrency)
+            );
+        }
+    }
+
+    fn cod(amount_cents: i64) -> CashOnDelivery {
+        CashOnDelivery {
+            amount_cents,
+            currency: bgn(),
+        }
+    }
+
+    #[test]
+    fn amount_must_be_positive_and_bounded() {
+        assert_eq!(cod(1).validate(), Ok(()));
+        assert_eq!(cod(MAX_COD_CENTS).validate(), Ok(()));
+
+        for bad in [0, -100, MAX_COD_CENTS + 1] {
+            assert_eq!(
+                cod(bad).validate(),
+                Err(CashOnDeliveryError::InvalidAmount { max: MAX_COD_CENTS })
+            );
+        }
+    }
+}
@@ -0,0 +1,1520 @@
+use std::collections::BTreeMap;
+
+use strata::value::Value;
+
+use crate::errors::EventDecodeError;
+use crate::network::{Route, RouteStop};
+use crate::pricing::{Price, ServiceLevel};
+use crate::shipment::{
+    ActorRef, Address, BlobHash, CashOnDelivery, CodCollected, CodRemitted, Currency,
+    DeliveryFailureReason, Dimensions, HoldPlaced, HoldReason, HoldReleased, OfficeContext, Parcel,
+    Party, PieceAdded, PieceScanned, ProofOfDelivery, RoutePlanned, ShipmentCreated,
+    ShipmentStatus, SlaBreached, StatusChanged,
+};
+
+/// Maps a domain event to and from the Strata payload stored in the event store.
+///
+/// Field names are part of the stored format. Optional fields decode as
+/// `None` when absent, so adding one does not break older packages; any
+/// other shape change bumps `SCHEMA_VERSION` and needs an upcaster from the
+/// previous version (see `core_eventstore::adapter::upcast`).
+///
+/// `decode` only understands the current version.
+pub trait EventCodec: Sized {
+    /// Stored in `packages.event_type` and in the payload's `event_type` key.
+    const EVENT_TYPE: &'static str;
+
+    /// Stored in `packages.schema_version`.
+    const SCHEMA_VERSION: i32;
+
+    fn encode(&self) -> Value;
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError>;
+}
+
+/// Any event found in a shipment stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShipmentEvent {
+    // boxed, the parties and parcel make it several times larger
+    Created(Box<ShipmentCreated>),
+    StatusChanged(StatusChanged),
+    HoldPlaced(HoldPlaced),
+    HoldReleased(HoldReleased),
+    PieceAdded(PieceAdded),
+    PieceScanned(PieceScanned),
+    CodCollected(CodCollected),
+    CodRemitted(CodRemitted),
+    RoutePlanned(RoutePlanned),
+    SlaBreached(SlaBreached),
+}
+
+impl ShipmentEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            ShipmentEvent::Created(_) => ShipmentCreated::EVENT_TYPE,
+            ShipmentEvent::StatusChanged(_) => StatusChanged::EVENT_TYPE,
+            ShipmentEvent::HoldPlaced(_) => HoldPlaced::EVENT_TYPE,
+            ShipmentEvent::HoldReleased(_) => HoldReleased::EVENT_TYPE,
+            ShipmentEvent::PieceAdded(_) => PieceAdded::EVENT_TYPE,
+            ShipmentEvent::PieceScanned(_) => PieceScanned::EVENT_TYPE,
+            ShipmentEvent::CodCollected(_) => CodCollected::EVENT_TYPE,
+            ShipmentEvent::CodRemitted(_) => CodRemitted::EVENT_TYPE,
+            ShipmentEvent::RoutePlanned(_) => RoutePlanned::EVENT_TYPE,
+            ShipmentEvent::SlaBreached(_) => SlaBreached::EVENT_TYPE,
+        }
+    }
+
+    pub fn schema_version(&self) -> i32 {
+        match self {
+            ShipmentEvent::Created(_) => ShipmentCreated::SCHEMA_VERSION,
+            ShipmentEvent::StatusChanged(_) => StatusChanged::SCHEMA_VERSION,
+            ShipmentEvent::HoldPlaced(_) => HoldPlaced::SCHEMA_VERSION,
+            ShipmentEvent::HoldReleased(_) => HoldReleased::SCHEMA_VERSION,
+            ShipmentEvent::PieceAdded(_) => PieceAdded::SCHEMA_VERSION,
+            ShipmentEvent::PieceScanned(_) => PieceScanned::SCHEMA_VERSION,
+            ShipmentEvent::CodCollected(_) => CodCollected::SCHEMA_VERSION,
+            ShipmentEvent::CodRemitted(_) => CodRemitted::SCHEMA_VERSION,
+            ShipmentEvent::RoutePlanned(_) => RoutePlanned::SCHEMA_VERSION,
+            ShipmentEvent::SlaBreached(_) => SlaBreached::SCHEMA_VERSION,
+        }
+    }
+
+    pub fn encode(&self) -> Value {
+        match self {
+            ShipmentEvent::Created(e) => e.encode(),
+            ShipmentEvent::StatusChanged(e) => e.encode(),
+            ShipmentEvent::HoldPlaced(e) => e.encode(),
+            ShipmentEvent::HoldReleased(e) => e.encode(),
+            ShipmentEvent::PieceAdded(e) => e.encode(),
+            ShipmentEvent::PieceScanned(e) => e.encode(),
+            ShipmentEvent::CodCollected(e) => e.encode(),
+            ShipmentEvent::CodRemitted(e) => e.encode(),
+            ShipmentEvent::RoutePlanned(e) => e.encode(),
+            ShipmentEvent::SlaBreached(e) => e.encode(),
+        }
+    }
+
+    /// Decodes a payload using the codec registered for `event_type`.
+    pub fn decode(event_type: &str, value: &Value) -> Result<Self, EventDecodeError> {
+        match event_type {
+            ShipmentCreated::EVENT_TYPE => ShipmentCreated::decode(value)
+                .map(Box::new)
+                .map(Self::Created),
+            StatusChanged::EVENT_TYPE => StatusChanged::decode(value).map(Self::StatusChanged),
+            HoldPlaced::EVENT_TYPE => HoldPlaced::decode(value).map(Self::HoldPlaced),
+            HoldReleased::EVENT_TYPE => HoldReleased::decode(value).map(Self::HoldReleased),
+            PieceAdded::EVENT_TYPE => PieceAdded::decode(value).map(Self::PieceAdded),
+            PieceScanned::EVENT_TYPE => PieceScanned::decode(value).map(Self::PieceScanned),
+            CodCollected::EVENT_TYPE => CodCollected::decode(value).map(Self::CodCollected),
+            CodRemitted::EVENT_TYPE => CodRemitted::decode(value).map(Self::CodRemitted),
+            RoutePlanned::EVENT_TYPE => RoutePlanned::decode(value).map(Self::RoutePlanned),
+            SlaBreached::EVENT_TYPE => SlaBreached::decode(value).map(Self::SlaBreached),
+            other => Err(EventDecodeError::UnknownEventType(other.to_owned())),
+        }
+    }
+}
+
+impl EventCodec for ShipmentCreated {
+    const EVENT_TYPE: &'static str = "ShipmentCreated";
+    // v2: `occured_at` renamed to `occurred_at_ms`
+    const SCHEMA_VERSION: i32 = 2;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert(
+            "shipment_id".into(),
+            Value::String(self.shipment_id.clone()),
+        );
+        if let Some(tracking_number) = &self.tracking_number {
+            fields.insert(
+                "tracking_number".into(),
+                Value::String(tracking_number.clone()),
+            );
+        }
+        if let Some(client_id) = &self.client_id {
+            fields.insert("client_id".into(), Value::String(client_id.clone()));
+        }
+        fields.insert("status".into(), Value::String(self.status.to_string()));
+        fields.insert("actor_user_id".into(), Value::String(self.actor.id.clone()));
+        fields.insert("office_id".into(), office_value(&self.office));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+        fields.insert("notes".into(), opt_string_value(&self.notes));
+        if let Some(sender) = &self.sender {
+            fields.insert("sender".into(), party_value(sender));
+        }
+        if let Some(recipient) = &self.recipient {
+            fields.insert("recipient".into(), party_value(recipient));
+        }
+        if let Some(office) = &self.destination_office {
+            fields.insert(
+                "destination_office_id".into(),
+                Value::String(office.office_id.clone()),
+            );
+        }
+        if let Some(parcel) = &self.parcel {
+            fields.insert("parcel".into(), parcel_value(parcel));
+        }
+        if let Some(cod) = &self.cod {
+            fields.insert("cod".into(), cod_value(cod));
+        }
+        // absent means STANDARD, which keeps older payloads byte-identical
+        if self.service_level != ServiceLevel::Standard {
+            fields.insert(
+                "service_level".into(),
+                Value::String(self.service_level.to_string()),
+            );
+        }
+        if let Some(price) = &self.price {
+            fields.insert("price".into(), price_value(price));
+        }
+        if let Some(tariff_id) = &self.tariff_id {
+            fields.insert("tariff_id".into(), Value::String(tariff_id.clone()));
+        }
+        if let Some(promised_by_ms) = self.promised_by_ms {
+            fields.insert("promised_by_ms".into(), Value::Int(promised_by_ms));
+        }
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            tracking_number: opt_string_field(fields, "tracking_number")?,
+            client_id: opt_string_field(fields, "client_id")?,
+            status: status_field(fields, "status")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            office: office_field(fields, "office_id")?,
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+            notes: opt_string_field(fields, "notes")?,
+            sender: party_field(fields, "sender")?,
+            recipient: party_field(fields, "recipient")?,
+            destination_office: office_field(fields, "destination_office_id")?,
+            parcel: parcel_field(fields, "parcel")?,
+            cod: cod_field(fields, "cod")?,
+            service_level: opt_string_field(fields, "service_level")?
+                .map(|raw| {
+                    raw.parse()
+                        .map_err(|_| EventDecodeError::InvalidField("service_level"))
+                })
+                .transpose()?
+                .unwrap_or_default(),
+            price: price_field(fields, "price")?,
+            tariff_id: opt_string_field(fields, "tariff_id")?,
+            promised_by_ms: opt_int_field(fields, "promised_by_ms")?,
+        })
+    }
+}
+
+impl EventCodec for StatusChanged {
+    const EVENT_TYPE: &'static str = "StatusChanged";
+    // v2: `occured_at` renamed to `occurred_at_ms`
+    const SCHEMA_VERSION: i32 = 2;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert(
+            "shipment_id".into(),
+            Value::String(self.shipment_id.clone()),
+        );
+        fields.insert(
+            "from_status".into(),
+            Value::String(self.from_status.to_string()),
+        );
+        fields.insert(
+            "to_status".into(),
+            Value::String(self.to_status.to_string()),
+        );
+        fields.insert("actor_user_id".into(), Value::String(self.actor.id.clone()));
+        fields.insert("from_office_id".into(), office_value(&self.from_office));
+        fields.insert("to_office_id".into(), office_value(&self.to_office));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+        fields.insert("notes".into(), opt_string_value(&self.notes));
+        if let Some(reason) = &self.failure_reason {
+            fields.insert("failure_reason".into(), Value::String(reason.to_string()));
+        }
+        if let Some(attempt) = self.attempt {
+            fields.insert("attempt".into(), Value::Int(attempt.into()));
+        }
+        if let Some(pod) = &self.proof_of_delivery {
+            fields.insert("proof_of_delivery".into(), pod_value(pod));
+        }
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            from_status: status_field(fields, "from_status")?,
+            to_status: status_field(fields, "to_status")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            from_office: office_field(fields, "from_office_id")?,
+            to_office: office_field(fields, "to_office_id")?,
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+            notes: opt_string_field(fields, "notes")?,
+            failure_reason: failure_reason_field(fields, "failure_reason")?,
+            attempt: opt_int_field(fields, "attempt")?
+                .map(|n| u32::try_from(n).map_err(|_| EventDecodeError::InvalidField("attempt")))
+                .transpose()?,
+            proof_of_delivery: pod_field(fields, "proof_of_delivery")?,
+        })
+    }
+}
+
+impl EventCodec for HoldPlaced {
+    const EVENT_TYPE: &'static str = "HoldPlaced";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        hold_value(
+            Self::EVENT_TYPE,
+            &self.shipment_id,
+            self.reason,
+            &self.actor,
+            &self.office,
+            self.occurred_at_ms,
+            &self.notes,
+        )
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            reason: hold_reason_field(fields, "reason")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            office: office_field(fields, "office_id")?,
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+            notes: opt_string_field(fields, "notes")?,
+        })
+    }
+}
+
+impl EventCodec for HoldReleased {
+    const EVENT_TYPE: &'static str = "HoldReleased";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        hold_value(
+            Self::EVENT_TYPE,
+            &self.shipment_id,
+            self.reason,
+            &self.actor,
+            &self.office,
+            self.occurred_at_ms,
+            &self.notes,
+        )
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            reason: hold_reason_field(fields, "reason")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            office: office_field(fields, "office_id")?,
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+            notes: opt_string_field(fields, "notes")?,
+        })
+    }
+}
+
+impl EventCodec for PieceAdded {
+    const EVENT_TYPE: &'static str = "PieceAdded";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert(
+            "shipment_id".into(),
+            Value::String(self.shipment_id.clone()),
+        );
+        fields.insert("piece_id".into(), Value::String(self.piece_id.clone()));
+        fields.insert("barcode".into(), Value::String(self.barcode.clone()));
+        fields.insert("status".into(), Value::String(self.status.to_string()));
+        fields.insert("actor_user_id".into(), Value::String(self.actor.id.clone()));
+        fields.insert("office_id".into(), office_value(&self.office));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+        if let Some(weight_g) = self.weight_g {
+            fields.insert("weight_g".into(), Value::Int(weight_g.into()));
+        }
+        if let Some(description) = &self.description {
+            fields.insert("description".into(), Value::String(description.clone()));
+        }
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            piece_id: string_field(fields, "piece_id")?,
+            barcode: string_field(fields, "barcode")?,
+            status: status_field(fields, "status")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            office: office_field(fields, "office_id")?,
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+            weight_g: opt_int_field(fields, "weight_g")?
+                .map(|n| i32::try_from(n).map_err(|_| EventDecodeError::InvalidField("weight_g")))
+                .transpose()?,
+            description: opt_string_field(fields, "description")?,
+        })
+    }
+}
+
+impl EventCodec for PieceScanned {
+    const EVENT_TYPE: &'static str = "PieceScanned";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert(
+            "shipment_id".into(),
+            Value::String(self.shipment_id.clone()),
+        );
+        fields.insert("piece_id".into(), Value::String(self.piece_id.clone()));
+        fields.insert("barcode".into(), Value::String(self.barcode.clone()));
+        fields.insert(
+            "from_status".into(),
+            Value::String(self.from_status.to_string()),
+        );
+        fields.insert(
+            "to_status".into(),
+            Value::String(self.to_status.to_string()),
+        );
+        fields.insert("actor_user_id".into(), Value::String(self.actor.id.clone()));
+        fields.insert("from_office_id".into(), office_value(&self.from_office));
+        fields.insert("to_office_id".into(), office_value(&self.to_office));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+        fields.insert("notes".into(), opt_string_value(&self.notes));
+        if let Some(reason) = &self.failure_reason {
+            fields.insert("failure_reason".into(), Value::String(reason.to_string()));
+        }
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            piece_id: string_field(fields, "piece_id")?,
+            barcode: string_field(fields, "barcode")?,
+            from_status: status_field(fields, "from_status")?,
+            to_status: status_field(fields, "to_status")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            from_office: office_field(fields, "from_office_id")?,
+            to_office: office_field(fields, "to_office_id")?,
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+            notes: opt_string_field(fields, "notes")?,
+            failure_reason: failure_reason_field(fields, "failure_reason")?,
+        })
+    }
+}
+
+impl EventCodec for CodCollected {
+    const EVENT_TYPE: &'static str = "CodCollected";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert(
+            "shipment_id".into(),
+            Value::String(self.shipment_id.clone()),
+        );
+        fields.insert(
+            "collection_id".into(),
+            Value::String(self.collection_id.clone()),
+        );
+        fields.insert("amount_cents".into(), Value::Int(self.amount_cents));
+        fields.insert("currency".into(), Value::String(self.currency.to_string()));
+        fields.insert("actor_user_id".into(), Value::String(self.actor.id.clone()));
+        fields.insert("office_id".into(), office_value(&self.office));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            collection_id: string_field(fields, "collection_id")?,
+            amount_cents: int_field(fields, "amount_cents")?,
+            currency: currency_field(fields, "currency")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            office: office_field(fields, "office_id")?,
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+        })
+    }
+}
+
+impl EventCodec for CodRemitted {
+    const EVENT_TYPE: &'static str = "CodRemitted";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert(
+            "shipment_id".into(),
+            Value::String(self.shipment_id.clone()),
+        );
+        fields.insert(
+            "collection_id".into(),
+            Value::String(self.collection_id.clone()),
+        );
+        fields.insert(
+            "remittance_id".into(),
+            Value::String(self.remittance_id.clone()),
+        );
+        fields.insert("amount_cents".into(), Value::Int(self.amount_cents));
+        fields.insert("currency".into(), Value::String(self.currency.to_string()));
+        fields.insert("actor_user_id".into(), Value::String(self.actor.id.clone()));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+        fields.insert("reference".into(), opt_string_value(&self.reference));
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            collection_id: string_field(fields, "collection_id")?,
+            remittance_id: string_field(fields, "remittance_id")?,
+            amount_cents: int_field(fields, "amount_cents")?,
+            currency: currency_field(fields, "currency")?,
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+            reference: opt_string_field(fields, "reference")?,
+        })
+    }
+}
+
+impl EventCodec for RoutePlanned {
+    const EVENT_TYPE: &'static str = "RoutePlanned";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert(
+            "shipment_id".into(),
+            Value::String(self.shipment_id.clone()),
+        );
+        fields.insert(
+            "stops".into(),
+            Value::List(self.route.stops.iter().map(route_stop_value).collect()),
+        );
+        fields.insert("actor_user_id".into(), Value::String(self.actor.id.clone()));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        let stops = list_field(fields, "stops")?
+            .iter()
+            .map(|item| {
+                let stop = map_item(item, "stops")?;
+                Ok(RouteStop {
+                    office: string_field(stop, "office_id")?,
+                    transit_minutes: i32_field(stop, "transit_minutes")?,
+                    cost_cents: int_field(stop, "cost_cents")?,
+                })
+            })
+            .collect::<Result<_, EventDecodeError>>()?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            route: Route { stops },
+            actor: ActorRef {
+                id: string_field(fields, "actor_user_id")?,
+            },
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+        })
+    }
+}
+
+impl EventCodec for SlaBreached {
+    const EVENT_TYPE: &'static str = "SlaBreached";
+    const SCHEMA_VERSION: i32 = 1;
+
+    fn encode(&self) -> Value {
+        let mut fields = BTreeMap::new();
+
+        fields.insert("event_type".into(), Value::String(Self::EVENT_TYPE.into()));
+        fields.insert(
+            "shipment_id".into(),
+            Value::String(self.shipment_id.clone()),
+        );
+        fields.insert("promised_by_ms".into(), Value::Int(self.promised_by_ms));
+        fields.insert("status".into(), Value::String(self.status.to_string()));
+        fields.insert("office_id".into(), office_value(&self.office));
+        fields.insert("occurred_at_ms".into(), Value::Int(self.occurred_at_ms));
+
+        Value::Map(fields)
+    }
+
+    fn decode(value: &Value) -> Result<Self, EventDecodeError> {
+        let fields = event_fields(value, Self::EVENT_TYPE)?;
+
+        Ok(Self {
+            shipment_id: string_field(fields, "shipment_id")?,
+            promised_by_ms: int_field(fields, "promised_by_ms")?,
+            status: status_field(fields, "status")?,
+            office: office_field(fields, "office_id")?,
+            occurred_at_ms: int_field(fields, "occurred_at_ms")?,
+        })
+    }
+}
+
+// both hold events share one payload shape
+fn hold_value(
+    event_type: &str,
+    shipment_id: &str,
+    reason: HoldReason,
+    actor: &ActorRef,
+    office: &Option<OfficeContext>,
+    occurred_at_ms: i64,
+    notes: &Option<String>,
+) -> Value {
+    let mut fields = BTreeMap::new();
+
+    fields.insert("event_type".into(), Value::String(event_type.into()));
+    fields.insert("shipment_id".into(), Value::String(shipment_id.into()));
+    fields.insert("reason".into(), Value::String(reason.to_string()));
+    fields.insert("actor_user_id".into(), Value::String(actor.id.clone()));
+    fields.insert("office_id".into(), office_value(office));
+    fields.insert("occurred_at_ms".into(), Value::Int(occurred_at_ms));
+    fields.insert("notes".into(), opt_string_value(notes));
+
+    Value::Map(fields)
+}
+
+fn office_value(office: &Option<OfficeContext>) -> Value {
+    match office {
+        Some(office) => Value::String(office.office_id.clone()),
+        None => Value::Null,
+    }
+}
+
+fn opt_string_value(value: &Option<String>) -> Value {
+    match value {
+        Some(s) => Value::String(s.clone()),
+        None => Value::Null,
+    }
+}
+
+fn route_stop_value(stop: &RouteStop<String>) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert("office_id".into(), Value::String(stop.office.clone()));
+    fields.insert(
+        "transit_minutes".into(),
+        Value::Int(stop.transit_minutes.into()),
+    );
+    fields.insert("cost_cents".into(), Value::Int(stop.cost_cents));
+
+    Value::Map(fields)
+}
+
+fn party_value(party: &Party) -> Value {
+    let mut address = BTreeMap::new();
+    address.insert("line1".into(), Value::String(party.address.line1.clone()));
+    address.insert("line2".into(), opt_string_value(&party.address.line2));
+    address.insert("city".into(), Value::String(party.address.city.clone()));
+    address.insert(
+        "postal_code".into(),
+        Value::String(party.address.postal_code.clone()),
+    );
+    address.insert(
+        "country".into(),
+        Value::String(party.address.country.clone()),
+    );
+
+    let mut fields = BTreeMap::new();
+    fields.insert("name".into(), Value::String(party.name.clone()));
+    fields.insert("phone".into(), opt_string_value(&party.phone));
+    fields.insert("email".into(), opt_string_value(&party.email));
+    fields.insert("address".into(), Value::Map(address));
+
+    Value::Map(fields)
+}
+
+fn parcel_value(parcel: &Parcel) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert("weight_g".into(), Value::Int(parcel.weight_g.into()));
+    fields.insert(
+        "dimensions".into(),
+        match parcel.dimensions {
+            Some(d) => {
+                let mut dims = BTreeMap::new();
+                dims.insert("length_mm".into(), Value::Int(d.length_mm.into()));
+                dims.insert("width_mm".into(), Value::Int(d.width_mm.into()));
+                dims.insert("height_mm".into(), Value::Int(d.height_mm.into()));
+                Value::Map(dims)
+            }
+            None => Value::Null,
+        },
+    );
+    fields.insert(
+        "declared_value_cents".into(),
+        match parcel.declared_value_cents {
+            Some(cents) => Value::Int(cents),
+            None => Value::Null,
+        },
+    );
+    fields.insert("description".into(), opt_string_value(&parcel.description));
+
+    Value::Map(fields)
+}
+
+fn pod_value(pod: &ProofOfDelivery) -> Value {
+    let blob = |hash: &Option<BlobHash>| match hash {
+        Some(hash) => Value::Bytes(hash.as_bytes().to_vec()),
+        None => Value::Null,
+    };
+
+    let mut fields = BTreeMap::new();
+    fields.insert(
+        "receiver_name".into(),
+        Value::String(pod.receiver_name.clone()),
+    );
+    fields.insert(
+        "relationship".into(),
+        Value::String(pod.relationship.to_string()),
+    );
+    fields.insert("received_at_ms".into(), Value::Int(pod.received_at_ms));
+    fields.insert("signature_hash".into(), blob(&pod.signature));
+    fields.insert("photo_hash".into(), blob(&pod.photo));
+
+    Value::Map(fields)
+}
+
+fn price_value(price: &Price) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert("currency".into(), Value::String(price.currency.to_string()));
+    fields.insert("zone".into(), Value::String(price.zone.clone()));
+    fields.insert(
+        "chargeable_weight_g".into(),
+        Value::Int(price.chargeable_weight_g.into()),
+    );
+    fields.insert("base_cents".into(), Value::Int(price.base_cents));
+    fields.insert(
+        "express_fee_cents".into(),
+        Value::Int(price.express_fee_cents),
+    );
+    fields.insert("cod_fee_cents".into(), Value::Int(price.cod_fee_cents));
+    fields.insert(
+        "declared_value_fee_cents".into(),
+        Value::Int(price.declared_value_fee_cents),
+    );
+    fields.insert("tax_cents".into(), Value::Int(price.tax_cents));
+    fields.insert("total_cents".into(), Value::Int(price.total_cents));
+
+    Value::Map(fields)
+}
+
+fn cod_value(cod: &CashOnDelivery) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert("amount_cents".into(), Value::Int(cod.amount_cents));
+    fields.insert("currency".into(), Value::String(cod.currency.to_string()));
+
+    Value::Map(fields)
+}
+
+pub(crate) fn event_fields<'a>(
+    value: &'a Value,
+    expected: &'static str,
+) -> Result<&'a BTreeMap<String, Value>, EventDecodeError> {
+    let Value::Map(fields) = value else {
+        return Err(EventDecodeError::NotAMap);
+    };
+
+    let found = string_field(fields, "event_type")?;
+    if found != expected {
+        return Err(EventDecodeError::EventType { expected, found });
+    }
+
+    Ok(fields)
+}
+
+pub(crate) fn opt_string_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<String>, EventDecodeError> {
+    match fields.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(EventDecodeError::InvalidField(key)),
+    }
+}
+
+pub(crate) fn string_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<String, EventDecodeError> {
+    opt_string_field(fields, key)?.ok_or(EventDecodeError::MissingField(key))
+}
+
+pub(crate) fn int_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<i64, EventDecodeError> {
+    match fields.get(key) {
+        Some(Value::Int(v)) => Ok(*v),
+        Some(_) => Err(EventDecodeError::InvalidField(key)),
+        None => Err(EventDecodeError::MissingField(key)),
+    }
+}
+
+pub(crate) fn opt_int_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<i64>, EventDecodeError> {
+    match fields.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(_) => int_field(fields, key).map(Some),
+    }
+}
+
+pub(crate) fn i32_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<i32, EventDecodeError> {
+    int_field(fields, key)?
+        .try_into()
+        .map_err(|_| EventDecodeError::InvalidField(key))
+}
+
+pub(crate) fn opt_map_field<'a>(
+    fields: &'a BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<&'a BTreeMap<String, Value>>, EventDecodeError> {
+    match fields.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Map(map)) => Ok(Some(map)),
+        Some(_) => Err(EventDecodeError::InvalidField(key)),
+    }
+}
+
+pub(crate) fn list_field<'a>(
+    fields: &'a BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<&'a [Value], EventDecodeError> {
+    match fields.get(key) {
+        Some(Value::List(items)) => Ok(items),
+        Some(_) => Err(EventDecodeError::InvalidField(key)),
+        None => Err(EventDecodeError::MissingField(key)),
+    }
+}
+
+pub(crate) fn map_item<'a>(
+    item: &'a Value,
+    key: &'static str,
+) -> Result<&'a BTreeMap<String, Value>, EventDecodeError> {
+    match item {
+        Value::Map(fields) => Ok(fields),
+        _ => Err(EventDecodeError::InvalidField(key)),
+    }
+}
+
+fn party_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<Party>, EventDecodeError> {
+    let Some(party) = opt_map_field(fields, key)? else {
+        return Ok(None);
+    };
+    let address =
+        opt_map_field(party, "address")?.ok_or(EventDecodeError::MissingField("address"))?;
+
+    Ok(Some(Party {
+        name: string_field(party, "name")?,
+        phone: opt_string_field(party, "phone")?,
+        email: opt_string_field(party, "email")?,
+        address: Address {
+            line1: string_field(address, "line1")?,
+            line2: opt_string_field(address, "line2")?,
+            city: string_field(address, "city")?,
+            postal_code: string_field(address, "postal_code")?,
+            country: string_field(address, "country")?,
+        },
+    }))
+}
+
+fn parcel_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<Parcel>, EventDecodeError> {
+    let Some(parcel) = opt_map_field(fields, key)? else {
+        return Ok(None);
+    };
+
+    let dimensions = match opt_map_field(parcel, "dimensions")? {
+        Some(dims) => Some(Dimensions {
+            length_mm: i32_field(dims, "length_mm")?,
+            width_mm: i32_field(dims, "width_mm")?,
+            height_mm: i32_field(dims, "height_mm")?,
+        }),
+        None => None,
+    };
+
+    Ok(Some(Parcel {
+        weight_g: i32_field(parcel, "weight_g")?,
+        dimensions,
+        declared_value_cents: opt_int_field(parcel, "declared_value_cents")?,
+        description: opt_string_field(parcel, "description")?,
+    }))
+}
+
+fn pod_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<ProofOfDelivery>, EventDecodeError> {
+    let Some(pod) = opt_map_field(fields, key)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(ProofOfDelivery {
+        receiver_name: string_field(pod, "receiver_name")?,
+        relationship: string_field(pod, "relationship")?
+            .parse()
+            .map_err(|_| EventDecodeError::InvalidField("relationship"))?,
+        received_at_ms: int_field(pod, "received_at_ms")?,
+        signature: blob_hash_field(pod, "signature_hash")?,
+        photo: blob_hash_field(pod, "photo_hash")?,
+    }))
+}
+
+fn cod_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<CashOnDelivery>, EventDecodeError> {
+    let Some(cod) = opt_map_field(fields, key)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(CashOnDelivery {
+        amount_cents: int_field(cod, "amount_cents")?,
+        currency: currency_field(cod, "currency")?,
+    }))
+}
+
+fn price_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<Price>, EventDecodeError> {
+    let Some(price) = opt_map_field(fields, key)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(Price {
+        currency: currency_field(price, "currency")?,
+        zone: string_field(price, "zone")?,
+        chargeable_weight_g: i32_field(price, "chargeable_weight_g")?,
+        base_cents: int_field(price, "base_cents")?,
+        express_fee_cents: int_field(price, "express_fee_cents")?,
+        cod_fee_cents: int_field(price, "cod_fee_cents")?,
+        declared_value_fee_cents: int_field(price, "declared_value_fee_cents")?,
+        tax_cents: int_field(price, "tax_cents")?,
+        total_cents: int_field(price, "total_cents")?,
+    }))
+}
+
+pub(crate) fn currency_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Currency, EventDecodeError> {
+    string_field(fields, key)?
+        .parse()
+        .map_err(|_| EventDecodeError::InvalidField(key))
+}
+
+fn blob_hash_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<BlobHash>, EventDecodeError> {
+    match fields.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Bytes(bytes)) => BlobHash::from_slice(bytes)
+            .map(Some)
+            .ok_or(EventDecodeError::InvalidField(key)),
+        Some(_) => Err(EventDecodeError::InvalidField(key)),
+    }
+}
+
+fn status_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<ShipmentStatus, EventDecodeError> {
+    string_field(fields, key)?
+        .parse()
+        .map_err(|_| EventDecodeError::InvalidField(key))
+}
+
+fn failure_reason_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<DeliveryFailureReason>, EventDecodeError> {
+    opt_string_field(fields, key)?
+        .map(|raw| raw.parse().map_err(|_| EventDecodeError::InvalidField(key)))
+        .transpose()
+}
+
+fn hold_reason_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<HoldReason, EventDecodeError> {
+    string_field(fields, key)?
+        .parse()
+        .map_err(|_| EventDecodeError::InvalidField(key))
+}
+
+fn office_field(
+    fields: &BTreeMap<String, Value>,
+    key: &'static str,
+) -> Result<Option<OfficeContext>, EventDecodeError> {
+    Ok(opt_string_field(fields, key)?.map(|office_id| OfficeContext { office_id }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata::{int, map, null, string};
+
+    fn created() -> ShipmentCreated {
+        ShipmentCreated {
+            shipment_id: "shipment-1".to_string(),
+            tracking_number: None,
+            client_id: Some("client-1".to_string()),
+            status: ShipmentStatus::New,
+            actor: ActorRef {
+                id: "user-1".to_string(),
+            },
+            office: Some(OfficeContext {
+                office_id: "office-1".to_string(),
+            }),
+            occurred_at_ms: 1_700_000_000_000,
+            notes: Some("fragile".to_string()),
+            sender: None,
+            recipient: None,
+            destination_office: None,
+            parcel: None,
+            cod: None,
+            service_level: ServiceLevel::Standard,
+            price: None,
+            tariff_id: None,
+            promised_by_ms: None,
+        }
+    }
+
+    fn party(name: &str, city: &str) -> Party {
+        Party {
+            name: name.to_string(),
+            phone: Some("+359888123456".to_string()),
+            email: None,
+            address: Address {
+                line1: "1 Vitosha Blvd".to_string(),
+                line2: None,
+                city: city.to_string(),
+                postal_code: "1000".to_string(),
+                country: "BG".to_string(),
+            },
+        }
+    }
+
+    fn status_changed() -> StatusChanged {
+        StatusChanged {
+            shipment_id: "shipment-1".to_string(),
+            from_status: ShipmentStatus::Processed,
+            to_status: ShipmentStatus::InTransit,
+            actor: ActorRef {
+                id: "user-1".to_string(),
+            },
+            from_office: Some(OfficeContext {
+                office_id: "office-1".to_string(),
+            }),
+            to_office: Some(OfficeContext {
+                office_id: "office-2".to_string(),
+            }),
+            occurred_at_ms: 1_700_000_000_001,
+            notes: None,
+            failure_reason: None,
+            attempt: None,
+            proof_of_delivery: None,
+        }
+    }
+
+    #[test]
+    fn shipment_created_round_trips() {
+        let event = created();
+        assert_eq!(ShipmentCreated::decode(&event.encode()).unwrap(), event);
+    }
+
+    #[test]
+    fn shipment_created_with_tracking_and_details_round_trips() {
+        let event = ShipmentCreated {
+            tracking_number: Some("SOF473124829".to_string()),
+            sender: Some(party("Jane Doe", "Sofia")),
+            recipient: Some(party("John Roe", "Plovdiv")),
+            destination_office: Some(OfficeContext {
+                office_id: "office-2".to_string(),
+            }),
+            parcel: Some(Parcel {
+                weight_g: 1_250,
+                dimensions: Some(Dimensions {
+                    length_mm: 300,
+                    width_mm: 200,
+                    height_mm: 100,
+                }),
+                declared_value_cents: Some(4_999),
+                description: Some("books".to_string()),
+            }),
+            cod: Some(CashOnDelivery {
+                amount_cents: 2_550,
+                currency: "BGN".parse().unwrap(),
+            }),
+            service_level: ServiceLevel::Express,
+            price: Some(Price {
+                currency: "BGN".parse().unwrap(),
+                zone: "NATIONAL".to_string(),
+                chargeable_weight_g: 1_250,
+                base_cents: 850,
+                express_fee_cents: 425,
+                cod_fee_cents: 100,
+                declared_value_fee_cents: 50,
+                tax_cents: 285,
+                total_cents: 1_710,
+            }),
+            tariff_id: Some("tariff-1".to_string()),
+            promised_by_ms: Some(1_700_000_500_000),
+            ..created()
+        };
+
+        assert_eq!(ShipmentCreated::decode(&event.encode()).unwrap(), event);
+    }
+
+    #[test]
+    fn status_changed_round_trips() {
+        let event = status_changed();
+        assert_eq!(StatusChanged::decode(&event.encode()).unwrap(), event);
+    }
+
+    #[test]
+    fn failed_delivery_round_trips() {
+        let event = StatusChanged {
+            from_status: ShipmentStatus::OutForDelivery,
+            to_status: ShipmentStatus::DeliveryFailed,
+            failure_reason: Some(DeliveryFailureReason::RecipientAbsent),
+            attempt: Some(2),
+            ..status_changed()
+        };
+        assert_eq!(StatusChanged::decode(&event.encode()).unwrap(), event);
+    }
+
+    #[test]
+    fn proof_of_delivery_round_trips() {
+        let event = StatusChanged {
+            from_status: ShipmentStatus::OutForDelivery,
+            to_status: ShipmentStatus::Delivered,
+            proof_of_delivery: Some(ProofOfDelivery {
+                receiver_name: "Maria Ivanova".to_string(),
+                relationship: crate::shipment::ReceiverRelationship::Neighbour,
+                received_at_ms: 1_700_000_000_000,
+                signature: Some(BlobHash::of(b"signature")),
+                photo: None,
+            }),
+            ..status_changed()
+        };
+        assert_eq!(StatusChanged::decode(&event.encode()).unwrap(), event);
+    }
+
+    #[test]
+    fn proof_of_delivery_with_short_hash_is_rejected() {
+        let Value::Map(mut fields) = status_changed().encode() else {
+            unreachable!();
+        };
+        fields.insert(
+            "proof_of_delivery".into(),
+            map! {
+                "receiver_name" => string!("Maria Ivanova"),
+                "relationship" => string!("NEIGHBOUR"),
+                "received_at_ms" => int!(1_700_000_000_000),
+                "signature_hash" => Value::Bytes(vec![0; 31]),
+                "photo_hash" => null!(),
+            },
+        );
+
+        assert_eq!(
+            StatusChanged::decode(&Value::Map(fields)).unwrap_err(),
+            EventDecodeError::InvalidField("signature_hash")
+        );
+    }
+
+    #[test]
+    fn hold_events_round_trip() {
+        let placed = HoldPlaced {
+            shipment_id: "shipment-1".to_string(),
+            reason: HoldReason::Customs,
+            actor: ActorRef {
+                id: "user-1".to_string(),
+            },
+            office: Some(OfficeContext {
+                office_id: "office-1".to_string(),
+            }),
+            occurred_at_ms: 1_700_000_000_002,
+            notes: Some("awaiting clearance".to_string()),
+        };
+        let released = HoldReleased {
+            shipment_id: placed.shipment_id.clone(),
+            reason: placed.reason,
+            actor: placed.actor.clone(),
+            office: None,
+            occurred_at_ms: 1_700_000_000_003,
+            notes: None,
+        };
+
+        for event in [
+            ShipmentEvent::HoldPlaced(placed),
+            ShipmentEvent::HoldReleased(released),
+        ] {
+            let decoded = ShipmentEvent::decode(event.event_type(), &event.encode()).unwrap();
+            assert_eq!(decoded, event);
+        }
+    }
+
+    #[test]
+    fn piece_events_round_trip() {
+        let added = PieceAdded {
+            shipment_id: "shipment-1".to_string(),
+            piece_id: "piece-1".to_string(),
+            barcode: "SOF473124829-01".to_string(),
+            status: ShipmentStatus::New,
+            actor: ActorRef {
+                id: "user-1".to_string(),
+            },
+            office: Some(OfficeContext {
+                office_id: "office-1".to_string(),
+            }),
+            occurred_at_ms: 1_700_000_000_004,
+            weight_g: Some(800),
+            description: Some("box 1 of 2".to_string()),
+        };
+        let scanned = PieceScanned {
+            shipment_id: added.shipment_id.clone(),
+            piece_id: added.piece_id.clone(),
+            barcode: added.barcode.clone(),
+            from_status: ShipmentStatus::OutForDelivery,
+            to_status: ShipmentStatus::DeliveryFailed,
+            actor: added.actor.clone(),
+            from_office: added.office.clone(),
+            to_office: added.office.clone(),
+            occurred_at_ms: 1_700_000_000_005,
+            notes: None,
+            failure_reason: Some(DeliveryFailureReason::NoAccess),
+        };
+
+        for event in [
+            ShipmentEvent::PieceAdded(added),
+            ShipmentEvent::PieceScanned(scanned),
+        ] {
+            let decoded = ShipmentEvent::decode(event.event_type(), &event.encode()).unwrap();
+            assert_eq!(decoded, event);
+        }
+    }
+
+    #[test]
+    fn cod_events_round_trip() {
+        let collected = CodCollected {
+            shipment_id: "shipment-1".to_string(),
+            collection_id: "collection-1".to_string(),
+            amount_cents: 2_550,
+            currency: "BGN".parse().unwrap(),
+            actor: ActorRef {
+                id: "user-1".to_string(),
+            },
+            office: Some(OfficeContext {
+                office_id: "office-1".to_string(),
+            }),
+            occurred_at_ms: 1_700_000_000_006,
+        };
+        let remitted = CodRemitted {
+            shipment_id: collected.shipment_id.clone(),
+            collection_id: collected.collection_id.clone(),
+            remittance_id: "remittance-1".to_string(),
+            amount_cents: collected.amount_cents,
+            currency: collected.currency.clone(),
+            actor: collected.actor.clone(),
+            occurred_at_ms: 1_700_000_000_007,
+            reference: Some("TRF-0042".to_string()),
+        };
+
+        for event in [
+            ShipmentEvent::CodCollected(collected),
+            ShipmentEvent::CodRemitted(remitted),
+        ] {
+            let decoded = ShipmentEvent::decode(event.event_type(), &event.encode()).unwrap();
+            assert_eq!(decoded, event);
+        }
+    }
+
+    #[test]
+    fn route_planned_round_trips() {
+        let planned = RoutePlanned {
+            shipment_id: "shipment-1".to_string(),
+            route: Route {
+                stops: vec![
+                    RouteStop {
+                        office: "office-1".to_string(),
+                        transit_minutes: 0,
+                        cost_cents: 0,
+                    },
+                    RouteStop {
+                        office: "office-2".to_string(),
+                        transit_minutes: 240,
+                        cost_cents: 650,
+                    },
+                ],
+            },
+            actor: ActorRef {
+                id: "user-1".to_string(),
+            },
+            occurred_at_ms: 1_700_000_000_008,
+        };
+        let unreachable = RoutePlanned {
+            route: Route { stops: Vec::new() },
+            ..planned.clone()
+        };
+
+        for event in [
+            ShipmentEvent::RoutePlanned(planned),
+            ShipmentEvent::RoutePlanned(unreachable),
+        ] {
+            let decoded = ShipmentEvent::decode(event.event_type(), &event.encode()).unwrap();
+            assert_eq!(decoded, event);
+        }
+    }
+
+    #[test]
+    fn sla_breached_round_trips() {
+        let breached = SlaBreached {
+            shipment_id: "shipment-1".to_string(),
+            promised_by_ms: 1_700_000_000_000,
+            status: ShipmentStatus::InTransit,
+            office: Some(OfficeContext {
+                office_id: "office-1".to_string(),
+            }),
+            occurred_at_ms: 1_700_000_060_000,
+        };
+        let officeless = SlaBreached {
+            office: None,
+            ..breached.clone()
+        };
+
+        for event in [
+            ShipmentEvent::SlaBreached(breached),
+            ShipmentEvent::SlaBreached(officeless),
+        ] {
+            let decoded = ShipmentEvent::decode(event.event_type(), &event.encode()).unwrap();
+            assert_eq!(decoded, event);
+        }
+    }
+
+    #[test]
+    fn cod_with_invalid_currency_is_rejected() {
+        let Value::Map(mut fields) = created().encode() else {
+            unreachable!();
+        };
+        fields.insert(
+            "cod".into(),
+            map! {
+                "amount_cents" => int!(2_550),
+                "currency" => string!("bgn"),
+            },
+        );
+
+        assert_eq!(
+            ShipmentCreated::decode(&Value::Map(fields)).unwrap_err(),
+            EventDecodeError::InvalidField("currency")
+        );
+    }
+
+    #[test]
+    fn hold_with_unknown_reason_is_rejected() {
+        let payload = map! {
+            "event_type" => string!("HoldPlaced"),
+            "shipment_id" => string!("shipment-1"),
+            "reason" => string!("BORED"),
+            "actor_user_id" => string!("user-1"),
+            "office_id" => null!(),
+            "occurred_at_ms" => int!(1_700_000_000_000),
+            "notes" => null!()
+        };
+
+        assert_eq!(
+            HoldPlaced::decode(&payload).unwrap_err(),
+            EventDecodeError::InvalidField("reason")
+        );
+    }
+
+    #[test]
+    fn shipment_event_dispatches_on_event_type() {
+        let event = ShipmentEvent::StatusChanged(status_changed());
+        let decoded = ShipmentEvent::decode(event.event_type(), &event.encode()).unwrap();
+        assert_eq!(decoded, event);
+    }
+
+    #[test]
+    fn shipment_created_golden_bytes() {
+        let bytes = strata::encode::encode(&created().encode()).unwrap();
+        assert_eq!(bytes, GOLDEN_CREATED);
+    }
+
+    #[test]
+    fn status_changed_golden_bytes() {
+        let bytes = strata::encode::encode(&status_changed().encode()).unwrap();
+        assert_eq!(bytes, GOLDEN_STATUS_CHANGED);
+    }
+
+    #[test]
+    fn created_without_client_id_decodes() {
+        let payload = map! {
+            "event_type" => string!("ShipmentCreated"),
+            "shipment_id" => string!("shipment-1"),
+            "status" => string!("NEW"),
+            "actor_user_id" => string!("user-1"),
+            "office_id" => null!(),
+            "occurred_at_ms" => int!(1_700_000_000_000),
+            "notes" => null!()
+        };
+
+        let event = ShipmentCreated::decode(&payload).unwrap();
+
+        assert_eq!(event.client_id, None);
+        assert_eq!(event.office, None);
+        assert_eq!(event.service_level, ServiceLevel::Standard);
+        assert_eq!(event.price, None);
+        // absent optional keys are not re-added on encode
+        assert_eq!(event.encode(), payload);
+    }
+
+    #[test]
+    fn wrong_event_type_is_rejected() {
+        let err = StatusChanged::decode(&created().encode()).unwrap_err();
+
+        assert_eq!(
+            err,
+            EventDecodeError::EventType {
+                expected: "StatusChanged",
+                found: "ShipmentCreated".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn missing_and_mistyped_fields_are_rejected() {
+        let missing = map! {
+            "event_type" => string!("StatusChanged"),
+            "shipment_id" => string!("shipment-1")
+        };
+        assert_eq!(
+            StatusChanged::decode(&missing).unwrap_err(),
+            EventDecodeError::MissingField("from_status")
+        );
+
+        let mut mistyped = status_changed().encode();
+        if let Value::Map(fields) = &mut mistyped {
+            fields.insert("occurred_at_ms".into(), string!("yesterday"));
+        }
+        assert_eq!(
+            StatusChanged::decode(&mistyped).unwrap_err(),
+            EventDecodeError::InvalidField("occurred_at_ms")
+        );
+    }
+
+    #[test]
+    fn unknown_event_type_is_rejected() {
+        let err = ShipmentEvent::decode("Teleported", &map! {}).unwrap_err();
+        assert_eq!(err, EventDecodeError::UnknownEventType("Teleported".into()));
+    }
+
+    /// Canonical bytes of `created()` at the current schema version.
+    /// A change here means stored payloads changed shape: bump the version
+    /// and register an upcaster.
+    const GOLDEN_CREATED: &[u8] = &[
+        0x40, 0x08, 0x20, 0x0d, 0x61, 0x63, 0x74, 0x6f, 0x72, 0x5f, 0x75, 0x73, 0x65, 0x72, 0x5f,
+        0x69, 0x64, 0x20, 0x06, 0x75, 0x73, 0x65, 0x72, 0x2d, 0x31, 0x20, 0x09, 0x63, 0x6c, 0x69,
+        0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x20, 0x08, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2d,
+        0x31, 0x20, 0x0a, 0x65, 0x76, 0x65, 0x6e, 0x74, 0x5f, 0x74, 0x79, 0x70, 0x65, 0x20, 0x0f,
+        0x53, 0x68, 0x69, 0x70, 0x6d, 0x65, 0x6e, 0x74, 0x43, 0x72, 0x65, 0x61, 0x74, 0x65, 0x64,
+        0x20, 0x05, 0x6e, 0x6f, 0x74, 0x65, 0x73, 0x20, 0x07, 0x66, 0x72, 0x61, 0x67, 0x69, 0x6c,
+        0x65, 0x20, 0x0e, 0x6f, 0x63, 0x63, 0x75, 0x72, 0x72, 0x65, 0x64, 0x5f, 0x61, 0x74, 0x5f,
+        0x6d, 0x73, 0x10, 0x80, 0xd0, 0x95, 0xff, 0xbc, 0x31, 0x20, 0x09, 0x6f, 0x66, 0x66, 0x69,
+        0x63, 0x65, 0x5f, 0x69, 0x64, 0x20, 0x08, 0x6f, 0x66, 0x66, 0x69, 0x63, 0x65, 0x2d, 0x31,
+        0x20, 0x0b, 0x73, 0x68, 0x69, 0x70, 0x6d, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x20, 0x0a,
+        0x73, 0x68, 0x69, 0x70, 0x6d, 0x65, 0x6e, 0x74, 0x2d, 0x31, 0x20, 0x06, 0x73, 0x74, 0x61,
+        0x74, 0x75, 0x73, 0x20, 0x03, 0x4e, 0x45, 0x57,
+    ];
+
+    /// Canonical bytes of `status_changed()`.
+    const GOLDEN_STATUS_CHANGED: &[u8] = &[
+        0x40, 0x09, 0x20, 0x0d, 0x61, 0x63, 0x74, 0x6f, 0x72, 0x5f, 0x75, 0x73, 0x65, 0x72, 0x5f,
+        0x69, 0x64, 0x20, 0x06, 0x75, 0x73, 0x65, 0x72, 0x2d, 0x31, 0x20, 0x0a, 0x65, 0x76, 0x65,
+        0x6e, 0x74, 0x5f, 0x74, 0x79, 0x70, 0x65, 0x20, 0x0d, 0x53, 0x74, 0x61, 0x74, 0x75, 0x73,
+        0x43, 0x68, 0x61, 0x6e, 0x67, 0x65, 0x64, 0x20, 0x0e, 0x66, 0x72, 0x6f, 0x6d, 0x5f, 0x6f,
+        0x66, 0x66, 0x69, 0x63, 0x65, 0x5f, 0x69, 0x64, 0x20, 0x08, 0x6f, 0x66, 0x66, 0x69, 0x63,
+        0x65, 0x2d, 0x31, 0x20, 0x0b, 0x66, 0x72, 0x6f, 0x6d, 0x5f, 0x73, 0x74, 0x61, 0x74, 0x75,
+        0x73, 0x20, 0x09, 0x50, 0x52, 0x4f, 0x43, 0x45, 0x53, 0x53, 0x45, 0x44, 0x20, 0x05, 0x6e,
+        0x6f, 0x74, 0x65, 0x73, 0x00, 0x20, 0x0e, 0x6f, 0x63, 0x63, 0x75, 0x72, 0x72, 0x65, 0x64,
+        0x5f, 0x61, 0x74, 0x5f, 0x6d, 0x73, 0x10, 0x81, 0xd0, 0x95, 0xff, 0xbc, 0x31, 0x20, 0x0b,
+        0x73, 0x68, 0x69, 0x70, 0x6d, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x20, 0x0a, 0x73, 0x68,
+        0x69, 0x70, 0x6d, 0x65, 0x6e, 0x74, 0x2d, 0x31, 0x20, 0x0c, 0x74, 0x6f, 0x5f, 0x6f, 0x66,
+        0x66, 0x69, 0x63, 0x65, 0x5f, 0x69, 0x64, 0x20, 0x08, 0x6f, 0x66, 0x66, 0x69, 0x63, 0x65,
+        0x2d, 0x32, 0x20, 0x09, 0x74, 0x6f, 0x5f, 0x73, 0x74, 0x61, 0x74, 0x75, 0x73, 0x20, 0x0a,
+        0x49, 0x4e, 0x5f, 0x54, 0x52, 0x41, 0x4e, 0x53, 0x49, 0x54,
+    ];
+}
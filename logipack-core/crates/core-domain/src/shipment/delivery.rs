@@ -0,0 +1,73 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Failed delivery attempts allowed by the default transition table before
+/// a shipment has to be returned to the sender.
+pub const DEFAULT_MAX_DELIVERY_ATTEMPTS: u32 = 3;
+
+/// Why a courier could not hand a shipment over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DeliveryFailureReason {
+    RecipientAbsent,
+    AddressNotFound,
+    Refused,
+    NoAccess,
+    Damaged,
+    Other,
+}
+
+impl std::str::FromStr for DeliveryFailureReason {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "RECIPIENT_ABSENT" => Ok(DeliveryFailureReason::RecipientAbsent),
+            "ADDRESS_NOT_FOUND" => Ok(DeliveryFailureReason::AddressNotFound),
+            "REFUSED" => Ok(DeliveryFailureReason::Refused),
+            "NO_ACCESS" => Ok(DeliveryFailureReason::NoAccess),
+            "DAMAGED" => Ok(DeliveryFailureReason::Damaged),
+            "OTHER" => Ok(DeliveryFailureReason::Other),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for DeliveryFailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason_str = match self {
+            DeliveryFailureReason::RecipientAbsent => "RECIPIENT_ABSENT",
+            DeliveryFailureReason::AddressNotFound => "ADDRESS_NOT_FOUND",
+            DeliveryFailureReason::Refused => "REFUSED",
+            DeliveryFailureReason::NoAccess => "NO_ACCESS",
+            DeliveryFailureReason::Damaged => "DAMAGED",
+            DeliveryFailureReason::Other => "OTHER",
+        };
+        write!(f, "{}", reason_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_and_parse_round_trip() {
+        use DeliveryFailureReason::*;
+
+        for reason in [
+            RecipientAbsent,
+            AddressNotFound,
+            Refused,
+            NoAccess,
+            Damaged,
+            Other,
+        ] {
+            assert_eq!(
+                reason.to_string().parse::<DeliveryFailureReason>(),
+                Ok(reason)
+            );
+        }
+    }
+}
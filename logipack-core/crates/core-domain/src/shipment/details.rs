@@ -0,0 +1,37 @@
+/// Postal address of a sender or recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Address {
+    pub line1: String,
+    pub line2: Option<String>,
+    pub city: String,
+    pub postal_code: String,
+    /// ISO 3166-1 alpha-2 code, e.g. `BG`.
+    pub country: String,
+}
+
+/// Contact and address block of a shipment's sender or recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Party {
+    pub name: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Address,
+}
+
+/// Outer dimensions of a parcel, in millimetres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dimensions {
+    pub length_mm: i32,
+    pub width_mm: i32,
+    pub height_mm: i32,
+}
+
+/// Physical and declared properties of what is being shipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parcel {
+    pub weight_g: i32,
+    pub dimensions: Option<Dimensions>,
+    /// Declared value in minor currency units.
+    pub declared_value_cents: Option<i64>,
+    pub description: Option<String>,
+}
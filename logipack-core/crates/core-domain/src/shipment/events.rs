@@ -1,4 +1,9 @@
-use crate::shipment::ShipmentStatus;
+use crate::network::Route;
+use crate::pricing::{Price, ServiceLevel};
+use crate::shipment::{
+    CashOnDelivery, Currency, DeliveryFailureReason, HoldReason, Parcel, Party, ProofOfDelivery,
+    ShipmentStatus,
+};
 
 /// Reference to the actor that caused the event.
 /// Kept intentionally generic for future expansion.
@@ -17,11 +22,35 @@ pub struct OfficeContext {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ShipmentCreated {
     pub shipment_id: String,
+    /// Absent on events written before tracking numbers were issued.
+    pub tracking_number: Option<String>,
+    /// Absent on events written before client ids were recorded.
+    pub client_id: Option<String>,
+    pub status: ShipmentStatus,
     pub actor: ActorRef,
     pub office: Option<OfficeContext>,
-    /// Unix timestamp in mins
-    pub occured_at: i64,
+    /// Unix timestamp in millis. Stored as `occured_at` before schema v2.
+    pub occurred_at_ms: i64,
     pub notes: Option<String>,
+    /// This and the fields below are absent on events written before
+    /// parcel details were recorded.
+    pub sender: Option<Party>,
+    pub recipient: Option<Party>,
+    pub destination_office: Option<OfficeContext>,
+    pub parcel: Option<Parcel>,
+    /// Amount to collect on delivery, if any.
+    pub cod: Option<CashOnDelivery>,
+    /// `Standard` on events written before service levels were recorded.
+    pub service_level: ServiceLevel,
+    /// Price as charged at creation. `None` when the shipment was created
+    /// without origin, destination or parcel, or before prices were recorded.
+    pub price: Option<Price>,
+    /// Tariff version that set `price`. `None` when priced by the
+    /// configured tariff.
+    pub tariff_id: Option<String>,
+    /// Delivery promised to the customer, Unix millis. `None` when no route
+    /// reaches the destination, or before promises were recorded.
+    pub promised_by_ms: Option<i64>,
 }
 
 /// Domain event emmited when a shipment status changes.
@@ -31,10 +60,150 @@ pub struct StatusChanged {
     pub from_status: ShipmentStatus,
     pub to_status: ShipmentStatus,
     pub actor: ActorRef,
+    /// Office the shipment was at before the change.
+    pub from_office: Option<OfficeContext>,
+    /// Office requested by the change.
+    pub to_office: Option<OfficeContext>,
+    /// Unix timestamp in millis. Stored as `occured_at` before schema v2.
+    pub occurred_at_ms: i64,
+    pub notes: Option<String>,
+    /// Set only on changes to DELIVERY_FAILED.
+    pub failure_reason: Option<DeliveryFailureReason>,
+    /// Failed delivery attempt number, starting at 1. Set with
+    /// `failure_reason`.
+    pub attempt: Option<u32>,
+    /// Set only on changes to DELIVERED, when proof was taken.
+    pub proof_of_delivery: Option<ProofOfDelivery>,
+}
+
+/// Domain event emmited when a shipment is put on hold.
+///
+/// Holds are orthogonal to the status: the status is kept, but no status
+/// change is accepted until the hold is released.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoldPlaced {
+    pub shipment_id: String,
+    pub reason: HoldReason,
+    pub actor: ActorRef,
+    /// Office the shipment was at when the hold was placed.
+    pub office: Option<OfficeContext>,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
+    pub notes: Option<String>,
+}
+
+/// Domain event emmited when a hold is lifted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoldReleased {
+    pub shipment_id: String,
+    /// Reason of the hold being released.
+    pub reason: HoldReason,
+    pub actor: ActorRef,
+    /// Office the shipment was at when the hold was released.
     pub office: Option<OfficeContext>,
-    /// Unix timestamp in mins
-    pub occured_at: i64,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
+    pub notes: Option<String>,
+}
+
+/// Domain event emmited when a piece is added to a shipment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceAdded {
+    pub shipment_id: String,
+    pub piece_id: String,
+    pub barcode: String,
+    /// Pieces start at the shipment's status.
+    pub status: ShipmentStatus,
+    pub actor: ActorRef,
+    pub office: Option<OfficeContext>,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
+    pub weight_g: Option<i32>,
+    pub description: Option<String>,
+}
+
+/// Domain event emmited when a single piece is scanned into a new status.
+///
+/// When the scan changes the status derived for the whole shipment, a
+/// `StatusChanged` follows it in the same stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceScanned {
+    pub shipment_id: String,
+    pub piece_id: String,
+    pub barcode: String,
+    pub from_status: ShipmentStatus,
+    pub to_status: ShipmentStatus,
+    pub actor: ActorRef,
+    /// Office the piece was at before the scan.
+    pub from_office: Option<OfficeContext>,
+    /// Office the piece was scanned at.
+    pub to_office: Option<OfficeContext>,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
     pub notes: Option<String>,
+    /// Set only on scans to DELIVERY_FAILED.
+    pub failure_reason: Option<DeliveryFailureReason>,
+}
+
+/// Domain event emmited when the cash on delivery of a shipment is taken
+/// from the recipient.
+///
+/// Follows the `StatusChanged` to DELIVERED in the same stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodCollected {
+    pub shipment_id: String,
+    pub collection_id: String,
+    pub amount_cents: i64,
+    pub currency: Currency,
+    pub actor: ActorRef,
+    /// Office holding the cash until it is remitted.
+    pub office: Option<OfficeContext>,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
+}
+
+/// Domain event emmited when collected cash is paid out to the client.
+///
+/// One remittance covers many shipments; each of them gets its own event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodRemitted {
+    pub shipment_id: String,
+    pub collection_id: String,
+    pub remittance_id: String,
+    pub amount_cents: i64,
+    pub currency: Currency,
+    pub actor: ActorRef,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
+    /// Bank transfer or cash slip reference.
+    pub reference: Option<String>,
+}
+
+/// Domain event emmited when a shipment gets its planned route: at creation
+/// and when an admin hops it off the previous route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePlanned {
+    pub shipment_id: String,
+    /// Office ids, origin first. No stops when the destination cannot be
+    /// reached from where the shipment is.
+    pub route: Route<String>,
+    pub actor: ActorRef,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
+}
+
+/// Domain event emmited when a shipment is still not delivered at its
+/// promised time. Recorded by a periodic check, so it has no actor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlaBreached {
+    pub shipment_id: String,
+    /// Unix timestamp in millis.
+    pub promised_by_ms: i64,
+    /// Status and office when the breach was detected.
+    pub status: ShipmentStatus,
+    pub office: Option<OfficeContext>,
+    /// Unix timestamp in millis.
+    pub occurred_at_ms: i64,
 }
 
 #[cfg(test)]
@@ -51,15 +220,21 @@ mod tests {
             actor: ActorRef {
                 id: "user-123".to_string(),
             },
-            office: Some(OfficeContext {
+            from_office: Some(OfficeContext {
+                office_id: "office-1".to_string(),
+            }),
+            to_office: Some(OfficeContext {
                 office_id: "office-1".to_string(),
             }),
-            occured_at: 1_700_000_000_000,
+            occurred_at_ms: 1_700_000_000_000,
             notes: Some("processed at warehouse".to_string()),
+            failure_reason: None,
+            attempt: None,
+            proof_of_delivery: None,
         };
 
         assert_eq!(event.from_status, ShipmentStatus::Accepted);
         assert_eq!(event.to_status, ShipmentStatus::Processed);
-        assert!(event.office.is_some());
+        assert!(event.from_office.is_some());
     }
 }
@@ -0,0 +1,71 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Why a shipment was put on hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HoldReason {
+    Damaged,
+    Customs,
+    AddressProblem,
+    PaymentIssue,
+    AwaitingDocuments,
+    Other,
+}
+
+impl std::str::FromStr for HoldReason {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "DAMAGED" => Ok(HoldReason::Damaged),
+            "CUSTOMS" => Ok(HoldReason::Customs),
+            "ADDRESS_PROBLEM" => Ok(HoldReason::AddressProblem),
+            "PAYMENT_ISSUE" => Ok(HoldReason::PaymentIssue),
+            "AWAITING_DOCUMENTS" => Ok(HoldReason::AwaitingDocuments),
+            "OTHER" => Ok(HoldReason::Other),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for HoldReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason_str = match self {
+            HoldReason::Damaged => "DAMAGED",
+            HoldReason::Customs => "CUSTOMS",
+            HoldReason::AddressProblem => "ADDRESS_PROBLEM",
+            HoldReason::PaymentIssue => "PAYMENT_ISSUE",
+            HoldReason::AwaitingDocuments => "AWAITING_DOCUMENTS",
+            HoldReason::Other => "OTHER",
+        };
+        write!(f, "{}", reason_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_and_parse_round_trip() {
+        use HoldReason::*;
+
+        for reason in [
+            Damaged,
+            Customs,
+            AddressProblem,
+            PaymentIssue,
+            AwaitingDocuments,
+            Other,
+        ] {
+            assert_eq!(reason.to_string().parse::<HoldReason>(), Ok(reason));
+        }
+    }
+
+    #[test]
+    fn unknown_reason_is_rejected() {
+        assert_eq!("LOST".parse::<HoldReason>(), Err(()));
+    }
+}
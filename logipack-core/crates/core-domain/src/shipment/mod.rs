@@ -1,7 +1,25 @@
+pub mod cod;
+pub mod codec;
+pub mod delivery;
+pub mod details;
 pub mod events;
+pub mod hold;
+pub mod piece;
+pub mod pod;
 pub mod status;
+pub mod tracking;
 pub mod transition;
 
+pub use cod::{CashOnDelivery, Currency, MAX_COD_CENTS};
+pub use codec::{EventCodec, ShipmentEvent};
+pub use delivery::{DEFAULT_MAX_DELIVERY_ATTEMPTS, DeliveryFailureReason};
+pub use details::{Address, Dimensions, Parcel, Party};
 pub use events::*;
+pub use hold::HoldReason;
+pub use piece::{MAX_PIECES, PieceBarcode, derive_parent_status};
+pub use pod::{
+    BlobHash, MAX_POD_IMAGE_BYTES, ProofOfDelivery, ReceiverRelationship, image_content_type,
+};
 pub use status::ShipmentStatus;
-pub use transition::validate_transition;
+pub use tracking::TrackingNumber;
+pub use transition::{TransitionContext, TransitionRule, TransitionTable};
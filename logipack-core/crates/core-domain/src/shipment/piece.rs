@@ -0,0 +1,170 @@
+use std::fmt;
+
+use crate::errors::PieceBarcodeError;
+use crate::shipment::{ShipmentStatus, TrackingNumber};
+
+/// Most pieces a shipment can have; piece numbers are two digits.
+pub const MAX_PIECES: u32 = 99;
+
+/// Label of one piece of a multi-piece shipment, e.g. `SOF473124829-02`.
+///
+/// The shipment's tracking number followed by the 1-based piece number, so
+/// a scanned piece always leads back to its shipment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PieceBarcode {
+    tracking_number: TrackingNumber,
+    piece_no: u32,
+}
+
+impl PieceBarcode {
+    pub fn new(tracking_number: TrackingNumber, piece_no: u32) -> Result<Self, PieceBarcodeError> {
+        if !(1..=MAX_PIECES).contains(&piece_no) {
+            return Err(PieceBarcodeError::PieceOutOfRange);
+        }
+
+        Ok(Self {
+            tracking_number,
+            piece_no,
+        })
+    }
+
+    /// Parses scanner input. The tracking number part is as lenient as
+    /// `TrackingNumber::parse`; the piece number follows the last dash.
+    pub fn parse(raw: &str) -> Result<Self, PieceBarcodeError> {
+        let (tracking, piece) = raw
+            .trim()
+            .rsplit_once('-')
+            .ok_or(PieceBarcodeError::InvalidFormat)?;
+
+        if piece.len() != 2 || !piece.chars().all(|ch| ch.is_ascii_digit()) {
+            return Err(PieceBarcodeError::InvalidFormat);
+        }
+
+        let piece_no = piece
+            .parse()
+            .map_err(|_| PieceBarcodeError::InvalidFormat)?;
+
+        Self::new(TrackingNumber::parse(tracking)?, piece_no)
+    }
+
+    pub fn tracking_number(&self) -> &TrackingNumber {
+        &self.tracking_number
+    }
+
+    pub fn piece_no(&self) -> u32 {
+        self.piece_no
+    }
+}
+
+impl fmt::Display for PieceBarcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{:02}", self.tracking_number, self.piece_no)
+    }
+}
+
+/// Parent status implied by the statuses of its pieces.
+///
+/// - all pieces share a status: that status
+/// - some pieces are delivered, others are not: `PartiallyDelivered`
+/// - otherwise, or without pieces: `None`, the parent keeps its status
+///   until the pieces catch up with each other
+pub fn derive_parent_status(
+    pieces: impl IntoIterator<Item = ShipmentStatus>,
+) -> Option<ShipmentStatus> {
+    let mut pieces = pieces.into_iter();
+    let first = pieces.next()?;
+
+    let mut uniform = true;
+    let mut any_delivered = first == ShipmentStatus::Delivered;
+
+    for status in pieces {
+        uniform &= status == first;
+        any_delivered |= status == ShipmentStatus::Delivered;
+    }
+
+    if uniform {
+        Some(first)
+    } else if any_delivered {
+        Some(ShipmentStatus::PartiallyDelivered)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ShipmentStatus::*;
+
+    fn tracking() -> TrackingNumber {
+        TrackingNumber::new("SOF", 47_312_482).unwrap()
+    }
+
+    #[test]
+    fn barcode_appends_padded_piece_number() {
+        let barcode = PieceBarcode::new(tracking(), 2).unwrap();
+        assert_eq!(barcode.to_string(), "SOF473124829-02");
+    }
+
+    #[test]
+    fn barcode_parse_round_trips_and_is_lenient() {
+        let barcode = PieceBarcode::new(tracking(), 17).unwrap();
+
+        assert_eq!(
+            PieceBarcode::parse(&barcode.to_string()),
+            Ok(barcode.clone())
+        );
+        assert_eq!(PieceBarcode::parse(" sof 4731-2482 9-17 "), Ok(barcode));
+    }
+
+    #[test]
+    fn barcode_rejects_bad_input() {
+        assert_eq!(
+            PieceBarcode::parse("SOF473124829"),
+            Err(PieceBarcodeError::InvalidFormat)
+        );
+        assert_eq!(
+            PieceBarcode::parse("SOF473124829-1"),
+            Err(PieceBarcodeError::InvalidFormat)
+        );
+        assert_eq!(
+            PieceBarcode::parse("SOF473124829-00"),
+            Err(PieceBarcodeError::PieceOutOfRange)
+        );
+        assert!(matches!(
+            PieceBarcode::parse("SOF473124820-01"),
+            Err(PieceBarcodeError::TrackingNumber(_))
+        ));
+        assert_eq!(
+            PieceBarcode::new(tracking(), MAX_PIECES + 1),
+            Err(PieceBarcodeError::PieceOutOfRange)
+        );
+    }
+
+    #[test]
+    fn uniform_pieces_set_the_parent_status() {
+        assert_eq!(
+            derive_parent_status([InTransit, InTransit]),
+            Some(InTransit)
+        );
+        assert_eq!(derive_parent_status([Delivered]), Some(Delivered));
+    }
+
+    #[test]
+    fn some_delivered_pieces_make_a_partial_delivery() {
+        assert_eq!(
+            derive_parent_status([Delivered, OutForDelivery, InTransit]),
+            Some(PartiallyDelivered)
+        );
+        assert_eq!(
+            derive_parent_status([Cancelled, Delivered]),
+            Some(PartiallyDelivered)
+        );
+    }
+
+    #[test]
+    fn mixed_pieces_keep_the_parent_status() {
+        assert_eq!(derive_parent_status([Accepted, New]), None);
+        assert_eq!(derive_parent_status([]), None);
+    }
+}
@@ -0,0 +1,267 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ProofOfDeliveryError;
+use crate::shipment::ShipmentStatus;
+
+/// Largest signature or photo accepted, in bytes.
+pub const MAX_POD_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Who took the shipment at the door, relative to the recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReceiverRelationship {
+    Recipient,
+    FamilyMember,
+    Neighbour,
+    Colleague,
+    Reception,
+    Other,
+}
+
+impl std::str::FromStr for ReceiverRelationship {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "RECIPIENT" => Ok(ReceiverRelationship::Recipient),
+            "FAMILY_MEMBER" => Ok(ReceiverRelationship::FamilyMember),
+            "NEIGHBOUR" => Ok(ReceiverRelationship::Neighbour),
+            "COLLEAGUE" => Ok(ReceiverRelationship::Colleague),
+            "RECEPTION" => Ok(ReceiverRelationship::Reception),
+            "OTHER" => Ok(ReceiverRelationship::Other),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for ReceiverRelationship {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let relationship_str = match self {
+            ReceiverRelationship::Recipient => "RECIPIENT",
+            ReceiverRelationship::FamilyMember => "FAMILY_MEMBER",
+            ReceiverRelationship::Neighbour => "NEIGHBOUR",
+            ReceiverRelationship::Colleague => "COLLEAGUE",
+            ReceiverRelationship::Reception => "RECEPTION",
+            ReceiverRelationship::Other => "OTHER",
+        };
+        write!(f, "{}", relationship_str)
+    }
+}
+
+/// BLAKE3-256 hash of a stored blob, the key of the blob table.
+///
+/// Displayed and parsed as 64 lowercase hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlobHash([u8; 32]);
+
+impl BlobHash {
+    pub fn of(bytes: &[u8]) -> Self {
+        Self(*blake3::hash(bytes).as_bytes())
+    }
+
+    /// `None` unless `bytes` is exactly 32 bytes long.
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(Self)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl std::str::FromStr for BlobHash {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.len() != 64 || !value.is_ascii() {
+            return Err(());
+        }
+
+        let mut out = [0u8; 32];
+        for (i, byte) in out.iter_mut().enumerate() {
+            let pair = &value[i * 2..i * 2 + 2];
+            if pair.bytes().any(|b| b.is_ascii_uppercase()) {
+                return Err(());
+            }
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| ())?;
+        }
+
+        Ok(Self(out))
+    }
+}
+
+impl fmt::Display for BlobHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Proof of delivery referenced from the `StatusChanged` to DELIVERED.
+///
+/// Images are stored as blobs and only their hashes are part of the event,
+/// so the audit chain covers them without carrying the bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofOfDelivery {
+    pub receiver_name: String,
+    pub relationship: ReceiverRelationship,
+    /// When the receiver took the shipment, unix millis. May precede the
+    /// event when the courier syncs later.
+    pub received_at_ms: i64,
+    pub signature: Option<BlobHash>,
+    pub photo: Option<BlobHash>,
+}
+
+impl ProofOfDelivery {
+    /// Checks everything but the images, which are checked by
+    /// `image_content_type` before they are stored.
+    pub fn validate(&self, to: ShipmentStatus) -> Result<(), ProofOfDeliveryError> {
+        if to != ShipmentStatus::Delivered {
+            return Err(ProofOfDeliveryError::NotADelivery { to });
+        }
+
+        if self.receiver_name.trim().is_empty() {
+            return Err(ProofOfDeliveryError::ReceiverNameRequired);
+        }
+
+        if self.signature.is_none() && self.photo.is_none() {
+            return Err(ProofOfDeliveryError::EvidenceRequired);
+        }
+
+        Ok(())
+    }
+}
+
+/// Content type of a signature or photo, from its leading bytes.
+/// Only PNG and JPEG are accepted.
+pub fn image_content_type(bytes: &[u8]) -> Result<&'static str, ProofOfDeliveryError> {
+    if bytes.len() > MAX_POD_IMAGE_BYTES {
+        return Err(ProofOfDeliveryError::ImageTooLarge {
+            max: MAX_POD_IMAGE_BYTES,
+        });
+    }
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Ok("image/png")
+    } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        Ok("image/jpeg")
+    } else {
+        Err(ProofOfDeliveryError::UnsupportedImage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod() -> ProofOfDelivery {
+        ProofOfDelivery {
+            receiver_name: "Maria Ivanova".to_string(),
+            relationship: ReceiverRelationship::Neighbour,
+            received_at_ms: 1_700_000_000_000,
+            signature: Some(BlobHash::of(b"signature")),
+            photo: None,
+        }
+    }
+
+    #[test]
+    fn relationship_display_and_parse_round_trip() {
+        use ReceiverRelationship::*;
+
+        for relationship in [
+            Recipient,
+            FamilyMember,
+            Neighbour,
+            Colleague,
+            Reception,
+            Other,
+        ] {
+            assert_eq!(relationship.to_string().parse(), Ok(relationship));
+        }
+
+        assert_eq!("neighbour".parse::<ReceiverRelationship>(), Err(()));
+    }
+
+    #[test]
+    fn blob_hash_is_blake3_as_lowercase_hex() {
+        let hash = BlobHash::of(b"abc");
+
+        assert_eq!(
+            hash.to_string(),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        assert_eq!(hash.to_string().parse(), Ok(hash));
+        assert_eq!(BlobHash::from_slice(hash.as_bytes()), Some(hash));
+    }
+
+    #[test]
+    fn malformed_blob_hashes_are_rejected() {
+        let hex = BlobHash::of(b"abc").to_string();
+
+        assert_eq!(hex[..62].parse::<BlobHash>(), Err(()));
+        assert_eq!(hex.to_uppercase().parse::<BlobHash>(), Err(()));
+        assert_eq!(format!("{}zz", &hex[..62]).parse::<BlobHash>(), Err(()));
+        assert_eq!(BlobHash::from_slice(&[0; 31]), None);
+    }
+
+    #[test]
+    fn proof_of_delivery_is_only_taken_on_delivery() {
+        assert_eq!(pod().validate(ShipmentStatus::Delivered), Ok(()));
+        assert_eq!(
+            pod().validate(ShipmentStatus::DeliveryFailed),
+            Err(ProofOfDeliveryError::NotADelivery {
+                to: ShipmentStatus::DeliveryFailed
+            })
+        );
+    }
+
+    #[test]
+    fn proof_of_delivery_needs_receiver_and_evidence() {
+        let unnamed = ProofOfDelivery {
+            receiver_name: "  ".to_string(),
+            ..pod()
+        };
+        assert_eq!(
+            unnamed.validate(ShipmentStatus::Delivered),
+            Err(ProofOfDeliveryError::ReceiverNameRequired)
+        );
+
+        let bare = ProofOfDelivery {
+            signature: None,
+            ..pod()
+        };
+        assert_eq!(
+            bare.validate(ShipmentStatus::Delivered),
+            Err(ProofOfDeliveryError::EvidenceRequired)
+        );
+    }
+
+    #[test]
+    fn images_are_sniffed_and_size_limited() {
+        assert_eq!(
+            image_content_type(b"\x89PNG\r\n\x1a\n...."),
+            Ok("image/png")
+        );
+        assert_eq!(
+            image_content_type(&[0xff, 0xd8, 0xff, 0xe0]),
+            Ok("image/jpeg")
+        );
+        assert_eq!(
+            image_content_type(b"GIF89a"),
+            Err(ProofOfDeliveryError::UnsupportedImage)
+        );
+
+        let mut huge = vec![0xff, 0xd8, 0xff];
+        huge.resize(MAX_POD_IMAGE_BYTES + 1, 0);
+        assert_eq!(
+            image_content_type(&huge),
+            Err(ProofOfDeliveryError::ImageTooLarge {
+                max: MAX_POD_IMAGE_BYTES
+            })
+        );
+    }
+}
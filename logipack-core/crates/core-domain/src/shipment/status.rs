@@ -9,13 +9,22 @@ pub enum ShipmentStatus {
     Accepted,
     Processed,
     InTransit,
+    OutForDelivery,
+    DeliveryFailed,
+    Returning,
+    Returned,
+    /// Derived for multi-piece shipments: some pieces delivered, not all.
+    PartiallyDelivered,
     Delivered,
     Cancelled,
 }
 
 impl ShipmentStatus {
     pub fn is_terminal(self) -> bool {
-        matches!(self, ShipmentStatus::Delivered | ShipmentStatus::Cancelled)
+        matches!(
+            self,
+            ShipmentStatus::Delivered | ShipmentStatus::Cancelled | ShipmentStatus::Returned
+        )
     }
 }
 
@@ -28,6 +37,11 @@ impl std::str::FromStr for ShipmentStatus {
             "ACCEPTED" => Ok(ShipmentStatus::Accepted),
             "PROCESSED" => Ok(ShipmentStatus::Processed),
             "IN_TRANSIT" => Ok(ShipmentStatus::InTransit),
+            "OUT_FOR_DELIVERY" => Ok(ShipmentStatus::OutForDelivery),
+            "DELIVERY_FAILED" => Ok(ShipmentStatus::DeliveryFailed),
+            "RETURNING" => Ok(ShipmentStatus::Returning),
+            "RETURNED" => Ok(ShipmentStatus::Returned),
+            "PARTIALLY_DELIVERED" => Ok(ShipmentStatus::PartiallyDelivered),
             "DELIVERED" => Ok(ShipmentStatus::Delivered),
             "CANCELLED" => Ok(ShipmentStatus::Cancelled),
             _ => Err(()),
@@ -42,6 +56,11 @@ impl fmt::Display for ShipmentStatus {
             ShipmentStatus::Accepted => "ACCEPTED",
             ShipmentStatus::Processed => "PROCESSED",
             ShipmentStatus::InTransit => "IN_TRANSIT",
+            ShipmentStatus::OutForDelivery => "OUT_FOR_DELIVERY",
+            ShipmentStatus::DeliveryFailed => "DELIVERY_FAILED",
+            ShipmentStatus::Returning => "RETURNING",
+            ShipmentStatus::Returned => "RETURNED",
+            ShipmentStatus::PartiallyDelivered => "PARTIALLY_DELIVERED",
             ShipmentStatus::Delivered => "DELIVERED",
             ShipmentStatus::Cancelled => "CANCELLED",
         };
@@ -58,11 +77,37 @@ mod tests {
         // Terminal statuses
         assert!(ShipmentStatus::Delivered.is_terminal());
         assert!(ShipmentStatus::Cancelled.is_terminal());
+        assert!(ShipmentStatus::Returned.is_terminal());
 
         // Non-terminal statuses
         assert!(!ShipmentStatus::New.is_terminal());
         assert!(!ShipmentStatus::Accepted.is_terminal());
         assert!(!ShipmentStatus::Processed.is_terminal());
         assert!(!ShipmentStatus::InTransit.is_terminal());
+        assert!(!ShipmentStatus::OutForDelivery.is_terminal());
+        assert!(!ShipmentStatus::DeliveryFailed.is_terminal());
+        assert!(!ShipmentStatus::Returning.is_terminal());
+        assert!(!ShipmentStatus::PartiallyDelivered.is_terminal());
+    }
+
+    #[test]
+    fn display_and_parse_round_trip() {
+        use ShipmentStatus::*;
+
+        for status in [
+            New,
+            Accepted,
+            Processed,
+            InTransit,
+            OutForDelivery,
+            DeliveryFailed,
+            Returning,
+            Returned,
+            PartiallyDelivered,
+            Delivered,
+            Cancelled,
+        ] {
+            assert_eq!(status.to_string().parse::<ShipmentStatus>(), Ok(status));
+        }
     }
 }
@@ -0,0 +1,249 @@
+use std::fmt;
+
+use crate::errors::TrackingNumberError;
+
+/// Digits in the serial part of a tracking number.
+pub const SERIAL_DIGITS: usize = 8;
+
+/// Prefix used when the origin office city yields fewer than two letters,
+/// or the shipment has no origin office.
+pub const FALLBACK_PREFIX: &str = "LP";
+
+/// S10 (UPU) weights for the eight serial digits.
+const WEIGHTS: [u32; SERIAL_DIGITS] = [8, 6, 4, 2, 3, 5, 9, 7];
+
+/// Human-friendly shipment identifier, e.g. `SOF473124829`.
+///
+/// Made of an office prefix of 2-3 letters, an 8-digit serial and a mod-11
+/// check digit computed over the serial as in S10 postal item numbers.
+/// Uniqueness comes from the serial alone; the prefix is informational.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrackingNumber(String);
+
+impl TrackingNumber {
+    pub fn new(prefix: &str, serial: u64) -> Result<Self, TrackingNumberError> {
+        if !valid_prefix(prefix) {
+            return Err(TrackingNumberError::InvalidFormat);
+        }
+
+        if serial >= 10u64.pow(SERIAL_DIGITS as u32) {
+            return Err(TrackingNumberError::SerialOutOfRange);
+        }
+
+        let digits = format!("{serial:0width$}", width = SERIAL_DIGITS);
+        let check = check_digit(&digits);
+
+        Ok(Self(format!("{prefix}{digits}{check}")))
+    }
+
+    /// Parses user input. Case, spaces and dashes are ignored, so
+    /// `sof 4731-2482 9` is accepted.
+    pub fn parse(raw: &str) -> Result<Self, TrackingNumberError> {
+        let code: String = raw
+            .chars()
+            .filter(|ch| !ch.is_whitespace() && *ch != '-')
+            .map(|ch| ch.to_ascii_uppercase())
+            .collect();
+
+        let split = code
+            .find(|ch: char| !ch.is_ascii_uppercase())
+            .unwrap_or(code.len());
+        let (prefix, rest) = code.split_at(split);
+
+        if !valid_prefix(prefix)
+            || rest.len() != SERIAL_DIGITS + 1
+            || !rest.chars().all(|ch| ch.is_ascii_digit())
+        {
+            return Err(TrackingNumberError::InvalidFormat);
+        }
+
+        let (digits, check) = rest.split_at(SERIAL_DIGITS);
+        if check_digit(digits).to_string() != check {
+            return Err(TrackingNumberError::InvalidCheckDigit);
+        }
+
+        Ok(Self(code))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TrackingNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Prefix for shipments originating in `city`: its first three letters,
+/// with Bulgarian Cyrillic transliterated, e.g. `София` -> `SOF`.
+pub fn office_prefix(city: &str) -> String {
+    let mut letters = String::new();
+
+    for ch in city.chars().flat_map(char::to_lowercase) {
+        if ch.is_ascii_alphabetic() {
+            letters.push(ch);
+        } else if let Some(latin) = transliterate(ch) {
+            letters.push_str(latin);
+        }
+
+        if letters.len() >= 3 {
+            break;
+        }
+    }
+
+    let prefix: String = letters.chars().take(3).collect();
+
+    if prefix.len() < 2 {
+        return FALLBACK_PREFIX.to_string();
+    }
+
+    prefix.to_ascii_uppercase()
+}
+
+fn valid_prefix(prefix: &str) -> bool {
+    (2..=3).contains(&prefix.len()) && prefix.chars().all(|ch| ch.is_ascii_uppercase())
+}
+
+/// `digits` must be `SERIAL_DIGITS` ASCII digits.
+fn check_digit(digits: &str) -> u32 {
+    let sum: u32 = digits
+        .chars()
+        .zip(WEIGHTS)
+        .map(|(ch, weight)| ch.to_digit(10).unwrap_or(0) * weight)
+        .sum();
+
+    match 11 - sum % 11 {
+        10 => 0,
+        11 => 5,
+        check => check,
+    }
+}
+
+/// Streamlined System, lower case only.
+fn transliterate(ch: char) -> Option<&'static str> {
+    let latin = match ch {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'е' => "e",
+        'ж' => "zh",
+        'з' => "z",
+        'и' => "i",
+        'й' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "h",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "sht",
+        'ъ' => "a",
+        'ь' => "y",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    };
+
+    Some(latin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_digit_matches_s10_reference() {
+        // UPU S10 example RR 47312482 9 GB
+        assert_eq!(check_digit("47312482"), 9);
+        // remainders 1 and 0 map to 0 and 5
+        assert_eq!(check_digit("02000000"), 0);
+        assert_eq!(check_digit("00000000"), 5);
+    }
+
+    #[test]
+    fn new_pads_serial_and_appends_check_digit() {
+        let number = TrackingNumber::new("SOF", 47_312_482).unwrap();
+        assert_eq!(number.as_str(), "SOF473124829");
+
+        let number = TrackingNumber::new("LP", 1).unwrap();
+        assert_eq!(number.as_str(), "LP000000014");
+    }
+
+    #[test]
+    fn new_rejects_bad_prefix_and_serial() {
+        assert_eq!(
+            TrackingNumber::new("S", 1),
+            Err(TrackingNumberError::InvalidFormat)
+        );
+        assert_eq!(
+            TrackingNumber::new("sof", 1),
+            Err(TrackingNumberError::InvalidFormat)
+        );
+        assert_eq!(
+            TrackingNumber::new("SOF", 100_000_000),
+            Err(TrackingNumberError::SerialOutOfRange)
+        );
+    }
+
+    #[test]
+    fn parse_normalizes_input() {
+        let number = TrackingNumber::parse(" sof 4731-2482 9 ").unwrap();
+        assert_eq!(number, TrackingNumber::new("SOF", 47_312_482).unwrap());
+    }
+
+    #[test]
+    fn parse_rejects_wrong_check_digit() {
+        assert_eq!(
+            TrackingNumber::parse("SOF473124828"),
+            Err(TrackingNumberError::InvalidCheckDigit)
+        );
+        // transposed digits are caught
+        assert_eq!(
+            TrackingNumber::parse("SOF473124289"),
+            Err(TrackingNumberError::InvalidCheckDigit)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_input() {
+        for raw in [
+            "",
+            "473124829",
+            "S473124829",
+            "SOFI473124829",
+            "SOF47312482",
+            "SOF4731248290",
+            "SOF47312482X",
+        ] {
+            assert_eq!(
+                TrackingNumber::parse(raw),
+                Err(TrackingNumberError::InvalidFormat),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn office_prefix_from_city() {
+        assert_eq!(office_prefix("Sofia"), "SOF");
+        assert_eq!(office_prefix("София"), "SOF");
+        assert_eq!(office_prefix("Шумен"), "SHU");
+        assert_eq!(office_prefix("Stara Zagora"), "STA");
+        assert_eq!(office_prefix("Ai"), "AI");
+        assert_eq!(office_prefix("X"), FALLBACK_PREFIX);
+        assert_eq!(office_prefix("東京"), FALLBACK_PREFIX);
+    }
+}
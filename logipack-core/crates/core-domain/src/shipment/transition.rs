@@ -1,41 +1,224 @@
-use crate::errors::TransitionError;
-use crate::shipment::ShipmentStatus;
+use serde::{Deserialize, Serialize};
 
-pub fn validate_transition(
-    from: ShipmentStatus,
-    to: ShipmentStatus,
-    office_changed: bool,
-) -> Result<(), TransitionError> {
-    // Terminal states reject all
-    if from.is_terminal() {
-        return Err(TransitionError::TerminalState { from });
-    }
+use crate::errors::{TransitionError, TransitionTableError};
+use crate::shipment::{DEFAULT_MAX_DELIVERY_ATTEMPTS, ShipmentStatus};
+
+/// Allowed status change with the guards that apply to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransitionRule {
+    pub from: ShipmentStatus,
+    pub to: ShipmentStatus,
+    /// Shipment may move to another office with this change.
+    #[serde(default)]
+    pub office_change: bool,
+    /// Change must carry a non-blank note.
+    #[serde(default)]
+    pub requires_note: bool,
+    /// Only admins may make this change.
+    #[serde(default)]
+    pub admin_only: bool,
+    /// Allowed only while fewer delivery attempts than this have failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+}
 
-    // Office hop policy
-    if office_changed && to != ShipmentStatus::InTransit {
-        return Err(TransitionError::OfficeHopNotAllowed { from, to });
+impl TransitionRule {
+    const fn edge(from: ShipmentStatus, to: ShipmentStatus) -> Self {
+        Self {
+            from,
+            to,
+            office_change: false,
+            requires_note: false,
+            admin_only: false,
+            max_attempts: None,
+        }
     }
+}
 
-    use ShipmentStatus::*;
+/// Facts about a requested status change that guards look at.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransitionContext {
+    pub office_changed: bool,
+    pub has_note: bool,
+    pub is_admin: bool,
+    /// A delivery failure reason accompanies the change.
+    pub has_failure_reason: bool,
+    /// Failed delivery attempts so far.
+    pub delivery_attempts: u32,
+}
+
+/// Shipment status machine: terminal states plus the allowed edges.
+///
+/// The default is the built-in LogiPack workflow. Other tables are
+/// deserialized from the same shape and validated on construction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "TransitionTableSpec", into = "TransitionTableSpec")]
+pub struct TransitionTable {
+    terminal: Vec<ShipmentStatus>,
+    rules: Vec<TransitionRule>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TransitionTableSpec {
+    terminal: Vec<ShipmentStatus>,
+    transitions: Vec<TransitionRule>,
+}
+
+impl Default for TransitionTable {
+    fn default() -> Self {
+        use ShipmentStatus::*;
 
-    let allowed = matches!(
-        (from, to),
-        // forward progression
-        (New, Accepted)
-            | (Accepted, Processed)
-            | (Processed, InTransit)
-            | (InTransit, Delivered)
+        let rules = vec![
+            // forward progression
+            TransitionRule::edge(New, Accepted),
+            TransitionRule::edge(Accepted, Processed),
+            TransitionRule {
+                office_change: true,
+                ..TransitionRule::edge(Processed, InTransit)
+            },
+            TransitionRule::edge(InTransit, Delivered),
+            // sorted again at each office along the route
+            TransitionRule::edge(InTransit, Processed),
+            // doorstep delivery
+            TransitionRule::edge(InTransit, OutForDelivery),
+            TransitionRule::edge(OutForDelivery, Delivered),
+            TransitionRule::edge(OutForDelivery, DeliveryFailed),
+            TransitionRule {
+                max_attempts: Some(DEFAULT_MAX_DELIVERY_ATTEMPTS),
+                ..TransitionRule::edge(DeliveryFailed, OutForDelivery)
+            },
+            // return to sender
+            TransitionRule {
+                office_change: true,
+                ..TransitionRule::edge(DeliveryFailed, Returning)
+            },
+            TransitionRule::edge(Returning, Returned),
             // cancellation
-            | (New, Cancelled)
-            | (Accepted, Cancelled)
-            | (Processed, Cancelled)
-            | (InTransit, Cancelled)
-    );
+            TransitionRule::edge(New, Cancelled),
+            TransitionRule::edge(Accepted, Cancelled),
+            TransitionRule::edge(Processed, Cancelled),
+            TransitionRule::edge(InTransit, Cancelled),
+        ];
+
+        Self {
+            terminal: vec![Delivered, Cancelled, Returned],
+            rules,
+        }
+    }
+}
+
+impl TransitionTable {
+    pub fn new(
+        terminal: Vec<ShipmentStatus>,
+        rules: Vec<TransitionRule>,
+    ) -> Result<Self, TransitionTableError> {
+        for (i, rule) in rules.iter().enumerate() {
+            if terminal.contains(&rule.from) {
+                return Err(TransitionTableError::EdgeFromTerminal {
+                    from: rule.from,
+                    to: rule.to,
+                });
+            }
+
+            if rule.from == rule.to {
+                return Err(TransitionTableError::SelfLoop { status: rule.from });
+            }
+
+            if rules[..i]
+                .iter()
+                .any(|other| other.from == rule.from && other.to == rule.to)
+            {
+                return Err(TransitionTableError::DuplicateEdge {
+                    from: rule.from,
+                    to: rule.to,
+                });
+            }
+        }
+
+        Ok(Self { terminal, rules })
+    }
 
-    if allowed {
-        Ok(())
-    } else {
-        Err(TransitionError::InvalidTransition { from, to })
+    pub fn is_terminal(&self, status: ShipmentStatus) -> bool {
+        self.terminal.contains(&status)
+    }
+
+    pub fn terminal(&self) -> &[ShipmentStatus] {
+        &self.terminal
+    }
+
+    pub fn rule(&self, from: ShipmentStatus, to: ShipmentStatus) -> Option<&TransitionRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.from == from && rule.to == to)
+    }
+
+    /// Edges leaving `from`, in table order.
+    pub fn next(&self, from: ShipmentStatus) -> impl Iterator<Item = &TransitionRule> {
+        self.rules.iter().filter(move |rule| rule.from == from)
+    }
+
+    /// Checks a status change against the table and its guards.
+    pub fn validate(
+        &self,
+        from: ShipmentStatus,
+        to: ShipmentStatus,
+        ctx: TransitionContext,
+    ) -> Result<&TransitionRule, TransitionError> {
+        // Terminal states reject all
+        if self.is_terminal(from) {
+            return Err(TransitionError::TerminalState { from });
+        }
+
+        let rule = self
+            .rule(from, to)
+            .ok_or(TransitionError::InvalidTransition { from, to })?;
+
+        if ctx.office_changed && !rule.office_change {
+            return Err(TransitionError::OfficeHopNotAllowed { from, to });
+        }
+
+        if rule.admin_only && !ctx.is_admin {
+            return Err(TransitionError::AdminOnly { from, to });
+        }
+
+        if rule.requires_note && !ctx.has_note {
+            return Err(TransitionError::NoteRequired { from, to });
+        }
+
+        // only failed attempts carry a reason
+        match (to == ShipmentStatus::DeliveryFailed, ctx.has_failure_reason) {
+            (true, false) => return Err(TransitionError::FailureReasonRequired),
+            (false, true) => return Err(TransitionError::UnexpectedFailureReason { to }),
+            _ => {}
+        }
+
+        if let Some(max) = rule.max_attempts
+            && ctx.delivery_attempts >= max
+        {
+            return Err(TransitionError::AttemptsExhausted {
+                attempts: ctx.delivery_attempts,
+                max,
+            });
+        }
+
+        Ok(rule)
+    }
+}
+
+impl TryFrom<TransitionTableSpec> for TransitionTable {
+    type Error = TransitionTableError;
+
+    fn try_from(spec: TransitionTableSpec) -> Result<Self, Self::Error> {
+        Self::new(spec.terminal, spec.transitions)
+    }
+}
+
+impl From<TransitionTable> for TransitionTableSpec {
+    fn from(table: TransitionTable) -> Self {
+        Self {
+            terminal: table.terminal,
+            transitions: table.rules,
+        }
     }
 }
 
@@ -44,6 +227,21 @@ mod tests {
     use super::*;
     use ShipmentStatus::*;
 
+    fn validate_transition(
+        from: ShipmentStatus,
+        to: ShipmentStatus,
+        office_changed: bool,
+    ) -> Result<(), TransitionError> {
+        let ctx = TransitionContext {
+            office_changed,
+            ..Default::default()
+        };
+
+        TransitionTable::default()
+            .validate(from, to, ctx)
+            .map(|_| ())
+    }
+
     #[test]
     fn allowed_forward_transitions_pass() {
         let cases = [
@@ -51,6 +249,7 @@ mod tests {
             (Accepted, Processed),
             (Processed, InTransit),
             (InTransit, Delivered),
+            (InTransit, Processed),
         ];
 
         for (from, to) in cases {
@@ -125,4 +324,143 @@ mod tests {
             "office hop should be rejected outside IN_TRANSIT"
         )
     }
+
+    #[test]
+    fn guards_are_enforced() {
+        let table = TransitionTable::new(
+            vec![Delivered, Cancelled],
+            vec![TransitionRule {
+                requires_note: true,
+                admin_only: true,
+                ..TransitionRule::edge(InTransit, Cancelled)
+            }],
+        )
+        .unwrap();
+
+        let employee = TransitionContext {
+            has_note: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            table.validate(InTransit, Cancelled, employee),
+            Err(TransitionError::AdminOnly {
+                from: InTransit,
+                to: Cancelled
+            })
+        );
+
+        let admin_without_note = TransitionContext {
+            is_admin: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            table.validate(InTransit, Cancelled, admin_without_note),
+            Err(TransitionError::NoteRequired {
+                from: InTransit,
+                to: Cancelled
+            })
+        );
+
+        let admin = TransitionContext {
+            has_note: true,
+            ..admin_without_note
+        };
+        assert!(table.validate(InTransit, Cancelled, admin).is_ok());
+    }
+
+    #[test]
+    fn next_lists_edges_in_table_order() {
+        let table = TransitionTable::default();
+        let next: Vec<_> = table.next(Processed).map(|rule| rule.to).collect();
+        assert_eq!(next, vec![InTransit, Cancelled]);
+
+        let next: Vec<_> = table.next(InTransit).map(|rule| rule.to).collect();
+        assert_eq!(next, vec![Delivered, Processed, OutForDelivery, Cancelled]);
+        assert_eq!(table.next(Delivered).count(), 0);
+    }
+
+    #[test]
+    fn invalid_tables_are_rejected() {
+        assert_eq!(
+            TransitionTable::new(vec![Delivered], vec![TransitionRule::edge(Delivered, New)]),
+            Err(TransitionTableError::EdgeFromTerminal {
+                from: Delivered,
+                to: New
+            })
+        );
+        assert_eq!(
+            TransitionTable::new(vec![], vec![TransitionRule::edge(New, New)]),
+            Err(TransitionTableError::SelfLoop { status: New })
+        );
+        assert_eq!(
+            TransitionTable::new(
+                vec![],
+                vec![
+                    TransitionRule::edge(New, Accepted),
+                    TransitionRule::edge(New, Accepted)
+                ]
+            ),
+            Err(TransitionTableError::DuplicateEdge {
+                from: New,
+                to: Accepted
+            })
+        );
+    }
+
+    #[test]
+    fn failed_delivery_needs_a_reason() {
+        let table = TransitionTable::default();
+
+        assert_eq!(
+            table.validate(OutForDelivery, DeliveryFailed, TransitionContext::default()),
+            Err(TransitionError::FailureReasonRequired)
+        );
+
+        let with_reason = TransitionContext {
+            has_failure_reason: true,
+            ..Default::default()
+        };
+        assert!(
+            table
+                .validate(OutForDelivery, DeliveryFailed, with_reason)
+                .is_ok()
+        );
+        assert_eq!(
+            table.validate(OutForDelivery, Delivered, with_reason),
+            Err(TransitionError::UnexpectedFailureReason { to: Delivered })
+        );
+    }
+
+    #[test]
+    fn redelivery_is_limited_and_return_stays_open() {
+        let table = TransitionTable::default();
+        let after = |delivery_attempts| TransitionContext {
+            delivery_attempts,
+            ..Default::default()
+        };
+
+        assert!(
+            table
+                .validate(DeliveryFailed, OutForDelivery, after(2))
+                .is_ok()
+        );
+        assert_eq!(
+            table.validate(DeliveryFailed, OutForDelivery, after(3)),
+            Err(TransitionError::AttemptsExhausted {
+                attempts: 3,
+                max: DEFAULT_MAX_DELIVERY_ATTEMPTS
+            })
+        );
+
+        let hop = TransitionContext {
+            office_changed: true,
+            ..after(3)
+        };
+        assert!(table.validate(DeliveryFailed, Returning, hop).is_ok());
+        assert!(table.validate(Returning, Returned, after(3)).is_ok());
+        assert!(matches!(
+            table.validate(Returned, New, after(3)),
+            Err(TransitionError::TerminalState { .. })
+        ));
+    }
 }
@@ -1,13 +1,17 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m2026_01_13_eventstore;
+mod m2026_10_18_package_schema_version;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m2026_01_13_eventstore::Migration)]
+        vec![
+            Box::new(m2026_01_13_eventstore::Migration),
+            Box::new(m2026_10_18_package_schema_version::Migration),
+        ]
     }
 
     fn migration_table_name() -> DynIden {
@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Every package written so far is schema version 1
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"ALTER TABLE packages ADD COLUMN IF NOT EXISTS schema_version INTEGER NOT NULL DEFAULT 1;"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(r#"ALTER TABLE packages DROP COLUMN IF EXISTS schema_version;"#)
+            .await?;
+
+        Ok(())
+    }
+}
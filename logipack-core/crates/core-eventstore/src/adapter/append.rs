@@ -1,11 +1,12 @@
 use sea_orm::{
-    ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait, QueryFilter,
-    QuerySelect, TransactionTrait,
+    ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter, QuerySelect,
+    TransactionTrait,
 };
 use strata::{list, string};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::adapter::upcast::INITIAL_SCHEMA_VERSION;
 use crate::hashing::{HashedPackage, hash_strata_value};
 use crate::schema::{packages, streams};
 
@@ -13,6 +14,8 @@ use crate::schema::{packages, streams};
 pub enum AppendError {
     #[error("stream not found")]
     StreamNotFound,
+    #[error("stream is at seq {actual}, expected {expected}")]
+    Conflict { expected: i64, actual: i64 },
     #[error("encoding error: {0:?}")]
     Encode(strata::error::EncodeError),
     #[error("db error: {0}")]
@@ -31,18 +34,49 @@ impl From<strata::error::EncodeError> for AppendError {
     }
 }
 
-/// Appends a Strata value as a package to an existing stream.
+/// Appends a Strata value as a package to an existing stream, at
+/// `INITIAL_SCHEMA_VERSION`.
 ///
 /// Guarantees:
 /// - append is atomic
 /// - seq is strictly monotonic per stream
 /// - prev_hash links correctly
 /// - streams.head_hash is updated
-pub async fn append_package(
-    db: &DatabaseConnection,
+///
+/// Accepts either a connection or a caller-owned transaction. Inside a
+/// transaction the append runs in a savepoint, so it commits or rolls back
+/// together with the caller's other writes.
+pub async fn append_package<C: TransactionTrait>(
+    db: &C,
     stream_id: Uuid,
     event_type: &str,
     value: &strata::value::Value,
+) -> Result<HashedPackage, AppendError> {
+    append_package_expecting(
+        db,
+        stream_id,
+        None,
+        event_type,
+        INITIAL_SCHEMA_VERSION,
+        value,
+    )
+    .await
+}
+
+/// Same as [`append_package`], with an optimistic concurrency check and an
+/// explicit payload `schema_version`.
+///
+/// When `expected_seq` is `Some`, the append only happens if the stream's
+/// last seq (0 for an empty stream) still equals it once the stream row is
+/// locked. Otherwise nothing is written and `AppendError::Conflict` carries
+/// the actual seq.
+pub async fn append_package_expecting<C: TransactionTrait>(
+    db: &C,
+    stream_id: Uuid,
+    expected_seq: Option<i64>,
+    event_type: &str,
+    schema_version: i32,
+    value: &strata::value::Value,
 ) -> Result<HashedPackage, AppendError> {
     let scoped = list![string!(stream_id.to_string()), value.clone()];
 
@@ -50,7 +84,15 @@ pub async fn append_package(
 
     let txn = db.begin().await?;
 
-    let result = append_package_txn(&txn, stream_id, event_type, &hashed).await;
+    let result = append_package_txn(
+        &txn,
+        stream_id,
+        expected_seq,
+        event_type,
+        schema_version,
+        &hashed,
+    )
+    .await;
 
     match result {
         Ok(_) => {
@@ -67,7 +109,9 @@ pub async fn append_package(
 async fn append_package_txn(
     txn: &DatabaseTransaction,
     stream_id: Uuid,
+    expected_seq: Option<i64>,
     event_type: &str,
+    schema_version: i32,
     hashed: &HashedPackage,
 ) -> Result<(), AppendError> {
     // Fetch stream to ensure it exists and get current head_hash.
@@ -88,7 +132,18 @@ async fn append_package_txn(
         .await?
         .flatten();
 
-    let next_seq = last_seq.unwrap_or(0) + 1;
+    let last_seq = last_seq.unwrap_or(0);
+
+    if let Some(expected) = expected_seq
+        && expected != last_seq
+    {
+        return Err(AppendError::Conflict {
+            expected,
+            actual: last_seq,
+        });
+    }
+
+    let next_seq = last_seq + 1;
 
     // Insert the new package.
     let pkg = packages::ActiveModel {
@@ -98,6 +153,7 @@ async fn append_package_txn(
         seq: sea_orm::ActiveValue::Set(next_seq),
         event_type: sea_orm::ActiveValue::Set(event_type.to_owned()),
         scb: sea_orm::ActiveValue::Set(hashed.scb.clone()),
+        schema_version: sea_orm::ActiveValue::Set(schema_version),
         created_at: sea_orm::ActiveValue::NotSet, //Db def
     };
 
@@ -1,14 +1,15 @@
-use sea_orm::DatabaseConnection;
+use sea_orm::TransactionTrait;
 use uuid::Uuid;
 
-use crate::adapter::append::{AppendError, append_package};
+use crate::adapter::append::{AppendError, append_package_expecting};
 
-pub async fn append_event(
-    db: &DatabaseConnection,
+pub async fn append_event<C: TransactionTrait>(
+    db: &C,
     stream_id: Uuid,
     event_type: &str,
+    schema_version: i32,
     payload: &strata::value::Value,
 ) -> Result<(), AppendError> {
-    append_package(db, stream_id, event_type, payload).await?;
+    append_package_expecting(db, stream_id, None, event_type, schema_version, payload).await?;
     Ok(())
 }
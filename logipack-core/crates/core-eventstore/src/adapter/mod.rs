@@ -2,3 +2,5 @@ pub mod append;
 pub mod events;
 pub mod read;
 pub mod streams;
+pub mod upcast;
+pub mod verify;
@@ -1,7 +1,11 @@
-use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+use strata::value::Value;
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::adapter::upcast::{UpcastError, UpcasterRegistry};
 use crate::schema::packages;
 
 #[derive(Debug, Clone)]
@@ -12,16 +16,35 @@ pub struct StreamPackage {
     pub prev_hash: Option<Vec<u8>>,
     /// Strata Canonical Bytes as stored in the DB.
     pub scb: Vec<u8>,
-    /// Decoded Strata value (optional for callers).
+    /// Schema version the package was stored with.
+    pub schema_version: i32,
+    /// Decoded Strata value, with the payload upcast to the current
+    /// schema version. `scb` keeps the stored bytes.
     pub value: strata::value::Value,
 }
 
+impl StreamPackage {
+    /// Payload as passed to `append_package`, without the stream scope.
+    pub fn payload(&self) -> Option<&Value> {
+        match &self.value {
+            Value::List(items) if items.len() == 2 => items.get(1),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ReadError {
     #[error("db error: {0}")]
     Db(DbErr),
     #[error("decode error: {0:?}")]
     Decode(strata::error::DecodeError),
+    #[error("upcast error at seq {seq}: {source}")]
+    Upcast {
+        seq: i64,
+        #[source]
+        source: UpcastError,
+    },
 }
 
 impl From<DbErr> for ReadError {
@@ -36,9 +59,11 @@ impl From<strata::error::DecodeError> for ReadError {
     }
 }
 
-pub async fn read_stream_packages(
-    db: &sea_orm::DatabaseConnection,
+/// Reads a stream in `seq` order, upcasting every payload through `registry`.
+pub async fn read_stream_packages<C: ConnectionTrait>(
+    db: &C,
     stream_id: Uuid,
+    registry: &UpcasterRegistry,
 ) -> Result<Vec<StreamPackage>, ReadError> {
     let rows = packages::Entity::find()
         .filter(packages::Column::StreamId.eq(stream_id))
@@ -48,16 +73,51 @@ pub async fn read_stream_packages(
 
     let mut out = Vec::with_capacity(rows.len());
     for r in rows {
-        let value = strata::decode::decode(&r.scb);
+        let value = strata::decode::decode(&r.scb)?;
+        let value = upcast_scoped(registry, &r.event_type, r.schema_version, value)
+            .map_err(|source| ReadError::Upcast { seq: r.seq, source })?;
+
         out.push(StreamPackage {
             seq: r.seq,
             event_type: r.event_type,
             hash: r.hash,
             prev_hash: r.prev_hash,
             scb: r.scb,
-            value: value?,
+            schema_version: r.schema_version,
+            value,
         });
     }
 
     Ok(out)
 }
+
+/// Upcasts the payload half of a `[stream_id, payload]` scope.
+/// Unscoped values are left alone; `verify_stream` reports those.
+fn upcast_scoped(
+    registry: &UpcasterRegistry,
+    event_type: &str,
+    version: i32,
+    value: Value,
+) -> Result<Value, UpcastError> {
+    match value {
+        Value::List(mut items) if items.len() == 2 => {
+            let payload = items.pop().unwrap_or(Value::Null);
+            items.push(registry.upcast(event_type, version, payload)?);
+            Ok(Value::List(items))
+        }
+        other => Ok(other),
+    }
+}
+
+/// Number of packages in a stream, not counting the `except` event types.
+pub async fn count_stream_packages<C: ConnectionTrait>(
+    db: &C,
+    stream_id: Uuid,
+    except: &[&str],
+) -> Result<u64, DbErr> {
+    packages::Entity::find()
+        .filter(packages::Column::StreamId.eq(stream_id))
+        .filter(packages::Column::EventType.is_not_in(except.iter().copied()))
+        .count(db)
+        .await
+}
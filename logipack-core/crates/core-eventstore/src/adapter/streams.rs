@@ -1,4 +1,7 @@
-use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -10,8 +13,8 @@ pub enum EnsureStreamError {
     Db(#[from] DbErr),
 }
 
-pub async fn ensure_stream(
-    db: &DatabaseConnection,
+pub async fn ensure_stream<C: ConnectionTrait>(
+    db: &C,
     stream_id: Uuid,
     kind: &str,
 ) -> Result<(), EnsureStreamError> {
@@ -30,3 +33,16 @@ pub async fn ensure_stream(
     model.insert(db).await?;
     Ok(())
 }
+
+/// Ids of every stream of the given kind, oldest first.
+pub async fn list_stream_ids<C: ConnectionTrait>(db: &C, kind: &str) -> Result<Vec<Uuid>, DbErr> {
+    streams::Entity::find()
+        .filter(streams::Column::Kind.eq(kind))
+        .order_by_asc(streams::Column::CreatedAt)
+        .order_by_asc(streams::Column::Id)
+        .select_only()
+        .column(streams::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await
+}
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use strata::value::Value;
+use thiserror::Error;
+
+/// Schema version of packages appended without an explicit version,
+/// and of every package written before versioning existed.
+pub const INITIAL_SCHEMA_VERSION: i32 = 1;
+
+/// Transforms a payload from version `n` to version `n + 1`.
+pub type Upcaster = fn(Value) -> Result<Value, UpcastError>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum UpcastError {
+    /// Stored version is newer than anything this registry knows about.
+    #[error("{event_type} v{version} is newer than supported v{current}")]
+    UnknownVersion {
+        event_type: String,
+        version: i32,
+        current: i32,
+    },
+    /// An upcaster rejected the payload.
+    #[error("cannot upcast {event_type} from v{from}: {reason}")]
+    Failed {
+        event_type: String,
+        from: i32,
+        reason: String,
+    },
+}
+
+impl UpcastError {
+    /// Convenience for upcasters; `event_type` and `from` are filled in by the registry.
+    pub fn invalid(reason: impl Into<String>) -> Self {
+        UpcastError::Failed {
+            event_type: String::new(),
+            from: 0,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Chains of upcasters per event type.
+///
+/// Stored packages are immutable, so payload shape changes are handled at
+/// read time: register one upcaster per version step and readers get the
+/// payload in the current shape. The current version of an event type is
+/// `INITIAL_SCHEMA_VERSION` plus the number of registered steps.
+///
+/// `packages.schema_version` is not part of the hashed bytes; a tampered
+/// version surfaces here as an upcast error, not in hash-chain verification.
+#[derive(Debug, Clone, Default)]
+pub struct UpcasterRegistry {
+    chains: HashMap<String, Vec<Upcaster>>,
+}
+
+impl UpcasterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the next upcaster for `event_type`, lifting the current
+    /// version by one. Steps must be registered oldest first.
+    pub fn register(mut self, event_type: &str, upcaster: Upcaster) -> Self {
+        self.chains
+            .entry(event_type.to_owned())
+            .or_default()
+            .push(upcaster);
+        self
+    }
+
+    /// Version new packages of `event_type` should be written with.
+    pub fn current_version(&self, event_type: &str) -> i32 {
+        let steps = self.chains.get(event_type).map(Vec::len).unwrap_or(0);
+        INITIAL_SCHEMA_VERSION + steps as i32
+    }
+
+    /// Lifts `payload` from `version` to the current version of `event_type`.
+    pub fn upcast(
+        &self,
+        event_type: &str,
+        version: i32,
+        payload: Value,
+    ) -> Result<Value, UpcastError> {
+        let current = self.current_version(event_type);
+
+        if !(INITIAL_SCHEMA_VERSION..=current).contains(&version) {
+            return Err(UpcastError::UnknownVersion {
+                event_type: event_type.to_owned(),
+                version,
+                current,
+            });
+        }
+
+        let steps = self
+            .chains
+            .get(event_type)
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+        let skip = (version - INITIAL_SCHEMA_VERSION) as usize;
+
+        let mut payload = payload;
+        for (i, step) in steps.iter().enumerate().skip(skip) {
+            payload = step(payload).map_err(|err| match err {
+                UpcastError::Failed { reason, .. } => UpcastError::Failed {
+                    event_type: event_type.to_owned(),
+                    from: INITIAL_SCHEMA_VERSION + i as i32,
+                    reason,
+                },
+                other => other,
+            })?;
+        }
+
+        Ok(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata::{int, map, string};
+
+    fn rename_ts(value: Value) -> Result<Value, UpcastError> {
+        let Value::Map(mut fields) = value else {
+            return Err(UpcastError::invalid("not a map"));
+        };
+        let ts = fields
+            .remove("ts")
+            .ok_or_else(|| UpcastError::invalid("ts missing"))?;
+        fields.insert("at".into(), ts);
+        Ok(Value::Map(fields))
+    }
+
+    fn add_flag(value: Value) -> Result<Value, UpcastError> {
+        let Value::Map(mut fields) = value else {
+            return Err(UpcastError::invalid("not a map"));
+        };
+        fields.insert("flag".into(), int!(1));
+        Ok(Value::Map(fields))
+    }
+
+    fn registry() -> UpcasterRegistry {
+        UpcasterRegistry::new()
+            .register("Thing", rename_ts)
+            .register("Thing", add_flag)
+    }
+
+    #[test]
+    fn unregistered_types_stay_at_initial_version() {
+        let reg = registry();
+        let v = map! { "x" => int!(1) };
+
+        assert_eq!(reg.current_version("Other"), INITIAL_SCHEMA_VERSION);
+        assert_eq!(reg.upcast("Other", 1, v.clone()).unwrap(), v);
+    }
+
+    #[test]
+    fn upcasts_through_every_remaining_step() {
+        let reg = registry();
+
+        assert_eq!(reg.current_version("Thing"), 3);
+
+        let v1 = map! { "ts" => int!(5) };
+        assert_eq!(
+            reg.upcast("Thing", 1, v1).unwrap(),
+            map! { "at" => int!(5), "flag" => int!(1) }
+        );
+
+        let v2 = map! { "at" => int!(5) };
+        assert_eq!(
+            reg.upcast("Thing", 2, v2).unwrap(),
+            map! { "at" => int!(5), "flag" => int!(1) }
+        );
+
+        let v3 = map! { "at" => int!(5), "flag" => int!(0) };
+        assert_eq!(reg.upcast("Thing", 3, v3.clone()).unwrap(), v3);
+    }
+
+    #[test]
+    fn future_and_invalid_versions_are_rejected() {
+        let reg = registry();
+
+        for version in [0, 4] {
+            assert!(matches!(
+                reg.upcast("Thing", version, map! {}),
+                Err(UpcastError::UnknownVersion { current: 3, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn failing_step_reports_its_version() {
+        let reg = registry();
+
+        let err = reg
+            .upcast("Thing", 1, map! { "nope" => string!("x") })
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            UpcastError::Failed {
+                event_type: "Thing".into(),
+                from: 1,
+                reason: "ts missing".into(),
+            }
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use strata::value::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::hashing::hash_strata_value;
+use crate::schema::{packages, streams};
+
+/// Result of walking a single stream's hash chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamVerification {
+    pub stream_id: Uuid,
+    /// Number of packages inspected before the walk stopped.
+    pub packages_checked: u64,
+    /// First broken link found, `None` when the chain is intact.
+    pub broken_link: Option<BrokenLink>,
+}
+
+impl StreamVerification {
+    pub fn is_intact(&self) -> bool {
+        self.broken_link.is_none()
+    }
+}
+
+/// Location and cause of the first integrity violation in a stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// Sequence number of the offending package.
+    /// For head mismatches this is the last package seq (0 for an empty stream).
+    pub seq: i64,
+    /// Stored hash of the offending package (empty for an empty stream).
+    pub hash: Vec<u8>,
+    pub kind: BreakKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakKind {
+    /// Sequence numbers are not contiguous starting from 1.
+    SeqGap { expected: i64, found: i64 },
+    /// Stored bytes are not valid Strata.
+    Undecodable,
+    /// Decoded value is not the `[stream_id, payload]` scope written by append.
+    NotScoped,
+    /// Package is scoped to a different stream.
+    ForeignStream { found: String },
+    /// Stored bytes decode, but are not the canonical encoding of their value.
+    NonCanonical,
+    /// Recomputed hash does not match the stored hash.
+    HashMismatch { computed: Vec<u8> },
+    /// `prev_hash` does not point at the previous package.
+    PrevHashMismatch {
+        expected: Option<Vec<u8>>,
+        found: Option<Vec<u8>>,
+    },
+    /// `streams.head_hash` does not point at the last package.
+    HeadHashMismatch {
+        expected: Option<Vec<u8>>,
+        found: Option<Vec<u8>>,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    #[error("stream not found")]
+    StreamNotFound,
+    #[error("db error: {0}")]
+    Db(#[from] DbErr),
+}
+
+/// Walks a stream in `seq` order and proves its hash chain is intact.
+///
+/// Checks, per package:
+/// - `seq` is contiguous starting from 1
+/// - `scb` decodes to the `[stream_id, payload]` scope of this stream
+/// - `scb` is canonical and its recomputed hash matches the stored hash
+/// - `prev_hash` links to the previous package
+///
+/// Finally checks that `streams.head_hash` points at the last package.
+/// The walk stops at the first broken link.
+pub async fn verify_stream(
+    db: &DatabaseConnection,
+    stream_id: Uuid,
+) -> Result<StreamVerification, VerifyError> {
+    let stream = streams::Entity::find_by_id(stream_id)
+        .one(db)
+        .await?
+        .ok_or(VerifyError::StreamNotFound)?;
+
+    let rows = packages::Entity::find()
+        .filter(packages::Column::StreamId.eq(stream_id))
+        .order_by_asc(packages::Column::Seq)
+        .all(db)
+        .await?;
+
+    Ok(verify_rows(&stream, &rows))
+}
+
+/// Verifies every stream in the store, ordered by creation time.
+pub async fn verify_all_streams(
+    db: &DatabaseConnection,
+) -> Result<Vec<StreamVerification>, VerifyError> {
+    let all = streams::Entity::find()
+        .order_by_asc(streams::Column::CreatedAt)
+        .order_by_asc(streams::Column::Id)
+        .all(db)
+        .await?;
+
+    let mut out = Vec::with_capacity(all.len());
+    for stream in all {
+        let rows = packages::Entity::find()
+            .filter(packages::Column::StreamId.eq(stream.id))
+            .order_by_asc(packages::Column::Seq)
+            .all(db)
+            .await?;
+
+        out.push(verify_rows(&stream, &rows));
+    }
+
+    Ok(out)
+}
+
+fn verify_rows(stream: &streams::Model, rows: &[packages::Model]) -> StreamVerification {
+    let mut prev_hash: Option<Vec<u8>> = None;
+    let mut checked = 0u64;
+
+    for (idx, row) in rows.iter().enumerate() {
+        checked += 1;
+
+        if let Some(kind) = check_package(stream.id, idx as i64 + 1, prev_hash.as_ref(), row) {
+            return StreamVerification {
+                stream_id: stream.id,
+                packages_checked: checked,
+                broken_link: Some(BrokenLink {
+                    seq: row.seq,
+                    hash: row.hash.clone(),
+                    kind,
+                }),
+            };
+        }
+
+        prev_hash = Some(row.hash.clone());
+    }
+
+    let broken_link = (stream.head_hash != prev_hash).then(|| {
+        let last = rows.last();
+        BrokenLink {
+            seq: last.map(|r| r.seq).unwrap_or(0),
+            hash: last.map(|r| r.hash.clone()).unwrap_or_default(),
+            kind: BreakKind::HeadHashMismatch {
+                expected: prev_hash,
+                found: stream.head_hash.clone(),
+            },
+        }
+    });
+
+    StreamVerification {
+        stream_id: stream.id,
+        packages_checked: checked,
+        broken_link,
+    }
+}
+
+fn check_package(
+    stream_id: Uuid,
+    expected_seq: i64,
+    prev_hash: Option<&Vec<u8>>,
+    row: &packages::Model,
+) -> Option<BreakKind> {
+    if row.seq != expected_seq {
+        return Some(BreakKind::SeqGap {
+            expected: expected_seq,
+            found: row.seq,
+        });
+    }
+
+    let Ok(value) = strata::decode::decode(&row.scb) else {
+        return Some(BreakKind::Undecodable);
+    };
+
+    // Packages are appended as `[stream_id, payload]` (see `append_package`).
+    match &value {
+        Value::List(items) if items.len() == 2 => match &items[0] {
+            Value::String(scope) if *scope == stream_id.to_string() => {}
+            Value::String(scope) => {
+                return Some(BreakKind::ForeignStream {
+                    found: scope.clone(),
+                });
+            }
+            _ => return Some(BreakKind::NotScoped),
+        },
+        _ => return Some(BreakKind::NotScoped),
+    }
+
+    let Ok(recomputed) = hash_strata_value(&value) else {
+        return Some(BreakKind::NonCanonical);
+    };
+
+    if recomputed.scb != row.scb {
+        return Some(BreakKind::NonCanonical);
+    }
+
+    if recomputed.hash != row.hash {
+        return Some(BreakKind::HashMismatch {
+            computed: recomputed.hash,
+        });
+    }
+
+    if row.prev_hash.as_ref() != prev_hash {
+        return Some(BreakKind::PrevHashMismatch {
+            expected: prev_hash.cloned(),
+            found: row.prev_hash.clone(),
+        });
+    }
+
+    None
+}
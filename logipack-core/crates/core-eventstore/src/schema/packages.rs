@@ -17,6 +17,10 @@ pub struct Model {
     pub event_type: String,
 
     pub seq: i64,
+
+    /// Payload schema version the package was encoded with.
+    /// Not covered by the hash; see `adapter::upcast`.
+    pub schema_version: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
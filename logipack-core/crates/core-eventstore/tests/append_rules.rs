@@ -1,10 +1,10 @@
 use sea_orm::{
     ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryFilter,
-    Statement,
+    Statement, TransactionTrait,
 };
 use uuid::Uuid;
 
-use core_eventstore::adapter::append::append_package;
+use core_eventstore::adapter::append::{AppendError, append_package, append_package_expecting};
 use core_eventstore::schema::{packages, streams};
 
 use strata::value::Value;
@@ -159,6 +159,100 @@ async fn append_rules() {
     assert_eq!(stream.head_hash.unwrap(), p2.hash);
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn append_inside_rolled_back_transaction_is_discarded() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = Uuid::new_v4();
+
+    let txn = db.begin().await.unwrap();
+
+    streams::Entity::insert(streams::ActiveModel {
+        id: sea_orm::ActiveValue::Set(stream_id),
+        kind: sea_orm::ActiveValue::Set("shipment".to_owned()),
+        head_hash: sea_orm::ActiveValue::Set(None),
+        created_at: sea_orm::ActiveValue::NotSet,
+    })
+    .exec(&txn)
+    .await
+    .unwrap();
+
+    let v = map! {
+        "event" => string!("Created"),
+        "seq" => int!(1),
+    };
+
+    append_package(&txn, stream_id, "ShipmentCreated", &v)
+        .await
+        .unwrap();
+
+    txn.rollback().await.unwrap();
+
+    let pkgs = packages::Entity::find()
+        .filter(packages::Column::StreamId.eq(stream_id))
+        .all(&db)
+        .await
+        .unwrap();
+    let stream = streams::Entity::find_by_id(stream_id)
+        .one(&db)
+        .await
+        .unwrap();
+
+    assert!(pkgs.is_empty());
+    assert!(stream.is_none());
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn append_with_stale_expected_seq_is_rejected() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = Uuid::new_v4();
+
+    streams::Entity::insert(streams::ActiveModel {
+        id: sea_orm::ActiveValue::Set(stream_id),
+        kind: sea_orm::ActiveValue::Set("shipment".to_owned()),
+        head_hash: sea_orm::ActiveValue::Set(None),
+        created_at: sea_orm::ActiveValue::NotSet,
+    })
+    .exec(&db)
+    .await
+    .unwrap();
+
+    let v1 = map! { "seq" => int!(1) };
+    let v2 = map! { "seq" => int!(2) };
+
+    // empty stream is at seq 0
+    let p1 = append_package_expecting(&db, stream_id, Some(0), "ShipmentCreated", 1, &v1)
+        .await
+        .unwrap();
+
+    let err = append_package_expecting(&db, stream_id, Some(0), "StatusChanged", 1, &v2)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        AppendError::Conflict {
+            expected: 0,
+            actual: 1
+        }
+    ));
+
+    let stream = streams::Entity::find_by_id(stream_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(stream.head_hash.unwrap(), p1.hash);
+
+    append_package_expecting(&db, stream_id, Some(1), "StatusChanged", 1, &v2)
+        .await
+        .unwrap();
+}
+
 async fn reset_eventstore_db(db: &DatabaseConnection) {
     // Prefer deterministic cleanup without table-level locks.
     db.execute(Statement::from_string(
@@ -0,0 +1,121 @@
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, Statement};
+use uuid::Uuid;
+
+use core_eventstore::adapter::append::{append_package, append_package_expecting};
+use core_eventstore::adapter::read::{ReadError, read_stream_packages};
+use core_eventstore::adapter::upcast::{UpcastError, UpcasterRegistry};
+use core_eventstore::schema::streams;
+
+use strata::value::Value;
+use strata::{int, list, map, string};
+
+use test_infra::test_db;
+
+fn rename_ts(value: Value) -> Result<Value, UpcastError> {
+    let Value::Map(mut fields) = value else {
+        return Err(UpcastError::invalid("not a map"));
+    };
+    let ts = fields
+        .remove("ts")
+        .ok_or_else(|| UpcastError::invalid("ts missing"))?;
+    fields.insert("at".into(), ts);
+    Ok(Value::Map(fields))
+}
+
+async fn seed_stream(db: &DatabaseConnection) -> Uuid {
+    let stream_id = Uuid::new_v4();
+
+    streams::Entity::insert(streams::ActiveModel {
+        id: sea_orm::ActiveValue::Set(stream_id),
+        kind: sea_orm::ActiveValue::Set("thing".to_owned()),
+        head_hash: sea_orm::ActiveValue::Set(None),
+        created_at: sea_orm::ActiveValue::NotSet,
+    })
+    .exec(db)
+    .await
+    .unwrap();
+
+    stream_id
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn read_upcasts_older_packages_to_current_shape() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = seed_stream(&db).await;
+    let registry = UpcasterRegistry::new().register("Thing", rename_ts);
+
+    // v1 written before the rename, v2 after
+    let v1 = append_package(&db, stream_id, "Thing", &map! { "ts" => int!(1) })
+        .await
+        .unwrap();
+    append_package_expecting(
+        &db,
+        stream_id,
+        None,
+        "Thing",
+        registry.current_version("Thing"),
+        &map! { "at" => int!(2) },
+    )
+    .await
+    .unwrap();
+
+    let pkgs = read_stream_packages(&db, stream_id, &registry)
+        .await
+        .unwrap();
+
+    assert_eq!(pkgs[0].schema_version, 1);
+    assert_eq!(pkgs[1].schema_version, 2);
+
+    assert_eq!(pkgs[0].payload(), Some(&map! { "at" => int!(1) }));
+    assert_eq!(pkgs[1].payload(), Some(&map! { "at" => int!(2) }));
+
+    // stored bytes are untouched
+    assert_eq!(pkgs[0].scb, v1.scb);
+    assert_eq!(
+        strata::decode::decode(&pkgs[0].scb).unwrap(),
+        list![string!(stream_id.to_string()), map! { "ts" => int!(1) }]
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn read_rejects_versions_newer_than_registry() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = seed_stream(&db).await;
+
+    append_package_expecting(&db, stream_id, None, "Thing", 3, &map! {})
+        .await
+        .unwrap();
+
+    let err = read_stream_packages(&db, stream_id, &UpcasterRegistry::new())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        ReadError::Upcast {
+            seq: 1,
+            source: UpcastError::UnknownVersion { version: 3, .. }
+        }
+    ));
+}
+
+async fn reset_eventstore_db(db: &DatabaseConnection) {
+    // Prefer deterministic cleanup without table-level locks.
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        "DELETE FROM packages".to_owned(),
+    ))
+    .await
+    .unwrap();
+
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        "DELETE FROM streams".to_owned(),
+    ))
+    .await
+    .unwrap();
+}
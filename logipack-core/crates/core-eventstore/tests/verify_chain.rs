@@ -0,0 +1,263 @@
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryFilter,
+    Statement, sea_query::Expr,
+};
+use uuid::Uuid;
+
+use core_eventstore::adapter::append::append_package;
+use core_eventstore::adapter::verify::{BreakKind, VerifyError, verify_all_streams, verify_stream};
+use core_eventstore::hashing::hash_strata_value;
+use core_eventstore::schema::{packages, streams};
+
+use strata::{int, list, map, string};
+
+use test_infra::test_db;
+
+async fn seed_stream(db: &DatabaseConnection, events: i64) -> Uuid {
+    let stream_id = Uuid::new_v4();
+
+    streams::Entity::insert(streams::ActiveModel {
+        id: sea_orm::ActiveValue::Set(stream_id),
+        kind: sea_orm::ActiveValue::Set("shipment".to_owned()),
+        head_hash: sea_orm::ActiveValue::Set(None),
+        created_at: sea_orm::ActiveValue::NotSet,
+    })
+    .exec(db)
+    .await
+    .unwrap();
+
+    for seq in 1..=events {
+        let v = map! {
+            "event" => string!("StatusChanged"),
+            "seq" => int!(seq),
+        };
+
+        append_package(db, stream_id, "StatusChanged", &v)
+            .await
+            .unwrap();
+    }
+
+    stream_id
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn intact_stream_verifies() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = seed_stream(&db, 3).await;
+
+    let report = verify_stream(&db, stream_id).await.unwrap();
+
+    assert!(
+        report.is_intact(),
+        "unexpected break: {:?}",
+        report.broken_link
+    );
+    assert_eq!(report.packages_checked, 3);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn empty_stream_verifies() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = seed_stream(&db, 0).await;
+
+    let report = verify_stream(&db, stream_id).await.unwrap();
+
+    assert!(report.is_intact());
+    assert_eq!(report.packages_checked, 0);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn missing_stream_is_an_error() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let err = verify_stream(&db, Uuid::new_v4()).await.unwrap_err();
+
+    assert!(matches!(err, VerifyError::StreamNotFound));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn tampered_payload_is_reported_as_hash_mismatch() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = seed_stream(&db, 3).await;
+
+    // Rewrite seq 2 with a different (but canonical, correctly scoped) payload.
+    let forged = list![
+        string!(stream_id.to_string()),
+        map! {
+            "event" => string!("StatusChanged"),
+            "seq" => int!(99),
+        }
+    ];
+    let forged = hash_strata_value(&forged).unwrap();
+
+    packages::Entity::update_many()
+        .col_expr(packages::Column::Scb, Expr::value(forged.scb))
+        .filter(packages::Column::StreamId.eq(stream_id))
+        .filter(packages::Column::Seq.eq(2))
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let report = verify_stream(&db, stream_id).await.unwrap();
+    let broken = report.broken_link.expect("tampering must be detected");
+
+    assert_eq!(broken.seq, 2);
+    assert_eq!(report.packages_checked, 2);
+    assert!(matches!(
+        broken.kind,
+        BreakKind::HashMismatch { ref computed } if *computed == forged.hash
+    ));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn garbage_bytes_are_reported_as_undecodable() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = seed_stream(&db, 2).await;
+
+    packages::Entity::update_many()
+        .col_expr(packages::Column::Scb, Expr::value(vec![0xffu8, 0x00]))
+        .filter(packages::Column::StreamId.eq(stream_id))
+        .filter(packages::Column::Seq.eq(1))
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let report = verify_stream(&db, stream_id).await.unwrap();
+    let broken = report.broken_link.unwrap();
+
+    assert_eq!(broken.seq, 1);
+    assert_eq!(broken.kind, BreakKind::Undecodable);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn deleted_package_is_reported_as_seq_gap() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = seed_stream(&db, 3).await;
+
+    packages::Entity::delete_many()
+        .filter(packages::Column::StreamId.eq(stream_id))
+        .filter(packages::Column::Seq.eq(2))
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let report = verify_stream(&db, stream_id).await.unwrap();
+    let broken = report.broken_link.unwrap();
+
+    assert_eq!(broken.seq, 3);
+    assert_eq!(
+        broken.kind,
+        BreakKind::SeqGap {
+            expected: 2,
+            found: 3
+        }
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn relinked_prev_hash_is_reported() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = seed_stream(&db, 2).await;
+
+    packages::Entity::update_many()
+        .col_expr(packages::Column::PrevHash, Expr::value(vec![0u8; 32]))
+        .filter(packages::Column::StreamId.eq(stream_id))
+        .filter(packages::Column::Seq.eq(2))
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let report = verify_stream(&db, stream_id).await.unwrap();
+    let broken = report.broken_link.unwrap();
+
+    assert_eq!(broken.seq, 2);
+    assert!(matches!(broken.kind, BreakKind::PrevHashMismatch { .. }));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn stale_head_hash_is_reported() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let stream_id = seed_stream(&db, 2).await;
+
+    let first = packages::Entity::find()
+        .filter(packages::Column::StreamId.eq(stream_id))
+        .filter(packages::Column::Seq.eq(1))
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+
+    streams::Entity::update_many()
+        .col_expr(streams::Column::HeadHash, Expr::value(first.hash.clone()))
+        .filter(streams::Column::Id.eq(stream_id))
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let report = verify_stream(&db, stream_id).await.unwrap();
+    let broken = report.broken_link.unwrap();
+
+    assert_eq!(broken.seq, 2);
+    assert!(matches!(
+        broken.kind,
+        BreakKind::HeadHashMismatch { ref found, .. } if *found == Some(first.hash.clone())
+    ));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn verify_all_streams_reports_each_stream() {
+    let db = test_db().await;
+    reset_eventstore_db(&db).await;
+
+    let good = seed_stream(&db, 2).await;
+    let bad = seed_stream(&db, 2).await;
+
+    packages::Entity::delete_many()
+        .filter(packages::Column::StreamId.eq(bad))
+        .filter(packages::Column::Seq.eq(1))
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let reports = verify_all_streams(&db).await.unwrap();
+
+    assert_eq!(reports.len(), 2);
+
+    let good_report = reports.iter().find(|r| r.stream_id == good).unwrap();
+    let bad_report = reports.iter().find(|r| r.stream_id == bad).unwrap();
+
+    assert!(good_report.is_intact());
+    assert!(!bad_report.is_intact());
+}
+
+async fn reset_eventstore_db(db: &DatabaseConnection) {
+    // Prefer deterministic cleanup without table-level locks.
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        "DELETE FROM packages".to_owned(),
+    ))
+    .await
+    .unwrap();
+
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        "DELETE FROM streams".to_owned(),
+    ))
+    .await
+    .unwrap();
+}
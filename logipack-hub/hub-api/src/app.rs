@@ -34,12 +34,15 @@ fn apply_auth_layer(router: Router<AppState>, cfg: &Config) -> Router<AppState>
 }
 
 pub fn router(cfg: Config, state: AppState) -> Router {
-    let public_router = Router::new().route("/health", get(routes::health::get_health));
+    let public_router = Router::new()
+        .route("/health", get(routes::health::get_health))
+        .merge(routes::track::router(&cfg));
 
     let protected_router = Router::new()
         .merge(routes::ensure_user::router())
         .merge(routes::me::router())
         .nest("/shipments", routes::shipments::router())
+        .nest("/reports", routes::reports::router())
         .nest("/admin", routes::admin::router());
     let protected_router = apply_auth_layer(protected_router, &cfg);
 
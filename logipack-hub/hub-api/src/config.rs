@@ -1,3 +1,9 @@
+use std::sync::Arc;
+
+use core_application::shipments::visibility::ReadPolicy;
+use core_domain::pricing::Tariff;
+use core_domain::shipment::TransitionTable;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AuthMode {
     DevSecret,
@@ -18,6 +24,23 @@ pub struct Config {
     pub auth0_audience: Option<String>,
     pub auth0_jwks_url: Option<String>,
     pub auth0_jwks_path: Option<String>,
+
+    // shipment reads
+    pub read_policy: ReadPolicy,
+    pub transitions: Arc<TransitionTable>,
+
+    // shipment pricing
+    pub tariff: Arc<Tariff>,
+
+    // public tracking
+    /// Requests per IP per minute on `/track`.
+    pub track_rate_limit: u32,
+    /// Take the client IP from `x-forwarded-for`; only behind a proxy.
+    pub trust_forwarded_for: bool,
+
+    // background jobs
+    /// Seconds between SLA breach checks; `0` turns the check off.
+    pub sla_check_interval_secs: u64,
 }
 
 impl Config {
@@ -41,6 +64,41 @@ impl Config {
         let auth0_jwks_url = std::env::var("AUTH0_JWKS_URL").ok();
         let auth0_jwks_path = std::env::var("AUTH0_JWKS_PATH").ok();
 
+        let read_policy = match std::env::var("SHIPMENT_READ_POLICY") {
+            Ok(raw) => ReadPolicy::parse(&raw).unwrap_or_else(|| {
+                panic!("SHIPMENT_READ_POLICY must list current, origin or history, got {raw:?}")
+            }),
+            Err(_) => ReadPolicy::default(),
+        };
+
+        let transitions = match std::env::var("SHIPMENT_TRANSITIONS_FILE") {
+            Ok(path) => load_transitions(&path)
+                .unwrap_or_else(|e| panic!("SHIPMENT_TRANSITIONS_FILE {path:?}: {e}")),
+            Err(_) => TransitionTable::default(),
+        };
+
+        let tariff = match std::env::var("SHIPMENT_TARIFF_FILE") {
+            Ok(path) => {
+                load_tariff(&path).unwrap_or_else(|e| panic!("SHIPMENT_TARIFF_FILE {path:?}: {e}"))
+            }
+            Err(_) => Tariff::default(),
+        };
+
+        let track_rate_limit = std::env::var("TRACK_RATE_LIMIT_PER_MINUTE")
+            .ok()
+            .and_then(|raw| raw.parse::<u32>().ok())
+            .unwrap_or(30);
+
+        let trust_forwarded_for = matches!(
+            std::env::var("TRUST_FORWARDED_FOR").as_deref(),
+            Ok("1") | Ok("true")
+        );
+
+        let sla_check_interval_secs = std::env::var("SLA_CHECK_INTERVAL_SECS")
+            .ok()
+            .and_then(|raw| raw.parse::<u64>().ok())
+            .unwrap_or(300);
+
         Self {
             host,
             port,
@@ -50,6 +108,12 @@ impl Config {
             auth0_audience,
             auth0_jwks_url,
             auth0_jwks_path,
+            read_policy,
+            transitions: Arc::new(transitions),
+            tariff: Arc::new(tariff),
+            track_rate_limit,
+            trust_forwarded_for,
+            sla_check_interval_secs,
         }
     }
 
@@ -57,3 +121,20 @@ impl Config {
         format!("{}:{}", self.host, self.port)
     }
 }
+
+/// Reads a JSON transition table: `terminal` statuses and `transitions`
+/// edges with optional `office_change`, `requires_note` and `admin_only`.
+pub fn load_transitions(path: &str) -> Result<TransitionTable, String> {
+    let raw = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&raw).map_err(|e| e.to_string())
+}
+
+/// Reads a JSON tariff: `currency`, zones, weight `bands` with prices per
+/// zone, surcharges and `tax_bps`. Rejected unless it validates.
+pub fn load_tariff(path: &str) -> Result<Tariff, String> {
+    let raw = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let tariff: Tariff = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
+    tariff.validate().map_err(|e| e.to_string())?;
+
+    Ok(tariff)
+}
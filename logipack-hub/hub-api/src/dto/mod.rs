@@ -3,5 +3,10 @@ pub mod employee_offices;
 pub mod employees;
 pub mod ensure_user;
 pub mod me;
+pub mod office_calendars;
+pub mod office_links;
 pub mod offices;
+pub mod reports;
 pub mod shipments;
+pub mod tariffs;
+pub mod track;
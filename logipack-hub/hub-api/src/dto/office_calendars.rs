@@ -0,0 +1,30 @@
+use core_data::repository::office_calendars_repo::StoredCalendar;
+use core_domain::network::OfficeCalendar;
+use serde::{Deserialize, Serialize};
+
+/// Office calendar. `calendar` holds an IANA timezone, weekly `hours`
+/// (`weekday` as `Mon`..`Sun`, local `opens` and `closes` as `HH:MM:SS`)
+/// and `holidays` (`date` as `YYYY-MM-DD`, optional `name`).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OfficeCalendarDto {
+    pub office_id: String,
+    pub calendar: OfficeCalendar,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetHolidayRequest {
+    pub name: Option<String>,
+}
+
+impl From<StoredCalendar> for OfficeCalendarDto {
+    fn from(value: StoredCalendar) -> Self {
+        Self {
+            office_id: value.office_id.to_string(),
+            calendar: value.calendar,
+            created_at: value.created_at.to_rfc3339(),
+            updated_at: value.updated_at.to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use core_data::entity::office_links;
+use core_domain::network::Route;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OfficeLinkDto {
+    pub from_office_id: String,
+    pub to_office_id: String,
+    pub transit_minutes: i32,
+    /// Minor currency units.
+    pub cost_cents: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetOfficeLinkRequest {
+    pub transit_minutes: i32,
+    pub cost_cents: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListOfficeLinksResponse {
+    pub links: Vec<OfficeLinkDto>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlanRouteParams {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Office on a route with the leg that reaches it; zero for the origin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteStopDto {
+    pub office_id: String,
+    pub transit_minutes: i32,
+    pub cost_cents: i64,
+}
+
+/// Planned route, origin first, with its totals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteDto {
+    pub stops: Vec<RouteStopDto>,
+    pub transit_minutes: i64,
+    pub cost_cents: i64,
+}
+
+impl From<office_links::Model> for OfficeLinkDto {
+    fn from(value: office_links::Model) -> Self {
+        Self {
+            from_office_id: value.from_office_id.to_string(),
+            to_office_id: value.to_office_id.to_string(),
+            transit_minutes: value.transit_minutes,
+            cost_cents: value.cost_cents,
+            created_at: value.created_at.to_rfc3339(),
+            updated_at: value.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl<N: PartialEq + ToString> From<Route<N>> for RouteDto {
+    fn from(value: Route<N>) -> Self {
+        Self {
+            transit_minutes: value.transit_minutes(),
+            cost_cents: value.cost_cents(),
+            stops: value
+                .stops
+                .into_iter()
+                .map(|stop| RouteStopDto {
+                    office_id: stop.office.to_string(),
+                    transit_minutes: stop.transit_minutes,
+                    cost_cents: stop.cost_cents,
+                })
+                .collect(),
+        }
+    }
+}
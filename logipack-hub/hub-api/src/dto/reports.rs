@@ -0,0 +1,194 @@
+use core_data::repository::reports_repo::{
+    ClientCount, CodBalance, CodOutstanding, HoldCount, OfficeCount, PeriodCount, SlaBreach,
+    StatusCount,
+};
+use serde::{Deserialize, Serialize};
+
+/// Query string shared by every report endpoint.
+///
+/// `from` and `to` are RFC 3339 timestamps or `YYYY-MM-DD` dates (UTC
+/// midnight). `from` is inclusive, `to` is exclusive.
+#[derive(Debug, Default, Deserialize)]
+pub struct ReportParams {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub office_id: Option<String>,
+    pub client_id: Option<String>,
+    pub status: Option<String>,
+    /// `true` counts only shipments on hold, `false` only the others.
+    pub on_hold: Option<bool>,
+    /// `day` (default), `week` or `month`; by-period only.
+    pub bucket: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportResponse<T> {
+    /// Inclusive lower bound, RFC 3339.
+    pub from: Option<String>,
+    /// Exclusive upper bound, RFC 3339.
+    pub to: Option<String>,
+    pub rows: Vec<T>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusRow {
+    pub status: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OfficeRow {
+    pub office_id: Option<String>,
+    pub office_name: Option<String>,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClientRow {
+    pub client_id: String,
+    pub client_name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PeriodRow {
+    /// Bucket start, RFC 3339 UTC.
+    pub period_start: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HoldRow {
+    pub reason: String,
+    pub count: i64,
+    /// Start of the longest running hold, RFC 3339.
+    pub oldest_since: String,
+}
+
+/// Shipment that broke its delivery promise; times are RFC 3339.
+#[derive(Debug, Serialize)]
+pub struct SlaBreachRow {
+    pub shipment_id: String,
+    pub tracking_number: Option<String>,
+    pub client_id: String,
+    pub current_status: String,
+    pub current_office_id: Option<String>,
+    pub promised_by: String,
+    pub breached_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CodOutstandingRow {
+    pub client_id: String,
+    pub client_name: String,
+    pub office_id: Option<String>,
+    pub office_name: Option<String>,
+    pub currency: String,
+    pub uncollected_count: i64,
+    pub uncollected_cents: i64,
+    pub unremitted_count: i64,
+    pub unremitted_cents: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CodBalanceRow {
+    pub office_id: Option<String>,
+    pub office_name: Option<String>,
+    pub currency: String,
+    pub collected_cents: i64,
+    pub remitted_cents: i64,
+    pub balance_cents: i64,
+    pub held_count: i64,
+}
+
+impl From<StatusCount> for StatusRow {
+    fn from(value: StatusCount) -> Self {
+        Self {
+            status: value.status,
+            count: value.count,
+        }
+    }
+}
+
+impl From<OfficeCount> for OfficeRow {
+    fn from(value: OfficeCount) -> Self {
+        Self {
+            office_id: value.office_id.map(|id| id.to_string()),
+            office_name: value.office_name,
+            count: value.count,
+        }
+    }
+}
+
+impl From<ClientCount> for ClientRow {
+    fn from(value: ClientCount) -> Self {
+        Self {
+            client_id: value.client_id.to_string(),
+            client_name: value.client_name,
+            count: value.count,
+        }
+    }
+}
+
+impl From<PeriodCount> for PeriodRow {
+    fn from(value: PeriodCount) -> Self {
+        Self {
+            period_start: value.period_start.and_utc().to_rfc3339(),
+            count: value.count,
+        }
+    }
+}
+
+impl From<HoldCount> for HoldRow {
+    fn from(value: HoldCount) -> Self {
+        Self {
+            reason: value.reason,
+            count: value.count,
+            oldest_since: value.oldest_since.to_rfc3339(),
+        }
+    }
+}
+
+impl From<SlaBreach> for SlaBreachRow {
+    fn from(value: SlaBreach) -> Self {
+        Self {
+            shipment_id: value.shipment_id.to_string(),
+            tracking_number: value.tracking_number,
+            client_id: value.client_id.to_string(),
+            current_status: value.current_status,
+            current_office_id: value.current_office_id.map(|id| id.to_string()),
+            promised_by: value.promised_by.to_rfc3339(),
+            breached_at: value.breached_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<CodOutstanding> for CodOutstandingRow {
+    fn from(value: CodOutstanding) -> Self {
+        Self {
+            client_id: value.client_id.to_string(),
+            client_name: value.client_name,
+            office_id: value.office_id.map(|id| id.to_string()),
+            office_name: value.office_name,
+            currency: value.currency,
+            uncollected_count: value.uncollected_count,
+            uncollected_cents: value.uncollected_cents,
+            unremitted_count: value.unremitted_count,
+            unremitted_cents: value.unremitted_cents,
+        }
+    }
+}
+
+impl From<CodBalance> for CodBalanceRow {
+    fn from(value: CodBalance) -> Self {
+        Self {
+            office_id: value.office_id.map(|id| id.to_string()),
+            office_name: value.office_name,
+            currency: value.currency,
+            collected_cents: value.collected_cents,
+            remitted_cents: value.remitted_cents,
+            balance_cents: value.balance_cents,
+            held_count: value.held_count,
+        }
+    }
+}
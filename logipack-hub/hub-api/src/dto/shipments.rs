@@ -1,28 +1,116 @@
 use base64::Engine;
-use core_domain::shipment::ShipmentStatus;
+use core_application::shipments::get_detail::ShipmentDetailView;
+use core_application::shipments::pod::RecordedProofOfDelivery;
+use core_application::shipments::timeline::{TimelineEntry, TimelineNames};
+use core_data::repository::shipments_repo::ShipmentDetails;
+use core_domain::pricing::{Price, ServiceLevel};
+use core_domain::shipment::{
+    Address, CashOnDelivery, DeliveryFailureReason, Dimensions, HoldReason, OfficeContext, Parcel,
+    Party, ProofOfDelivery, ReceiverRelationship, ShipmentEvent, ShipmentStatus, TransitionRule,
+};
+use sea_orm::prelude::ChronoDateTimeUtc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::dto::clients::ClientDto;
+use crate::dto::office_links::RouteDto;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ShipmentListItem {
     pub id: String,
+    pub tracking_number: Option<String>,
     pub client_id: String,
     pub current_status: String,
     pub current_office_id: Option<String>,
+    /// Reason of the active hold, `None` when not on hold.
+    pub hold_reason: Option<String>,
+    pub held_since: Option<String>,
+    pub cod: Option<CodDto>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Query string of `GET /shipments`.
+///
+/// Date ranges take RFC 3339 times or `YYYY-MM-DD` dates; `*_from` is
+/// inclusive, `*_to` exclusive.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListShipmentsParams {
+    pub status: Option<String>,
+    pub office_id: Option<String>,
+    pub client_id: Option<String>,
+    /// `true` lists only shipments on hold, `false` only the others.
+    pub on_hold: Option<bool>,
+    /// Hold reason code, e.g. `CUSTOMS`.
+    pub hold_reason: Option<String>,
+    pub created_from: Option<String>,
+    pub created_to: Option<String>,
+    pub updated_from: Option<String>,
+    pub updated_to: Option<String>,
+    /// `created_at` (default), `updated_at` or `status`.
+    pub sort: Option<String>,
+    /// `asc` or `desc` (default).
+    pub order: Option<String>,
+    pub limit: Option<u64>,
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShipmentListResponse {
+    pub items: Vec<ShipmentListItem>,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ShipmentDetail {
     pub id: String,
+    /// `None` for shipments created before tracking numbers were issued.
+    pub tracking_number: Option<String>,
     pub client: ClientDto,
     pub current_status: String,
     pub current_office: Option<OfficeDto>,
+    pub origin_office_id: Option<String>,
+    pub destination_office_id: Option<String>,
+    pub sender: Option<PartyDto>,
+    pub recipient: Option<PartyDto>,
+    pub parcel: Option<ParcelDto>,
+    pub cod: Option<CodDto>,
+    pub service_level: ServiceLevel,
+    /// Price charged at creation; `None` for shipments created without
+    /// both offices and a parcel.
+    pub price: Option<PriceDto>,
+    /// Tariff version that set `price`, `None` for the configured tariff.
+    pub tariff_id: Option<String>,
+    /// Failed delivery attempts so far.
+    pub delivery_attempts: i32,
+    /// Reason of the active hold, `None` when not on hold.
+    pub hold_reason: Option<String>,
+    pub held_since: Option<String>,
+    /// Planned office route, `None` when none was planned.
+    pub route: Option<RouteDto>,
+    /// Estimated delivery promised at creation, `None` without a route.
+    pub promised_by: Option<String>,
+    /// When the promise was found broken, `None` while it holds.
+    pub sla_breached_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Most recent status history row.
+    pub latest_change: Option<StatusChangeDto>,
+    /// Domain events recorded for the shipment.
+    pub event_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusChangeDto {
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub changed_at: String,
+    pub actor_user_id: Option<String>,
+    pub office_id: Option<String>,
+    pub notes: Option<String>,
+    pub failure_reason: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,11 +121,79 @@ pub struct OfficeDto {
     pub address: String,
 }
 
+/// Contact and address block of a sender or recipient.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartyDto {
+    pub name: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: AddressDto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressDto {
+    pub line1: String,
+    pub line2: Option<String>,
+    pub city: String,
+    pub postal_code: String,
+    /// ISO 3166-1 alpha-2 code.
+    pub country: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParcelDto {
+    pub weight_g: i32,
+    pub dimensions: Option<DimensionsDto>,
+    /// Minor currency units.
+    pub declared_value_cents: Option<i64>,
+    pub description: Option<String>,
+}
+
+/// Cash on delivery, collected from the recipient.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodDto {
+    /// Minor currency units.
+    pub amount_cents: i64,
+    /// ISO 4217 code, e.g. `BGN`.
+    pub currency: String,
+}
+
+/// Price with its breakdown, in minor currency units.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceDto {
+    pub currency: String,
+    pub zone: String,
+    /// Greater of the actual and the volumetric weight.
+    pub chargeable_weight_g: i32,
+    pub base_cents: i64,
+    pub express_fee_cents: i64,
+    pub cod_fee_cents: i64,
+    pub declared_value_fee_cents: i64,
+    pub tax_cents: i64,
+    pub total_cents: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DimensionsDto {
+    pub length_mm: i32,
+    pub width_mm: i32,
+    pub height_mm: i32,
+}
+
+/// `current_office_id` is the origin office. Sender, recipient and parcel
+/// may be omitted and are validated when present.
 #[derive(Deserialize)]
 pub struct CreateShipmentRequest {
     pub client_id: Uuid,
     pub current_office_id: Option<Uuid>,
     pub notes: Option<String>,
+    pub sender: Option<PartyDto>,
+    pub recipient: Option<PartyDto>,
+    pub destination_office_id: Option<Uuid>,
+    pub parcel: Option<ParcelDto>,
+    pub cod: Option<CodDto>,
+    #[serde(default)]
+    pub service_level: ServiceLevel,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -45,11 +201,168 @@ pub struct CreateShipmentResponse {
     pub shipment_id: Uuid,
 }
 
+/// Shipment to price without creating it.
+#[derive(Deserialize)]
+pub struct QuoteShipmentRequest {
+    pub origin_office_id: Uuid,
+    pub destination_office_id: Uuid,
+    pub parcel: ParcelDto,
+    pub cod: Option<CodDto>,
+    #[serde(default)]
+    pub service_level: ServiceLevel,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuoteResponse {
+    pub service_level: ServiceLevel,
+    /// Tariff version in force, `None` for the configured tariff.
+    pub tariff_id: Option<String>,
+    pub price: PriceDto,
+}
+
 #[derive(Deserialize)]
 pub struct ChangeStatusRequest {
     pub to_status: ShipmentStatus,
     pub to_office_id: Option<Uuid>,
     pub notes: Option<String>,
+    /// Required with `DELIVERY_FAILED`.
+    pub failure_reason: Option<DeliveryFailureReason>,
+    /// Last timeline seq the client has seen; stale values get a 409.
+    pub expected_seq: Option<i64>,
+    /// Only with `DELIVERED`.
+    pub proof_of_delivery: Option<ProofOfDeliveryRequest>,
+    /// Admins only: allow an `IN_TRANSIT` hop off the planned route and
+    /// re-plan from the new office.
+    #[serde(default)]
+    pub override_route: bool,
+}
+
+#[derive(Deserialize)]
+pub struct ProofOfDeliveryRequest {
+    pub receiver_name: String,
+    pub relationship: ReceiverRelationship,
+    /// RFC 3339; defaults to the time of the status change.
+    pub received_at: Option<String>,
+    /// PNG or JPEG, base64 encoded. At least one of the images is required.
+    pub signature: Option<String>,
+    pub photo: Option<String>,
+}
+
+/// Proof of delivery as recorded in the `StatusChanged` event. Images are
+/// referenced by their BLAKE3 hash, lowercase hex.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofOfDeliveryDto {
+    pub receiver_name: String,
+    pub relationship: ReceiverRelationship,
+    pub received_at: Option<String>,
+    pub signature_hash: Option<String>,
+    pub photo_hash: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordedProofOfDeliveryDto {
+    #[serde(flatten)]
+    pub proof: ProofOfDeliveryDto,
+    /// Timeline seq of the status change carrying the proof.
+    pub seq: i64,
+    pub delivered_by: String,
+    pub delivered_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PlaceHoldRequest {
+    pub reason: HoldReason,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ReleaseHoldRequest {
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AddPieceRequest {
+    pub weight_g: Option<i32>,
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ScanPieceRequest {
+    pub to_status: ShipmentStatus,
+    pub to_office_id: Option<Uuid>,
+    pub notes: Option<String>,
+    /// Required with `DELIVERY_FAILED`.
+    pub failure_reason: Option<DeliveryFailureReason>,
+    /// Admins only: allow an `IN_TRANSIT` hop off the planned route.
+    #[serde(default)]
+    pub override_route: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PieceDto {
+    pub id: String,
+    pub piece_no: i32,
+    /// Tracking number plus two-digit piece number, e.g. `SOF473124829-01`.
+    pub barcode: String,
+    pub status: String,
+    pub current_office_id: Option<String>,
+    pub weight_g: Option<i32>,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanPieceResponse {
+    pub piece: PieceDto,
+    /// Shipment status derived from all of its pieces.
+    pub shipment_status: ShipmentStatus,
+}
+
+/// Pays out a client's collected cash on delivery in one currency.
+#[derive(Deserialize)]
+pub struct RemitCodRequest {
+    pub client_id: Uuid,
+    pub currency: String,
+    /// Only remit the cash held by this office.
+    pub office_id: Option<Uuid>,
+    /// Bank transfer or cash slip reference.
+    pub reference: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodRemittanceDto {
+    pub id: String,
+    pub client_id: String,
+    pub currency: String,
+    pub amount_cents: i64,
+    pub shipment_count: i32,
+    pub reference: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: String,
+}
+
+/// A status the shipment can move to next, with the guards to satisfy.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NextStatusDto {
+    pub status: ShipmentStatus,
+    pub office_change: bool,
+    pub requires_note: bool,
+    pub admin_only: bool,
+    /// Failed delivery attempts after which this status is unavailable.
+    pub max_attempts: Option<u32>,
+}
+
+impl From<TransitionRule> for NextStatusDto {
+    fn from(rule: TransitionRule) -> Self {
+        Self {
+            status: rule.to,
+            office_change: rule.office_change,
+            requires_note: rule.requires_note,
+            admin_only: rule.admin_only,
+            max_attempts: rule.max_attempts,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -60,8 +373,308 @@ pub struct TimelineItem {
     pub scb: String,
 }
 
-impl From<core_eventstore::adapter::read::StreamPackage> for TimelineItem {
-    fn from(value: core_eventstore::adapter::read::StreamPackage) -> Self {
+/// `?view=` on the timeline endpoint.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineView {
+    /// Stored bytes, for verification tooling.
+    #[default]
+    Raw,
+    /// Event fields as JSON with resolved names.
+    Decoded,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimelineQuery {
+    #[serde(default)]
+    pub view: TimelineView,
+}
+
+#[derive(Serialize)]
+pub struct DecodedTimelineItem {
+    pub seq: i64,
+    pub event_type: String,
+    pub schema_version: i32,
+    /// Package hash, lowercase hex.
+    pub hash: String,
+    /// Previous package hash, lowercase hex.
+    pub prev_hash: Option<String>,
+    /// `None` for the stream metadata package.
+    pub event: Option<TimelineEventDto>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum TimelineEventDto {
+    ShipmentCreated {
+        tracking_number: Option<String>,
+        status: String,
+        client_id: Option<String>,
+        office: Option<NamedRef>,
+        actor: NamedRef,
+        occurred_at: Option<String>,
+        occurred_at_ms: i64,
+        notes: Option<String>,
+        // boxed to keep the variants close in size
+        sender: Option<Box<PartyDto>>,
+        recipient: Option<Box<PartyDto>>,
+        destination_office: Option<NamedRef>,
+        parcel: Option<ParcelDto>,
+    },
+    StatusChanged {
+        from_status: String,
+        to_status: String,
+        from_office: Option<NamedRef>,
+        to_office: Option<NamedRef>,
+        actor: NamedRef,
+        occurred_at: Option<String>,
+        occurred_at_ms: i64,
+        notes: Option<String>,
+        failure_reason: Option<DeliveryFailureReason>,
+        attempt: Option<u32>,
+        proof_of_delivery: Option<ProofOfDeliveryDto>,
+    },
+    /// `HoldPlaced` and `HoldReleased`, told apart by `event_type`.
+    Hold {
+        reason: HoldReason,
+        office: Option<NamedRef>,
+        actor: NamedRef,
+        occurred_at: Option<String>,
+        occurred_at_ms: i64,
+        notes: Option<String>,
+    },
+    PieceAdded {
+        piece_id: String,
+        barcode: String,
+        status: String,
+        office: Option<NamedRef>,
+        actor: NamedRef,
+        occurred_at: Option<String>,
+        occurred_at_ms: i64,
+        weight_g: Option<i32>,
+        description: Option<String>,
+    },
+    PieceScanned {
+        piece_id: String,
+        barcode: String,
+        from_status: String,
+        to_status: String,
+        from_office: Option<NamedRef>,
+        to_office: Option<NamedRef>,
+        actor: NamedRef,
+        occurred_at: Option<String>,
+        occurred_at_ms: i64,
+        notes: Option<String>,
+        failure_reason: Option<DeliveryFailureReason>,
+    },
+    CodCollected {
+        collection_id: String,
+        amount_cents: i64,
+        currency: String,
+        office: Option<NamedRef>,
+        actor: NamedRef,
+        occurred_at: Option<String>,
+        occurred_at_ms: i64,
+    },
+    CodRemitted {
+        collection_id: String,
+        remittance_id: String,
+        amount_cents: i64,
+        currency: String,
+        actor: NamedRef,
+        occurred_at: Option<String>,
+        occurred_at_ms: i64,
+        reference: Option<String>,
+    },
+    /// Empty `stops` when no route reaches the destination.
+    RoutePlanned {
+        route: RouteDto,
+        actor: NamedRef,
+        occurred_at: Option<String>,
+        occurred_at_ms: i64,
+    },
+    /// Recorded by the SLA check, so it carries no actor.
+    SlaBreached {
+        promised_by: Option<String>,
+        promised_by_ms: i64,
+        status: String,
+        office: Option<NamedRef>,
+        occurred_at: Option<String>,
+        occurred_at_ms: i64,
+    },
+}
+
+/// Id plus display name, `None` when the id no longer resolves.
+#[derive(Serialize)]
+pub struct NamedRef {
+    pub id: String,
+    pub name: Option<String>,
+}
+
+impl DecodedTimelineItem {
+    pub fn new(entry: TimelineEntry, names: &TimelineNames) -> Self {
+        Self {
+            seq: entry.seq,
+            event_type: entry.event_type,
+            schema_version: entry.schema_version,
+            hash: to_hex(&entry.hash),
+            prev_hash: entry.prev_hash.as_deref().map(to_hex),
+            event: entry.event.map(|e| TimelineEventDto::new(e, names)),
+        }
+    }
+}
+
+impl TimelineEventDto {
+    fn new(event: ShipmentEvent, names: &TimelineNames) -> Self {
+        let office = |o: Option<OfficeContext>| {
+            o.map(|o| NamedRef {
+                name: names.office(&o).map(str::to_owned),
+                id: o.office_id,
+            })
+        };
+        let actor = |id: String| NamedRef {
+            name: names.user(&id).map(str::to_owned),
+            id,
+        };
+
+        match event {
+            ShipmentEvent::Created(e) => TimelineEventDto::ShipmentCreated {
+                tracking_number: e.tracking_number,
+                status: e.status.to_string(),
+                client_id: e.client_id,
+                office: office(e.office),
+                actor: actor(e.actor.id),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+                notes: e.notes,
+                sender: e.sender.map(|p| Box::new(p.into())),
+                recipient: e.recipient.map(|p| Box::new(p.into())),
+                destination_office: office(e.destination_office),
+                parcel: e.parcel.map(ParcelDto::from),
+            },
+            ShipmentEvent::StatusChanged(e) => TimelineEventDto::StatusChanged {
+                from_status: e.from_status.to_string(),
+                to_status: e.to_status.to_string(),
+                from_office: office(e.from_office),
+                to_office: office(e.to_office),
+                actor: actor(e.actor.id),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+                notes: e.notes,
+                failure_reason: e.failure_reason,
+                attempt: e.attempt,
+                proof_of_delivery: e.proof_of_delivery.map(ProofOfDeliveryDto::from),
+            },
+            ShipmentEvent::HoldPlaced(e) => TimelineEventDto::Hold {
+                reason: e.reason,
+                office: office(e.office),
+                actor: actor(e.actor.id),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+                notes: e.notes,
+            },
+            ShipmentEvent::HoldReleased(e) => TimelineEventDto::Hold {
+                reason: e.reason,
+                office: office(e.office),
+                actor: actor(e.actor.id),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+                notes: e.notes,
+            },
+            ShipmentEvent::PieceAdded(e) => TimelineEventDto::PieceAdded {
+                piece_id: e.piece_id,
+                barcode: e.barcode,
+                status: e.status.to_string(),
+                office: office(e.office),
+                actor: actor(e.actor.id),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+                weight_g: e.weight_g,
+                description: e.description,
+            },
+            ShipmentEvent::PieceScanned(e) => TimelineEventDto::PieceScanned {
+                piece_id: e.piece_id,
+                barcode: e.barcode,
+                from_status: e.from_status.to_string(),
+                to_status: e.to_status.to_string(),
+                from_office: office(e.from_office),
+                to_office: office(e.to_office),
+                actor: actor(e.actor.id),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+                notes: e.notes,
+                failure_reason: e.failure_reason,
+            },
+            ShipmentEvent::CodCollected(e) => TimelineEventDto::CodCollected {
+                collection_id: e.collection_id,
+                amount_cents: e.amount_cents,
+                currency: e.currency.to_string(),
+                office: office(e.office),
+                actor: actor(e.actor.id),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+            },
+            ShipmentEvent::CodRemitted(e) => TimelineEventDto::CodRemitted {
+                collection_id: e.collection_id,
+                remittance_id: e.remittance_id,
+                amount_cents: e.amount_cents,
+                currency: e.currency.to_string(),
+                actor: actor(e.actor.id),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+                reference: e.reference,
+            },
+            ShipmentEvent::RoutePlanned(e) => TimelineEventDto::RoutePlanned {
+                route: RouteDto::from(e.route),
+                actor: actor(e.actor.id),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+            },
+            ShipmentEvent::SlaBreached(e) => TimelineEventDto::SlaBreached {
+                promised_by: to_rfc3339(e.promised_by_ms),
+                promised_by_ms: e.promised_by_ms,
+                status: e.status.to_string(),
+                office: office(e.office),
+                occurred_at: to_rfc3339(e.occurred_at_ms),
+                occurred_at_ms: e.occurred_at_ms,
+            },
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn to_rfc3339(millis: i64) -> Option<String> {
+    ChronoDateTimeUtc::from_timestamp_millis(millis).map(|at| at.to_rfc3339())
+}
+
+impl From<ProofOfDelivery> for ProofOfDeliveryDto {
+    fn from(value: ProofOfDelivery) -> Self {
+        Self {
+            receiver_name: value.receiver_name,
+            relationship: value.relationship,
+            received_at: to_rfc3339(value.received_at_ms),
+            signature_hash: value.signature.map(|hash| hash.to_string()),
+            photo_hash: value.photo.map(|hash| hash.to_string()),
+        }
+    }
+}
+
+impl From<RecordedProofOfDelivery> for RecordedProofOfDeliveryDto {
+    fn from(value: RecordedProofOfDelivery) -> Self {
+        Self {
+            proof: value.proof.into(),
+            seq: value.seq,
+            delivered_by: value.actor_id,
+            delivered_at: to_rfc3339(value.delivered_at_ms),
+        }
+    }
+}
+
+impl From<TimelineEntry> for TimelineItem {
+    fn from(value: TimelineEntry) -> Self {
         Self {
             seq: value.seq,
             event_type: value.event_type,
@@ -72,31 +685,201 @@ impl From<core_eventstore::adapter::read::StreamPackage> for TimelineItem {
 
 impl From<core_data::entity::shipments::Model> for ShipmentListItem {
     fn from(value: core_data::entity::shipments::Model) -> Self {
+        let cod = ShipmentDetails::from_model(&value).cod.map(CodDto::from);
+
         Self {
             id: value.id.to_string(),
+            tracking_number: value.tracking_number,
             client_id: value.client_id.to_string(),
             current_status: value.current_status,
             current_office_id: value.current_office_id.map(|id| id.to_string()),
+            hold_reason: value.hold_reason,
+            held_since: value.held_since.map(|at| at.to_rfc3339()),
+            cod,
             created_at: value.created_at.to_rfc3339(),
             updated_at: value.updated_at.to_rfc3339(),
         }
     }
 }
 
-impl From<core_data::entity::shipments::Model> for ShipmentDetail {
-    fn from(value: core_data::entity::shipments::Model) -> Self {
+impl From<core_data::entity::cod_remittances::Model> for CodRemittanceDto {
+    fn from(value: core_data::entity::cod_remittances::Model) -> Self {
         Self {
             id: value.id.to_string(),
-            client: ClientDto {
-                id: value.client_id.to_string(),
-                name: "".to_string(),
+            client_id: value.client_id.to_string(),
+            currency: value.currency,
+            amount_cents: value.amount_cents,
+            shipment_count: value.shipment_count,
+            reference: value.reference,
+            created_by: value.created_by.map(|id| id.to_string()),
+            created_at: value.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<core_data::entity::shipment_pieces::Model> for PieceDto {
+    fn from(value: core_data::entity::shipment_pieces::Model) -> Self {
+        Self {
+            id: value.id.to_string(),
+            piece_no: value.piece_no,
+            barcode: value.barcode,
+            status: value.status,
+            current_office_id: value.current_office_id.map(|id| id.to_string()),
+            weight_g: value.weight_g,
+            description: value.description,
+            created_at: value.created_at.to_rfc3339(),
+            updated_at: value.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<Party> for PartyDto {
+    fn from(value: Party) -> Self {
+        Self {
+            name: value.name,
+            phone: value.phone,
+            email: value.email,
+            address: AddressDto {
+                line1: value.address.line1,
+                line2: value.address.line2,
+                city: value.address.city,
+                postal_code: value.address.postal_code,
+                country: value.address.country,
+            },
+        }
+    }
+}
+
+impl From<PartyDto> for Party {
+    fn from(value: PartyDto) -> Self {
+        Self {
+            name: value.name,
+            phone: value.phone,
+            email: value.email,
+            address: Address {
+                line1: value.address.line1,
+                line2: value.address.line2,
+                city: value.address.city,
+                postal_code: value.address.postal_code,
+                country: value.address.country,
+            },
+        }
+    }
+}
+
+impl From<Parcel> for ParcelDto {
+    fn from(value: Parcel) -> Self {
+        Self {
+            weight_g: value.weight_g,
+            dimensions: value.dimensions.map(|d| DimensionsDto {
+                length_mm: d.length_mm,
+                width_mm: d.width_mm,
+                height_mm: d.height_mm,
+            }),
+            declared_value_cents: value.declared_value_cents,
+            description: value.description,
+        }
+    }
+}
+
+impl From<ParcelDto> for Parcel {
+    fn from(value: ParcelDto) -> Self {
+        Self {
+            weight_g: value.weight_g,
+            dimensions: value.dimensions.map(|d| Dimensions {
+                length_mm: d.length_mm,
+                width_mm: d.width_mm,
+                height_mm: d.height_mm,
+            }),
+            declared_value_cents: value.declared_value_cents,
+            description: value.description,
+        }
+    }
+}
+
+impl From<CashOnDelivery> for CodDto {
+    fn from(value: CashOnDelivery) -> Self {
+        Self {
+            amount_cents: value.amount_cents,
+            currency: value.currency.to_string(),
+        }
+    }
+}
+
+impl From<Price> for PriceDto {
+    fn from(value: Price) -> Self {
+        Self {
+            currency: value.currency.to_string(),
+            zone: value.zone,
+            chargeable_weight_g: value.chargeable_weight_g,
+            base_cents: value.base_cents,
+            express_fee_cents: value.express_fee_cents,
+            cod_fee_cents: value.cod_fee_cents,
+            declared_value_fee_cents: value.declared_value_fee_cents,
+            tax_cents: value.tax_cents,
+            total_cents: value.total_cents,
+        }
+    }
+}
+
+impl From<ShipmentDetailView> for ShipmentDetail {
+    fn from(value: ShipmentDetailView) -> Self {
+        let details = ShipmentDetails::from_model(&value.shipment);
+        let shipment = value.shipment;
+
+        let client = match value.client {
+            Some(client) => ClientDto {
+                id: client.id.to_string(),
+                name: client.name,
+                email: client.email,
+                phone: client.phone,
+            },
+            None => ClientDto {
+                id: shipment.client_id.to_string(),
+                name: String::new(),
                 email: None,
                 phone: None,
             },
-            current_status: value.current_status,
-            current_office: None,
-            created_at: value.created_at.to_rfc3339(),
-            updated_at: value.updated_at.to_rfc3339(),
+        };
+
+        Self {
+            id: shipment.id.to_string(),
+            tracking_number: shipment.tracking_number,
+            client,
+            current_status: shipment.current_status,
+            current_office: value.current_office.map(|office| OfficeDto {
+                id: office.id.to_string(),
+                name: office.name,
+                city: office.city,
+                address: office.address,
+            }),
+            origin_office_id: shipment.origin_office_id.map(|id| id.to_string()),
+            destination_office_id: details.destination_office_id.map(|id| id.to_string()),
+            sender: details.sender.map(PartyDto::from),
+            recipient: details.recipient.map(PartyDto::from),
+            parcel: details.parcel.map(ParcelDto::from),
+            cod: details.cod.map(CodDto::from),
+            service_level: details.service_level,
+            price: details.price.map(PriceDto::from),
+            tariff_id: details.tariff_id.map(|id| id.to_string()),
+            delivery_attempts: shipment.delivery_attempts,
+            hold_reason: shipment.hold_reason,
+            held_since: shipment.held_since.map(|at| at.to_rfc3339()),
+            route: value.route.map(RouteDto::from),
+            promised_by: shipment.promised_by.map(|at| at.to_rfc3339()),
+            sla_breached_at: shipment.sla_breached_at.map(|at| at.to_rfc3339()),
+            created_at: shipment.created_at.to_rfc3339(),
+            updated_at: shipment.updated_at.to_rfc3339(),
+            latest_change: value.latest_change.map(|row| StatusChangeDto {
+                from_status: row.from_status,
+                to_status: row.to_status,
+                changed_at: row.changed_at.to_rfc3339(),
+                actor_user_id: row.actor_user_id.map(|id| id.to_string()),
+                office_id: row.office_id.map(|id| id.to_string()),
+                notes: row.notes,
+                failure_reason: row.failure_reason,
+            }),
+            event_count: value.event_count,
         }
     }
 }
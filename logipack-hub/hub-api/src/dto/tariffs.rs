@@ -0,0 +1,54 @@
+use core_data::repository::tariffs_repo::TariffVersion;
+use core_domain::pricing::Tariff;
+use serde::{Deserialize, Serialize};
+
+/// Tariff version, in force from `effective_from` up to but excluding
+/// `effective_to`. `tariff` has the shape of the tariff config file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TariffVersionDto {
+    pub id: String,
+    pub effective_from: String,
+    /// `None` while open-ended.
+    pub effective_to: Option<String>,
+    pub tariff: Tariff,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Times are RFC 3339 or a bare date meaning UTC midnight.
+#[derive(Debug, Deserialize)]
+pub struct CreateTariffRequest {
+    /// Defaults to now; cannot be in the past.
+    pub effective_from: Option<String>,
+    pub effective_to: Option<String>,
+    pub tariff: Tariff,
+}
+
+/// Replaces the period and rules. Once in force only `effective_to` may
+/// change.
+#[derive(Debug, Deserialize)]
+pub struct UpdateTariffRequest {
+    pub effective_from: String,
+    pub effective_to: Option<String>,
+    pub tariff: Tariff,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListTariffsResponse {
+    pub tariffs: Vec<TariffVersionDto>,
+}
+
+impl From<TariffVersion> for TariffVersionDto {
+    fn from(value: TariffVersion) -> Self {
+        Self {
+            id: value.id.to_string(),
+            effective_from: value.effective_from.to_rfc3339(),
+            effective_to: value.effective_to.map(|at| at.to_rfc3339()),
+            tariff: value.tariff,
+            created_by: value.created_by.map(|id| id.to_string()),
+            created_at: value.created_at.to_rfc3339(),
+            updated_at: value.updated_at.to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use core_application::shipments::track::{PublicTrackingStep, PublicTrackingView};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrackingStepDto {
+    pub status: String,
+    pub city: Option<String>,
+    pub at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrackingResponse {
+    pub tracking_number: String,
+    pub status: String,
+    pub history: Vec<TrackingStepDto>,
+}
+
+impl From<PublicTrackingStep> for TrackingStepDto {
+    fn from(value: PublicTrackingStep) -> Self {
+        Self {
+            status: value.status,
+            city: value.city,
+            at: value.at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<PublicTrackingView> for TrackingResponse {
+    fn from(value: PublicTrackingView) -> Self {
+        Self {
+            tracking_number: value.tracking_number,
+            status: value.status,
+            history: value.history.into_iter().map(Into::into).collect(),
+        }
+    }
+}
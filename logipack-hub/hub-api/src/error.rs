@@ -1,16 +1,38 @@
 use axum::{Json, http::StatusCode, response::IntoResponse};
+use core_application::office_calendars::{
+    get::GetOfficeCalendarError, holidays::OfficeHolidayError, remove::RemoveOfficeCalendarError,
+    set::SetOfficeCalendarError,
+};
+use core_application::office_links::{
+    list::ListOfficeLinksError, plan::PlanRouteError, remove::RemoveOfficeLinkError,
+    set::SetOfficeLinkError,
+};
+use core_application::reports::scope::ShipmentReportError;
 use core_application::shipments::{
-    change_status::ChangeStatusError, create::CreateShipmentError, timeline::TimelineError,
+    change_status::ChangeStatusError, cod::RemitCodError, create::CreateShipmentError,
+    get_by_tracking::TrackingLookupError, hold::HoldError, pieces::PieceError, pod::PodError,
+    quote::QuoteError, timeline::TimelineError, track::PublicTrackingError,
+};
+use core_application::tariffs::{
+    TariffPeriodError, create::CreateTariffError, delete::DeleteTariffError, get::GetTariffError,
+    list::ListTariffsError, update::UpdateTariffError,
 };
 use core_application::users::ensure_user::EnsureUserError;
 use core_application::users::me::MeError;
+use core_data::repository::office_calendars_repo::OfficeCalendarRepoError;
+use core_data::repository::office_links_repo::OfficeLinkRepoError;
+use core_data::repository::shipment_query::ShipmentQueryError;
 use core_data::repository::shipments_repo::ShipmentSnapshotError;
+use core_data::repository::tariffs_repo::TariffRepoError;
+use core_domain::errors::TransitionError;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
 struct ApiErrorBody {
     code: &'static str,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
 }
 
 #[derive(Debug)]
@@ -18,6 +40,7 @@ pub struct ApiError {
     status: StatusCode,
     code: &'static str,
     message: String,
+    details: Option<serde_json::Value>,
 }
 
 impl ApiError {
@@ -26,6 +49,7 @@ impl ApiError {
             status: StatusCode::INTERNAL_SERVER_ERROR,
             code: "internal_error",
             message: message.into(),
+            details: None,
         }
     }
 
@@ -34,6 +58,7 @@ impl ApiError {
             status: StatusCode::NOT_FOUND,
             code,
             message: message.into(),
+            details: None,
         }
     }
 
@@ -42,6 +67,7 @@ impl ApiError {
             status: StatusCode::BAD_REQUEST,
             code,
             message: message.into(),
+            details: None,
         }
     }
 
@@ -50,6 +76,7 @@ impl ApiError {
             status: StatusCode::FORBIDDEN,
             code,
             message: message.into(),
+            details: None,
         }
     }
 
@@ -58,8 +85,24 @@ impl ApiError {
             status: StatusCode::CONFLICT,
             code,
             message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn too_many_requests(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            code,
+            message: message.into(),
+            details: None,
         }
     }
+
+    /// Attaches a machine-readable payload, serialized as `details`.
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
 }
 
 impl From<sea_orm::DbErr> for ApiError {
@@ -80,11 +123,13 @@ impl From<sea_orm::DbErr> for ApiError {
                     status: StatusCode::INTERNAL_SERVER_ERROR,
                     code: "db_error",
                     message: other.to_string(),
+                    details: None,
                 },
                 None => ApiError {
                     status: StatusCode::INTERNAL_SERVER_ERROR,
                     code: "db_error",
                     message: other.to_string(),
+                    details: None,
                 },
             },
         }
@@ -99,6 +144,45 @@ impl From<ShipmentSnapshotError> for ApiError {
     }
 }
 
+impl From<ShipmentQueryError> for ApiError {
+    fn from(value: ShipmentQueryError) -> Self {
+        match value {
+            ShipmentQueryError::InvalidCursor => {
+                ApiError::bad_request("invalid_cursor", "cursor does not match sort or order")
+            }
+            ShipmentQueryError::DbError(db) => db.into(),
+        }
+    }
+}
+
+impl From<TrackingLookupError> for ApiError {
+    fn from(value: TrackingLookupError) -> Self {
+        match value {
+            TrackingLookupError::InvalidTrackingNumber(e) => {
+                ApiError::bad_request("invalid_tracking_number", e.to_string())
+            }
+            TrackingLookupError::Snapshot(e) => e.into(),
+        }
+    }
+}
+
+impl From<PublicTrackingError> for ApiError {
+    fn from(value: PublicTrackingError) -> Self {
+        match value {
+            PublicTrackingError::InvalidTrackingNumber(e) => {
+                ApiError::bad_request("invalid_tracking_number", e.to_string())
+            }
+            PublicTrackingError::NotFound => {
+                ApiError::not_found("not_found", "No shipment with this tracking number")
+            }
+            PublicTrackingError::Snapshot(e) => e.into(),
+            PublicTrackingError::Offices(e) => {
+                ApiError::internal(format!("office lookup error: {e}"))
+            }
+        }
+    }
+}
+
 impl From<CreateShipmentError> for ApiError {
     fn from(err: CreateShipmentError) -> Self {
         match err {
@@ -106,8 +190,39 @@ impl From<CreateShipmentError> for ApiError {
                 ApiError::forbidden("forbidden", "you are not allowed to create shipments")
             }
 
+            CreateShipmentError::Validation(e) => {
+                ApiError::bad_request("invalid_shipment", e.to_string())
+            }
+
+            CreateShipmentError::CashOnDelivery(e) => {
+                ApiError::bad_request("invalid_cash_on_delivery", e.to_string())
+            }
+
+            CreateShipmentError::DestinationNotFound => ApiError::bad_request(
+                "destination_office_not_found",
+                "Destination office not found",
+            ),
+
+            CreateShipmentError::Pricing(e) => {
+                ApiError::bad_request("unpriceable_shipment", e.to_string())
+            }
+
+            CreateShipmentError::Tariff(e) => e.into(),
+
+            CreateShipmentError::Network(e) => e.into(),
+
+            CreateShipmentError::Calendar(e) => e.into(),
+
             CreateShipmentError::DbError(db) => db.into(),
 
+            CreateShipmentError::OfficeError(e) => {
+                ApiError::internal(format!("office lookup error: {e}"))
+            }
+
+            CreateShipmentError::TrackingNumber(e) => {
+                ApiError::internal(format!("tracking number error: {e}"))
+            }
+
             CreateShipmentError::EventstoreError(e) => {
                 ApiError::internal(format!("eventstore error: {e}"))
             }
@@ -121,6 +236,28 @@ impl From<CreateShipmentError> for ApiError {
     }
 }
 
+impl From<QuoteError> for ApiError {
+    fn from(err: QuoteError) -> Self {
+        match err {
+            QuoteError::OfficeNotFound => {
+                ApiError::not_found("office_not_found", "Office not found")
+            }
+
+            QuoteError::Validation(e) => ApiError::bad_request("invalid_shipment", e.to_string()),
+
+            QuoteError::CashOnDelivery(e) => {
+                ApiError::bad_request("invalid_cash_on_delivery", e.to_string())
+            }
+
+            QuoteError::Pricing(e) => ApiError::bad_request("unpriceable_shipment", e.to_string()),
+
+            QuoteError::Tariff(e) => e.into(),
+
+            QuoteError::DbError(db) => db.into(),
+        }
+    }
+}
+
 impl From<ChangeStatusError> for ApiError {
     fn from(err: ChangeStatusError) -> Self {
         match err {
@@ -128,6 +265,46 @@ impl From<ChangeStatusError> for ApiError {
                 ApiError::forbidden("forbidden", "you are not allowed to change shipment status")
             }
 
+            ChangeStatusError::Conflict {
+                expected_seq,
+                current_seq,
+                current_status,
+                current_office_id,
+            } => ApiError::conflict("shipment_changed", "shipment was updated by someone else")
+                .with_details(serde_json::json!({
+                    "expected_seq": expected_seq,
+                    "current_seq": current_seq,
+                    "current_status": current_status,
+                    "current_office_id": current_office_id.map(|id| id.to_string()),
+                })),
+
+            ChangeStatusError::OnHold { reason } => ApiError::conflict(
+                "shipment_on_hold",
+                format!("shipment is on hold ({reason}), release it first"),
+            ),
+
+            ChangeStatusError::HasPieces => ApiError::conflict(
+                "shipment_has_pieces",
+                "shipment status follows its pieces, scan them instead",
+            ),
+
+            ChangeStatusError::OffRoute { expected_office_id } => off_route(expected_office_id),
+
+            ChangeStatusError::NotAtDestination {
+                destination_office_id,
+            } => not_at_destination(destination_office_id),
+
+            ChangeStatusError::ProofOfDelivery(e) => {
+                ApiError::bad_request("invalid_proof_of_delivery", e.to_string())
+            }
+
+            ChangeStatusError::Domain(TransitionError::AdminOnly { from, to }) => {
+                ApiError::forbidden(
+                    "forbidden",
+                    format!("only admins may change status from {from} to {to}"),
+                )
+            }
+
             ChangeStatusError::Domain(e) => ApiError::bad_request(
                 "domain_transition_error",
                 format!("invalid status transition: {e:?}"),
@@ -135,6 +312,8 @@ impl From<ChangeStatusError> for ApiError {
 
             ChangeStatusError::SnapshotError(e) => ApiError::from(e),
 
+            ChangeStatusError::Network(e) => e.into(),
+
             ChangeStatusError::DbError(db) => db.into(),
 
             ChangeStatusError::StreamError(e) => ApiError::internal(format!("stream error: {e}")),
@@ -146,10 +325,178 @@ impl From<ChangeStatusError> for ApiError {
     }
 }
 
+impl From<HoldError> for ApiError {
+    fn from(err: HoldError) -> Self {
+        match err {
+            HoldError::Forbidden => {
+                ApiError::forbidden("forbidden", "you are not allowed to hold this shipment")
+            }
+
+            HoldError::AlreadyOnHold { reason } => ApiError::conflict(
+                "shipment_on_hold",
+                format!("shipment is already on hold ({reason})"),
+            ),
+
+            HoldError::NotOnHold => {
+                ApiError::conflict("shipment_not_on_hold", "shipment is not on hold")
+            }
+
+            HoldError::TerminalState { status } => ApiError::bad_request(
+                "terminal_state",
+                format!("shipment is {status} and cannot be held"),
+            ),
+
+            HoldError::SnapshotError(e) => ApiError::from(e),
+
+            HoldError::DbError(db) => db.into(),
+
+            HoldError::StreamError(e) => ApiError::internal(format!("stream error: {e}")),
+
+            HoldError::EventstoreError(e) => ApiError::internal(format!("eventstore error: {e}")),
+        }
+    }
+}
+
+// admins may pass `override_route` to take the hop anyway
+fn off_route(expected_office_id: Option<uuid::Uuid>) -> ApiError {
+    ApiError::conflict("off_route", "hop leaves the planned route").with_details(
+        serde_json::json!({
+            "expected_office_id": expected_office_id.map(|id| id.to_string()),
+        }),
+    )
+}
+
+fn not_at_destination(destination_office_id: uuid::Uuid) -> ApiError {
+    ApiError::conflict(
+        "not_at_destination",
+        "shipment has not reached its destination",
+    )
+    .with_details(serde_json::json!({
+        "destination_office_id": destination_office_id.to_string(),
+    }))
+}
+
+impl From<PieceError> for ApiError {
+    fn from(err: PieceError) -> Self {
+        match err {
+            PieceError::Forbidden => {
+                ApiError::forbidden("forbidden", "you are not allowed to handle this piece")
+            }
+
+            PieceError::InvalidBarcode(e) => {
+                ApiError::bad_request("invalid_piece_barcode", e.to_string())
+            }
+
+            PieceError::NotFound => ApiError::not_found("piece_not_found", "Piece not found"),
+
+            PieceError::NoTrackingNumber => ApiError::bad_request(
+                "no_tracking_number",
+                "shipment has no tracking number to derive piece barcodes from",
+            ),
+
+            PieceError::PiecesClosed { status } => ApiError::conflict(
+                "pieces_closed",
+                format!("pieces cannot be added once the shipment is {status}"),
+            ),
+
+            e @ PieceError::TooManyPieces => ApiError::conflict("too_many_pieces", e.to_string()),
+
+            PieceError::InvalidWeight => {
+                ApiError::bad_request("invalid_weight", "weight_g must be positive")
+            }
+
+            PieceError::OnHold { reason } => ApiError::conflict(
+                "shipment_on_hold",
+                format!("shipment is on hold ({reason}), release it first"),
+            ),
+
+            PieceError::OffRoute { expected_office_id } => off_route(expected_office_id),
+
+            PieceError::NotAtDestination {
+                destination_office_id,
+            } => not_at_destination(destination_office_id),
+
+            PieceError::Domain(TransitionError::AdminOnly { from, to }) => ApiError::forbidden(
+                "forbidden",
+                format!("only admins may change status from {from} to {to}"),
+            ),
+
+            PieceError::Domain(e) => ApiError::bad_request(
+                "domain_transition_error",
+                format!("invalid status transition: {e:?}"),
+            ),
+
+            PieceError::TrackingNumber(e) => {
+                ApiError::internal(format!("tracking number error: {e}"))
+            }
+
+            PieceError::SnapshotError(e) => ApiError::from(e),
+
+            PieceError::DbError(db) => db.into(),
+
+            PieceError::StreamError(e) => ApiError::internal(format!("stream error: {e}")),
+
+            PieceError::EventstoreError(e) => ApiError::internal(format!("eventstore error: {e}")),
+        }
+    }
+}
+
+impl From<PodError> for ApiError {
+    fn from(err: PodError) -> Self {
+        match err {
+            PodError::NotFound => ApiError::not_found(
+                "proof_of_delivery_not_found",
+                "Shipment has no proof of delivery",
+            ),
+            PodError::NoImage(_) => ApiError::not_found(
+                "proof_of_delivery_image_not_found",
+                "Proof of delivery has no such image",
+            ),
+            e @ PodError::Corrupted(_) => ApiError::internal(e.to_string()),
+            PodError::Timeline(e) => e.into(),
+            PodError::DbError(db) => db.into(),
+        }
+    }
+}
+
+impl From<RemitCodError> for ApiError {
+    fn from(err: RemitCodError) -> Self {
+        match err {
+            RemitCodError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            e @ RemitCodError::NothingToRemit => {
+                ApiError::not_found("nothing_to_remit", e.to_string())
+            }
+            e @ RemitCodError::Conflict => ApiError::conflict("cod_remitted", e.to_string()),
+            RemitCodError::SnapshotError(e) => ApiError::from(e),
+            RemitCodError::DbError(db) => db.into(),
+            RemitCodError::StreamError(e) => ApiError::internal(format!("stream error: {e}")),
+            RemitCodError::EventstoreError(e) => {
+                ApiError::internal(format!("eventstore error: {e}"))
+            }
+        }
+    }
+}
+
 impl From<TimelineError> for ApiError {
     fn from(value: TimelineError) -> Self {
         match value {
             TimelineError::Read(e) => ApiError::internal(format!("eventstore read error: {e}")),
+            e @ (TimelineError::Decode { .. }
+            | TimelineError::Offices(_)
+            | TimelineError::Users(_)) => ApiError::internal(e.to_string()),
+            TimelineError::Snapshot(e) => e.into(),
+        }
+    }
+}
+
+impl From<ShipmentReportError> for ApiError {
+    fn from(err: ShipmentReportError) -> Self {
+        match err {
+            ShipmentReportError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            ShipmentReportError::InvalidPeriod => {
+                ApiError::bad_request("invalid_period", "from must be before to")
+            }
+            ShipmentReportError::ReportError(e) => ApiError::internal(e.to_string()),
         }
     }
 }
@@ -189,8 +536,241 @@ impl IntoResponse for ApiError {
         let body = ApiErrorBody {
             code: self.code,
             message: self.message,
+            details: self.details,
         };
 
         (self.status, Json(body)).into_response()
     }
 }
+
+impl From<TariffRepoError> for ApiError {
+    fn from(err: TariffRepoError) -> Self {
+        match err {
+            TariffRepoError::DbError(db) => db.into(),
+            e @ TariffRepoError::InvalidRules(_) => ApiError::internal(e.to_string()),
+        }
+    }
+}
+
+impl From<TariffPeriodError> for ApiError {
+    fn from(err: TariffPeriodError) -> Self {
+        match err {
+            e @ TariffPeriodError::Empty => {
+                ApiError::bad_request("invalid_tariff_period", e.to_string())
+            }
+            e @ TariffPeriodError::Overlaps(_) => {
+                ApiError::conflict("tariff_overlap", e.to_string())
+            }
+        }
+    }
+}
+
+impl From<CreateTariffError> for ApiError {
+    fn from(err: CreateTariffError) -> Self {
+        match err {
+            CreateTariffError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            CreateTariffError::Invalid(e) => ApiError::bad_request("invalid_tariff", e.to_string()),
+            e @ CreateTariffError::Retroactive => {
+                ApiError::bad_request("invalid_tariff_period", e.to_string())
+            }
+            CreateTariffError::Period(e) => e.into(),
+            CreateTariffError::Repo(e) => e.into(),
+            CreateTariffError::DbError(db) => db.into(),
+            CreateTariffError::StreamError(e) => ApiError::internal(format!("stream error: {e}")),
+            CreateTariffError::EventstoreError(e) => {
+                ApiError::internal(format!("eventstore error: {e}"))
+            }
+        }
+    }
+}
+
+impl From<UpdateTariffError> for ApiError {
+    fn from(err: UpdateTariffError) -> Self {
+        match err {
+            UpdateTariffError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            UpdateTariffError::NotFound => {
+                ApiError::not_found("tariff_not_found", "Tariff version not found")
+            }
+            UpdateTariffError::Invalid(e) => ApiError::bad_request("invalid_tariff", e.to_string()),
+            e @ UpdateTariffError::InForce => ApiError::conflict("tariff_in_force", e.to_string()),
+            e @ UpdateTariffError::Retroactive => {
+                ApiError::bad_request("invalid_tariff_period", e.to_string())
+            }
+            UpdateTariffError::Period(e) => e.into(),
+            UpdateTariffError::Repo(e) => e.into(),
+            UpdateTariffError::DbError(db) => db.into(),
+            UpdateTariffError::EventstoreError(e) => {
+                ApiError::internal(format!("eventstore error: {e}"))
+            }
+        }
+    }
+}
+
+impl From<DeleteTariffError> for ApiError {
+    fn from(err: DeleteTariffError) -> Self {
+        match err {
+            DeleteTariffError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            DeleteTariffError::NotFound => {
+                ApiError::not_found("tariff_not_found", "Tariff version not found")
+            }
+            e @ DeleteTariffError::InForce => ApiError::conflict("tariff_in_force", e.to_string()),
+            DeleteTariffError::Repo(e) => e.into(),
+            DeleteTariffError::DbError(db) => db.into(),
+            DeleteTariffError::EventstoreError(e) => {
+                ApiError::internal(format!("eventstore error: {e}"))
+            }
+        }
+    }
+}
+
+impl From<GetTariffError> for ApiError {
+    fn from(err: GetTariffError) -> Self {
+        match err {
+            GetTariffError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            GetTariffError::NotFound => {
+                ApiError::not_found("tariff_not_found", "Tariff version not found")
+            }
+            GetTariffError::Repo(e) => e.into(),
+        }
+    }
+}
+
+impl From<ListTariffsError> for ApiError {
+    fn from(err: ListTariffsError) -> Self {
+        match err {
+            ListTariffsError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            ListTariffsError::Repo(e) => e.into(),
+        }
+    }
+}
+
+impl From<OfficeLinkRepoError> for ApiError {
+    fn from(err: OfficeLinkRepoError) -> Self {
+        match err {
+            OfficeLinkRepoError::DbError(db) => db.into(),
+            OfficeLinkRepoError::OfficeNotFound => {
+                ApiError::not_found("office_not_found", "Office not found")
+            }
+        }
+    }
+}
+
+impl From<SetOfficeLinkError> for ApiError {
+    fn from(err: SetOfficeLinkError) -> Self {
+        match err {
+            SetOfficeLinkError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            SetOfficeLinkError::Invalid(e) => {
+                ApiError::bad_request("invalid_office_link", e.to_string())
+            }
+            SetOfficeLinkError::OfficeNotFound => {
+                ApiError::not_found("office_not_found", "Office not found")
+            }
+            SetOfficeLinkError::Repo(e) => e.into(),
+        }
+    }
+}
+
+impl From<RemoveOfficeLinkError> for ApiError {
+    fn from(err: RemoveOfficeLinkError) -> Self {
+        match err {
+            RemoveOfficeLinkError::Forbidden => {
+                ApiError::forbidden("access_denied", "Access denied")
+            }
+            RemoveOfficeLinkError::NotFound => {
+                ApiError::not_found("office_link_not_found", "Office link not found")
+            }
+            RemoveOfficeLinkError::Repo(e) => e.into(),
+        }
+    }
+}
+
+impl From<ListOfficeLinksError> for ApiError {
+    fn from(err: ListOfficeLinksError) -> Self {
+        match err {
+            ListOfficeLinksError::Forbidden => {
+                ApiError::forbidden("access_denied", "Access denied")
+            }
+            ListOfficeLinksError::Repo(e) => e.into(),
+        }
+    }
+}
+
+impl From<PlanRouteError> for ApiError {
+    fn from(err: PlanRouteError) -> Self {
+        match err {
+            PlanRouteError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            PlanRouteError::Repo(e) => e.into(),
+        }
+    }
+}
+
+impl From<OfficeCalendarRepoError> for ApiError {
+    fn from(err: OfficeCalendarRepoError) -> Self {
+        match err {
+            OfficeCalendarRepoError::DbError(db) => db.into(),
+            e @ OfficeCalendarRepoError::InvalidCalendar(_) => ApiError::internal(e.to_string()),
+            OfficeCalendarRepoError::OfficeNotFound => {
+                ApiError::not_found("office_not_found", "Office not found")
+            }
+        }
+    }
+}
+
+impl From<GetOfficeCalendarError> for ApiError {
+    fn from(err: GetOfficeCalendarError) -> Self {
+        match err {
+            GetOfficeCalendarError::Forbidden => {
+                ApiError::forbidden("access_denied", "Access denied")
+            }
+            GetOfficeCalendarError::NotFound => office_calendar_not_found(),
+            GetOfficeCalendarError::Repo(e) => e.into(),
+        }
+    }
+}
+
+impl From<SetOfficeCalendarError> for ApiError {
+    fn from(err: SetOfficeCalendarError) -> Self {
+        match err {
+            SetOfficeCalendarError::Forbidden => {
+                ApiError::forbidden("access_denied", "Access denied")
+            }
+            SetOfficeCalendarError::Invalid(e) => {
+                ApiError::bad_request("invalid_office_calendar", e.to_string())
+            }
+            SetOfficeCalendarError::OfficeNotFound => {
+                ApiError::not_found("office_not_found", "Office not found")
+            }
+            SetOfficeCalendarError::Repo(e) => e.into(),
+        }
+    }
+}
+
+impl From<RemoveOfficeCalendarError> for ApiError {
+    fn from(err: RemoveOfficeCalendarError) -> Self {
+        match err {
+            RemoveOfficeCalendarError::Forbidden => {
+                ApiError::forbidden("access_denied", "Access denied")
+            }
+            RemoveOfficeCalendarError::NotFound => office_calendar_not_found(),
+            RemoveOfficeCalendarError::Repo(e) => e.into(),
+        }
+    }
+}
+
+impl From<OfficeHolidayError> for ApiError {
+    fn from(err: OfficeHolidayError) -> Self {
+        match err {
+            OfficeHolidayError::Forbidden => ApiError::forbidden("access_denied", "Access denied"),
+            OfficeHolidayError::CalendarNotFound => office_calendar_not_found(),
+            OfficeHolidayError::HolidayNotFound => {
+                ApiError::not_found("holiday_not_found", "Holiday not found")
+            }
+            OfficeHolidayError::Repo(e) => e.into(),
+            OfficeHolidayError::DbError(db) => db.into(),
+        }
+    }
+}
+
+fn office_calendar_not_found() -> ApiError {
+    ApiError::not_found("office_calendar_not_found", "Office has no calendar")
+}
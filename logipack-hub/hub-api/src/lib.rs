@@ -7,5 +7,7 @@ pub mod dto;
 pub mod error;
 pub mod migrate;
 pub mod policy;
+pub mod rate_limit;
 pub mod routes;
+pub mod sla_monitor;
 pub mod state;
@@ -1,4 +1,6 @@
-use hub_api::{app, config::Config, migrate::migrate, state::AppState};
+use std::{net::SocketAddr, time::Duration};
+
+use hub_api::{app, config::Config, migrate::migrate, sla_monitor, state::AppState};
 
 #[tokio::main]
 async fn main() {
@@ -19,15 +21,27 @@ async fn main() {
     let state = AppState {
         db,
         auth_mode: cfg.auth_mode,
+        read_policy: cfg.read_policy,
+        transitions: cfg.transitions.clone(),
+        tariff: cfg.tariff.clone(),
     };
 
+    if cfg.sla_check_interval_secs > 0 {
+        sla_monitor::spawn(
+            state.db.clone(),
+            state.transitions.clone(),
+            Duration::from_secs(cfg.sla_check_interval_secs),
+        );
+    }
+
     let listener = tokio::net::TcpListener::bind(cfg.bind_addr())
         .await
         .expect("bind hub-api listener");
 
     tracing::info!(addr = %listener.local_addr().unwrap(), "hub-api listening");
 
-    axum::serve(listener, app::router(cfg, state))
-        .await
-        .expect("serve hub-api");
+    // Client addresses feed the per-IP rate limit on public routes
+    let app = app::router(cfg, state).into_make_service_with_connect_info::<SocketAddr>();
+
+    axum::serve(listener, app).await.expect("serve hub-api");
 }
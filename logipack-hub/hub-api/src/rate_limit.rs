@@ -0,0 +1,106 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Request, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::ApiError;
+
+/// Fixed-window request counter per client IP.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    trust_forwarded_for: bool,
+    windows: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    /// Allows `limit` requests per IP every `window`. With
+    /// `trust_forwarded_for` the first `x-forwarded-for` entry is used as
+    /// the client IP, which is only safe behind a proxy that overwrites it.
+    pub fn new(limit: u32, window: Duration, trust_forwarded_for: bool) -> Self {
+        Self {
+            limit,
+            window,
+            trust_forwarded_for,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Counts a request from `ip` at `now`. Returns how long to wait when
+    /// the limit is exhausted.
+    pub fn check(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Keep memory bounded by whoever stopped calling
+        if windows.len() > 10_000 {
+            windows.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+        }
+
+        let (started, count) = windows.entry(ip).or_insert((now, 0));
+
+        if now.duration_since(*started) >= self.window {
+            *started = now;
+            *count = 0;
+        }
+
+        if *count >= self.limit {
+            return Err(self.window.saturating_sub(now.duration_since(*started)));
+        }
+
+        *count += 1;
+        Ok(())
+    }
+
+    fn client_ip(&self, req: &Request<Body>) -> IpAddr {
+        if self.trust_forwarded_for
+            && let Some(ip) = req
+                .headers()
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .and_then(|v| v.trim().parse().ok())
+        {
+            return ip;
+        }
+
+        // Without connection info (e.g. in-process tests) all callers share
+        // one window
+        req.extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip())
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+    }
+}
+
+pub async fn rate_limit_middleware(
+    req: Request<Body>,
+    next: Next,
+    limiter: Arc<RateLimiter>,
+) -> Response {
+    let ip = limiter.client_ip(&req);
+
+    match limiter.check(ip, Instant::now()) {
+        Ok(()) => next.run(req).await,
+        Err(retry_after) => {
+            // Round up so clients never retry a moment too early
+            let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+
+            (
+                [(header::RETRY_AFTER, secs.to_string())],
+                ApiError::too_many_requests("rate_limited", "Too many requests, slow down"),
+            )
+                .into_response()
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    routes::admin_ep::{clients, employees, offices},
+    routes::admin_ep::{clients, cod_remittances, employees, office_links, offices, tariffs},
     state::AppState,
 };
 use axum::Router;
@@ -7,6 +7,9 @@ use axum::Router;
 pub fn router() -> Router<AppState> {
     Router::new()
         .nest("/clients", clients::router())
+        .nest("/cod-remittances", cod_remittances::router())
         .nest("/employees", employees::router())
+        .nest("/office-links", office_links::router())
         .nest("/offices", offices::router())
+        .nest("/tariffs", tariffs::router())
 }
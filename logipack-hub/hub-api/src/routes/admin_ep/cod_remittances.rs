@@ -0,0 +1,46 @@
+use axum::{Json, Router, extract::State, routing::post};
+use core_application::{
+    actor::ActorContext,
+    shipments::cod::{RemitCod, remit_cod},
+};
+
+use crate::{
+    dto::shipments::{CodRemittanceDto, RemitCodRequest},
+    error::ApiError,
+    policy,
+    state::AppState,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", post(remit_cod_handler))
+}
+
+async fn remit_cod_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Json(req): Json<RemitCodRequest>,
+) -> Result<Json<CodRemittanceDto>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let currency = req.currency.parse().map_err(|_| {
+        ApiError::bad_request(
+            "invalid_currency",
+            "Currency must be an ISO 4217 code, e.g. BGN",
+        )
+    })?;
+
+    let remittance = remit_cod(
+        &state.db,
+        &actor,
+        RemitCod {
+            client_id: req.client_id,
+            currency,
+            office_id: req.office_id,
+            reference: req.reference,
+        },
+    )
+    .await?;
+
+    Ok(Json(remittance.into()))
+}
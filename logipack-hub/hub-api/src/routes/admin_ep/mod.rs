@@ -1,4 +1,8 @@
 pub mod clients;
+pub mod cod_remittances;
 pub mod employee_offices;
 pub mod employees;
+pub mod office_calendars;
+pub mod office_links;
 pub mod offices;
+pub mod tariffs;
@@ -0,0 +1,118 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, put},
+};
+use core_application::{
+    actor::ActorContext,
+    office_calendars::{
+        get::get_office_calendar,
+        holidays::{remove_office_holiday, set_office_holiday},
+        remove::remove_office_calendar,
+        set::set_office_calendar,
+    },
+};
+use core_domain::network::{Holiday, OfficeCalendar};
+use sea_orm::prelude::ChronoDate;
+use uuid::Uuid;
+
+use crate::{
+    dto::office_calendars::{OfficeCalendarDto, SetHolidayRequest},
+    error::ApiError,
+    policy,
+    state::AppState,
+};
+
+/// Nested under `/admin/offices/:id/calendar`.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_calendar_handler))
+        .route("/", put(set_calendar_handler))
+        .route("/", delete(remove_calendar_handler))
+        .route("/holidays/:date", put(set_holiday_handler))
+        .route("/holidays/:date", delete(remove_holiday_handler))
+}
+
+async fn get_calendar_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path(office): Path<String>,
+) -> Result<Json<OfficeCalendarDto>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let calendar = get_office_calendar(&state.db, &actor, office_id(&office)?).await?;
+
+    Ok(Json(calendar.into()))
+}
+
+async fn set_calendar_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path(office): Path<String>,
+    Json(calendar): Json<OfficeCalendar>,
+) -> Result<Json<OfficeCalendarDto>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let calendar = set_office_calendar(&state.db, &actor, office_id(&office)?, calendar).await?;
+
+    Ok(Json(calendar.into()))
+}
+
+async fn remove_calendar_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path(office): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    remove_office_calendar(&state.db, &actor, office_id(&office)?).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn set_holiday_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path((office, date)): Path<(String, String)>,
+    Json(req): Json<SetHolidayRequest>,
+) -> Result<Json<OfficeCalendarDto>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let holiday = Holiday {
+        date: holiday_date(&date)?,
+        name: req.name,
+    };
+
+    let calendar = set_office_holiday(&state.db, &actor, office_id(&office)?, holiday).await?;
+
+    Ok(Json(calendar.into()))
+}
+
+async fn remove_holiday_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path((office, date)): Path<(String, String)>,
+) -> Result<Json<OfficeCalendarDto>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let calendar =
+        remove_office_holiday(&state.db, &actor, office_id(&office)?, holiday_date(&date)?).await?;
+
+    Ok(Json(calendar.into()))
+}
+
+fn office_id(raw: &str) -> Result<Uuid, ApiError> {
+    raw.parse()
+        .map_err(|_| ApiError::bad_request("invalid_office_id", "Office ID must be a valid UUID"))
+}
+
+fn holiday_date(raw: &str) -> Result<ChronoDate, ApiError> {
+    raw.parse()
+        .map_err(|_| ApiError::bad_request("invalid_date", "Date must be YYYY-MM-DD"))
+}
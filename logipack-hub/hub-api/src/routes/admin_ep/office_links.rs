@@ -0,0 +1,120 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, put},
+};
+use core_application::{
+    actor::ActorContext,
+    office_links::{
+        list::list_office_links,
+        plan::plan_route,
+        remove::remove_office_link,
+        set::{SetOfficeLink, set_office_link},
+    },
+};
+use uuid::Uuid;
+
+use crate::{
+    dto::office_links::{
+        ListOfficeLinksResponse, OfficeLinkDto, PlanRouteParams, RouteDto, SetOfficeLinkRequest,
+    },
+    error::ApiError,
+    policy,
+    routes::params::uuid_param,
+    state::AppState,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_office_links_handler))
+        .route("/route", get(plan_route_handler))
+        .route("/:from/:to", put(set_office_link_handler))
+        .route("/:from/:to", delete(remove_office_link_handler))
+}
+
+async fn list_office_links_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+) -> Result<Json<ListOfficeLinksResponse>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let links = list_office_links(&state.db, &actor).await?;
+
+    Ok(Json(ListOfficeLinksResponse {
+        links: links.into_iter().map(Into::into).collect(),
+    }))
+}
+
+async fn set_office_link_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path((from, to)): Path<(String, String)>,
+    Json(req): Json<SetOfficeLinkRequest>,
+) -> Result<Json<OfficeLinkDto>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let link = set_office_link(
+        &state.db,
+        &actor,
+        SetOfficeLink {
+            from_office_id: office_id(&from)?,
+            to_office_id: office_id(&to)?,
+            transit_minutes: req.transit_minutes,
+            cost_cents: req.cost_cents,
+        },
+    )
+    .await?;
+
+    Ok(Json(link.into()))
+}
+
+async fn remove_office_link_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path((from, to)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    remove_office_link(&state.db, &actor, office_id(&from)?, office_id(&to)?).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Route a shipment between the two offices would be planned on now.
+async fn plan_route_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Query(params): Query<PlanRouteParams>,
+) -> Result<Json<RouteDto>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let (Some(from), Some(to)) = (
+        office_param(params.from.as_deref())?,
+        office_param(params.to.as_deref())?,
+    ) else {
+        return Err(ApiError::bad_request(
+            "invalid_office_id",
+            "Both from and to office IDs are required",
+        ));
+    };
+
+    let route = plan_route(&state.db, &actor, from, to)
+        .await?
+        .ok_or_else(|| ApiError::not_found("route_not_found", "No route between the offices"))?;
+
+    Ok(Json(route.into()))
+}
+
+fn office_id(raw: &str) -> Result<Uuid, ApiError> {
+    raw.parse()
+        .map_err(|_| ApiError::bad_request("invalid_office_id", "Office ID must be a valid UUID"))
+}
+
+fn office_param(raw: Option<&str>) -> Result<Option<Uuid>, ApiError> {
+    uuid_param(raw, "invalid_office_id", "Office ID must be a valid UUID")
+}
@@ -22,6 +22,7 @@ pub fn router() -> Router<AppState> {
         .route("/", post(create_office_handler))
         .route("/:id", put(update_office_handler))
         .route("/:id", delete(delete_office_handler))
+        .nest("/:id/calendar", super::office_calendars::router())
 }
 
 async fn list_offices_handler(
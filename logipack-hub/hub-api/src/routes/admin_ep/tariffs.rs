@@ -0,0 +1,140 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post, put},
+};
+use core_application::{
+    actor::ActorContext,
+    tariffs::{
+        create::{CreateTariff, create_tariff},
+        delete::delete_tariff,
+        get::get_tariff,
+        list::list_tariffs,
+        update::{UpdateTariff, update_tariff},
+    },
+};
+use sea_orm::prelude::ChronoDateTimeUtc;
+use uuid::Uuid;
+
+use crate::{
+    dto::tariffs::{
+        CreateTariffRequest, ListTariffsResponse, TariffVersionDto, UpdateTariffRequest,
+    },
+    error::ApiError,
+    policy,
+    routes::params::{instant_param, parse_instant},
+    state::AppState,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_tariffs_handler))
+        .route("/:id", get(get_tariff_handler))
+        .route("/", post(create_tariff_handler))
+        .route("/:id", put(update_tariff_handler))
+        .route("/:id", delete(delete_tariff_handler))
+}
+
+async fn list_tariffs_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+) -> Result<Json<ListTariffsResponse>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let versions = list_tariffs(&state.db, &actor).await?;
+
+    Ok(Json(ListTariffsResponse {
+        tariffs: versions.into_iter().map(Into::into).collect(),
+    }))
+}
+
+async fn get_tariff_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path(id): Path<String>,
+) -> Result<Json<TariffVersionDto>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let version = get_tariff(&state.db, &actor, tariff_id(&id)?).await?;
+
+    Ok(Json(version.into()))
+}
+
+async fn create_tariff_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Json(req): Json<CreateTariffRequest>,
+) -> Result<(StatusCode, Json<TariffVersionDto>), ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let version = create_tariff(
+        &state.db,
+        &actor,
+        CreateTariff {
+            effective_from: instant(req.effective_from.as_deref(), "effective_from")?,
+            effective_to: instant(req.effective_to.as_deref(), "effective_to")?,
+            tariff: req.tariff,
+        },
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(version.into())))
+}
+
+async fn update_tariff_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateTariffRequest>,
+) -> Result<Json<TariffVersionDto>, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let id = tariff_id(&id)?;
+    let effective_from = parse_instant(&req.effective_from).ok_or_else(|| {
+        ApiError::bad_request(
+            "invalid_tariff_period",
+            "effective_from must be an RFC 3339 time or a date",
+        )
+    })?;
+
+    let version = update_tariff(
+        &state.db,
+        &actor,
+        UpdateTariff {
+            id,
+            effective_from: effective_from.to_utc(),
+            effective_to: instant(req.effective_to.as_deref(), "effective_to")?,
+            tariff: req.tariff,
+        },
+    )
+    .await?;
+
+    Ok(Json(version.into()))
+}
+
+async fn delete_tariff_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    policy::require_admin(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    delete_tariff(&state.db, &actor, tariff_id(&id)?).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn tariff_id(raw: &str) -> Result<Uuid, ApiError> {
+    raw.parse()
+        .map_err(|_| ApiError::bad_request("invalid_tariff_id", "Tariff ID must be a valid UUID"))
+}
+
+fn instant(raw: Option<&str>, name: &str) -> Result<Option<ChronoDateTimeUtc>, ApiError> {
+    Ok(instant_param(raw, "invalid_tariff_period", name)?.map(|at| at.to_utc()))
+}
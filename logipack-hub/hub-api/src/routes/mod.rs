@@ -2,7 +2,10 @@ pub mod admin;
 pub mod ensure_user;
 pub mod health;
 pub mod me;
+pub mod reports;
 pub mod shipments;
+pub mod track;
 
 mod admin_ep;
 mod auth_sub;
+mod params;
@@ -0,0 +1,43 @@
+//! Parsing helpers for query-string parameters.
+
+use sea_orm::prelude::{ChronoDate, DateTimeWithTimeZone};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+/// RFC 3339, or a bare date meaning UTC midnight.
+pub fn parse_instant(raw: &str) -> Option<DateTimeWithTimeZone> {
+    if let Ok(at) = DateTimeWithTimeZone::parse_from_rfc3339(raw) {
+        return Some(at);
+    }
+
+    let date: ChronoDate = raw.parse().ok()?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc().fixed_offset())
+}
+
+/// Optional date parameter; `code` is returned on a malformed value.
+pub fn instant_param(
+    raw: Option<&str>,
+    code: &'static str,
+    name: &str,
+) -> Result<Option<DateTimeWithTimeZone>, ApiError> {
+    raw.map(|raw| {
+        parse_instant(raw).ok_or_else(|| {
+            ApiError::bad_request(code, format!("{name} must be an RFC 3339 time or a date"))
+        })
+    })
+    .transpose()
+}
+
+/// Optional UUID parameter; `code` is returned on a malformed value.
+pub fn uuid_param(
+    raw: Option<&str>,
+    code: &'static str,
+    message: &'static str,
+) -> Result<Option<Uuid>, ApiError> {
+    raw.map(|raw| {
+        raw.parse::<Uuid>()
+            .map_err(|_| ApiError::bad_request(code, message))
+    })
+    .transpose()
+}
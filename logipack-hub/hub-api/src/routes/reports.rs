@@ -0,0 +1,204 @@
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    routing::get,
+};
+use core_data::repository::reports_repo::PeriodBucket;
+use core_domain::shipment::ShipmentStatus;
+
+use crate::{
+    dto::reports::{
+        ClientRow, CodBalanceRow, CodOutstandingRow, HoldRow, OfficeRow, PeriodRow, ReportParams,
+        ReportResponse, SlaBreachRow, StatusRow,
+    },
+    error::ApiError,
+    policy,
+    routes::params::{instant_param, uuid_param},
+    state::AppState,
+};
+
+use core_application::{
+    actor::ActorContext,
+    reports::{
+        by_client::shipments_by_client,
+        by_office::shipments_by_office,
+        by_period::shipments_by_period,
+        by_status::shipments_by_status,
+        cod::{cod_balances, cod_outstanding},
+        on_hold::shipments_on_hold,
+        scope::ReportQuery,
+        sla::sla_breaches,
+    },
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/shipments-by-status", get(by_status_handler))
+        .route("/shipments-by-office", get(by_office_handler))
+        .route("/shipments-by-client", get(by_client_handler))
+        .route("/shipments-by-period", get(by_period_handler))
+        .route("/shipments-on-hold", get(on_hold_handler))
+        .route("/cod-outstanding", get(cod_outstanding_handler))
+        .route("/cod-balances", get(cod_balances_handler))
+        .route("/sla-breaches", get(sla_breaches_handler))
+}
+
+async fn by_status_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Query(params): Query<ReportParams>,
+) -> Result<Json<ReportResponse<StatusRow>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let query = parse_query(&params)?;
+    let rows = shipments_by_status(&state.db, &actor, query.clone()).await?;
+
+    Ok(Json(response(&query, rows)))
+}
+
+async fn by_office_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Query(params): Query<ReportParams>,
+) -> Result<Json<ReportResponse<OfficeRow>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let query = parse_query(&params)?;
+    let rows = shipments_by_office(&state.db, &actor, query.clone()).await?;
+
+    Ok(Json(response(&query, rows)))
+}
+
+async fn by_client_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Query(params): Query<ReportParams>,
+) -> Result<Json<ReportResponse<ClientRow>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let query = parse_query(&params)?;
+    let rows = shipments_by_client(&state.db, &actor, query.clone()).await?;
+
+    Ok(Json(response(&query, rows)))
+}
+
+async fn by_period_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Query(params): Query<ReportParams>,
+) -> Result<Json<ReportResponse<PeriodRow>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let query = parse_query(&params)?;
+    let bucket = match params.bucket.as_deref() {
+        None | Some("day") => PeriodBucket::Day,
+        Some("week") => PeriodBucket::Week,
+        Some("month") => PeriodBucket::Month,
+        Some(_) => {
+            return Err(ApiError::bad_request(
+                "invalid_bucket",
+                "bucket must be day, week or month",
+            ));
+        }
+    };
+
+    let rows = shipments_by_period(&state.db, &actor, query.clone(), bucket).await?;
+
+    Ok(Json(response(&query, rows)))
+}
+
+async fn on_hold_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Query(params): Query<ReportParams>,
+) -> Result<Json<ReportResponse<HoldRow>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let query = parse_query(&params)?;
+    let rows = shipments_on_hold(&state.db, &actor, query.clone()).await?;
+
+    Ok(Json(response(&query, rows)))
+}
+
+async fn cod_outstanding_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Query(params): Query<ReportParams>,
+) -> Result<Json<ReportResponse<CodOutstandingRow>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let query = parse_query(&params)?;
+    let rows = cod_outstanding(&state.db, &actor, query.clone()).await?;
+
+    Ok(Json(response(&query, rows)))
+}
+
+async fn cod_balances_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Query(params): Query<ReportParams>,
+) -> Result<Json<ReportResponse<CodBalanceRow>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let query = parse_query(&params)?;
+    let rows = cod_balances(&state.db, &actor, query.clone()).await?;
+
+    Ok(Json(response(&query, rows)))
+}
+
+async fn sla_breaches_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Query(params): Query<ReportParams>,
+) -> Result<Json<ReportResponse<SlaBreachRow>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let query = parse_query(&params)?;
+    let rows = sla_breaches(&state.db, &actor, query.clone()).await?;
+
+    Ok(Json(response(&query, rows)))
+}
+
+fn response<T, R: From<T>>(query: &ReportQuery, rows: Vec<T>) -> ReportResponse<R> {
+    ReportResponse {
+        from: query.from.map(|at| at.to_rfc3339()),
+        to: query.to.map(|at| at.to_rfc3339()),
+        rows: rows.into_iter().map(R::from).collect(),
+    }
+}
+
+fn parse_query(params: &ReportParams) -> Result<ReportQuery, ApiError> {
+    let status = params
+        .status
+        .as_deref()
+        .map(|raw| {
+            raw.parse::<ShipmentStatus>()
+                .map_err(|_| ApiError::bad_request("invalid_status", "Unknown shipment status"))
+        })
+        .transpose()?;
+
+    Ok(ReportQuery {
+        from: instant_param(params.from.as_deref(), "invalid_from", "from")?,
+        to: instant_param(params.to.as_deref(), "invalid_to", "to")?,
+        office_id: uuid_param(
+            params.office_id.as_deref(),
+            "invalid_office_id",
+            "Office ID must be a valid UUID",
+        )?,
+        client_id: uuid_param(
+            params.client_id.as_deref(),
+            "invalid_client_id",
+            "Client ID must be a valid UUID",
+        )?,
+        status,
+        on_hold: params.on_hold,
+    })
+}
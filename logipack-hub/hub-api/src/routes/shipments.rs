@@ -1,50 +1,189 @@
 use axum::{
     Json, Router,
-    extract::{Path, State},
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
+use base64::Engine;
 use uuid::Uuid;
 
 use crate::{
     dto::shipments::{
-        ChangeStatusRequest, CreateShipmentRequest, CreateShipmentResponse, ShipmentDetail,
-        ShipmentListItem, TimelineItem,
+        AddPieceRequest, ChangeStatusRequest, CodDto, CreateShipmentRequest,
+        CreateShipmentResponse, DecodedTimelineItem, ListShipmentsParams, NextStatusDto, PieceDto,
+        PlaceHoldRequest, ProofOfDeliveryRequest, QuoteResponse, QuoteShipmentRequest,
+        RecordedProofOfDeliveryDto, ReleaseHoldRequest, ScanPieceRequest, ScanPieceResponse,
+        ShipmentDetail, ShipmentListItem, ShipmentListResponse, TimelineItem, TimelineQuery,
+        TimelineView,
     },
     error::ApiError,
     policy,
+    routes::params::{instant_param, parse_instant, uuid_param},
     state::AppState,
 };
+use core_data::repository::shipment_query::{
+    ShipmentCursor, ShipmentQuery, ShipmentSort, SortDirection,
+};
+use core_domain::shipment::{CashOnDelivery, HoldReason, ShipmentStatus};
 
 use core_application::{
     actor::ActorContext,
     shipments::{
         change_status::{ChangeStatus, change_status},
         create::{CreateShipment, create_shipment},
-        get as shipments_get, list as shipments_list,
-        timeline::read_timeline,
+        get_by_tracking::get_shipment_by_tracking,
+        get_detail::get_shipment_detail,
+        hold::{PlaceHold, ReleaseHold, place_hold, release_hold},
+        list as shipments_list,
+        next_statuses::allowed_next_statuses,
+        pieces::{AddPiece, ScanPiece, add_piece, list_pieces, scan_piece},
+        pod::{PodImage, ProofOfDeliveryInput, get_proof_of_delivery, get_proof_of_delivery_image},
+        quote::{QuoteShipment, quote_shipment},
+        timeline::{read_timeline_with_names, read_visible_timeline},
     },
 };
 
+/// Status changes may carry two base64 images of up to
+/// `MAX_POD_IMAGE_BYTES` each.
+const STATUS_BODY_LIMIT: usize = 16 * 1024 * 1024;
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_shipments))
+        .route("/by-tracking/:code", get(get_shipment_by_tracking_handler))
         .route("/:id", get(get_shipment))
         .route("/", post(create_shipment_handler))
-        .route("/:id/status", post(change_status_handler))
+        .route("/quote", post(quote_handler))
+        .route(
+            "/:id/status",
+            post(change_status_handler).layer(DefaultBodyLimit::max(STATUS_BODY_LIMIT)),
+        )
+        .route("/:id/proof-of-delivery", get(proof_of_delivery_handler))
+        .route(
+            "/:id/proof-of-delivery/:image",
+            get(proof_of_delivery_image_handler),
+        )
+        .route("/:id/hold", post(place_hold_handler))
+        .route("/:id/release", post(release_hold_handler))
+        .route("/:id/next-statuses", get(next_statuses_handler))
+        .route("/:id/pieces", get(list_pieces_handler))
+        .route("/:id/pieces", post(add_piece_handler))
+        .route("/pieces/:barcode/scan", post(scan_piece_handler))
         .route("/:id/timeline", get(get_timeline_handler))
 }
 
-/// List all shipments
+/// List shipments, filtered and paginated by `ListShipmentsParams`
 async fn list_shipments(
     State(state): State<AppState>,
     _actor: ActorContext,
-) -> Result<Json<Vec<ShipmentListItem>>, ApiError> {
+    Query(params): Query<ListShipmentsParams>,
+) -> Result<Json<ShipmentListResponse>, ApiError> {
     policy::require_employee(&_actor)
         .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
 
-    let rows = shipments_list::list_shipments(&state.db).await?;
-    let result = rows.into_iter().map(ShipmentListItem::from).collect();
-    Ok(Json(result))
+    let query = parse_list_query(&params)?;
+    let page = shipments_list::list_shipments(&state.db, &_actor, state.read_policy, query).await?;
+
+    Ok(Json(ShipmentListResponse {
+        items: page.items.into_iter().map(ShipmentListItem::from).collect(),
+        next_cursor: page.next_cursor.map(|cursor| cursor.encode()),
+    }))
+}
+
+fn parse_list_query(params: &ListShipmentsParams) -> Result<ShipmentQuery, ApiError> {
+    let status = params
+        .status
+        .as_deref()
+        .map(|raw| {
+            raw.parse::<ShipmentStatus>()
+                .map_err(|_| ApiError::bad_request("invalid_status", "Unknown shipment status"))
+        })
+        .transpose()?;
+
+    let hold_reason = params
+        .hold_reason
+        .as_deref()
+        .map(|raw| {
+            raw.parse::<HoldReason>()
+                .map_err(|_| ApiError::bad_request("invalid_hold_reason", "Unknown hold reason"))
+        })
+        .transpose()?;
+
+    let sort = params
+        .sort
+        .as_deref()
+        .map(|raw| {
+            ShipmentSort::parse(raw).ok_or_else(|| {
+                ApiError::bad_request(
+                    "invalid_sort",
+                    "sort must be created_at, updated_at or status",
+                )
+            })
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let direction = params
+        .order
+        .as_deref()
+        .map(|raw| {
+            SortDirection::parse(raw)
+                .ok_or_else(|| ApiError::bad_request("invalid_order", "order must be asc or desc"))
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let after = params
+        .cursor
+        .as_deref()
+        .map(|raw| {
+            ShipmentCursor::decode(raw)
+                .map_err(|_| ApiError::bad_request("invalid_cursor", "Invalid cursor"))
+        })
+        .transpose()?;
+
+    Ok(ShipmentQuery {
+        status,
+        office_id: uuid_param(
+            params.office_id.as_deref(),
+            "invalid_office_id",
+            "Office ID must be a valid UUID",
+        )?,
+        client_id: uuid_param(
+            params.client_id.as_deref(),
+            "invalid_client_id",
+            "Client ID must be a valid UUID",
+        )?,
+        on_hold: params.on_hold,
+        hold_reason,
+        created_from: instant_param(
+            params.created_from.as_deref(),
+            "invalid_created_from",
+            "created_from",
+        )?,
+        created_to: instant_param(
+            params.created_to.as_deref(),
+            "invalid_created_to",
+            "created_to",
+        )?,
+        updated_from: instant_param(
+            params.updated_from.as_deref(),
+            "invalid_updated_from",
+            "updated_from",
+        )?,
+        updated_to: instant_param(
+            params.updated_to.as_deref(),
+            "invalid_updated_to",
+            "updated_to",
+        )?,
+        sort,
+        direction,
+        limit: params.limit,
+        after,
+        // set by the use case from the actor and read policy
+        visible_to: None,
+    })
 }
 
 /// Get shipment by id
@@ -59,11 +198,25 @@ async fn get_shipment(
     let shipment_id = id
         .parse()
         .map_err(|_e| ApiError::bad_request("invalid_shipment_id", "Invalid shipment id"))?;
-    let row = shipments_get::get_shipment(&state.db, shipment_id).await?;
+    let row = get_shipment_detail(&state.db, &_actor, state.read_policy, shipment_id).await?;
     let result = ShipmentDetail::from(row);
     Ok(Json(result))
 }
 
+/// Shipment detail by tracking number. Malformed numbers and wrong check
+/// digits are rejected with 400 before any lookup.
+async fn get_shipment_by_tracking_handler(
+    Path(code): Path<String>,
+    State(state): State<AppState>,
+    actor: ActorContext,
+) -> Result<Json<ShipmentDetail>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let row = get_shipment_by_tracking(&state.db, &actor, state.read_policy, &code).await?;
+    Ok(Json(ShipmentDetail::from(row)))
+}
+
 async fn create_shipment_handler(
     State(state): State<AppState>,
     actor: ActorContext,
@@ -72,13 +225,22 @@ async fn create_shipment_handler(
     policy::require_employee(&actor)
         .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
 
+    let cod = req.cod.map(cod_input).transpose()?;
+
     let id = create_shipment(
         &state.db,
         &actor,
+        &state.tariff,
         CreateShipment {
             client_id: req.client_id,
             current_office_id: req.current_office_id,
             notes: req.notes,
+            sender: req.sender.map(Into::into),
+            recipient: req.recipient.map(Into::into),
+            destination_office_id: req.destination_office_id,
+            parcel: req.parcel.map(Into::into),
+            cod,
+            service_level: req.service_level,
         },
     )
     .await?;
@@ -86,6 +248,51 @@ async fn create_shipment_handler(
     Ok(Json(CreateShipmentResponse { shipment_id: id }))
 }
 
+/// Price of a shipment between two offices under the current tariff.
+async fn quote_handler(
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Json(req): Json<QuoteShipmentRequest>,
+) -> Result<Json<QuoteResponse>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let cod = req.cod.map(cod_input).transpose()?;
+
+    let quote = quote_shipment(
+        &state.db,
+        &state.tariff,
+        QuoteShipment {
+            origin_office_id: req.origin_office_id,
+            destination_office_id: req.destination_office_id,
+            parcel: req.parcel.into(),
+            cod,
+            service_level: req.service_level,
+        },
+    )
+    .await?;
+
+    Ok(Json(QuoteResponse {
+        service_level: req.service_level,
+        tariff_id: quote.tariff_id.map(|id| id.to_string()),
+        price: quote.price.into(),
+    }))
+}
+
+fn cod_input(cod: CodDto) -> Result<CashOnDelivery, ApiError> {
+    let currency = cod.currency.parse().map_err(|_| {
+        ApiError::bad_request(
+            "invalid_cash_on_delivery",
+            "Currency must be an ISO 4217 code, e.g. BGN",
+        )
+    })?;
+
+    Ok(CashOnDelivery {
+        amount_cents: cod.amount_cents,
+        currency,
+    })
+}
+
 async fn change_status_handler(
     Path(id): Path<Uuid>,
     State(state): State<AppState>,
@@ -98,11 +305,124 @@ async fn change_status_handler(
     change_status(
         &state.db,
         &actor,
+        &state.transitions,
         ChangeStatus {
             shipment_id: id,
             to_status: req.to_status,
             to_office_id: req.to_office_id,
             notes: req.notes,
+            failure_reason: req.failure_reason,
+            expected_seq: req.expected_seq,
+            proof_of_delivery: req.proof_of_delivery.map(pod_input).transpose()?,
+            override_route: req.override_route,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn pod_input(req: ProofOfDeliveryRequest) -> Result<ProofOfDeliveryInput, ApiError> {
+    let image = |raw: Option<String>, name: &str| {
+        raw.map(|raw| {
+            base64::engine::general_purpose::STANDARD
+                .decode(raw)
+                .map_err(|_| {
+                    ApiError::bad_request(
+                        "invalid_proof_of_delivery",
+                        format!("{name} must be base64"),
+                    )
+                })
+        })
+        .transpose()
+    };
+
+    let received_at = req
+        .received_at
+        .map(|raw| {
+            parse_instant(&raw).ok_or_else(|| {
+                ApiError::bad_request(
+                    "invalid_proof_of_delivery",
+                    "received_at must be an RFC 3339 time",
+                )
+            })
+        })
+        .transpose()?;
+
+    Ok(ProofOfDeliveryInput {
+        receiver_name: req.receiver_name,
+        relationship: req.relationship,
+        received_at: received_at.map(|at| at.to_utc()),
+        signature: image(req.signature, "signature")?,
+        photo: image(req.photo, "photo")?,
+    })
+}
+
+/// Latest proof of delivery, as recorded in the shipment stream
+async fn proof_of_delivery_handler(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    actor: ActorContext,
+) -> Result<Json<RecordedProofOfDeliveryDto>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let recorded = get_proof_of_delivery(&state.db, &actor, state.read_policy, id).await?;
+
+    Ok(Json(recorded.into()))
+}
+
+/// Download the `signature` or `photo` of the proof of delivery
+async fn proof_of_delivery_image_handler(
+    Path((id, image)): Path<(Uuid, String)>,
+    State(state): State<AppState>,
+    actor: ActorContext,
+) -> Result<Response, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let image = match image.as_str() {
+        "signature" => PodImage::Signature,
+        "photo" => PodImage::Photo,
+        _ => {
+            return Err(ApiError::not_found(
+                "proof_of_delivery_image_not_found",
+                "Image must be signature or photo",
+            ));
+        }
+    };
+
+    let blob = get_proof_of_delivery_image(&state.db, &actor, state.read_policy, id, image).await?;
+    let etag: String = blob.hash.iter().map(|b| format!("{b:02x}")).collect();
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, blob.content_type),
+            (header::ETAG, format!("\"{etag}\"")),
+        ],
+        blob.bytes,
+    )
+        .into_response())
+}
+
+/// Put the shipment on hold; status changes are refused until released
+async fn place_hold_handler(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Json(req): Json<PlaceHoldRequest>,
+) -> Result<(), ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    place_hold(
+        &state.db,
+        &actor,
+        &state.transitions,
+        PlaceHold {
+            shipment_id: id,
+            reason: req.reason,
+            notes: req.notes,
         },
     )
     .await?;
@@ -110,16 +430,138 @@ async fn change_status_handler(
     Ok(())
 }
 
+async fn release_hold_handler(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Json(req): Json<ReleaseHoldRequest>,
+) -> Result<(), ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    release_hold(
+        &state.db,
+        &actor,
+        ReleaseHold {
+            shipment_id: id,
+            notes: req.notes,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn list_pieces_handler(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    actor: ActorContext,
+) -> Result<Json<Vec<PieceDto>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let pieces = list_pieces(&state.db, &actor, state.read_policy, id).await?;
+
+    Ok(Json(pieces.into_iter().map(PieceDto::from).collect()))
+}
+
+/// Add a piece; allowed while the shipment is NEW or ACCEPTED
+async fn add_piece_handler(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Json(req): Json<AddPieceRequest>,
+) -> Result<Json<PieceDto>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let piece = add_piece(
+        &state.db,
+        &actor,
+        AddPiece {
+            shipment_id: id,
+            weight_g: req.weight_g,
+            description: req.description,
+        },
+    )
+    .await?;
+
+    Ok(Json(PieceDto::from(piece)))
+}
+
+/// Move one piece by its barcode; the shipment status follows its pieces
+async fn scan_piece_handler(
+    Path(barcode): Path<String>,
+    State(state): State<AppState>,
+    actor: ActorContext,
+    Json(req): Json<ScanPieceRequest>,
+) -> Result<Json<ScanPieceResponse>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let scanned = scan_piece(
+        &state.db,
+        &actor,
+        &state.transitions,
+        ScanPiece {
+            barcode,
+            to_status: req.to_status,
+            to_office_id: req.to_office_id,
+            notes: req.notes,
+            failure_reason: req.failure_reason,
+            override_route: req.override_route,
+        },
+    )
+    .await?;
+
+    Ok(Json(ScanPieceResponse {
+        piece: PieceDto::from(scanned.piece),
+        shipment_status: scanned.shipment_status,
+    }))
+}
+
+/// Statuses the actor may move the shipment to, per the transition table
+async fn next_statuses_handler(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    actor: ActorContext,
+) -> Result<Json<Vec<NextStatusDto>>, ApiError> {
+    policy::require_employee(&actor)
+        .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
+
+    let rules =
+        allowed_next_statuses(&state.db, &actor, state.read_policy, &state.transitions, id).await?;
+
+    Ok(Json(rules.into_iter().map(NextStatusDto::from).collect()))
+}
+
+/// Shipment timeline. `?view=decoded` returns event fields as JSON with
+/// resolved names; the default raw view returns the stored Strata bytes.
 async fn get_timeline_handler(
     Path(id): Path<Uuid>,
+    Query(query): Query<TimelineQuery>,
     State(state): State<AppState>,
     actor: ActorContext,
-) -> Result<Json<Vec<TimelineItem>>, ApiError> {
+) -> Result<Response, ApiError> {
     policy::require_employee(&actor)
         .map_err(|_| ApiError::forbidden("access_denied", "Access denied"))?;
 
-    let rows = read_timeline(&state.db, id).await?;
-    let result = rows.into_iter().map(TimelineItem::from).collect();
+    match query.view {
+        TimelineView::Raw => {
+            let rows = read_visible_timeline(&state.db, &actor, state.read_policy, id).await?;
+            let result: Vec<TimelineItem> = rows.into_iter().map(TimelineItem::from).collect();
 
-    Ok(Json(result))
+            Ok(Json(result).into_response())
+        }
+        TimelineView::Decoded => {
+            let (rows, names) =
+                read_timeline_with_names(&state.db, &actor, state.read_policy, id).await?;
+            let result: Vec<DecodedTimelineItem> = rows
+                .into_iter()
+                .map(|row| DecodedTimelineItem::new(row, &names))
+                .collect();
+
+            Ok(Json(result).into_response())
+        }
+    }
 }
@@ -0,0 +1,40 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::get,
+};
+use core_application::shipments::track::track_shipment;
+
+use crate::{
+    config::Config,
+    dto::track::TrackingResponse,
+    error::ApiError,
+    rate_limit::{RateLimiter, rate_limit_middleware},
+    state::AppState,
+};
+
+/// Unauthenticated tracking for recipients, rate limited per client IP.
+pub fn router(cfg: &Config) -> Router<AppState> {
+    let limiter = Arc::new(RateLimiter::new(
+        cfg.track_rate_limit,
+        Duration::from_secs(60),
+        cfg.trust_forwarded_for,
+    ));
+
+    Router::new()
+        .route("/track/:tracking_code", get(track_handler))
+        .layer(axum::middleware::from_fn(move |req, next| {
+            rate_limit_middleware(req, next, limiter.clone())
+        }))
+}
+
+/// Redacted status history of a shipment by tracking number
+async fn track_handler(
+    State(state): State<AppState>,
+    Path(tracking_code): Path<String>,
+) -> Result<Json<TrackingResponse>, ApiError> {
+    let view = track_shipment(&state.db, &tracking_code).await?;
+    Ok(Json(view.into()))
+}
@@ -0,0 +1,37 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use core_application::shipments::sla::check_sla_breaches;
+use core_domain::shipment::TransitionTable;
+use sea_orm::{DatabaseConnection, prelude::ChronoDateTimeUtc};
+
+/// Runs the SLA check every `interval` in the background.
+///
+/// Errors are logged and the next tick tries again; shipments left over
+/// from a full batch are picked up on the following ticks.
+pub fn spawn(
+    db: DatabaseConnection,
+    transitions: Arc<TransitionTable>,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match check_sla_breaches(&db, &transitions, now()).await {
+                Ok(flagged) if flagged.is_empty() => {}
+                Ok(flagged) => tracing::info!(count = flagged.len(), "flagged SLA breaches"),
+                Err(err) => tracing::error!(error = %err, "SLA check failed"),
+            }
+        }
+    })
+}
+
+fn now() -> ChronoDateTimeUtc {
+    SystemTime::now().into()
+}
@@ -1,7 +1,18 @@
+use std::sync::Arc;
+
+use core_application::shipments::visibility::ReadPolicy;
+use core_domain::pricing::Tariff;
+use core_domain::shipment::TransitionTable;
 use sea_orm::DatabaseConnection;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: DatabaseConnection,
     pub auth_mode: crate::config::AuthMode,
+    /// Which shipments employees may read.
+    pub read_policy: ReadPolicy,
+    /// Shipment status machine.
+    pub transitions: Arc<TransitionTable>,
+    /// Prices new shipments and quotes.
+    pub tariff: Arc<Tariff>,
 }
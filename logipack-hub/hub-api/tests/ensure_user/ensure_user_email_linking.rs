@@ -30,6 +30,9 @@ fn rebuild_app(db: sea_orm::DatabaseConnection) -> axum::Router {
     let state = hub_api::state::AppState {
         db,
         auth_mode: hub_api::config::AuthMode::DevSecret,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
     };
     let cfg = hub_api::config::Config {
         host: "127.0.0.1".to_string(),
@@ -40,6 +43,12 @@ fn rebuild_app(db: sea_orm::DatabaseConnection) -> axum::Router {
         auth0_audience: None,
         auth0_jwks_url: None,
         auth0_jwks_path: None,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
+        track_rate_limit: 30,
+        trust_forwarded_for: false,
+        sla_check_interval_secs: 0,
     };
     hub_api::app::router(cfg, state)
 }
@@ -39,6 +39,9 @@ async fn ensure_user_is_idempotent_no_duplicates() {
     let state = hub_api::state::AppState {
         db: db.clone(),
         auth_mode: hub_api::config::AuthMode::DevSecret,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
     };
     let cfg = hub_api::config::Config {
         host: "127.0.0.1".to_string(),
@@ -49,6 +52,12 @@ async fn ensure_user_is_idempotent_no_duplicates() {
         auth0_audience: None,
         auth0_jwks_url: None,
         auth0_jwks_path: None,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
+        track_rate_limit: 30,
+        trust_forwarded_for: false,
+        sla_check_interval_secs: 0,
     };
     let app2 = hub_api::app::router(cfg, state);
 
@@ -98,6 +107,9 @@ async fn ensure_user_idempotent_same_data_succeeds() {
     let state = hub_api::state::AppState {
         db: db.clone(),
         auth_mode: hub_api::config::AuthMode::DevSecret,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
     };
     let cfg = hub_api::config::Config {
         host: "127.0.0.1".to_string(),
@@ -108,6 +120,12 @@ async fn ensure_user_idempotent_same_data_succeeds() {
         auth0_audience: None,
         auth0_jwks_url: None,
         auth0_jwks_path: None,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
+        track_rate_limit: 30,
+        trust_forwarded_for: false,
+        sla_check_interval_secs: 0,
     };
     let app2 = hub_api::app::router(cfg, state);
 
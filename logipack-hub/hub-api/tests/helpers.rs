@@ -6,8 +6,12 @@ use std::sync::Once;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use axum::Router;
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use http_body_util::BodyExt;
 use sea_orm::sqlx::types::chrono;
 use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use tower::ServiceExt;
 use uuid::Uuid;
 
 use core_application::actor::ActorContext;
@@ -116,22 +120,35 @@ pub fn test_config() -> Config {
         auth0_audience: None,
         auth0_jwks_url: None,
         auth0_jwks_path: None,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
+        track_rate_limit: 30,
+        trust_forwarded_for: false,
+        sla_check_interval_secs: 0,
     }
 }
 
 pub async fn cleanup_db(db: &DatabaseConnection) {
     let tables = [
+        "cod_collections",
+        "cod_remittances",
         "shipment_status_history",
+        "shipment_route_stops",
         "shipments",
+        "tariffs",
         "employee_offices",
         "employees",
         "user_roles",
         "users",
         "clients",
         "roles",
+        "office_calendars",
+        "office_links",
         "offices",
         "packages",
         "streams",
+        "blobs",
     ];
 
     for t in tables {
@@ -157,6 +174,12 @@ fn test_auth0_config() -> Config {
             "{}/tests/fixtures/jwks.json",
             env!("CARGO_MANIFEST_DIR")
         )),
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
+        track_rate_limit: 30,
+        trust_forwarded_for: false,
+        sla_check_interval_secs: 0,
     }
 }
 
@@ -169,6 +192,9 @@ pub async fn setup_auth0_app() -> axum::Router {
     let state = AppState {
         db,
         auth_mode: AuthMode::Auth0,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
     };
 
     app::router(test_auth0_config(), state)
@@ -183,6 +209,9 @@ pub async fn setup_auth0_app_with_db() -> (Router, DatabaseConnection) {
     let state = AppState {
         db: db.clone(),
         auth_mode: AuthMode::Auth0,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
     };
 
     (app::router(test_auth0_config(), state), db)
@@ -249,6 +278,9 @@ pub async fn setup_app_with_employee() -> (Router, ActorContext) {
     let state = AppState {
         db,
         auth_mode: AuthMode::DevSecret,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
     };
 
     let cfg = test_config();
@@ -280,6 +312,9 @@ pub async fn setup_app() -> Router {
     let state = AppState {
         db,
         auth_mode: AuthMode::DevSecret,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
     };
 
     let cfg = test_config();
@@ -294,6 +329,9 @@ pub async fn setup_app_with_db() -> (Router, DatabaseConnection) {
     let state = AppState {
         db: db.clone(),
         auth_mode: AuthMode::DevSecret,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
     };
 
     let cfg = test_config();
@@ -530,6 +568,9 @@ pub async fn setup_app_with_admin() -> (Router, DatabaseConnection, ActorContext
     let state = AppState {
         db: db.clone(),
         auth_mode: AuthMode::DevSecret,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
     };
 
     let cfg = test_config();
@@ -540,3 +581,37 @@ pub async fn setup_app_with_admin() -> (Router, DatabaseConnection, ActorContext
 
     (app, db, admin_actor)
 }
+
+/// Sends a dev-auth request as `sub`, with `body` as JSON when given.
+/// Returns the status and the parsed response body, `Null` when empty.
+pub async fn send_json(
+    app: &Router,
+    sub: &str,
+    method: Method,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let builder = Request::builder()
+        .header("x-dev-secret", "test_secret")
+        .header("x-dev-user-sub", sub)
+        .header("content-type", "application/json")
+        .method(method)
+        .uri(uri);
+
+    let req = match body {
+        Some(body) => builder.body(Body::from(serde_json::to_vec(&body).unwrap())),
+        None => builder.body(Body::empty()),
+    }
+    .unwrap();
+
+    let res = app.clone().oneshot(req).await.unwrap();
+    let status = res.status();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let body = if body.is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null)
+    };
+
+    (status, body)
+}
@@ -0,0 +1,65 @@
+use axum::{Router, http::Method, http::StatusCode};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers::send_json;
+
+#[path = "helpers.rs"]
+pub mod helpers;
+
+#[path = "office_calendars/office_calendars_get.rs"]
+mod office_calendars_get;
+
+#[path = "office_calendars/office_calendars_set.rs"]
+mod office_calendars_set;
+
+#[path = "office_calendars/office_calendars_delete.rs"]
+mod office_calendars_delete;
+
+#[path = "office_calendars/office_holidays_set.rs"]
+mod office_holidays_set;
+
+#[path = "office_calendars/office_holidays_delete.rs"]
+mod office_holidays_delete;
+
+fn calendar_uri(office: Uuid) -> String {
+    format!("/admin/offices/{office}/calendar")
+}
+
+/// Open 09:00-18:00 Monday to Friday, Sofia time.
+fn working_week() -> Value {
+    let hours: Vec<_> = ["Mon", "Tue", "Wed", "Thu", "Fri"]
+        .iter()
+        .map(|day| json!({ "weekday": day, "opens": "09:00:00", "closes": "18:00:00" }))
+        .collect();
+
+    json!({ "timezone": "Europe/Sofia", "hours": hours })
+}
+
+/// Gives `office` the working week calendar through the API.
+async fn set_calendar(app: &Router, sub: &str, office: Uuid) {
+    let (status, body) = send_json(
+        app,
+        sub,
+        Method::PUT,
+        &calendar_uri(office),
+        Some(working_week()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+}
+
+/// Adds a holiday on `date` to the calendar of `office` through the API.
+async fn set_holiday(app: &Router, sub: &str, office: Uuid, date: &str) -> Value {
+    let (status, body) = send_json(
+        app,
+        sub,
+        Method::PUT,
+        &format!("{}/holidays/{date}", calendar_uri(office)),
+        Some(json!({ "name": "Liberation Day" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    body
+}
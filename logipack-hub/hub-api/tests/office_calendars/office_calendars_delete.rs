@@ -0,0 +1,66 @@
+use axum::http::{Method, StatusCode};
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::{calendar_uri, set_calendar};
+
+#[tokio::test]
+async fn admin_can_delete_office_calendar() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+
+    let (status, _) = send_json(
+        &app,
+        &admin.sub,
+        Method::DELETE,
+        &calendar_uri(office),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) =
+        send_json(&app, &admin.sub, Method::GET, &calendar_uri(office), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "office_calendar_not_found");
+}
+
+#[tokio::test]
+async fn delete_missing_office_calendar_returns_not_found() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::DELETE,
+        &calendar_uri(office),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "office_calendar_not_found");
+}
+
+#[tokio::test]
+async fn employee_cannot_delete_office_calendar() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::DELETE,
+        &calendar_uri(office),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
@@ -0,0 +1,71 @@
+use axum::http::{Method, StatusCode};
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::{calendar_uri, set_calendar, set_holiday};
+
+#[tokio::test]
+async fn admin_can_get_office_calendar() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+    set_holiday(&app, &admin.sub, office, "2026-03-03").await;
+
+    let (status, body) =
+        send_json(&app, &admin.sub, Method::GET, &calendar_uri(office), None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["office_id"], office.to_string());
+    assert_eq!(body["calendar"]["timezone"], "Europe/Sofia");
+    assert_eq!(body["calendar"]["holidays"][0]["date"], "2026-03-03");
+}
+
+#[tokio::test]
+async fn get_missing_office_calendar_returns_not_found() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    let (status, body) =
+        send_json(&app, &admin.sub, Method::GET, &calendar_uri(office), None).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "office_calendar_not_found");
+}
+
+#[tokio::test]
+async fn get_office_calendar_invalid_uuid() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::GET,
+        "/admin/offices/not-a-uuid/calendar",
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "invalid_office_id");
+}
+
+#[tokio::test]
+async fn employee_cannot_get_office_calendar() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::GET,
+        &calendar_uri(office),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
@@ -0,0 +1,105 @@
+use axum::http::{Method, StatusCode};
+use hub_api::dto::office_calendars::OfficeCalendarDto;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::{calendar_uri, working_week};
+
+#[tokio::test]
+async fn admin_can_set_office_calendar() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &calendar_uri(office),
+        Some(working_week()),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let calendar: OfficeCalendarDto = serde_json::from_value(body).unwrap();
+    assert_eq!(calendar.office_id, office.to_string());
+    assert_eq!(calendar.calendar.hours.len(), 5);
+    assert!(calendar.calendar.holidays.is_empty());
+}
+
+#[tokio::test]
+async fn hours_closing_before_opening_are_rejected() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &calendar_uri(office),
+        Some(json!({
+            "timezone": "Europe/Sofia",
+            "hours": [{ "weekday": "Mon", "opens": "18:00:00", "closes": "09:00:00" }],
+        })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "invalid_office_calendar");
+}
+
+#[tokio::test]
+async fn unknown_timezone_is_rejected() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    let mut calendar = working_week();
+    calendar["timezone"] = json!("Mars/Olympus");
+
+    let (status, _) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &calendar_uri(office),
+        Some(calendar),
+    )
+    .await;
+
+    assert!(status.is_client_error());
+}
+
+#[tokio::test]
+async fn calendar_for_unknown_office_is_rejected() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &calendar_uri(Uuid::new_v4()),
+        Some(working_week()),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "office_not_found");
+}
+
+#[tokio::test]
+async fn employee_cannot_set_office_calendar() {
+    let (app, db, _admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+    let office = seed_office(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::PUT,
+        &calendar_uri(office),
+        Some(working_week()),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
@@ -0,0 +1,68 @@
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::{calendar_uri, set_calendar, set_holiday};
+
+#[tokio::test]
+async fn admin_can_delete_office_holiday() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+    set_holiday(&app, &admin.sub, office, "2026-03-03").await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::DELETE,
+        &format!("{}/holidays/2026-03-03", calendar_uri(office)),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["calendar"]["holidays"], json!([]));
+}
+
+#[tokio::test]
+async fn delete_missing_holiday_returns_not_found() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::DELETE,
+        &format!("{}/holidays/2026-03-03", calendar_uri(office)),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "holiday_not_found");
+}
+
+#[tokio::test]
+async fn employee_cannot_delete_office_holiday() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+    set_holiday(&app, &admin.sub, office, "2026-03-03").await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::DELETE,
+        &format!("{}/holidays/2026-03-03", calendar_uri(office)),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
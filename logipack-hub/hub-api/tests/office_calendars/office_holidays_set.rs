@@ -0,0 +1,82 @@
+use axum::http::{Method, StatusCode};
+use hub_api::dto::office_calendars::OfficeCalendarDto;
+use serde_json::json;
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::{calendar_uri, set_calendar, set_holiday};
+
+#[tokio::test]
+async fn admin_can_set_office_holiday() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+
+    let body = set_holiday(&app, &admin.sub, office, "2026-03-03").await;
+
+    let calendar: OfficeCalendarDto = serde_json::from_value(body).unwrap();
+    assert_eq!(calendar.calendar.holidays.len(), 1);
+    assert_eq!(
+        calendar.calendar.holidays[0].name.as_deref(),
+        Some("Liberation Day")
+    );
+}
+
+#[tokio::test]
+async fn holiday_with_invalid_date_is_rejected() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &format!("{}/holidays/not-a-date", calendar_uri(office)),
+        Some(json!({})),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "invalid_date");
+}
+
+#[tokio::test]
+async fn holiday_without_calendar_is_rejected() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let office = seed_office(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &format!("{}/holidays/2026-03-03", calendar_uri(office)),
+        Some(json!({})),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "office_calendar_not_found");
+}
+
+#[tokio::test]
+async fn employee_cannot_set_office_holiday() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+    let office = seed_office(&db).await;
+
+    set_calendar(&app, &admin.sub, office).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::PUT,
+        &format!("{}/holidays/2026-03-03", calendar_uri(office)),
+        Some(json!({ "name": "Liberation Day" })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
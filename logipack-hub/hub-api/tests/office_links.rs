@@ -0,0 +1,67 @@
+use axum::{Router, http::Method, http::StatusCode};
+use core_application::actor::ActorContext;
+use core_application::shipments::create::{CreateShipment, create_shipment};
+use core_domain::pricing::Tariff;
+use hub_api::dto::office_links::OfficeLinkDto;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{seed_client, send_json};
+
+#[path = "helpers.rs"]
+pub mod helpers;
+
+#[path = "office_links/office_links_set.rs"]
+mod office_links_set;
+
+#[path = "office_links/office_links_list.rs"]
+mod office_links_list;
+
+#[path = "office_links/office_links_route.rs"]
+mod office_links_route;
+
+#[path = "office_links/office_links_delete.rs"]
+mod office_links_delete;
+
+#[path = "office_links/office_links_shipments.rs"]
+mod office_links_shipments;
+
+/// Links `from` to `to` through the API, one hour and 250 cents apart.
+async fn set_link(app: &Router, sub: &str, from: Uuid, to: Uuid) -> OfficeLinkDto {
+    let (status, body) = send_json(
+        app,
+        sub,
+        Method::PUT,
+        &format!("/admin/office-links/{from}/{to}"),
+        Some(json!({ "transit_minutes": 60, "cost_cents": 250 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    serde_json::from_value(body).unwrap()
+}
+
+/// Creates a shipment at `origin` bound for `destination`.
+async fn seed_shipment(
+    db: &DatabaseConnection,
+    actor: &ActorContext,
+    origin: Uuid,
+    destination: Uuid,
+) -> Uuid {
+    let client = seed_client(db).await;
+
+    create_shipment(
+        db,
+        actor,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(origin),
+            destination_office_id: Some(destination),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+}
@@ -0,0 +1,69 @@
+use axum::http::{Method, StatusCode};
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::set_link;
+
+#[tokio::test]
+async fn admin_can_delete_office_link() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+
+    let (status, _) = send_json(
+        &app,
+        &admin.sub,
+        Method::DELETE,
+        &format!("/admin/office-links/{a}/{b}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) =
+        send_json(&app, &admin.sub, Method::GET, "/admin/office-links", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["links"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn delete_missing_office_link_returns_not_found() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::DELETE,
+        &format!("/admin/office-links/{a}/{b}"),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "office_link_not_found");
+}
+
+#[tokio::test]
+async fn employee_cannot_delete_office_link() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::DELETE,
+        &format!("/admin/office-links/{a}/{b}"),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
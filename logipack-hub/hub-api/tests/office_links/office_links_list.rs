@@ -0,0 +1,41 @@
+use axum::http::{Method, StatusCode};
+use hub_api::dto::office_links::ListOfficeLinksResponse;
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::set_link;
+
+#[tokio::test]
+async fn admin_can_list_office_links() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+    set_link(&app, &admin.sub, b, c).await;
+
+    let (status, body) =
+        send_json(&app, &admin.sub, Method::GET, "/admin/office-links", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let list: ListOfficeLinksResponse = serde_json::from_value(body).unwrap();
+    assert_eq!(list.links.len(), 2);
+}
+
+#[tokio::test]
+async fn employee_cannot_list_office_links() {
+    let (app, db, _admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::GET,
+        "/admin/office-links",
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
@@ -0,0 +1,74 @@
+use axum::http::{Method, StatusCode};
+use hub_api::dto::office_links::RouteDto;
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::set_link;
+
+#[tokio::test]
+async fn admin_can_plan_route_across_links() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+    set_link(&app, &admin.sub, b, c).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::GET,
+        &format!("/admin/office-links/route?from={a}&to={c}"),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let route: RouteDto = serde_json::from_value(body).unwrap();
+    assert_eq!(route.stops.len(), 3);
+    assert_eq!(route.transit_minutes, 120);
+    assert_eq!(route.cost_cents, 500);
+}
+
+#[tokio::test]
+async fn route_against_link_direction_is_not_found() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::GET,
+        &format!("/admin/office-links/route?from={b}&to={a}"),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "route_not_found");
+}
+
+#[tokio::test]
+async fn employee_cannot_plan_route() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::GET,
+        &format!("/admin/office-links/route?from={a}&to={b}"),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
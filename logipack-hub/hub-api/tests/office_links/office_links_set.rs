@@ -0,0 +1,98 @@
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::set_link;
+
+#[tokio::test]
+async fn admin_can_set_office_link() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    let link = set_link(&app, &admin.sub, a, b).await;
+
+    assert_eq!(link.from_office_id, a.to_string());
+    assert_eq!(link.to_office_id, b.to_string());
+    assert_eq!(link.transit_minutes, 60);
+    assert_eq!(link.cost_cents, 250);
+}
+
+#[tokio::test]
+async fn admin_can_replace_office_link() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &format!("/admin/office-links/{a}/{b}"),
+        Some(json!({ "transit_minutes": 90, "cost_cents": 400 })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["transit_minutes"], 90);
+    assert_eq!(body["cost_cents"], 400);
+}
+
+#[tokio::test]
+async fn self_loop_link_is_rejected() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &format!("/admin/office-links/{a}/{a}"),
+        Some(json!({ "transit_minutes": 60, "cost_cents": 0 })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "invalid_office_link");
+}
+
+#[tokio::test]
+async fn link_to_unknown_office_is_rejected() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &format!("/admin/office-links/{a}/{}", Uuid::new_v4()),
+        Some(json!({ "transit_minutes": 60, "cost_cents": 0 })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "office_not_found");
+}
+
+#[tokio::test]
+async fn employee_cannot_set_office_link() {
+    let (app, db, _admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::PUT,
+        &format!("/admin/office-links/{a}/{b}"),
+        Some(json!({ "transit_minutes": 60, "cost_cents": 250 })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
@@ -0,0 +1,162 @@
+use axum::{
+    Router,
+    http::{Method, StatusCode},
+};
+use hub_api::dto::shipments::ShipmentDetail;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers::{seed_office, send_json, setup_app_with_admin};
+use crate::{seed_shipment, set_link};
+
+async fn change_status(
+    app: &Router,
+    sub: &str,
+    shipment_id: Uuid,
+    body: Value,
+) -> (StatusCode, Value) {
+    send_json(
+        app,
+        sub,
+        Method::POST,
+        &format!("/shipments/{shipment_id}/status"),
+        Some(body),
+    )
+    .await
+}
+
+async fn detail(app: &Router, sub: &str, shipment_id: Uuid) -> ShipmentDetail {
+    let (status, body) = send_json(
+        app,
+        sub,
+        Method::GET,
+        &format!("/shipments/{shipment_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    serde_json::from_value(body).unwrap()
+}
+
+/// Accepts and processes the shipment at `office`, ready to leave.
+async fn ready_to_leave(app: &Router, sub: &str, shipment_id: Uuid, office: Uuid) {
+    for to_status in ["ACCEPTED", "PROCESSED"] {
+        let (status, body) = change_status(
+            app,
+            sub,
+            shipment_id,
+            json!({ "to_status": to_status, "to_office_id": office }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+    }
+}
+
+#[tokio::test]
+async fn shipment_detail_shows_planned_route() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+    set_link(&app, &admin.sub, b, c).await;
+    let shipment_id = seed_shipment(&db, &admin, a, c).await;
+
+    let offices: Vec<String> = detail(&app, &admin.sub, shipment_id)
+        .await
+        .route
+        .unwrap()
+        .stops
+        .into_iter()
+        .map(|stop| stop.office_id)
+        .collect();
+
+    assert_eq!(offices, vec![a.to_string(), b.to_string(), c.to_string()]);
+}
+
+#[tokio::test]
+async fn in_transit_hop_off_route_is_rejected() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+    set_link(&app, &admin.sub, b, c).await;
+    let shipment_id = seed_shipment(&db, &admin, a, c).await;
+    ready_to_leave(&app, &admin.sub, shipment_id, a).await;
+
+    let (status, body) = change_status(
+        &app,
+        &admin.sub,
+        shipment_id,
+        json!({ "to_status": "IN_TRANSIT", "to_office_id": c }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "off_route");
+    assert_eq!(body["details"]["expected_office_id"], b.to_string());
+}
+
+#[tokio::test]
+async fn admin_override_replans_route() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+    set_link(&app, &admin.sub, b, c).await;
+    let shipment_id = seed_shipment(&db, &admin, a, c).await;
+    ready_to_leave(&app, &admin.sub, shipment_id, a).await;
+
+    let (status, body) = change_status(
+        &app,
+        &admin.sub,
+        shipment_id,
+        json!({ "to_status": "IN_TRANSIT", "to_office_id": c, "override_route": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let route = detail(&app, &admin.sub, shipment_id).await.route.unwrap();
+    assert_eq!(route.stops.len(), 1);
+    assert_eq!(route.stops[0].office_id, c.to_string());
+}
+
+#[tokio::test]
+async fn delivery_before_destination_is_rejected() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let c = seed_office(&db).await;
+
+    set_link(&app, &admin.sub, a, b).await;
+    set_link(&app, &admin.sub, b, c).await;
+    let shipment_id = seed_shipment(&db, &admin, a, c).await;
+    ready_to_leave(&app, &admin.sub, shipment_id, a).await;
+
+    let (status, body) = change_status(
+        &app,
+        &admin.sub,
+        shipment_id,
+        json!({ "to_status": "IN_TRANSIT", "to_office_id": b }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (status, body) = change_status(
+        &app,
+        &admin.sub,
+        shipment_id,
+        json!({ "to_status": "OUT_FOR_DELIVERY", "to_office_id": b }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "not_at_destination");
+    assert_eq!(body["details"]["destination_office_id"], c.to_string());
+}
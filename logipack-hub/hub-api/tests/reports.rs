@@ -0,0 +1,236 @@
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use core_data::entity::{employee_offices, employees, shipments};
+use sea_orm::sqlx::types::chrono;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use serde_json::Value;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use crate::helpers::{seed_client, seed_employee, seed_office, setup_app_with_admin};
+
+#[allow(dead_code)]
+mod helpers;
+
+async fn seed_shipment(db: &DatabaseConnection, client_id: Uuid, office_id: Uuid, at: &str) {
+    let created_at = chrono::NaiveDateTime::parse_from_str(at, "%Y-%m-%d %H:%M")
+        .unwrap()
+        .and_utc();
+
+    shipments::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        client_id: Set(client_id),
+        current_status: Set("NEW".into()),
+        current_office_id: Set(Some(office_id)),
+        created_at: Set(created_at.into()),
+        updated_at: Set(created_at.into()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+}
+
+async fn get(app: &Router, sub: &str, uri: &str) -> (StatusCode, Value) {
+    let res = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", sub)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn counts(json: &Value, key: &str) -> Vec<(String, i64)> {
+    json["rows"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| {
+            (
+                row[key].as_str().unwrap().to_string(),
+                row["count"].as_i64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn reports_return_exact_aggregates_within_period() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    seed_shipment(&db, client, office, "2026-03-01 00:00").await;
+    seed_shipment(&db, client, office, "2026-03-01 23:00").await;
+    seed_shipment(&db, client, office, "2026-03-02 00:00").await;
+
+    let (status, json) = get(
+        &app,
+        &admin.sub,
+        "/reports/shipments-by-status?from=2026-03-01&to=2026-03-02",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["from"], "2026-03-01T00:00:00+00:00");
+    assert_eq!(json["to"], "2026-03-02T00:00:00+00:00");
+    assert_eq!(counts(&json, "status"), vec![("NEW".to_string(), 2)]);
+
+    let (_, json) = get(&app, &admin.sub, "/reports/shipments-by-office").await;
+    assert_eq!(
+        counts(&json, "office_name"),
+        vec![("Main Office".into(), 3)]
+    );
+
+    let (_, json) = get(&app, &admin.sub, "/reports/shipments-by-client").await;
+    assert_eq!(
+        counts(&json, "client_name"),
+        vec![("Test Client".into(), 3)]
+    );
+
+    let (_, json) = get(
+        &app,
+        &admin.sub,
+        "/reports/shipments-by-period?from=2026-03-01T00:00:00Z",
+    )
+    .await;
+    assert_eq!(
+        counts(&json, "period_start"),
+        vec![
+            ("2026-03-01T00:00:00+00:00".into(), 2),
+            ("2026-03-02T00:00:00+00:00".into(), 1),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn employee_reports_are_scoped_to_their_offices() {
+    let (app, db, _admin) = setup_app_with_admin().await;
+
+    let own = seed_office(&db).await;
+    let other = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    seed_shipment(&db, client, own, "2026-03-01 10:00").await;
+    seed_shipment(&db, client, other, "2026-03-01 10:00").await;
+    seed_shipment(&db, client, other, "2026-03-01 11:00").await;
+
+    let employee = seed_employee(&db).await;
+    let employee_id = Uuid::new_v4();
+
+    employees::ActiveModel {
+        id: Set(employee_id),
+        user_id: Set(employee.user_id),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    employee_offices::ActiveModel {
+        employee_id: Set(employee_id),
+        office_id: Set(own),
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    let (status, json) = get(&app, &employee.sub, "/reports/shipments-by-office").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let rows = json["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["office_id"], own.to_string());
+    assert_eq!(rows[0]["count"], 1);
+
+    let (status, json) = get(
+        &app,
+        &employee.sub,
+        &format!("/reports/shipments-by-status?office_id={other}"),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(json["code"], "access_denied");
+}
+
+#[tokio::test]
+async fn invalid_report_params_are_rejected() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    for (uri, code) in [
+        (
+            "/reports/shipments-by-status?from=yesterday",
+            "invalid_from",
+        ),
+        (
+            "/reports/shipments-by-status?from=2026-03-02&to=2026-03-01",
+            "invalid_period",
+        ),
+        ("/reports/shipments-by-client?status=LOST", "invalid_status"),
+        ("/reports/shipments-by-period?bucket=year", "invalid_bucket"),
+    ] {
+        let (status, json) = get(&app, &admin.sub, uri).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+        assert_eq!(json["code"], code, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn on_hold_report_counts_held_shipments_per_reason() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    seed_shipment(&db, client, office, "2026-03-01 00:00").await;
+
+    let held_since = chrono::NaiveDateTime::parse_from_str("2026-03-02 08:00", "%Y-%m-%d %H:%M")
+        .unwrap()
+        .and_utc();
+    shipments::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        client_id: Set(client),
+        current_status: Set("IN_TRANSIT".into()),
+        current_office_id: Set(Some(office)),
+        hold_reason: Set(Some("CUSTOMS".into())),
+        held_since: Set(Some(held_since.into())),
+        created_at: Set(held_since.into()),
+        updated_at: Set(held_since.into()),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    let (status, json) = get(&app, &admin.sub, "/reports/shipments-on-hold").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(counts(&json, "reason"), vec![("CUSTOMS".into(), 1)]);
+    assert_eq!(json["rows"][0]["oldest_since"], "2026-03-02T08:00:00+00:00");
+
+    let (_, json) = get(
+        &app,
+        &admin.sub,
+        "/reports/shipments-by-status?on_hold=false",
+    )
+    .await;
+    assert_eq!(counts(&json, "status"), vec![("NEW".into(), 1)]);
+}
@@ -3,8 +3,9 @@ use axum::{
     extract::Request,
     http::{Method, StatusCode},
 };
+use core_domain::pricing::ServiceLevel;
 use http_body_util::BodyExt;
-use hub_api::dto::shipments::CreateShipmentResponse;
+use hub_api::dto::shipments::{CreateShipmentResponse, QuoteResponse, ShipmentDetail};
 use serde_json::json;
 use tower::ServiceExt;
 use uuid::Uuid;
@@ -162,6 +163,41 @@ async fn create_shipment_invalid_client() {
     assert_eq!(res.status(), StatusCode::BAD_REQUEST);
 }
 
+#[tokio::test]
+async fn create_shipment_invalid_destination() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .header("content-type", "application/json")
+                .method(Method::POST)
+                .uri("/shipments")
+                .body(Body::from(
+                    serde_json::to_vec(&json!({
+                        "client_id": client,
+                        "current_office_id": office,
+                        "destination_office_id": Uuid::new_v4()
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "destination_office_not_found");
+}
+
 #[tokio::test]
 async fn create_shipment_missing_client_id() {
     let (app, _db, admin) = setup_app_with_admin().await;
@@ -184,3 +220,217 @@ async fn create_shipment_missing_client_id() {
 
     assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
 }
+
+#[tokio::test]
+async fn create_shipment_with_parties_and_parcel() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let client = seed_client(&db).await;
+    let origin = seed_office(&db).await;
+    let destination = seed_office(&db).await;
+
+    let address = json!({
+        "line1": "1 Vitosha Blvd",
+        "line2": null,
+        "city": "Sofia",
+        "postal_code": "1000",
+        "country": "BG"
+    });
+
+    let res = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .header("content-type", "application/json")
+                .method(Method::POST)
+                .uri("/shipments")
+                .body(Body::from(
+                    serde_json::to_vec(&json!({
+                        "client_id": client,
+                        "current_office_id": origin,
+                        "destination_office_id": destination,
+                        "sender": { "name": "Jane Doe", "phone": "+359888123456", "address": address },
+                        "recipient": { "name": "John Roe", "email": "john@example.com", "address": address },
+                        "parcel": {
+                            "weight_g": 800,
+                            "dimensions": { "length_mm": 200, "width_mm": 150, "height_mm": 50 },
+                            "declared_value_cents": 2500,
+                            "description": "phone case"
+                        }
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let body: CreateShipmentResponse = serde_json::from_slice(&body).unwrap();
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .uri(format!("/shipments/{}", body.shipment_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let detail: ShipmentDetail = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(detail.origin_office_id, Some(origin.to_string()));
+    assert_eq!(detail.destination_office_id, Some(destination.to_string()));
+    assert_eq!(detail.sender.unwrap().name, "Jane Doe");
+
+    let recipient = detail.recipient.unwrap();
+    assert_eq!(recipient.email.as_deref(), Some("john@example.com"));
+    assert_eq!(recipient.address.city, "Sofia");
+
+    let parcel = detail.parcel.unwrap();
+    assert_eq!(parcel.weight_g, 800);
+    assert_eq!(parcel.dimensions.unwrap().height_mm, 50);
+    assert_eq!(parcel.declared_value_cents, Some(2500));
+}
+
+#[tokio::test]
+async fn create_shipment_invalid_parcel() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let client = seed_client(&db).await;
+    let office = seed_office(&db).await;
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .header("content-type", "application/json")
+                .method(Method::POST)
+                .uri("/shipments")
+                .body(Body::from(
+                    serde_json::to_vec(&json!({
+                        "client_id": client,
+                        "current_office_id": office,
+                        "parcel": { "weight_g": 0 }
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "invalid_shipment");
+    assert_eq!(body["message"], "parcel: invalid weight");
+}
+
+#[tokio::test]
+async fn quote_matches_the_price_recorded_at_creation() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let client = seed_client(&db).await;
+    let origin = seed_office(&db).await;
+    let destination = seed_office(&db).await;
+
+    let post = |uri: &str, body: serde_json::Value| {
+        Request::builder()
+            .header("x-dev-secret", "test_secret")
+            .header("x-dev-user-sub", admin.sub.clone())
+            .header("content-type", "application/json")
+            .method(Method::POST)
+            .uri(uri)
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+
+    let res = app
+        .clone()
+        .oneshot(post(
+            "/shipments/quote",
+            json!({
+                "origin_office_id": origin,
+                "destination_office_id": destination,
+                "parcel": { "weight_g": 800 },
+                "service_level": "EXPRESS"
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let quote: QuoteResponse = serde_json::from_slice(&body).unwrap();
+
+    // same city: local base price plus the express minimum, 20% tax
+    assert_eq!(quote.service_level, ServiceLevel::Express);
+    assert_eq!(quote.price.zone, "LOCAL");
+    assert_eq!(quote.price.base_cents, 450);
+    assert_eq!(quote.price.express_fee_cents, 300);
+    assert_eq!(quote.price.total_cents, 900);
+
+    let res = app
+        .clone()
+        .oneshot(post(
+            "/shipments",
+            json!({
+                "client_id": client,
+                "current_office_id": origin,
+                "destination_office_id": destination,
+                "parcel": { "weight_g": 800 },
+                "service_level": "EXPRESS"
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let body: CreateShipmentResponse = serde_json::from_slice(&body).unwrap();
+
+    let res = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .uri(format!("/shipments/{}", body.shipment_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let detail: ShipmentDetail = serde_json::from_slice(&body).unwrap();
+    assert_eq!(detail.service_level, ServiceLevel::Express);
+    assert_eq!(detail.price.unwrap().total_cents, 900);
+
+    let res = app
+        .oneshot(post(
+            "/shipments/quote",
+            json!({
+                "origin_office_id": origin,
+                "destination_office_id": destination,
+                "parcel": { "weight_g": 40000 }
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "unpriceable_shipment");
+}
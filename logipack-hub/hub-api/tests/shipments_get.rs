@@ -1,14 +1,17 @@
 use axum::{body::Body, extract::Request, http::StatusCode};
+use core_domain::pricing::Tariff;
 use http_body_util::BodyExt;
 use tower::ServiceExt;
 use uuid::Uuid;
 
 use core_application::shipments::create::{CreateShipment, create_shipment};
-use hub_api::dto::shipments::{ShipmentDetail, ShipmentListItem};
+use hub_api::dto::shipments::{ShipmentDetail, ShipmentListResponse};
 
 #[allow(dead_code)]
 mod helpers;
-use helpers::{seed_client, seed_office, setup_app_with_admin, setup_app_with_employee};
+use helpers::{
+    seed_client, seed_employee, seed_office, setup_app_with_admin, setup_app_with_employee,
+};
 
 #[tokio::test]
 async fn list_shipments_empty() {
@@ -29,8 +32,9 @@ async fn list_shipments_empty() {
     assert_eq!(res.status(), StatusCode::OK);
 
     let body = res.into_body().collect().await.unwrap().to_bytes();
-    let body: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
-    assert!(body.is_empty());
+    let body: ShipmentListResponse = serde_json::from_slice(&body).unwrap();
+    assert!(body.items.is_empty());
+    assert!(body.next_cursor.is_none());
 }
 
 #[tokio::test]
@@ -43,10 +47,12 @@ async fn list_shipments_returns_rows() {
     create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -65,9 +71,106 @@ async fn list_shipments_returns_rows() {
         .unwrap();
 
     let body = res.into_body().collect().await.unwrap().to_bytes();
-    let body: Vec<ShipmentListItem> = serde_json::from_slice(&body).unwrap();
-    assert_eq!(body.len(), 1);
-    assert_eq!(body[0].current_status, "NEW");
+    let body: ShipmentListResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body.items.len(), 1);
+    assert_eq!(body.items[0].current_status, "NEW");
+}
+
+#[tokio::test]
+async fn list_shipments_pages_with_cursor_and_filters() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let client = seed_client(&db).await;
+    let office = seed_office(&db).await;
+
+    let mut created = Vec::new();
+    for office_id in [Some(office), Some(office), None] {
+        let id = create_shipment(
+            &db,
+            &admin,
+            &Tariff::default(),
+            CreateShipment {
+                client_id: client,
+                current_office_id: office_id,
+                notes: None,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        created.push(id.to_string());
+    }
+
+    let list = |uri: String| {
+        let app = app.clone();
+        let sub = admin.sub.clone();
+        async move {
+            let res = app
+                .oneshot(
+                    Request::builder()
+                        .uri(uri)
+                        .header("x-dev-secret", "test_secret")
+                        .header("x-dev-user-sub", sub)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(res.status(), StatusCode::OK);
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            serde_json::from_slice::<ShipmentListResponse>(&body).unwrap()
+        }
+    };
+
+    let first = list("/shipments?sort=created_at&order=asc&limit=2".into()).await;
+    let cursor = first.next_cursor.expect("more pages");
+    let second = list(format!(
+        "/shipments?sort=created_at&order=asc&limit=2&cursor={cursor}"
+    ))
+    .await;
+
+    assert!(second.next_cursor.is_none());
+    let ids: Vec<String> = first
+        .items
+        .iter()
+        .chain(&second.items)
+        .map(|item| item.id.clone())
+        .collect();
+    assert_eq!(ids, created);
+
+    let at_office = list(format!("/shipments?office_id={office}&status=NEW")).await;
+    assert_eq!(at_office.items.len(), 2);
+}
+
+#[tokio::test]
+async fn list_shipments_rejects_bad_params() {
+    let (app, employee) = setup_app_with_employee().await;
+
+    for (uri, code) in [
+        ("/shipments?sort=name", "invalid_sort"),
+        ("/shipments?order=up", "invalid_order"),
+        ("/shipments?cursor=garbage", "invalid_cursor"),
+        ("/shipments?created_from=soon", "invalid_created_from"),
+    ] {
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header("x-dev-secret", "test_secret")
+                    .header("x-dev-user-sub", employee.sub.clone())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{uri}");
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], code, "{uri}");
+    }
 }
 
 #[tokio::test]
@@ -99,10 +202,12 @@ async fn get_shipment_returns_detail() {
     let shipment = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: Some("hello".into()),
+            ..Default::default()
         },
     )
     .await
@@ -127,4 +232,130 @@ async fn get_shipment_returns_detail() {
 
     assert_eq!(body.id.to_string(), shipment.to_string());
     assert_eq!(body.current_status, "NEW");
+    assert_eq!(body.client.id, client.to_string());
+    assert_eq!(body.client.name, "Test Client");
+
+    let current_office = body.current_office.expect("office joined");
+    assert_eq!(current_office.id, office.to_string());
+    assert_eq!(current_office.name, "Main Office");
+
+    let latest = body.latest_change.expect("creation row");
+    assert_eq!(latest.from_status, None);
+    assert_eq!(latest.to_status, "NEW");
+    assert_eq!(latest.notes.as_deref(), Some("hello"));
+    assert_eq!(body.event_count, 1);
+}
+
+#[tokio::test]
+async fn employee_without_office_cannot_read_shipments() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let client = seed_client(&db).await;
+    let office = seed_office(&db).await;
+    let employee = seed_employee(&db).await;
+
+    let shipment = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let get = |uri: String| {
+        let app = app.clone();
+        let sub = employee.sub.clone();
+        async move {
+            app.oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header("x-dev-secret", "test_secret")
+                    .header("x-dev-user-sub", sub)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+        }
+    };
+
+    let res = get("/shipments".into()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let body: ShipmentListResponse = serde_json::from_slice(&body).unwrap();
+    assert!(body.items.is_empty());
+
+    let res = get(format!("/shipments/{shipment}")).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let res = get(format!("/shipments/{shipment}/timeline")).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn get_shipment_by_tracking_number() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let client = seed_client(&db).await;
+    let office = seed_office(&db).await;
+
+    let shipment = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let get = |uri: String| {
+        app.clone().oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let res = get(format!("/shipments/{shipment}")).await.unwrap();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let detail: ShipmentDetail = serde_json::from_slice(&body).unwrap();
+    let code = detail.tracking_number.expect("tracking number issued");
+    // seeded office is in "Test City"
+    assert!(code.starts_with("TES"), "{code}");
+
+    let res = get(format!("/shipments/by-tracking/{}", code.to_lowercase()))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let detail: ShipmentDetail = serde_json::from_slice(&body).unwrap();
+    assert_eq!(detail.id, shipment.to_string());
+
+    // flip the check digit
+    let last = code.chars().last().unwrap().to_digit(10).unwrap();
+    let typo = format!("{}{}", &code[..code.len() - 1], (last + 1) % 10);
+    let res = get(format!("/shipments/by-tracking/{typo}")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "invalid_tracking_number");
+
+    // well-formed but never issued
+    let res = get("/shipments/by-tracking/SOF473124829".into())
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
 }
@@ -0,0 +1,792 @@
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use base64::Engine;
+use core_application::shipments::change_status::{ChangeStatus, change_status};
+use core_application::shipments::create::{CreateShipment, create_shipment};
+use core_domain::pricing::Tariff;
+use core_domain::shipment::{BlobHash, ShipmentStatus, TransitionTable};
+use hub_api::{
+    config::{load_tariff, load_transitions},
+    dto::shipments::{
+        CodRemittanceDto, CreateShipmentResponse, NextStatusDto, PieceDto,
+        RecordedProofOfDeliveryDto, ScanPieceResponse, ShipmentDetail, ShipmentListResponse,
+    },
+};
+use tower::ServiceExt;
+
+use crate::helpers::{seed_client, seed_office, setup_app_with_admin};
+
+#[allow(dead_code)]
+mod helpers;
+
+#[tokio::test]
+async fn change_status_with_current_seq_succeeds() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let body = serde_json::json!({
+        "to_status": "ACCEPTED",
+        "to_office_id": office,
+        "expected_seq": 2,
+    });
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .uri(format!("/shipments/{}/status", shipment_id))
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn change_status_with_stale_seq_is_409_with_current_state() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let body = serde_json::json!({
+        "to_status": "ACCEPTED",
+        "to_office_id": office,
+        "expected_seq": 1,
+    });
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .uri(format!("/shipments/{}/status", shipment_id))
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["code"], "shipment_changed");
+    assert_eq!(json["details"]["expected_seq"], 1);
+    assert_eq!(json["details"]["current_seq"], 2);
+    assert_eq!(json["details"]["current_status"], "NEW");
+    assert_eq!(json["details"]["current_office_id"], office.to_string());
+}
+
+#[tokio::test]
+async fn next_statuses_lists_allowed_transitions() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/shipments/{shipment_id}/next-statuses"))
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let next: Vec<NextStatusDto> = serde_json::from_slice(&body).unwrap();
+    let statuses: Vec<_> = next.iter().map(|n| n.status).collect();
+    assert_eq!(
+        statuses,
+        vec![ShipmentStatus::Accepted, ShipmentStatus::Cancelled]
+    );
+    assert!(next.iter().all(|n| !n.office_change && !n.requires_note));
+}
+
+#[test]
+fn shipped_transitions_file_matches_builtin_table() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/config/transitions.json");
+    let table = load_transitions(path).unwrap();
+    assert_eq!(table, TransitionTable::default());
+}
+
+#[test]
+fn invalid_transitions_file_is_rejected() {
+    let path = std::env::temp_dir().join("logipack-invalid-transitions.json");
+    std::fs::write(
+        &path,
+        r#"{"terminal": ["DELIVERED"], "transitions": [{"from": "DELIVERED", "to": "NEW"}]}"#,
+    )
+    .unwrap();
+
+    let err = load_transitions(path.to_str().unwrap()).unwrap_err();
+    assert!(err.contains("terminal"), "{err}");
+}
+
+#[test]
+fn shipped_tariff_file_matches_builtin_tariff() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/config/tariff.json");
+    let tariff = load_tariff(path).unwrap();
+    assert_eq!(tariff, Tariff::default());
+}
+
+#[test]
+fn invalid_tariff_file_is_rejected() {
+    let path = std::env::temp_dir().join("logipack-invalid-tariff.json");
+    std::fs::write(
+        &path,
+        r#"{"currency": "BGN", "local_zone": "LOCAL", "default_zone": "NATIONAL",
+            "volumetric_divisor": 5000000,
+            "bands": [{"max_weight_g": 1000, "prices": {"LOCAL": 450}}]}"#,
+    )
+    .unwrap();
+
+    let err = load_tariff(path.to_str().unwrap()).unwrap_err();
+    assert!(err.contains("NATIONAL"), "{err}");
+}
+
+#[tokio::test]
+async fn delivery_failure_needs_reason_and_counts_attempt() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    for to_status in [
+        ShipmentStatus::Accepted,
+        ShipmentStatus::Processed,
+        ShipmentStatus::InTransit,
+        ShipmentStatus::OutForDelivery,
+    ] {
+        change_status(
+            &db,
+            &admin,
+            &TransitionTable::default(),
+            ChangeStatus {
+                shipment_id,
+                to_status,
+                to_office_id: Some(office),
+                notes: None,
+                failure_reason: None,
+                expected_seq: None,
+                proof_of_delivery: None,
+                override_route: false,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let post_status = |body: serde_json::Value| {
+        Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .header("x-dev-secret", "test_secret")
+            .header("x-dev-user-sub", admin.sub.clone())
+            .uri(format!("/shipments/{}/status", shipment_id))
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let res = app
+        .clone()
+        .oneshot(post_status(serde_json::json!({
+            "to_status": "DELIVERY_FAILED",
+            "to_office_id": office,
+        })))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = app
+        .clone()
+        .oneshot(post_status(serde_json::json!({
+            "to_status": "DELIVERY_FAILED",
+            "to_office_id": office,
+            "failure_reason": "REFUSED",
+        })))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/shipments/{shipment_id}"))
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let detail: ShipmentDetail = serde_json::from_slice(&body).unwrap();
+    assert_eq!(detail.current_status, "DELIVERY_FAILED");
+    assert_eq!(detail.delivery_attempts, 1);
+    assert_eq!(
+        detail.latest_change.unwrap().failure_reason.as_deref(),
+        Some("REFUSED")
+    );
+}
+
+#[tokio::test]
+async fn held_shipment_refuses_status_changes_until_released() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let post = |path: &str, body: serde_json::Value| {
+        Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .header("x-dev-secret", "test_secret")
+            .header("x-dev-user-sub", admin.sub.clone())
+            .uri(format!("/shipments/{shipment_id}/{path}"))
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+    let accept = serde_json::json!({
+        "to_status": "ACCEPTED",
+        "to_office_id": office,
+    });
+
+    let res = app
+        .clone()
+        .oneshot(post(
+            "hold",
+            serde_json::json!({ "reason": "LOST_IN_SPACE" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let res = app
+        .clone()
+        .oneshot(post(
+            "hold",
+            serde_json::json!({ "reason": "ADDRESS_PROBLEM", "notes": "no such street" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app
+        .clone()
+        .oneshot(post("status", accept.clone()))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["code"], "shipment_on_hold");
+
+    let res = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/shipments?on_hold=true&hold_reason=ADDRESS_PROBLEM")
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let list: ShipmentListResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(list.items.len(), 1);
+    assert_eq!(
+        list.items[0].hold_reason.as_deref(),
+        Some("ADDRESS_PROBLEM")
+    );
+
+    let res = app
+        .clone()
+        .oneshot(post("release", serde_json::json!({})))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app
+        .clone()
+        .oneshot(post("release", serde_json::json!({})))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let res = app.oneshot(post("status", accept)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn pieces_are_added_and_scanned_by_barcode() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let post = |path: String, body: serde_json::Value| {
+        Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .header("x-dev-secret", "test_secret")
+            .header("x-dev-user-sub", admin.sub.clone())
+            .uri(path)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let mut pieces = Vec::new();
+    for weight_g in [1500, 700] {
+        let res = app
+            .clone()
+            .oneshot(post(
+                format!("/shipments/{shipment_id}/pieces"),
+                serde_json::json!({ "weight_g": weight_g }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let piece: PieceDto = serde_json::from_slice(&body).unwrap();
+        pieces.push(piece);
+    }
+    assert_eq!(pieces[1].piece_no, 2);
+    assert!(pieces[1].barcode.ends_with("-02"));
+
+    let res = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/shipments/{shipment_id}/pieces"))
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let listed: Vec<PieceDto> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(listed.len(), 2);
+
+    let accept = serde_json::json!({
+        "to_status": "ACCEPTED",
+        "to_office_id": office,
+    });
+
+    let res = app
+        .clone()
+        .oneshot(post(
+            format!("/shipments/{shipment_id}/status"),
+            accept.clone(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["code"], "shipment_has_pieces");
+
+    let res = app
+        .clone()
+        .oneshot(post(
+            "/shipments/pieces/NOT-A-BARCODE/scan".into(),
+            accept.clone(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let mut shipment_statuses = Vec::new();
+    for piece in &pieces {
+        let res = app
+            .clone()
+            .oneshot(post(
+                format!("/shipments/pieces/{}/scan", piece.barcode),
+                accept.clone(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let scanned: ScanPieceResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(scanned.piece.status, "ACCEPTED");
+        shipment_statuses.push(scanned.shipment_status);
+    }
+
+    // the shipment only moves once every piece has
+    assert_eq!(
+        shipment_statuses,
+        vec![ShipmentStatus::New, ShipmentStatus::Accepted]
+    );
+}
+
+#[tokio::test]
+async fn delivery_with_proof_of_delivery_can_be_downloaded() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    for to_status in [
+        ShipmentStatus::Accepted,
+        ShipmentStatus::Processed,
+        ShipmentStatus::InTransit,
+    ] {
+        change_status(
+            &db,
+            &admin,
+            &TransitionTable::default(),
+            ChangeStatus {
+                shipment_id,
+                to_status,
+                to_office_id: Some(office),
+                notes: None,
+                failure_reason: None,
+                expected_seq: None,
+                proof_of_delivery: None,
+                override_route: false,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let get = |path: &str| {
+        Request::builder()
+            .uri(format!("/shipments/{shipment_id}/{path}"))
+            .header("x-dev-secret", "test_secret")
+            .header("x-dev-user-sub", admin.sub.clone())
+            .body(Body::empty())
+            .unwrap()
+    };
+    let deliver = |signature: &str| {
+        Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .header("x-dev-secret", "test_secret")
+            .header("x-dev-user-sub", admin.sub.clone())
+            .uri(format!("/shipments/{shipment_id}/status"))
+            .body(Body::from(
+                serde_json::json!({
+                    "to_status": "DELIVERED",
+                    "to_office_id": office,
+                    "proof_of_delivery": {
+                        "receiver_name": "Maria Ivanova",
+                        "relationship": "RECIPIENT",
+                        "received_at": "2026-10-18T09:30:00Z",
+                        "signature": signature,
+                    },
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    let res = app.clone().oneshot(get("proof-of-delivery")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let res = app.clone().oneshot(deliver("not base64!")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["code"], "invalid_proof_of_delivery");
+
+    let png = b"\x89PNG\r\n\x1a\n-signature".to_vec();
+    let res = app
+        .clone()
+        .oneshot(deliver(
+            &base64::engine::general_purpose::STANDARD.encode(&png),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.clone().oneshot(get("proof-of-delivery")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let recorded: RecordedProofOfDeliveryDto = serde_json::from_slice(&body).unwrap();
+    assert_eq!(recorded.proof.receiver_name, "Maria Ivanova");
+    assert_eq!(
+        recorded.proof.received_at.as_deref(),
+        Some("2026-10-18T09:30:00+00:00")
+    );
+    assert_eq!(
+        recorded.proof.signature_hash,
+        Some(BlobHash::of(&png).to_string())
+    );
+    assert_eq!(recorded.proof.photo_hash, None);
+
+    let res = app
+        .clone()
+        .oneshot(get("proof-of-delivery/signature"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["content-type"], "image/png");
+    assert_eq!(
+        res.headers()["etag"].to_str().unwrap(),
+        format!("\"{}\"", BlobHash::of(&png))
+    );
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body.as_ref(), png.as_slice());
+
+    let res = app
+        .clone()
+        .oneshot(get("proof-of-delivery/photo"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn cash_on_delivery_is_collected_on_delivery_and_remitted_by_admin() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let post = |uri: &str, body: serde_json::Value| {
+        Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .header("x-dev-secret", "test_secret")
+            .header("x-dev-user-sub", admin.sub.clone())
+            .uri(uri)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+    let get = |uri: &str| {
+        Request::builder()
+            .header("x-dev-secret", "test_secret")
+            .header("x-dev-user-sub", admin.sub.clone())
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let create = |currency: &str| {
+        post(
+            "/shipments",
+            serde_json::json!({
+                "client_id": client,
+                "current_office_id": office,
+                "cod": { "amount_cents": 2550, "currency": currency },
+            }),
+        )
+    };
+
+    let res = app.clone().oneshot(create("bgn")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["code"], "invalid_cash_on_delivery");
+
+    let res = app.clone().oneshot(create("BGN")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let created: CreateShipmentResponse = serde_json::from_slice(&body).unwrap();
+    let shipment_id = created.shipment_id;
+
+    let res = app
+        .clone()
+        .oneshot(get(&format!("/shipments/{shipment_id}")))
+        .await
+        .unwrap();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let detail: ShipmentDetail = serde_json::from_slice(&body).unwrap();
+    let cod = detail.cod.unwrap();
+    assert_eq!((cod.amount_cents, cod.currency.as_str()), (2550, "BGN"));
+
+    let remit = || {
+        post(
+            "/admin/cod-remittances",
+            serde_json::json!({
+                "client_id": client,
+                "currency": "BGN",
+                "reference": "TRF-1",
+            }),
+        )
+    };
+
+    // nothing is owed before delivery
+    let res = app.clone().oneshot(remit()).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    for to_status in [
+        ShipmentStatus::Accepted,
+        ShipmentStatus::Processed,
+        ShipmentStatus::InTransit,
+        ShipmentStatus::Delivered,
+    ] {
+        change_status(
+            &db,
+            &admin,
+            &TransitionTable::default(),
+            ChangeStatus {
+                shipment_id,
+                to_status,
+                to_office_id: Some(office),
+                notes: None,
+                failure_reason: None,
+                expected_seq: None,
+                proof_of_delivery: None,
+                override_route: false,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let res = app
+        .clone()
+        .oneshot(get("/reports/cod-balances"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["rows"][0]["balance_cents"], 2550);
+
+    let res = app.clone().oneshot(remit()).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let remittance: CodRemittanceDto = serde_json::from_slice(&body).unwrap();
+    assert_eq!(remittance.amount_cents, 2550);
+    assert_eq!(remittance.shipment_count, 1);
+    assert_eq!(remittance.reference.as_deref(), Some("TRF-1"));
+
+    let res = app
+        .clone()
+        .oneshot(get("/reports/cod-outstanding"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["rows"], serde_json::json!([]));
+
+    let res = app.clone().oneshot(remit()).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
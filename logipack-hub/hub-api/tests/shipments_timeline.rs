@@ -6,7 +6,8 @@ use core_application::shipments::{
     change_status::{ChangeStatus, change_status},
     create::{CreateShipment, create_shipment},
 };
-use core_domain::shipment::ShipmentStatus;
+use core_domain::pricing::Tariff;
+use core_domain::shipment::{ShipmentStatus, TransitionTable};
 use tower::ServiceExt;
 
 use crate::helpers::{seed_client, seed_office, setup_app_with_admin};
@@ -24,10 +25,12 @@ async fn read_timeline_returns_ordered_events() {
     let shipment_id = create_shipment(
         &db,
         &admin,
+        &Tariff::default(),
         CreateShipment {
             client_id: client,
             current_office_id: Some(office),
             notes: None,
+            ..Default::default()
         },
     )
     .await
@@ -36,11 +39,16 @@ async fn read_timeline_returns_ordered_events() {
     change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Accepted,
             to_office_id: Some(office),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -49,11 +57,16 @@ async fn read_timeline_returns_ordered_events() {
     change_status(
         &db,
         &admin,
+        &TransitionTable::default(),
         ChangeStatus {
             shipment_id,
             to_status: ShipmentStatus::Processed,
             to_office_id: Some(office),
             notes: None,
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
         },
     )
     .await
@@ -92,3 +105,127 @@ async fn read_timeline_returns_ordered_events() {
         assert!(scb.is_some() && !scb.unwrap().is_null());
     }
 }
+
+#[tokio::test]
+async fn decoded_timeline_resolves_names_and_fields() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    change_status(
+        &db,
+        &admin,
+        &TransitionTable::default(),
+        ChangeStatus {
+            shipment_id,
+            to_status: ShipmentStatus::Accepted,
+            to_office_id: Some(office),
+            notes: Some("checked in".into()),
+            failure_reason: None,
+            expected_seq: None,
+            proof_of_delivery: None,
+            override_route: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .uri(format!("/shipments/{}/timeline?view=decoded", shipment_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let timeline = json.as_array().unwrap();
+
+    assert_eq!(timeline.len(), 3);
+
+    // stream metadata has no event
+    assert!(timeline[0]["event"].is_null());
+    assert!(timeline[0]["prev_hash"].is_null());
+    assert!(timeline[0].get("scb").is_none());
+
+    let hash = timeline[0]["hash"].as_str().unwrap();
+    assert_eq!(hash.len(), 64);
+    assert!(hash.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')));
+    assert_eq!(timeline[1]["prev_hash"], hash);
+
+    let created = &timeline[1]["event"];
+    assert_eq!(timeline[1]["event_type"], "ShipmentCreated");
+    assert_eq!(created["status"], "NEW");
+    assert_eq!(created["client_id"], client.to_string());
+    assert_eq!(created["office"]["id"], office.to_string());
+    assert_eq!(created["office"]["name"], "Main Office");
+    assert_eq!(created["actor"]["id"], admin.user_id.to_string());
+    assert_eq!(created["actor"]["name"], "Test User");
+    assert!(created["occurred_at"].as_str().unwrap().contains('T'));
+
+    let changed = &timeline[2]["event"];
+    assert_eq!(timeline[2]["event_type"], "StatusChanged");
+    assert_eq!(changed["from_status"], "NEW");
+    assert_eq!(changed["to_status"], "ACCEPTED");
+    assert_eq!(changed["to_office"]["name"], "Main Office");
+    assert_eq!(changed["notes"], "checked in");
+}
+
+#[tokio::test]
+async fn unknown_timeline_view_is_rejected() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let office = seed_office(&db).await;
+    let client = seed_client(&db).await;
+
+    let shipment_id = create_shipment(
+        &db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: None,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .header("x-dev-secret", "test_secret")
+                .header("x-dev-user-sub", admin.sub.clone())
+                .uri(format!("/shipments/{}/timeline?view=pretty", shipment_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,198 @@
+use std::time::Duration;
+
+use axum::http::{Method, StatusCode};
+use core_application::actor::ActorContext;
+use core_application::office_links::set::{SetOfficeLink, set_office_link};
+use core_application::shipments::create::{CreateShipment, create_shipment};
+use core_application::shipments::sla::check_sla_breaches;
+use core_data::entity::{employee_offices, employees};
+use core_domain::pricing::Tariff;
+use core_domain::shipment::TransitionTable;
+use hub_api::dto::shipments::ShipmentDetail;
+use sea_orm::sqlx::types::chrono;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use uuid::Uuid;
+
+use crate::helpers::{seed_client, seed_employee, seed_office, send_json, setup_app_with_admin};
+
+#[allow(dead_code)]
+pub mod helpers;
+
+/// Creates a shipment from `origin` to `destination`, an hour apart.
+async fn seed_shipment(
+    db: &DatabaseConnection,
+    admin: &ActorContext,
+    origin: Uuid,
+    destination: Uuid,
+) -> Uuid {
+    let client = seed_client(db).await;
+
+    set_office_link(
+        db,
+        admin,
+        SetOfficeLink {
+            from_office_id: origin,
+            to_office_id: destination,
+            transit_minutes: 60,
+            cost_cents: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    create_shipment(
+        db,
+        admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(origin),
+            destination_office_id: Some(destination),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+}
+
+/// Runs the SLA check a day from now, when every promise is overdue.
+async fn check_tomorrow(db: &DatabaseConnection) -> Vec<Uuid> {
+    check_sla_breaches(
+        db,
+        &TransitionTable::default(),
+        chrono::Utc::now() + Duration::from_secs(24 * 60 * 60),
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn flagged_shipments_are_listed_as_sla_breaches() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let a = seed_office(&db).await;
+    let b = seed_office(&db).await;
+    let shipment_id = seed_shipment(&db, &admin, a, b).await;
+
+    let (status, body) =
+        send_json(&app, &admin.sub, Method::GET, "/reports/sla-breaches", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["rows"].as_array().unwrap().len(), 0);
+
+    assert_eq!(check_tomorrow(&db).await, vec![shipment_id]);
+
+    let (status, body) =
+        send_json(&app, &admin.sub, Method::GET, "/reports/sla-breaches", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let rows = body["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["shipment_id"], shipment_id.to_string());
+    assert_eq!(rows[0]["current_office_id"], a.to_string());
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::GET,
+        &format!("/shipments/{shipment_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let detail: ShipmentDetail = serde_json::from_value(body).unwrap();
+    assert_eq!(
+        detail.promised_by.as_deref(),
+        rows[0]["promised_by"].as_str()
+    );
+    assert!(detail.sla_breached_at.is_some());
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::GET,
+        &format!("/shipments/{shipment_id}/timeline"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.to_string().contains("SlaBreached"), "{body}");
+}
+
+#[tokio::test]
+async fn employee_sla_breaches_are_scoped_to_their_offices() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let own = seed_office(&db).await;
+    let other = seed_office(&db).await;
+    let destination = seed_office(&db).await;
+
+    let own_shipment = seed_shipment(&db, &admin, own, destination).await;
+    seed_shipment(&db, &admin, other, destination).await;
+    assert_eq!(check_tomorrow(&db).await.len(), 2);
+
+    let employee = seed_employee(&db).await;
+    let employee_id = Uuid::new_v4();
+
+    employees::ActiveModel {
+        id: Set(employee_id),
+        user_id: Set(employee.user_id),
+        created_at: Set(chrono::Utc::now().into()),
+        updated_at: Set(chrono::Utc::now().into()),
+        deleted_at: Set(None),
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    employee_offices::ActiveModel {
+        employee_id: Set(employee_id),
+        office_id: Set(own),
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::GET,
+        "/reports/sla-breaches",
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let rows = body["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["shipment_id"], own_shipment.to_string());
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::GET,
+        &format!("/reports/sla-breaches?office_id={other}"),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
+
+#[tokio::test]
+async fn invalid_sla_breach_params_are_rejected() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    for (uri, code) in [
+        ("/reports/sla-breaches?from=yesterday", "invalid_from"),
+        (
+            "/reports/sla-breaches?from=2026-03-02&to=2026-03-01",
+            "invalid_period",
+        ),
+        ("/reports/sla-breaches?office_id=nope", "invalid_office_id"),
+    ] {
+        let (status, body) = send_json(&app, &admin.sub, Method::GET, uri, None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+        assert_eq!(body["code"], code, "{uri}");
+    }
+}
@@ -0,0 +1,43 @@
+use axum::{Router, http::Method, http::StatusCode};
+use core_domain::pricing::Tariff;
+use hub_api::dto::tariffs::TariffVersionDto;
+use serde_json::Value;
+
+use crate::helpers::send_json;
+
+#[path = "helpers.rs"]
+pub mod helpers;
+
+#[path = "tariffs/tariffs_create.rs"]
+mod tariffs_create;
+
+#[path = "tariffs/tariffs_get.rs"]
+mod tariffs_get;
+
+#[path = "tariffs/tariffs_list.rs"]
+mod tariffs_list;
+
+#[path = "tariffs/tariffs_update.rs"]
+mod tariffs_update;
+
+#[path = "tariffs/tariffs_delete.rs"]
+mod tariffs_delete;
+
+/// Default tariff with every base price raised by `extra_cents`.
+fn tariff(extra_cents: i64) -> Tariff {
+    let mut tariff = Tariff::default();
+    for band in &mut tariff.bands {
+        for price in band.prices.values_mut() {
+            *price += extra_cents;
+        }
+    }
+    tariff
+}
+
+/// Creates a tariff version through the API.
+async fn create_version(app: &Router, sub: &str, body: Value) -> TariffVersionDto {
+    let (status, body) = send_json(app, sub, Method::POST, "/admin/tariffs", Some(body)).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    serde_json::from_value(body).unwrap()
+}
@@ -0,0 +1,146 @@
+use axum::http::{Method, StatusCode};
+use core_domain::pricing::Tariff;
+use hub_api::dto::shipments::QuoteResponse;
+use serde_json::json;
+
+use crate::helpers::{seed_employee, seed_office, send_json, setup_app_with_admin};
+use crate::{create_version, tariff};
+
+#[tokio::test]
+async fn admin_can_create_tariff_version() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let version = create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_to": "2100-01-01", "tariff": tariff(100) }),
+    )
+    .await;
+
+    assert_eq!(version.created_by, Some(admin.user_id.to_string()));
+    assert_eq!(version.tariff, tariff(100));
+    assert!(
+        version
+            .effective_to
+            .unwrap()
+            .starts_with("2100-01-01T00:00:00")
+    );
+}
+
+#[tokio::test]
+async fn employee_cannot_create_tariff_version() {
+    let (app, db, _admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::POST,
+        "/admin/tariffs",
+        Some(json!({ "tariff": tariff(100) })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
+
+#[tokio::test]
+async fn overlapping_tariff_version_is_rejected() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_from": "2100-01-01", "tariff": tariff(200) }),
+    )
+    .await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::POST,
+        "/admin/tariffs",
+        Some(json!({ "effective_from": "2099-01-01", "tariff": tariff(200) })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "tariff_overlap");
+}
+
+#[tokio::test]
+async fn retroactive_tariff_version_is_rejected() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::POST,
+        "/admin/tariffs",
+        Some(json!({ "effective_from": "2020-01-01", "tariff": tariff(200) })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "invalid_tariff_period");
+}
+
+#[tokio::test]
+async fn invalid_tariff_rules_are_rejected() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let mut rules = Tariff::default();
+    rules.bands.clear();
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::POST,
+        "/admin/tariffs",
+        Some(json!({ "tariff": rules })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "invalid_tariff");
+}
+
+#[tokio::test]
+async fn quotes_use_the_tariff_version_in_force() {
+    let (app, db, admin) = setup_app_with_admin().await;
+
+    let origin = seed_office(&db).await;
+    let destination = seed_office(&db).await;
+
+    let current = create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_to": "2100-01-01", "tariff": tariff(100) }),
+    )
+    .await;
+    create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_from": "2100-01-01", "tariff": tariff(200) }),
+    )
+    .await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::POST,
+        "/shipments/quote",
+        Some(json!({
+            "origin_office_id": origin,
+            "destination_office_id": destination,
+            "parcel": { "weight_g": 800 }
+        })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let quote: QuoteResponse = serde_json::from_value(body).unwrap();
+    assert_eq!(quote.tariff_id, Some(current.id));
+    assert_eq!(quote.price.base_cents, 550);
+}
@@ -0,0 +1,87 @@
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{seed_employee, send_json, setup_app_with_admin};
+use crate::{create_version, tariff};
+
+#[tokio::test]
+async fn admin_can_delete_future_tariff_version() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let future = create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_from": "2100-01-01", "tariff": tariff(200) }),
+    )
+    .await;
+    let uri = format!("/admin/tariffs/{}", future.id);
+
+    let (status, _) = send_json(&app, &admin.sub, Method::DELETE, &uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) = send_json(&app, &admin.sub, Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "tariff_not_found");
+}
+
+#[tokio::test]
+async fn tariff_version_in_force_cannot_be_deleted() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let current = create_version(&app, &admin.sub, json!({ "tariff": tariff(100) })).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::DELETE,
+        &format!("/admin/tariffs/{}", current.id),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "tariff_in_force");
+}
+
+#[tokio::test]
+async fn delete_missing_tariff_version_returns_not_found() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::DELETE,
+        &format!("/admin/tariffs/{}", Uuid::new_v4()),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "tariff_not_found");
+}
+
+#[tokio::test]
+async fn employee_cannot_delete_tariff_version() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+
+    let future = create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_from": "2100-01-01", "tariff": tariff(200) }),
+    )
+    .await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::DELETE,
+        &format!("/admin/tariffs/{}", future.id),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
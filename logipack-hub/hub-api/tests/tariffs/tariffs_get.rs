@@ -0,0 +1,82 @@
+use axum::http::{Method, StatusCode};
+use hub_api::dto::tariffs::TariffVersionDto;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{seed_employee, send_json, setup_app_with_admin};
+use crate::{create_version, tariff};
+
+#[tokio::test]
+async fn admin_can_get_tariff_version() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let version = create_version(&app, &admin.sub, json!({ "tariff": tariff(100) })).await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::GET,
+        &format!("/admin/tariffs/{}", version.id),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let got: TariffVersionDto = serde_json::from_value(body).unwrap();
+    assert_eq!(got.id, version.id);
+    assert_eq!(got.tariff, tariff(100));
+}
+
+#[tokio::test]
+async fn get_missing_tariff_version_returns_not_found() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::GET,
+        &format!("/admin/tariffs/{}", Uuid::new_v4()),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "tariff_not_found");
+}
+
+#[tokio::test]
+async fn get_tariff_version_invalid_uuid() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::GET,
+        "/admin/tariffs/not-a-uuid",
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "invalid_tariff_id");
+}
+
+#[tokio::test]
+async fn employee_cannot_get_tariff_version() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+
+    let version = create_version(&app, &admin.sub, json!({ "tariff": tariff(100) })).await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::GET,
+        &format!("/admin/tariffs/{}", version.id),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
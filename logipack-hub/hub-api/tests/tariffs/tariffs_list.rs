@@ -0,0 +1,44 @@
+use axum::http::{Method, StatusCode};
+use hub_api::dto::tariffs::ListTariffsResponse;
+use serde_json::json;
+
+use crate::helpers::{seed_employee, send_json, setup_app_with_admin};
+use crate::{create_version, tariff};
+
+#[tokio::test]
+async fn admin_lists_tariff_versions_by_start() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let future = create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_from": "2100-01-01", "tariff": tariff(200) }),
+    )
+    .await;
+    let current = create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_to": "2100-01-01", "tariff": tariff(100) }),
+    )
+    .await;
+
+    let (status, body) = send_json(&app, &admin.sub, Method::GET, "/admin/tariffs", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let listed: ListTariffsResponse = serde_json::from_value(body).unwrap();
+    assert_eq!(
+        listed.tariffs.iter().map(|v| &v.id).collect::<Vec<_>>(),
+        vec![&current.id, &future.id]
+    );
+}
+
+#[tokio::test]
+async fn employee_cannot_list_tariff_versions() {
+    let (app, db, _admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+
+    let (status, body) = send_json(&app, &employee.sub, Method::GET, "/admin/tariffs", None).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
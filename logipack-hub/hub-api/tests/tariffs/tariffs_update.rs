@@ -0,0 +1,103 @@
+use axum::http::{Method, StatusCode};
+use hub_api::dto::tariffs::TariffVersionDto;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{seed_employee, send_json, setup_app_with_admin};
+use crate::{create_version, tariff};
+
+#[tokio::test]
+async fn admin_can_update_future_tariff_version() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let future = create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_from": "2100-01-01", "tariff": tariff(200) }),
+    )
+    .await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &format!("/admin/tariffs/{}", future.id),
+        Some(json!({ "effective_from": "2100-01-01", "tariff": tariff(300) })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let repriced: TariffVersionDto = serde_json::from_value(body).unwrap();
+    assert_eq!(repriced.id, future.id);
+    assert_eq!(repriced.tariff, tariff(300));
+}
+
+#[tokio::test]
+async fn rules_of_tariff_version_in_force_cannot_change() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let current = create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_to": "2100-01-01", "tariff": tariff(100) }),
+    )
+    .await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &format!("/admin/tariffs/{}", current.id),
+        Some(json!({
+            "effective_from": current.effective_from,
+            "effective_to": "2100-01-01",
+            "tariff": tariff(150)
+        })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "tariff_in_force");
+}
+
+#[tokio::test]
+async fn update_missing_tariff_version_returns_not_found() {
+    let (app, _db, admin) = setup_app_with_admin().await;
+
+    let (status, body) = send_json(
+        &app,
+        &admin.sub,
+        Method::PUT,
+        &format!("/admin/tariffs/{}", Uuid::new_v4()),
+        Some(json!({ "effective_from": "2100-01-01", "tariff": tariff(300) })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "tariff_not_found");
+}
+
+#[tokio::test]
+async fn employee_cannot_update_tariff_version() {
+    let (app, db, admin) = setup_app_with_admin().await;
+    let employee = seed_employee(&db).await;
+
+    let future = create_version(
+        &app,
+        &admin.sub,
+        json!({ "effective_from": "2100-01-01", "tariff": tariff(200) }),
+    )
+    .await;
+
+    let (status, body) = send_json(
+        &app,
+        &employee.sub,
+        Method::PUT,
+        &format!("/admin/tariffs/{}", future.id),
+        Some(json!({ "effective_from": "2100-01-01", "tariff": tariff(300) })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "access_denied");
+}
@@ -0,0 +1,141 @@
+use axum::{Router, body::Body, extract::Request, http::StatusCode};
+use core_domain::pricing::Tariff;
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use core_application::shipments::create::{CreateShipment, create_shipment};
+use core_data::entity::shipments;
+use hub_api::{app, config::AuthMode, dto::track::TrackingResponse, state::AppState};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+#[allow(dead_code)]
+mod helpers;
+use helpers::{seed_client, seed_office, setup_app_with_admin, test_config};
+
+async fn seed_tracked_shipment(db: &DatabaseConnection) -> String {
+    let admin = helpers::seed_admin_actor(db).await;
+    let client = seed_client(db).await;
+    let office = seed_office(db).await;
+
+    let id = create_shipment(
+        db,
+        &admin,
+        &Tariff::default(),
+        CreateShipment {
+            client_id: client,
+            current_office_id: Some(office),
+            notes: Some("leave with neighbour".into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    shipments::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .tracking_number
+        .unwrap()
+}
+
+fn track_request(code: &str, forwarded_for: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().uri(format!("/track/{code}"));
+
+    if let Some(ip) = forwarded_for {
+        builder = builder.header("x-forwarded-for", ip);
+    }
+
+    builder.body(Body::empty()).unwrap()
+}
+
+fn limited_app(db: DatabaseConnection, limit: u32) -> Router {
+    let state = AppState {
+        db,
+        auth_mode: AuthMode::DevSecret,
+        read_policy: Default::default(),
+        transitions: Default::default(),
+        tariff: Default::default(),
+    };
+
+    let mut cfg = test_config();
+    cfg.track_rate_limit = limit;
+    cfg.trust_forwarded_for = true;
+
+    app::router(cfg, state)
+}
+
+#[tokio::test]
+async fn track_without_auth_returns_redacted_history() {
+    let (app, db, _admin) = setup_app_with_admin().await;
+    let number = seed_tracked_shipment(&db).await;
+
+    let res = app.oneshot(track_request(&number, None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let raw: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    // only status, city and time per step
+    let step = raw["history"][0].as_object().unwrap();
+    let mut keys: Vec<_> = step.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["at", "city", "status"]);
+    assert!(!String::from_utf8_lossy(&body).contains("neighbour"));
+
+    let body: TrackingResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body.tracking_number, number);
+    assert_eq!(body.status, "NEW");
+    assert_eq!(body.history.len(), 1);
+    assert_eq!(body.history[0].city.as_deref(), Some("Test City"));
+}
+
+#[tokio::test]
+async fn track_rejects_bad_check_digit_and_unknown_numbers() {
+    let (app, _db, _admin) = setup_app_with_admin().await;
+
+    let res = app
+        .clone()
+        .oneshot(track_request("SOF473124828", None))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = app
+        .oneshot(track_request("SOF473124829", None))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn track_is_rate_limited_per_ip() {
+    let (_app, db, _admin) = setup_app_with_admin().await;
+    let number = seed_tracked_shipment(&db).await;
+    let app = limited_app(db, 2);
+
+    for _ in 0..2 {
+        let res = app
+            .clone()
+            .oneshot(track_request(&number, Some("203.0.113.7")))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    let res = app
+        .clone()
+        .oneshot(track_request(&number, Some("203.0.113.7")))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(res.headers().contains_key("retry-after"));
+
+    // another client is unaffected
+    let res = app
+        .oneshot(track_request(&number, Some("198.51.100.1")))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
@@ -13,7 +13,7 @@ type ShipmentsResult =
  *
  * Expected endpoint: GET {HUB_API_BASE}/shipments
  * Headers:           Authorization: Bearer <accessToken>
- * Response shape:    { shipments: ShipmentRow[] }
+ * Response shape:    { items: ShipmentListItem[], next_cursor: string | null }
  *
  * The access token lives in the encrypted session cookie.
  * See `src/routes/callback/+server.ts` for the existing fetch pattern